@@ -1,8 +1,13 @@
 use clap::Parser;
 use color_eyre::eyre::{Result, Context, eyre};
 use ta_lib::analyzer::{Analyzer, AnalysisOptions};
-use ta_lib::output::OutputFormat;
+use ta_lib::output::{OutputFormat, PathDisplay, RenderOptions};
 use ignore::WalkBuilder;
+use std::path::PathBuf;
+use crate::commands::file::report_import_issues;
+use crate::exit::Outcome;
+use crate::status;
+use crate::utils::{Declarations, TAIGNORE_FILENAME, analyze_with_progress, is_test_path, is_typescript_path, resolve_explicit_files};
 use colored::Colorize;
 
 /// Analyze module dependencies
@@ -12,81 +17,142 @@ pub struct DepsArgs {
     #[arg(value_name = "FILTER")]
     pub filters: Vec<String>,
 
+    /// Analyze exactly these files (or directories) instead of walking the project
+    #[arg(long, value_name = "PATH", num_args = 1.., conflicts_with = "filters")]
+    pub files: Vec<PathBuf>,
+
+    /// Only walk N directory levels below the project root (`src/a.ts` is at depth 2)
+    #[arg(long, value_name = "N", conflicts_with = "files")]
+    pub max_depth: Option<usize>,
+
+    /// Worker threads for parallel analysis (`0` uses all cores, `1` analyzes files serially)
+    #[arg(short = 'j', long, value_name = "N", default_value_t = 0)]
+    pub jobs: usize,
+
+    /// Print per-file analysis durations to stderr, slowest first
+    #[arg(long)]
+    pub timings: bool,
+
     /// Only show external dependencies
     #[arg(short, long)]
     pub external_only: bool,
+
+    /// Follow re-exports through barrel files to the module that defines each symbol
+    #[arg(long)]
+    pub resolve_barrels: bool,
+
+    /// Report relative imports that resolve to no file and exit 1 if any are found
+    #[arg(long)]
+    pub check: bool,
+
+    /// Only follow type-only imports (`import type`), which have no runtime effect
+    #[arg(long, conflicts_with = "value_only")]
+    pub type_only: bool,
+
+    /// Ignore type-only imports and follow only imports that exist at runtime
+    #[arg(long)]
+    pub value_only: bool,
+
+    /// Skip declaration files (`*.d.ts`)
+    #[arg(long)]
+    pub no_declarations: bool,
+
+    /// Only include declaration files (`*.d.ts`)
+    #[arg(long, conflicts_with = "no_declarations")]
+    pub declarations_only: bool,
 }
 
-pub fn handle_deps(args: DepsArgs, format: OutputFormat) -> Result<()> {
+pub fn handle_deps(args: DepsArgs, format: OutputFormat, render: &RenderOptions) -> Result<Outcome> {
     log::debug!("Handling deps command with args: {:?}", args);
 
     let options = AnalysisOptions {
         parallel: true,
+        jobs: args.jobs,
+        timings: args.timings,
+        check_imports: args.check,
         ..Default::default()
     };
 
     let analyzer = Analyzer::new(options);
 
-    // Use ignore crate to walk files, respecting .gitignore
-    // BASE pattern: same as source command - all TypeScript source files
-    let walker = WalkBuilder::new(".")
-        .standard_filters(true)
-        .build();
+    // Explicit --files bypass the directory walk and filters entirely
+    let files = if !args.files.is_empty() {
+        resolve_explicit_files(&args.files)?
+    } else {
+        // Use ignore crate to walk files, respecting .gitignore
+        // BASE pattern: same as source command - all TypeScript source files
+        let walker = WalkBuilder::new(".")
+            .standard_filters(true)
+            .add_custom_ignore_filename(TAIGNORE_FILENAME)
+            .max_depth(args.max_depth)
+            .build();
+
+        let mut files = Vec::new();
+        for entry in walker {
+            let entry = entry.wrap_err("Failed to walk directory")?;
+
+            if let Some(file_type) = entry.file_type() {
+                if !file_type.is_file() {
+                    continue;
+                }
+            }
+
+            let path = entry.path();
+            let path_str = path.to_string_lossy();
 
-    let mut files = Vec::new();
-    for entry in walker {
-        let entry = entry.wrap_err("Failed to walk directory")?;
+            // BASE pattern: TypeScript files in src/ or scripts/ directories
+            let is_in_source_dir = path_str.contains("/src/") ||
+                                    path_str.contains("/scripts/") ||
+                                    path_str.starts_with("src/") ||
+                                    path_str.starts_with("scripts/");
 
-        if let Some(file_type) = entry.file_type() {
-            if !file_type.is_file() {
+            let is_typescript = is_typescript_path(&path_str);
+
+            if !is_in_source_dir || !is_typescript {
                 continue;
             }
-        }
 
-        let path = entry.path();
-        let path_str = path.to_string_lossy();
+            // Exclude test files
+            if is_test_path(&path_str) {
+                continue;
+            }
 
-        // BASE pattern: TypeScript files in src/ or scripts/ directories
-        let is_in_source_dir = path_str.contains("/src/") ||
-                                path_str.contains("/scripts/") ||
-                                path_str.starts_with("src/") ||
-                                path_str.starts_with("scripts/");
+            files.push(path.to_path_buf());
+        }
 
-        let is_typescript = path_str.ends_with(".ts") || path_str.ends_with(".tsx");
+        Declarations::from_flags(false, args.no_declarations, args.declarations_only, Declarations::Include)
+            .retain(&mut files);
 
-        if !is_in_source_dir || !is_typescript {
-            continue;
+        // Apply user filters if provided (OR'd together)
+        if !args.filters.is_empty() {
+            files.retain(|f| {
+                let path_str = f.to_string_lossy();
+                args.filters.iter().any(|filter| path_str.contains(filter.as_str()))
+            });
         }
 
-        // Exclude test files
-        if path_str.ends_with(".test.ts") ||
-           path_str.ends_with(".spec.ts") ||
-           path_str.ends_with(".test.tsx") ||
-           path_str.ends_with(".spec.tsx") {
-            continue;
-        }
+        files
+    };
 
-        files.push(path.to_path_buf());
+    if files.is_empty() {
+        return Err(eyre!("No source files found"));
     }
 
-    // Apply user filters if provided (OR'd together)
-    if !args.filters.is_empty() {
-        files.retain(|f| {
-            let path_str = f.to_string_lossy();
-            args.filters.iter().any(|filter| path_str.contains(filter.as_str()))
-        });
-    }
+    status!(render, "Analyzing dependencies for {} files...", files.len());
+    let result = analyze_with_progress(analyzer, &files, render)?;
 
-    if files.is_empty() {
-        return Err(eyre!("No source files found"));
+    if args.check {
+        return report_import_issues(&result.file_imports, format, render);
     }
 
-    eprintln!("Analyzing dependencies for {} files...", files.len());
-    let result = analyzer.analyze_files(&files)?;
+    // (symbol_name, source_file, barrel, type_only)
+    type ImportedSymbol = (String, String, Option<String>, bool);
 
-    // Build mapping: file → imported symbols with sources
-    let mut file_to_imports: std::collections::HashMap<String, Vec<(String, String)>> =
+    // Build mapping: file → imported symbols with sources, the barrel they came through
+    // and whether the import is type-only
+    let mut file_to_imports: std::collections::HashMap<String, Vec<ImportedSymbol>> =
         std::collections::HashMap::new();
+    let mut barrels = ta_lib::dependencies::BarrelResolver::new();
 
     for file_import in &result.file_imports {
         for import_info in &file_import.imports {
@@ -115,10 +181,28 @@ pub fn handle_deps(args: DepsArgs, format: OutputFormat) -> Result<()> {
             };
 
             for symbol in &import_info.symbols {
+                // `import { helper, type Options }` mixes value and type-only symbols
+                let type_only = import_info.is_type_only(symbol);
+                if (args.type_only && !type_only) || (args.value_only && type_only) {
+                    continue;
+                }
+
+                // Namespace imports (`* as ns`) refer to the whole module, not a re-exported name
+                let origin = if args.resolve_barrels && !is_external && !symbol.starts_with("* as ") {
+                    barrels.resolve(std::path::Path::new(&source_file), symbol)
+                } else {
+                    None
+                };
+
+                let (from, via) = match origin {
+                    Some(origin) if origin.file != source_file => (origin.file, Some(source_file.clone())),
+                    _ => (source_file.clone(), None),
+                };
+
                 file_to_imports
                     .entry(file_import.file.clone())
                     .or_default()
-                    .push((symbol.clone(), source_file.clone()));
+                    .push((symbol.clone(), from, via, type_only));
             }
         }
     }
@@ -127,7 +211,7 @@ pub fn handle_deps(args: DepsArgs, format: OutputFormat) -> Result<()> {
     #[derive(Debug)]
     struct SymbolDep<'a> {
         symbol_info: &'a ta_lib::models::SymbolInfo,
-        depends_on: Vec<(String, String)>, // (symbol_name, source_file)
+        depends_on: Vec<ImportedSymbol>,
     }
 
     let mut symbol_deps = Vec::new();
@@ -138,11 +222,21 @@ pub fn handle_deps(args: DepsArgs, format: OutputFormat) -> Result<()> {
             continue;
         }
 
-        // Get imports used in this symbol's file
+        // Bind only the imports actually referenced within this symbol's declaration
         if let Some(imports) = file_to_imports.get(&symbol_info.file) {
+            let depends_on = imports
+                .iter()
+                .filter(|(symbol, _, _, _)| {
+                    // Namespace imports are recorded as "* as name"
+                    let local = symbol.strip_prefix("* as ").unwrap_or(symbol);
+                    symbol_info.references.iter().any(|r| r == local)
+                })
+                .cloned()
+                .collect();
+
             symbol_deps.push(SymbolDep {
                 symbol_info,
-                depends_on: imports.clone(),
+                depends_on,
             });
         }
     }
@@ -152,15 +246,20 @@ pub fn handle_deps(args: DepsArgs, format: OutputFormat) -> Result<()> {
             let output: Vec<_> = symbol_deps.iter().map(|dep| {
                 serde_json::json!({
                     "symbol": serde_json::to_value(dep.symbol_info).unwrap(),
-                    "depends_on": dep.depends_on.iter().map(|(sym, src)| {
-                        serde_json::json!({
+                    "depends_on": dep.depends_on.iter().map(|(sym, src, via, type_only)| {
+                        let mut entry = serde_json::json!({
                             "symbol": sym,
-                            "from": src
-                        })
+                            "from": src,
+                            "type_only": type_only
+                        });
+                        if let Some(via) = via {
+                            entry["via"] = serde_json::json!(via);
+                        }
+                        entry
                     }).collect::<Vec<_>>()
                 })
             }).collect();
-            println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            println!("{}", render.to_json(&output));
         }
         _ => {
             if symbol_deps.is_empty() {
@@ -169,36 +268,58 @@ pub fn handle_deps(args: DepsArgs, format: OutputFormat) -> Result<()> {
                 // Sort by symbol name
                 symbol_deps.sort_by(|a, b| a.symbol_info.name.cmp(&b.symbol_info.name));
 
+                let paths = PathDisplay::new(
+                    render.path_style,
+                    symbol_deps.iter().map(|dep| dep.symbol_info.file.as_str()),
+                );
+
                 for dep in &symbol_deps {
                     // Use colored signature display
-                    let signature = ta_lib::output::OutputFormatter::format_symbol_signature_colored(dep.symbol_info);
-                    let location = format!("{}:{}", dep.symbol_info.file, dep.symbol_info.start_line).blue();
+                    let signature = ta_lib::output::OutputFormatter::format_symbol_signature_with_options(dep.symbol_info, render);
+                    let location = render.paint(format!("{}:{}", paths.display(&dep.symbol_info.file), dep.symbol_info.start_line).blue());
 
                     println!("{} {}", signature, location);
 
                     // Show JSDoc if present
                     if let Some(jsdoc) = &dep.symbol_info.jsdoc {
-                        println!("  {}", jsdoc.dimmed().italic());
+                        for line in jsdoc.lines() {
+                            if line.is_empty() {
+                                println!();
+                            } else {
+                                println!("  {}", render.paint(line.dimmed().italic()));
+                            }
+                        }
                     }
 
                     if dep.depends_on.is_empty() {
                         println!("  (no dependencies)");
                     } else {
-                        for (symbol, source) in &dep.depends_on {
-                            println!("  → {} {} {}",
-                                symbol,
-                                "from".white().dimmed(),
-                                source
-                            );
+                        for (symbol, source, via, type_only) in &dep.depends_on {
+                            let kind = if *type_only { format!(" {}", render.paint("(type)".dimmed())) } else { String::new() };
+                            match via {
+                                Some(via) => println!("  → {}{} {} {} {}",
+                                    symbol,
+                                    kind,
+                                    render.paint("from".white().dimmed()),
+                                    source,
+                                    render.paint(format!("(via {})", via).dimmed())
+                                ),
+                                None => println!("  → {}{} {} {}",
+                                    symbol,
+                                    kind,
+                                    render.paint("from".white().dimmed()),
+                                    source
+                                ),
+                            }
                         }
                     }
                     println!();
                 }
 
-                eprintln!("Found {} exported symbols with dependencies.", symbol_deps.len());
+                status!(render, "Found {} exported symbols with dependencies.", symbol_deps.len());
             }
         }
     }
 
-    Ok(())
+    Ok(Outcome::Clean)
 }
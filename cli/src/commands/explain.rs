@@ -0,0 +1,50 @@
+use clap::Parser;
+use color_eyre::eyre::Result;
+use colored::*;
+use ta_lib::explain::{docs_url, explain, normalize_code};
+use ta_lib::output::{OutputFormat, RenderOptions};
+
+/// Explain a TypeScript diagnostic code
+#[derive(Parser, Debug)]
+pub struct ExplainArgs {
+    /// Diagnostic code, e.g. `TS2322` or `2322`
+    #[arg(value_name = "CODE")]
+    pub code: String,
+}
+
+pub fn handle_explain(args: ExplainArgs, format: OutputFormat, render: &RenderOptions) -> Result<()> {
+    log::debug!("Handling explain command with args: {:?}", args);
+
+    let code = normalize_code(&args.code).unwrap_or_else(|| args.code.clone());
+    let explanation = explain(&code);
+
+    if format == OutputFormat::Json {
+        let value = match explanation {
+            Some(explanation) => serde_json::json!({
+                "code": explanation.code,
+                "title": explanation.title,
+                "description": explanation.description,
+                "docs_url": explanation.docs_url(),
+            }),
+            None => serde_json::json!({ "code": code, "docs_url": docs_url(&code) }),
+        };
+        println!("{}", render.to_json(&value));
+        return Ok(());
+    }
+
+    match explanation {
+        Some(explanation) => {
+            println!("{} {}\n", render.paint(explanation.code.red().bold()), render.paint(explanation.title.bold()));
+            println!("{}\n", explanation.description);
+            println!("{} {}", render.paint("See:".dimmed()), explanation.docs_url());
+        }
+        None => {
+            println!("{}: no explanation available.", code);
+            if normalize_code(&code).is_some() {
+                println!("{} {}", render.paint("See:".dimmed()), docs_url(&code));
+            }
+        }
+    }
+
+    Ok(())
+}
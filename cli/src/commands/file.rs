@@ -1,8 +1,15 @@
 use clap::Parser;
-use color_eyre::eyre::{Result, Context, eyre};
-use ta_lib::analyzer::{Analyzer, AnalysisOptions};
-use ta_lib::output::OutputFormat;
-use ignore::WalkBuilder;
+use color_eyre::eyre::{Result, eyre};
+use ta_lib::analyzer::{Analyzer, AnalysisOptions, FileImports};
+use ta_lib::dependencies::{load_path_aliases, resolve_import_path};
+use ta_lib::models::DependencyScope;
+use ta_lib::output::{OutputFormat, PathDisplay, RenderOptions};
+use ta_lib::visitors::dependency_visitor::ImportInfo;
+use std::path::{Path, PathBuf};
+use crate::commands::preflight::CONFIG_FILENAME;
+use crate::exit::Outcome;
+use crate::status;
+use crate::utils::{Declarations, analyze_with_progress, base_source_files, load_rules, status_glyphs, resolve_explicit_files};
 use colored::Colorize;
 
 /// Analyze file-level dependencies (imports/exports) for all source files
@@ -11,124 +18,185 @@ pub struct FileArgs {
     /// Optional filter(s) to match against source file paths (OR'd together)
     #[arg(value_name = "FILTER")]
     pub filters: Vec<String>,
-}
-
-pub fn handle_file(args: FileArgs, format: OutputFormat) -> Result<()> {
-    log::debug!("Handling file command with args: {:?}", args);
 
-    let options = AnalysisOptions {
-        parallel: true,
-        ..Default::default()
-    };
+    /// Analyze exactly these files (or directories) instead of walking the project
+    #[arg(long, value_name = "PATH", num_args = 1.., conflicts_with = "filters")]
+    pub files: Vec<PathBuf>,
 
-    let analyzer = Analyzer::new(options);
+    /// Only walk N directory levels below the project root (`src/a.ts` is at depth 2)
+    #[arg(long, value_name = "N", conflicts_with = "files")]
+    pub max_depth: Option<usize>,
 
-    // Use ignore crate to walk files, respecting .gitignore
-    // BASE pattern: same as source command - all TypeScript source files
-    let walker = WalkBuilder::new(".")
-        .standard_filters(true)  // Respects .gitignore, .ignore, etc.
-        .build();
+    /// Worker threads for parallel analysis (`0` uses all cores, `1` analyzes files serially)
+    #[arg(short = 'j', long, value_name = "N", default_value_t = 0)]
+    pub jobs: usize,
 
-    let mut files = Vec::new();
-    for entry in walker {
-        let entry = entry.wrap_err("Failed to walk directory")?;
+    /// Print per-file analysis durations to stderr, slowest first
+    #[arg(long)]
+    pub timings: bool,
 
-        if let Some(file_type) = entry.file_type() {
-            if !file_type.is_file() {
-                continue;
-            }
-        }
+    /// Report duplicate imports and imported names that are never used
+    #[arg(long)]
+    pub lint: bool,
 
-        let path = entry.path();
-        let path_str = path.to_string_lossy();
+    /// Group each file's imports into external packages, tsconfig path aliases and relative imports
+    #[arg(long)]
+    pub group_imports: bool,
 
-        // BASE pattern: TypeScript files in src/ or scripts/ directories
-        let is_in_source_dir = path_str.contains("/src/") ||
-                                path_str.contains("/scripts/") ||
-                                path_str.starts_with("src/") ||
-                                path_str.starts_with("scripts/");
+    /// Skip declaration files (`*.d.ts`)
+    #[arg(long)]
+    pub no_declarations: bool,
 
-        let is_typescript = path_str.ends_with(".ts") || path_str.ends_with(".tsx");
+    /// Only include declaration files (`*.d.ts`)
+    #[arg(long, conflicts_with = "no_declarations")]
+    pub declarations_only: bool,
+}
 
-        if !is_in_source_dir || !is_typescript {
-            continue;
-        }
+/// Sections printed by `--group-imports`, in order
+const IMPORT_GROUPS: [(DependencyScope, &str); 3] = [
+    (DependencyScope::External, "External"),
+    (DependencyScope::Repo, "Alias"),
+    (DependencyScope::Local, "Relative"),
+];
 
-        // Exclude test files
-        if path_str.ends_with(".test.ts") ||
-           path_str.ends_with(".spec.ts") ||
-           path_str.ends_with(".test.tsx") ||
-           path_str.ends_with(".spec.tsx") {
-            continue;
-        }
+/// Non-empty `--group-imports` sections of one file's imports
+fn import_groups(imports: &[ImportInfo]) -> Vec<(DependencyScope, &'static str, Vec<&ImportInfo>)> {
+    IMPORT_GROUPS
+        .iter()
+        .map(|&(scope, label)| (scope, label, imports.iter().filter(|i| i.scope == scope).collect::<Vec<_>>()))
+        .filter(|(_, _, group)| !group.is_empty())
+        .collect()
+}
 
-        files.push(path.to_path_buf());
+/// File an import of `file` resolves to, `None` for packages and unresolved specifiers
+fn resolved_import(import: &ImportInfo, file: &str) -> Option<String> {
+    match import.scope {
+        DependencyScope::External => None,
+        _ => resolve_import_path(&import.source, Path::new(file)).map(|resolved| resolved.to_string_lossy().to_string()),
     }
+}
 
-    // Apply user filters if provided (OR'd together)
-    if !args.filters.is_empty() {
-        files.retain(|f| {
-            let path_str = f.to_string_lossy();
-            args.filters.iter().any(|filter| path_str.contains(filter.as_str()))
-        });
+/// Resolved path for relative imports, the specifier otherwise
+fn import_display(import: &ImportInfo, file: &str, paths: &PathDisplay) -> String {
+    match (import.scope, resolved_import(import, file)) {
+        (DependencyScope::External, _) => format!("{} (external)", import.source),
+        (_, Some(resolved)) => paths.display(&resolved),
+        (_, None) => import.source.clone(),
     }
+}
+
+pub fn handle_file(args: FileArgs, format: OutputFormat, render: &RenderOptions) -> Result<Outcome> {
+    log::debug!("Handling file command with args: {:?}", args);
+
+    let options = AnalysisOptions {
+        parallel: true,
+        jobs: args.jobs,
+        timings: args.timings,
+        lint_imports: args.lint,
+        path_aliases: load_path_aliases(Path::new(".")),
+        rules: load_rules(Path::new(CONFIG_FILENAME))?,
+        ..Default::default()
+    };
+
+    let analyzer = Analyzer::new(options);
+
+    // Explicit --files bypass the directory walk and filters entirely
+    let files = if !args.files.is_empty() {
+        resolve_explicit_files(&args.files)?
+    } else {
+        // BASE pattern: same as source command - all TypeScript source files
+        let mut files = base_source_files(args.max_depth)?;
+        Declarations::from_flags(false, args.no_declarations, args.declarations_only, Declarations::Include)
+            .retain(&mut files);
+
+        // Apply user filters if provided (OR'd together)
+        if !args.filters.is_empty() {
+            files.retain(|f| {
+                let path_str = f.to_string_lossy();
+                args.filters.iter().any(|filter| path_str.contains(filter.as_str()))
+            });
+        }
+
+        files
+    };
 
     if files.is_empty() {
         return Err(eyre!("No source files found"));
     }
 
-    eprintln!("Analyzing dependencies for {} files...", files.len());
-    let result = analyzer.analyze_files(&files)?;
+    status!(render, "Analyzing dependencies for {} files...", files.len());
+    let result = analyze_with_progress(analyzer, &files, render)?;
+
+    if args.lint {
+        return report_import_issues(&result.file_imports, format, render);
+    }
 
     // Use file_imports which contains resolved information
     let file_imports = &result.file_imports;
+    // Imported files share the listed files' display, so colliding basenames are told apart
+    let resolved: Vec<String> = file_imports
+        .iter()
+        .flat_map(|f| f.imports.iter().filter_map(|import| resolved_import(import, &f.file)))
+        .collect();
+    let paths = PathDisplay::new(
+        render.path_style,
+        file_imports.iter().map(|f| f.file.as_str()).chain(resolved.iter().map(String::as_str)),
+    );
 
     match format {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&file_imports).unwrap());
+            println!("{}", render.to_json(&file_imports));
         }
         OutputFormat::Html => {
-            println!("<div class='file-dependencies'>");
+            let class = |name: &str| render.html.class(name);
+            println!("<div class='{}'>", class("file-dependencies"));
             for file_import in file_imports {
-                println!("  <div class='file-dep'>");
-                println!("    <div class='file'>{}</div>", file_import.file);
-                println!("    <ul class='imports'>");
-                for import in &file_import.imports {
-                    // Resolve import path
-                    let resolved = ta_lib::dependencies::resolve_import_path(
-                        &import.source,
-                        std::path::Path::new(&file_import.file)
-                    );
-                    let display_path = if let Some(resolved_path) = resolved {
-                        resolved_path.to_string_lossy().to_string()
-                    } else {
-                        format!("{} (external)", import.source)
-                    };
-                    println!("      <li>{}</li>", display_path);
+                println!("  <div class='{}'>", class("file-dep"));
+                println!("    <div class='{}'>{}</div>", class("file"), paths.display(&file_import.file));
+                println!("    <ul class='{}'>", class("imports"));
+                if args.group_imports {
+                    for (scope, label, group) in import_groups(&file_import.imports) {
+                        println!("      <li class='{}' data-scope='{:?}'>{} ({})", class("import-group"), scope, label, group.len());
+                        println!("        <ul>");
+                        for import in group {
+                            println!("          <li>{}</li>", import_display(import, &file_import.file, &paths));
+                        }
+                        println!("        </ul>");
+                        println!("      </li>");
+                    }
+                } else {
+                    for import in &file_import.imports {
+                        println!("      <li>{}</li>", import_display(import, &file_import.file, &paths));
+                    }
+                }
+                for reexport in &file_import.reexports {
+                    println!("      <li class='{}'>{} (re-export)</li>", class("reexport"), reexport.source);
                 }
                 println!("    </ul>");
                 println!("  </div>");
             }
             println!("</div>");
         }
-        OutputFormat::Console => {
+        OutputFormat::Console | OutputFormat::Markdown => {
             if file_imports.is_empty() {
                 println!("No dependencies found.");
             } else {
                 for file_import in file_imports {
-                    println!("{}:", file_import.file.blue());
-                    for import in &file_import.imports {
-                        // Resolve import path
-                        let resolved = ta_lib::dependencies::resolve_import_path(
-                            &import.source,
-                            std::path::Path::new(&file_import.file)
-                        );
-                        let display_path = if let Some(resolved_path) = resolved {
-                            resolved_path.to_string_lossy().to_string()
-                        } else {
-                            format!("{} (external)", import.source)
-                        };
-                        println!("  → {}", display_path);
+                    println!("{}:", render.paint(paths.display(&file_import.file).blue()));
+                    if args.group_imports {
+                        for (_, label, group) in import_groups(&file_import.imports) {
+                            println!("  {} {}", render.paint(label.bold()), render.paint(format!("({})", group.len()).dimmed()));
+                            for import in group {
+                                println!("    → {}", import_display(import, &file_import.file, &paths));
+                            }
+                        }
+                    } else {
+                        for import in &file_import.imports {
+                            println!("  → {}", import_display(import, &file_import.file, &paths));
+                        }
+                    }
+                    for reexport in &file_import.reexports {
+                        println!("  ↳ {} {} {}", reexport.exported, render.paint("re-exported from".dimmed()), reexport.source);
                     }
                     println!();
                 }
@@ -137,7 +205,62 @@ pub fn handle_file(args: FileArgs, format: OutputFormat) -> Result<()> {
     }
 
     let total_imports: usize = file_imports.iter().map(|f| f.imports.len()).sum();
-    eprintln!("Found {} files with {} total imports.", file_imports.len(), total_imports);
+    status!(render, "Found {} files with {} total imports.", file_imports.len(), total_imports);
+
+    Ok(Outcome::Clean)
+}
+
+/// Print only the files with import hygiene issues (`ta file --lint`, `ta deps --check`)
+pub(crate) fn report_import_issues(file_imports: &[FileImports], format: OutputFormat, render: &RenderOptions) -> Result<Outcome> {
+    let flagged: Vec<&FileImports> = file_imports.iter().filter(|f| !f.issues.is_empty()).collect();
+    let paths = PathDisplay::new(render.path_style, flagged.iter().map(|f| f.file.as_str()));
+
+    match format {
+        OutputFormat::Json => {
+            println!("{}", render.to_json(&flagged));
+        }
+        OutputFormat::Html => {
+            let class = |name: &str| render.html.class(name);
+            println!("<div class='{}'>", class("import-issues"));
+            for file_import in &flagged {
+                println!("  <div class='{}'>", class("file-issues"));
+                println!("    <div class='{}'>{}</div>", class("file"), paths.display(&file_import.file));
+                println!("    <ul class='{}'>", class("issues"));
+                for issue in &file_import.issues {
+                    println!(
+                        "      <li class='{}' data-kind='{:?}' data-line='{}'>{}</li>",
+                        class("issue"), issue.kind, issue.line, issue.message
+                    );
+                }
+                println!("    </ul>");
+                println!("  </div>");
+            }
+            println!("</div>");
+        }
+        OutputFormat::Console | OutputFormat::Markdown => {
+            if flagged.is_empty() {
+                println!("No import issues found.");
+            }
+            let marker = status_glyphs(render).warning;
+            for file_import in &flagged {
+                println!("{}:", render.paint(paths.display(&file_import.file).blue()));
+                for issue in &file_import.issues {
+                    println!("  {} {} {}", render.paint(marker.yellow()), render.paint(format!("line {}:", issue.line).dimmed()), issue.message);
+                }
+                println!();
+            }
+        }
+    }
+
+    let total_issues: usize = flagged.iter().map(|f| f.issues.len()).sum();
+    status!(
+        render,
+        "Found {} import issue{} in {} file{}.",
+        total_issues,
+        if total_issues == 1 { "" } else { "s" },
+        flagged.len(),
+        if flagged.len() == 1 { "" } else { "s" }
+    );
 
-    Ok(())
+    Ok(if flagged.is_empty() { Outcome::Clean } else { Outcome::ErrorsFound })
 }
@@ -0,0 +1,83 @@
+use clap::Parser;
+use color_eyre::eyre::{Result, Context};
+use std::io::Read;
+use std::path::PathBuf;
+use ta_lib::highlighting::{
+    format_markdown, highlight_code, highlight_to_json, syntax_language,
+    HighlightOptions, MarkdownOptions,
+};
+use ta_lib::output::{OutputFormat, RenderOptions, DEFAULT_WIDTH};
+
+/// Syntax-highlight code from stdin or a file
+#[derive(Parser, Debug)]
+pub struct HighlightArgs {
+    /// File to highlight (reads stdin when omitted)
+    #[arg(value_name = "FILE")]
+    pub file: Option<PathBuf>,
+
+    /// Language of the code (defaults to the file extension, or `ts` for stdin)
+    #[arg(short, long)]
+    pub lang: Option<String>,
+}
+
+pub fn handle_highlight(args: HighlightArgs, format: OutputFormat, render: &RenderOptions) -> Result<()> {
+    log::debug!("Handling highlight command with args: {:?}", args);
+
+    let code = match &args.file {
+        Some(path) => std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?,
+        None => {
+            let mut code = String::new();
+            std::io::stdin()
+                .read_to_string(&mut code)
+                .wrap_err("Failed to read code from stdin")?;
+            code
+        }
+    };
+
+    let language = args
+        .lang
+        .clone()
+        .or_else(|| {
+            args.file
+                .as_ref()
+                .and_then(|path| path.extension())
+                .map(|ext| ext.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "ts".to_string());
+
+    let result = match format {
+        OutputFormat::Json => highlight_to_json(&code, &language),
+        // Markdown documents render as prose with their fenced code blocks highlighted
+        _ if is_markdown(&language) => {
+            let options = MarkdownOptions::new()
+                .with_glyphs(render.glyphs)
+                .with_plain(render.plain)
+                .with_width(render.width.unwrap_or(DEFAULT_WIDTH))
+                .with_html(render.html.clone())
+                .for_format(format);
+            format_markdown(&code, options).map(|formatted| formatted.output)
+        }
+        // Plain console output has no colors, so there is nothing to highlight
+        OutputFormat::Console if render.plain => Ok(code.clone()),
+        _ => {
+            let options = HighlightOptions::new(syntax_language(&language))
+                .with_html(render.html.clone())
+                .for_format(format);
+            highlight_code(&code, options).map(|highlighted| match format {
+                OutputFormat::Html => highlighted.render_html(),
+                _ => highlighted.render_console(),
+            })
+        }
+    };
+
+    // Unsupported languages and oversized input are usage errors like any other
+    let output = result.wrap_err("Highlighting failed")?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Whether `language` names a Markdown document (`md`/`markdown`)
+fn is_markdown(language: &str) -> bool {
+    language.eq_ignore_ascii_case("md") || language.eq_ignore_ascii_case("markdown")
+}
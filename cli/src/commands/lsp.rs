@@ -0,0 +1,28 @@
+use color_eyre::eyre::Result;
+use std::io::{BufReader, Write};
+use ta_lib::lsp::{read_message, write_message, LspSession};
+
+/// Serve type-error diagnostics over stdio until the client sends `exit` or closes stdin
+pub fn handle_lsp() -> Result<()> {
+    log::debug!("Starting LSP diagnostics adapter on stdio");
+
+    let mut session = LspSession::new();
+    let mut reader = BufReader::new(std::io::stdin().lock());
+    let mut writer = std::io::stdout().lock();
+
+    while let Some(message) = read_message(&mut reader)? {
+        for reply in session.handle(&message) {
+            write_message(&mut writer, &reply)?;
+        }
+        if session.exited() {
+            break;
+        }
+    }
+    writer.flush()?;
+
+    // LSP requires exit code 1 when the client exits without a prior shutdown
+    if session.exit_code() != 0 {
+        std::process::exit(session.exit_code());
+    }
+    Ok(())
+}
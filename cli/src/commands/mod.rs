@@ -4,3 +4,8 @@ pub mod test;
 pub mod file;
 pub mod deps;
 pub mod watch;
+pub mod highlight;
+pub mod explain;
+pub mod lsp;
+pub mod themes;
+pub mod preflight;
@@ -0,0 +1,196 @@
+use color_eyre::eyre::Result;
+use colored::Colorize;
+use std::path::Path;
+use std::time::Instant;
+use ta_lib::dependencies::read_tsconfig;
+use ta_lib::highlighting::themes::get_theme_by_name;
+use ta_lib::highlighting::warm_caches;
+use ta_lib::output::{OutputFormat, RenderOptions};
+use crate::exit::Outcome;
+use crate::status;
+use crate::utils::{base_source_files, load_rules, status_glyphs};
+
+/// Optional project configuration file checked by `ta preflight`
+pub const CONFIG_FILENAME: &str = "ta.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl CheckStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warning",
+            CheckStatus::Error => "error",
+        }
+    }
+}
+
+/// Result of one preflight check
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name, status, detail: detail.into() }
+    }
+}
+
+/// Time loading the highlighting data and validate configuration before a timed run.
+///
+/// `themes` are the names given by `--theme`/`--light-theme`/`--dark-theme`
+/// (or their environment variables). Any failed check is reported as
+/// [`Outcome::ErrorsFound`]; warnings alone are not.
+pub fn handle_preflight(themes: &[&str], format: OutputFormat, render: &RenderOptions) -> Result<Outcome> {
+    let checks = vec![
+        check_highlighting(),
+        check_themes(themes),
+        check_config(Path::new(CONFIG_FILENAME)),
+        check_tsconfig(Path::new(".")),
+        check_source_files(),
+    ];
+
+    match format {
+        OutputFormat::Json => {
+            let checks: Vec<_> = checks
+                .iter()
+                .map(|c| serde_json::json!({ "name": c.name, "status": c.status.as_str(), "detail": c.detail }))
+                .collect();
+            println!("{}", render.to_json(&checks));
+        }
+        OutputFormat::Html => {
+            let class = |name: &str| render.html.class(name);
+            println!("<ul class='{}'>", class("preflight"));
+            for check in &checks {
+                println!(
+                    "  <li class='{}' data-status='{}'><span class='{}'>{}</span> {}</li>",
+                    class("check"),
+                    check.status.as_str(),
+                    class("name"),
+                    check.name,
+                    html_escape::encode_text(&check.detail)
+                );
+            }
+            println!("</ul>");
+        }
+        OutputFormat::Console | OutputFormat::Markdown => {
+            let glyphs = status_glyphs(render);
+            let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+            for check in &checks {
+                let marker = match check.status {
+                    CheckStatus::Ok => render.paint(glyphs.success.green()),
+                    CheckStatus::Warning => render.paint(glyphs.warning.yellow()),
+                    CheckStatus::Error => render.paint(glyphs.error.red()),
+                };
+                println!("{} {:<width$}  {}", marker, render.paint(check.name.bold()), check.detail, width = width);
+            }
+        }
+    }
+
+    let errors = checks.iter().filter(|c| c.status == CheckStatus::Error).count();
+    if errors > 0 {
+        status!(render, "Preflight found {} problem{}.", errors, if errors == 1 { "" } else { "s" });
+        Ok(Outcome::ErrorsFound)
+    } else {
+        status!(render, "Preflight passed.");
+        Ok(Outcome::Clean)
+    }
+}
+
+fn check_highlighting() -> Check {
+    let start = Instant::now();
+    warm_caches();
+    Check::new(
+        "highlighting",
+        CheckStatus::Ok,
+        format!("syntaxes and themes loaded in {}ms", start.elapsed().as_millis()),
+    )
+}
+
+fn check_themes(themes: &[&str]) -> Check {
+    if themes.is_empty() {
+        return Check::new("themes", CheckStatus::Ok, "using the default themes");
+    }
+
+    let unknown: Vec<&str> = themes.iter().copied().filter(|name| get_theme_by_name(name).is_err()).collect();
+    if unknown.is_empty() {
+        Check::new("themes", CheckStatus::Ok, themes.join(", "))
+    } else {
+        Check::new(
+            "themes",
+            CheckStatus::Error,
+            format!("unknown theme {} (see `ta list-themes`)", unknown.join(", ")),
+        )
+    }
+}
+
+/// `ta.toml` is optional, but when present it must be valid TOML with valid `[[rules]]`
+fn check_config(path: &Path) -> Check {
+    let name = CONFIG_FILENAME;
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Check::new(name, CheckStatus::Ok, "not present"),
+        Err(e) => return Check::new(name, CheckStatus::Error, format!("cannot be read: {}", e)),
+    };
+
+    if let Err(e) = text.parse::<toml::Table>() {
+        return Check::new(name, CheckStatus::Error, e.message().to_string());
+    }
+    match load_rules(path) {
+        Ok(_) => Check::new(name, CheckStatus::Ok, "valid"),
+        Err(e) => Check::new(name, CheckStatus::Error, e.to_string()),
+    }
+}
+
+fn check_tsconfig(dir: &Path) -> Check {
+    match read_tsconfig(dir) {
+        None => Check::new("tsconfig.json", CheckStatus::Ok, "not present"),
+        Some(Ok(_)) => Check::new("tsconfig.json", CheckStatus::Ok, "valid"),
+        Some(Err(e)) => Check::new("tsconfig.json", CheckStatus::Error, e.to_string()),
+    }
+}
+
+/// An empty file list is suspicious but not an error: the repo may not be set up yet
+fn check_source_files() -> Check {
+    match base_source_files(None) {
+        Ok(files) if files.is_empty() => {
+            Check::new("source files", CheckStatus::Warning, "no TypeScript files under src/ or scripts/")
+        }
+        Ok(files) => Check::new("source files", CheckStatus::Ok, format!("{} files under src/ and scripts/", files.len())),
+        Err(e) => Check::new("source files", CheckStatus::Error, e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_config_check_reports_invalid_toml() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILENAME);
+
+        assert_eq!(check_config(&path).status, CheckStatus::Ok);
+
+        std::fs::write(&path, "[analysis]\njobs = 4\n").unwrap();
+        assert_eq!(check_config(&path).status, CheckStatus::Ok);
+
+        std::fs::write(&path, "jobs = \n").unwrap();
+        assert_eq!(check_config(&path).status, CheckStatus::Error);
+    }
+
+    #[test]
+    fn test_unknown_theme_fails() {
+        assert_eq!(check_themes(&["solarized-light"]).status, CheckStatus::Ok);
+        assert_eq!(check_themes(&["no-such-theme"]).status, CheckStatus::Error);
+    }
+}
@@ -1,13 +1,35 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use color_eyre::eyre::{Result, Context, eyre};
-use ta_lib::analyzer::{Analyzer, AnalysisOptions};
-use ta_lib::output::{OutputFormatter, OutputFormat};
+use ta_lib::analyzer::{Analyzer, AnalysisOptions, AnalysisResult, FailedFile, FailureKind};
+use ta_lib::highlighting::{ansi_to_html, ErrorSeverity};
+use ta_lib::baseline::{Baseline, BaselineEntry};
+use ta_lib::explicit_any::ANY_ERROR_ID;
+use ta_lib::models::{AnalysisManifest, TypeError};
+use ta_lib::sfc::is_sfc_path;
+use ta_lib::output::{OutputFormatter, OutputFormat, RenderOptions};
+use ta_lib::type_errors::{IgnoreDirective, ScopeNaming, DEFAULT_SCOPE_SEPARATOR};
+use ta_lib::walk::project_walker;
 use ignore::WalkBuilder;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::error::Error;
+use crate::exit::Outcome;
+use crate::commands::preflight::CONFIG_FILENAME;
+use crate::status;
+use crate::utils::{Declarations, analyze_with_progress, format_timings, load_rules, status_glyphs, is_test_path, is_typescript_path, print_report, resolve_explicit_files, retain_changed_since};
+use globset::{ErrorKind, Glob, GlobSet, GlobSetBuilder};
 use colored::*;
 
 /// Expand brace patterns like {a,b,c} into multiple patterns
 /// Example: "{src,scripts}/**/*.{ts,tsx}" -> ["./src/**/*.ts", "./src/**/*.tsx", "./scripts/**/*.ts", "./scripts/**/*.tsx"]
-fn expand_braces(pattern: &str) -> Vec<String> {
+///
+/// Returns [`Error::InvalidGlob`] when a `{` is never closed or a `}` has no opening brace.
+fn expand_braces(pattern: &str) -> std::result::Result<Vec<String>, Error> {
+    check_balanced_braces(pattern)?;
+
     let mut result = vec![pattern.to_string()];
 
     // Keep expanding until no more braces found
@@ -43,13 +65,427 @@ fn expand_braces(pattern: &str) -> Vec<String> {
     }
 
     // Normalize patterns to start with ./ to match WalkBuilder output
-    result.into_iter().map(|p| {
+    Ok(result.into_iter().map(|p| {
         if p.starts_with("./") {
             p
         } else {
             format!("./{}", p)
         }
-    }).collect()
+    }).collect())
+}
+
+/// Reject patterns whose `{`/`}` don't pair up, reporting the offending brace's offset
+fn check_balanced_braces(pattern: &str) -> std::result::Result<(), Error> {
+    let invalid = |offset: usize, reason: &str| Error::InvalidGlob {
+        pattern: pattern.to_string(),
+        expanded: pattern.to_string(),
+        offset,
+        reason: reason.to_string(),
+    };
+
+    let mut open = Vec::new();
+    for (offset, ch) in pattern.chars().enumerate() {
+        match ch {
+            '{' => open.push(offset),
+            '}' if open.pop().is_none() => return Err(invalid(offset, "unmatched '}'")),
+            _ => {}
+        }
+    }
+
+    match open.first() {
+        Some(&offset) => Err(invalid(offset, "unclosed '{'")),
+        None => Ok(()),
+    }
+}
+
+/// Compile a `--glob` pattern into a glob set, expanding braces first.
+///
+/// Failures name the specific expanded sub-pattern and where in it parsing failed.
+fn build_glob_set(pattern: &str) -> std::result::Result<(GlobSet, Vec<String>), Error> {
+    let expanded_patterns = expand_braces(pattern)?;
+
+    let mut builder = GlobSetBuilder::new();
+    for expanded in &expanded_patterns {
+        let glob = Glob::new(expanded).map_err(|e| Error::InvalidGlob {
+            pattern: pattern.to_string(),
+            expanded: expanded.clone(),
+            offset: glob_error_offset(expanded, e.kind()),
+            reason: e.kind().to_string(),
+        })?;
+        builder.add(glob);
+    }
+
+    let glob_set = builder.build().map_err(|e| Error::InvalidGlob {
+        pattern: pattern.to_string(),
+        expanded: e.glob().unwrap_or(pattern).to_string(),
+        offset: 0,
+        reason: e.kind().to_string(),
+    })?;
+
+    Ok((glob_set, expanded_patterns))
+}
+
+/// Best-effort character offset of a globset failure within `glob`
+fn glob_error_offset(glob: &str, kind: &ErrorKind) -> usize {
+    let chars: Vec<char> = glob.chars().collect();
+    match kind {
+        ErrorKind::UnclosedClass => {
+            // The last '[' not followed by a closing ']' is the one left open
+            let mut open = None;
+            for (i, &ch) in chars.iter().enumerate() {
+                match ch {
+                    '[' if open.is_none() => open = Some(i),
+                    ']' if open.is_some_and(|start| i > start + 1) => open = None,
+                    _ => {}
+                }
+            }
+            open.unwrap_or(0)
+        }
+        ErrorKind::InvalidRange(start, end) => chars
+            .windows(3)
+            .position(|w| w[0] == *start && w[1] == '-' && w[2] == *end)
+            .unwrap_or(0),
+        ErrorKind::DanglingEscape => chars.len().saturating_sub(1),
+        _ => 0,
+    }
+}
+
+/// Run `analyzer` over `files` for `--flush-per-file`, printing each file's errors
+/// and flushing stdout as soon as it and every file before it are analyzed.
+///
+/// Output is console (or `--compact`) text; JSON and HTML are single documents.
+///
+/// Errors are filtered and capped at `--max-errors` as in the batched report, so
+/// together the files print exactly what that report would. `out` is flushed
+/// after every file that printed something.
+fn analyze_flushing_per_file(
+    analyzer: Analyzer,
+    files: &[PathBuf],
+    args: &SourceArgs,
+    format: OutputFormat,
+    render: &RenderOptions,
+    out: &mut impl Write,
+) -> Result<AnalysisResult> {
+    let render = render
+        .clone()
+        .with_scope_depth(args.scope_depth.map(|depth| depth as usize), &args.scope_separator);
+    let mut printed = 0;
+    let result = analyzer.analyze_files_each(files, |analysis| {
+        let mut errors = analysis.type_errors.clone();
+        apply_error_filters(&mut errors, args);
+        errors.truncate(args.max_errors.saturating_sub(printed));
+        if errors.is_empty() {
+            return;
+        }
+        printed += errors.len();
+
+        let report = if args.compact {
+            OutputFormatter::format_type_errors_compact(&errors, &render)
+        } else {
+            OutputFormatter::format_type_errors_with_options(&errors, format, &render)
+        };
+        let _ = write!(out, "{}", report);
+        let _ = out.flush();
+    })?;
+    // Like the batched report, console output ends with an empty line
+    if !args.compact {
+        writeln!(out)?;
+    }
+
+    // Only present with `--timings`, as after a batched analysis
+    if !result.timings.is_empty() {
+        eprint!("{}", format_timings(&result.timings, &render));
+    }
+    Ok(result)
+}
+
+/// Warn on stderr about files that were skipped, grouped by cause
+fn report_failed_files(failed: &[FailedFile], render: &RenderOptions) {
+    if failed.is_empty() {
+        return;
+    }
+
+    let marker = status_glyphs(render).warning;
+    status!(render, 
+        "{} Skipped {} file{} that could not be analyzed:",
+        marker,
+        failed.len(),
+        if failed.len() == 1 { "" } else { "s" }
+    );
+    for failure in failed {
+        let cause = match failure.kind {
+            FailureKind::Io => "unreadable",
+            FailureKind::UnsupportedSourceType => "unsupported source type",
+            FailureKind::Timeout => "timed out",
+            FailureKind::Other => "analysis failed",
+        };
+        status!(render, 
+            "  - {} ({}): {}",
+            render.paint(failure.file.to_string_lossy().yellow()),
+            cause,
+            render.paint(failure.message.dimmed())
+        );
+    }
+}
+
+/// Warn on stderr about `ta-ignore` directives that suppressed nothing
+fn report_unused_ignores(unused: &[IgnoreDirective], render: &RenderOptions) {
+    let marker = status_glyphs(render).warning;
+    for directive in unused {
+        let kind = if directive.target_line == directive.line { "ta-ignore" } else { "ta-ignore-next-line" };
+        let codes = if directive.codes.is_empty() { String::new() } else { format!(" {}", directive.codes.join(" ")) };
+        eprintln!(
+            "{} Unused {}{} directive at {}:{}",
+            marker,
+            kind,
+            codes,
+            render.paint(directive.file.yellow()),
+            directive.line
+        );
+    }
+}
+
+/// List on stderr the baseline errors no longer reported
+fn report_fixed(fixed: &[BaselineEntry], baseline: &Path, render: &RenderOptions) {
+    if fixed.is_empty() {
+        status!(render, "No errors in {} have been fixed.", baseline.display());
+        return;
+    }
+
+    let marker = status_glyphs(render).success;
+    status!(render, "{} error{} in {} fixed (run with --update-baseline to drop them):", fixed.len(), if fixed.len() == 1 { "" } else { "s" }, baseline.display());
+    for entry in fixed {
+        status!(render, "{} {} {} {}", marker, render.paint(entry.file.green()), render.paint(entry.id.dimmed()), entry.message);
+    }
+}
+
+/// Lowest severity that makes `ta source` exit with a non-zero code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum FailOn {
+    /// Fail only when errors are reported
+    #[default]
+    Error,
+    /// Fail on warnings and errors
+    Warning,
+    /// Fail on any diagnostic
+    Info,
+    /// Never fail because of reported diagnostics
+    Never,
+}
+
+impl FailOn {
+    /// Whether a diagnostic of the given severity reaches this threshold
+    pub fn is_met_by(self, severity: ErrorSeverity) -> bool {
+        match self {
+            FailOn::Never => false,
+            FailOn::Info => true,
+            FailOn::Warning => !matches!(severity, ErrorSeverity::Info),
+            FailOn::Error => matches!(severity, ErrorSeverity::Error),
+        }
+    }
+
+    /// Whether any of the reported errors reaches this threshold
+    pub fn should_fail(self, errors: &[TypeError]) -> bool {
+        errors.iter().any(|e| self.is_met_by(e.severity))
+    }
+}
+
+/// Summarize errors per severity, e.g. "2 errors, 1 warning"
+fn severity_breakdown(errors: &[TypeError]) -> String {
+    [ErrorSeverity::Error, ErrorSeverity::Warning, ErrorSeverity::Info]
+        .iter()
+        .filter_map(|severity| {
+            let count = errors.iter().filter(|e| e.severity == *severity).count();
+            (count > 0).then(|| {
+                format!("{} {}{}", count, severity.label(), if count == 1 { "" } else { "s" })
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Whether an error code matches a `--ignore-code`/`--only-code` pattern (case-insensitive prefix)
+fn code_matches(id: &str, pattern: &str) -> bool {
+    id.get(..pattern.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(pattern))
+}
+
+/// Keep the errors selected by `--error-filter` (a leading `!` excludes matches),
+/// `--only-code` and `--ignore-code`
+fn apply_error_filters(errors: &mut Vec<TypeError>, args: &SourceArgs) {
+    // Filters match errors by message or scope, not files
+    if let Some(filter) = &args.error_filter {
+        if let Some(negative_filter) = filter.strip_prefix('!') {
+            errors.retain(|e| !e.message.contains(negative_filter) && !e.scope.contains(negative_filter));
+        } else {
+            errors.retain(|e| e.message.contains(filter.as_str()) || e.scope.contains(filter.as_str()));
+        }
+    }
+    filter_by_code(errors, &args.only_codes, &args.ignore_codes);
+}
+
+/// Apply `--only-code` and then `--ignore-code` to the extracted errors
+fn filter_by_code(errors: &mut Vec<TypeError>, only: &[String], ignore: &[String]) {
+    errors.retain(|e| {
+        (only.is_empty() || only.iter().any(|code| code_matches(&e.id, code)))
+            && !ignore.iter().any(|code| code_matches(&e.id, code))
+    });
+}
+
+/// Why the `source` directory walk keeps or skips a file, see `--explain-selection`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Selection {
+    Included,
+    /// Not TypeScript (or not matched by `--glob`), so never a candidate
+    NotCandidate,
+    NotInSourceDir,
+    TestFile,
+    Declaration,
+    Filter,
+    /// Excluded by `.gitignore` or `.taignore`; only found by [`explain_selection`]
+    Gitignored,
+}
+
+impl Selection {
+    fn verdict(self) -> &'static str {
+        match self {
+            Self::Included => "included",
+            Self::NotCandidate => "skipped: not a source file",
+            Self::NotInSourceDir => "skipped: not in src/ or scripts/",
+            Self::TestFile => "skipped: test file",
+            Self::Declaration => "skipped: declaration file",
+            Self::Filter => "skipped: filter",
+            Self::Gitignored => "skipped: gitignored",
+        }
+    }
+}
+
+/// The `--glob` pattern as a glob set, `None` for the BASE pattern
+fn source_glob_set(args: &SourceArgs) -> std::result::Result<Option<GlobSet>, Error> {
+    let Some(pattern) = &args.glob else {
+        return Ok(None);
+    };
+    // Manually expand brace patterns since globset doesn't support them
+    let (glob_set, expanded_patterns) = build_glob_set(pattern)?;
+    log::debug!("Expanded patterns: {:?}", expanded_patterns);
+    Ok(Some(glob_set))
+}
+
+/// The directory walk of `ta source`: the [`project_walker`] of the current directory
+///
+/// A custom `--glob` sees hidden files too, so only the ignore files filter it.
+fn source_walker(args: &SourceArgs) -> WalkBuilder {
+    let mut builder = project_walker(Path::new("."));
+    if args.glob.is_some() {
+        builder.standard_filters(false).git_ignore(true).git_exclude(true);
+    }
+    builder.max_depth(args.max_depth);
+    builder
+}
+
+/// Where a walked file stands against the selection rules, checked in order:
+/// `--glob` (or the BASE pattern: TypeScript under `src/` or `scripts/`, not a
+/// test file), declaration files, then the positional filters
+fn select_file(path: &Path, args: &SourceArgs, glob_set: Option<&GlobSet>) -> Selection {
+    let path_str = path.to_string_lossy();
+
+    if let Some(glob_set) = glob_set {
+        if !glob_set.is_match(path) {
+            return Selection::NotCandidate;
+        }
+    } else {
+        let is_typescript = is_typescript_path(&path_str) || (args.include_sfc && is_sfc_path(path));
+        if !is_typescript {
+            return Selection::NotCandidate;
+        }
+        let is_in_source_dir = path_str.contains("/src/") ||
+                                path_str.contains("/scripts/") ||
+                                path_str.starts_with("src/") ||
+                                path_str.starts_with("scripts/");
+        if !is_in_source_dir {
+            return Selection::NotInSourceDir;
+        }
+        // Use .ends_with() to avoid false positives like "contest.ts"
+        if !args.include_tests && is_test_path(&path_str) {
+            return Selection::TestFile;
+        }
+    }
+
+    // Declarations are ambient types without runtime code, so skipped unless asked for
+    if !Declarations::from_flags(args.include_declarations, args.no_declarations, args.declarations_only, Declarations::Exclude).keeps(&path_str) {
+        return Selection::Declaration;
+    }
+
+    // Multiple filters are OR'd: ta source foo bar → files with "foo" OR "bar" in path
+    if !args.filters.is_empty() && !args.filters.iter().any(|filter| path_str.contains(filter.as_str())) {
+        return Selection::Filter;
+    }
+
+    Selection::Included
+}
+
+/// Print every candidate file of the walk with its [`Selection`] verdict instead of analyzing
+///
+/// Ignored files never reach the walk, so a second walk without ignore files
+/// finds them; an ignored directory is reported once rather than file by file.
+fn explain_selection(args: &SourceArgs, format: OutputFormat, render: &RenderOptions) -> Result<Outcome> {
+    let glob_set = source_glob_set(args)?;
+
+    let mut walked = HashSet::new();
+    let mut verdicts = Vec::new();
+    for entry in source_walker(args).build() {
+        let entry = entry.wrap_err("Failed to walk directory")?;
+        walked.insert(entry.path().to_path_buf());
+        if entry.file_type().is_some_and(|t| t.is_file()) {
+            let selection = select_file(entry.path(), args, glob_set.as_ref());
+            if selection != Selection::NotCandidate {
+                verdicts.push((entry.into_path(), selection));
+            }
+        }
+    }
+
+    let walked = Arc::new(walked);
+    let ignored = Arc::new(Mutex::new(Vec::new()));
+    let (seen, skipped) = (Arc::clone(&walked), Arc::clone(&ignored));
+    WalkBuilder::new(".")
+        .standard_filters(false)
+        .hidden(args.glob.is_none())
+        .max_depth(args.max_depth)
+        .filter_entry(move |e| {
+            if e.file_name() == ".git" || seen.contains(e.path()) {
+                return e.file_name() != ".git";
+            }
+            skipped.lock().unwrap().push((e.path().to_path_buf(), e.file_type().is_some_and(|t| t.is_dir())));
+            false
+        })
+        .build()
+        .for_each(drop);
+    for (path, is_dir) in ignored.lock().unwrap().drain(..) {
+        if is_dir || select_file(&path, args, glob_set.as_ref()) != Selection::NotCandidate {
+            verdicts.push((path, Selection::Gitignored));
+        }
+    }
+    verdicts.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let display = |path: &Path| path.to_string_lossy().trim_start_matches("./").to_string();
+    if format == OutputFormat::Json {
+        let entries: Vec<serde_json::Value> = verdicts
+            .iter()
+            .map(|(path, selection)| serde_json::json!({ "file": display(path), "verdict": selection.verdict() }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+    } else {
+        for (path, selection) in &verdicts {
+            let verdict = match selection {
+                Selection::Included => render.paint(selection.verdict().green()),
+                _ => render.paint(selection.verdict().dimmed()),
+            };
+            println!("{} {}", display(path), verdict);
+        }
+    }
+
+    let included = verdicts.iter().filter(|(_, selection)| *selection == Selection::Included).count();
+    status!(render, "{} of {} candidate files would be analyzed.", included, verdicts.len());
+    Ok(Outcome::Clean)
 }
 
 /// Analyze source files for type errors
@@ -59,176 +495,313 @@ pub struct SourceArgs {
     #[arg(value_name = "FILTER")]
     pub filters: Vec<String>,
 
+    /// Analyze exactly these files (or directories) instead of walking the project
+    #[arg(long, value_name = "PATH", num_args = 1.., conflicts_with = "filters")]
+    pub files: Vec<PathBuf>,
+
+    /// Only walk N directory levels below the project root (`src/a.ts` is at depth 2)
+    #[arg(long, value_name = "N", conflicts_with = "files")]
+    pub max_depth: Option<usize>,
+
+    /// Worker threads for parallel analysis (`0` uses all cores, `1` analyzes files serially)
+    #[arg(short = 'j', long, value_name = "N", default_value_t = 0)]
+    pub jobs: usize,
+
+    /// Give up on a file after this many milliseconds of analysis and report it as skipped
+    #[arg(long, value_name = "MS")]
+    pub timeout_ms: Option<u64>,
+
+    /// Print per-file analysis durations to stderr, slowest first
+    #[arg(long)]
+    pub timings: bool,
+
+    /// Only analyze files changed since this git ref (e.g. `HEAD`, `origin/main`)
+    #[arg(long, value_name = "REF", conflicts_with = "files")]
+    pub since: Option<String>,
+
     /// Filter errors by message or scope
     #[arg(short, long)]
     pub error_filter: Option<String>,
 
+    /// Drop errors whose code starts with CODE (repeatable; `TS70` matches `TS7006`)
+    #[arg(long = "ignore-code", value_name = "CODE")]
+    pub ignore_codes: Vec<String>,
+
+    /// Keep only errors whose code starts with CODE (repeatable)
+    #[arg(long = "only-code", value_name = "CODE")]
+    pub only_codes: Vec<String>,
+
+    /// Report comments containing these markers as info diagnostics (comma-separated, case-insensitive)
+    #[arg(long, value_name = "MARKERS", value_delimiter = ',')]
+    pub flag_comments: Vec<String>,
+
+    /// Separator between nested scope names in each error's scope (e.g. `Class.method`)
+    #[arg(long, value_name = "SEP", default_value = DEFAULT_SCOPE_SEPARATOR)]
+    pub scope_separator: String,
+
+    /// Start each error's scope with its module path (`src/shapes.Circle.area`)
+    #[arg(long)]
+    pub module_scopes: bool,
+
+    /// Show only the innermost N scope segments in console headers (`…inner.fn`); JSON keeps the full scope
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub scope_depth: Option<u64>,
+
+    /// Warn (id `SHADOW`) about variables that shadow one declared in an enclosing scope
+    #[arg(long)]
+    pub lint_shadowing: bool,
+
+    /// Report each explicit `any` (`: any`, `as any`, `Array<any>`) as an info diagnostic (id `ANY`)
+    #[arg(long)]
+    pub report_any: bool,
+
+    /// Warn (id `EMPTY_CATCH`) about `catch` blocks with no statements, unless marked `// intentionally-empty`
+    #[arg(long)]
+    pub lint_empty_catch: bool,
+
     /// Include test files in analysis
     #[arg(long)]
     pub include_tests: bool,
 
+    /// Also analyze the `<script>` blocks of `.vue` and `.svelte` components
+    #[arg(long)]
+    pub include_sfc: bool,
+
+    /// Include declaration files (`*.d.ts`), which are skipped by default
+    #[arg(long)]
+    pub include_declarations: bool,
+
+    /// Analyze only declaration files (`*.d.ts`), e.g. to check published types
+    #[arg(long, conflicts_with = "include_declarations")]
+    pub declarations_only: bool,
+
+    /// Skip declaration files (`*.d.ts`), as `source` does by default
+    #[arg(long, conflicts_with_all = ["include_declarations", "declarations_only"])]
+    pub no_declarations: bool,
+
     /// Maximum number of errors to report
     #[arg(long, default_value = "100")]
     pub max_errors: usize,
 
-    /// Custom glob pattern (default: {src,scripts}/**/*.{ts,tsx})
+    /// Custom glob pattern (default: {src,scripts}/**/*.{ts,tsx,mts,cts})
     #[arg(long)]
     pub glob: Option<String>,
+
+    /// Lowest severity that causes a non-zero exit code
+    #[arg(long, value_enum, default_value_t = FailOn::Error)]
+    pub fail_on: FailOn,
+
+    /// Print each file once as a header with its errors indented beneath it
+    #[arg(long)]
+    pub group_by_file: bool,
+
+    /// Lines of code to show around each error (0 = only the error line)
+    #[arg(long, value_name = "N")]
+    pub context_lines: Option<usize>,
+
+    /// Warn about `// ta-ignore` comments that suppress no error
+    #[arg(long)]
+    pub report_unused_ignores: bool,
+
+    /// Only report errors not in this baseline file; a missing baseline is written from the current errors.
+    /// Only the analyzed files' entries are compared or rewritten
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["error_filter", "ignore_codes", "only_codes"])]
+    pub baseline: Option<PathBuf>,
+
+    /// Rewrite the --baseline file from the current errors
+    #[arg(long, requires = "baseline")]
+    pub update_baseline: bool,
+
+    /// List baseline errors that are no longer reported (fixed since the baseline was written)
+    #[arg(long, requires = "baseline")]
+    pub report_fixed: bool,
+
+    /// Print a JSON summary of the run (file, error and warning counts, duration) instead of the errors
+    #[arg(long)]
+    pub manifest: bool,
+
+    /// Print one `file:line:col: [CODE] message` line per error (no code, no color) for quickfix and grep
+    #[arg(long, conflicts_with_all = ["manifest", "group_by_file"])]
+    pub compact: bool,
+
+    /// Print and flush each file's errors as soon as it is analyzed instead of one report at the end
+    /// (console and --compact output; files still appear in order)
+    #[arg(long, conflicts_with_all = ["manifest", "group_by_file", "ansi_to_html", "baseline"])]
+    pub flush_per_file: bool,
+
+    /// Render the colored console report, then convert its ANSI styling and links to HTML
+    #[arg(long, conflicts_with_all = ["manifest", "compact"])]
+    pub ansi_to_html: bool,
+
+    /// List each candidate file with why it is analyzed or skipped, instead of analyzing
+    #[arg(long, conflicts_with = "files")]
+    pub explain_selection: bool,
 }
 
-pub fn handle_source(args: SourceArgs, format: OutputFormat, verbose: bool) -> Result<()> {
+pub fn handle_source(
+    args: SourceArgs,
+    format: OutputFormat,
+    verbose: bool,
+    render: &RenderOptions,
+) -> Result<Outcome> {
     log::debug!("Handling source command with args: {:?}", args);
 
     let options = AnalysisOptions {
         parallel: true,
+        jobs: args.jobs,
+        per_file_timeout: args.timeout_ms.map(Duration::from_millis),
+        timings: args.timings,
+        context_lines: args.context_lines,
+        comment_markers: args.flag_comments.clone(),
+        lint_shadowing: args.lint_shadowing,
+        report_any: args.report_any,
+        lint_empty_catch: args.lint_empty_catch,
+        include_sfc: args.include_sfc,
+        rules: load_rules(Path::new(CONFIG_FILENAME))?,
+        scope_naming: ScopeNaming::default()
+            .with_separator(args.scope_separator.as_str())
+            .with_module(args.module_scopes),
         ..Default::default()
     };
 
     let analyzer = Analyzer::new(options);
 
     // Determine glob pattern
-    let default_glob = if args.include_tests {
-        "{src,scripts}/**/*.{ts,tsx}"
-    } else {
-        "{src,scripts}/**/*.{ts,tsx}"
-    };
+    let default_glob = "{src,scripts}/**/*.{ts,tsx,mts,cts}";
     let glob_pattern = args.glob.as_deref().unwrap_or(default_glob);
 
     // Show glob pattern in verbose mode
     if verbose {
-        eprintln!("Using glob pattern: {}", glob_pattern.cyan());
+        status!(render, "Using glob pattern: {}", render.paint(glob_pattern.cyan()));
     }
 
-    let mut files = Vec::new();
-
-    // Use custom glob pattern if provided, otherwise use default logic
-    if args.glob.is_some() {
-        // Manually expand brace patterns since globset doesn't support them
-        let expanded_patterns = expand_braces(glob_pattern);
-
-        use globset::GlobSetBuilder;
-        let mut builder = GlobSetBuilder::new();
-        for pattern in &expanded_patterns {
-            builder.add(globset::Glob::new(pattern).wrap_err("Invalid glob pattern")?);
-        }
-        let glob_set = builder.build().wrap_err("Failed to build glob set")?;
-
-        log::debug!("Expanded patterns: {:?}", expanded_patterns);
-
-        // Walk all files in current directory
-        let walker = WalkBuilder::new(".")
-            .standard_filters(false)  // Don't use standard filters when using custom glob
-            .git_ignore(true)         // But still respect .gitignore
-            .git_exclude(true)        // Respect .git/info/exclude
-            .filter_entry(|e| {
-                // Never descend into .git directory
-                e.file_name() != ".git"
-            })
-            .build();
-
-        for entry in walker {
-            let entry = entry.wrap_err("Failed to walk directory")?;
-
-            if let Some(file_type) = entry.file_type() {
-                if !file_type.is_file() {
-                    continue;
-                }
-            }
+    if args.explain_selection {
+        return explain_selection(&args, format, render);
+    }
+    if args.flush_per_file && matches!(format, OutputFormat::Json | OutputFormat::Html) {
+        return Err(eyre!("--flush-per-file only writes console and --compact output"));
+    }
 
-            let path = entry.path();
-            log::debug!("Testing path: {:?} against glob", path);
-            if glob_set.is_match(path) {
-                log::debug!("  ✓ Matched!");
-                files.push(path.to_path_buf());
-            }
-        }
+    // Explicit --files bypass the directory walk and filters entirely
+    let mut files = if !args.files.is_empty() {
+        resolve_explicit_files(&args.files)?
     } else {
-        // Use ignore crate to walk files, respecting .gitignore
-        // BASE pattern: all TypeScript source files in src/ and scripts/ directories
-        let walker = WalkBuilder::new(".")
-            .standard_filters(true)  // Respects .gitignore, .ignore, etc.
-            .build();
-
-        for entry in walker {
+        let glob_set = source_glob_set(&args)?;
+        let mut files = Vec::new();
+        for entry in source_walker(&args).build() {
             let entry = entry.wrap_err("Failed to walk directory")?;
-
-            if let Some(file_type) = entry.file_type() {
-                if !file_type.is_file() {
-                    continue;
-                }
+            if entry.file_type().is_some_and(|t| t.is_file())
+                && select_file(entry.path(), &args, glob_set.as_ref()) == Selection::Included
+            {
+                files.push(entry.into_path());
             }
+        }
+        files
+    };
 
-            let path = entry.path();
-            let path_str = path.to_string_lossy();
+    // Narrow to files changed since the given git ref (filters above still apply)
+    if let Some(git_ref) = &args.since {
+        retain_changed_since(&mut files, git_ref)?;
+        if files.is_empty() {
+            status!(render, "No source files changed since {}.", git_ref);
+            return Ok(Outcome::Clean);
+        }
+    }
 
-            // BASE pattern: TypeScript files in src/ or scripts/ directories
-            let is_in_source_dir = path_str.contains("/src/") ||
-                                    path_str.contains("/scripts/") ||
-                                    path_str.starts_with("src/") ||
-                                    path_str.starts_with("scripts/");
+    if files.is_empty() {
+        return Err(eyre!("No source files found"));
+    }
 
-            let is_typescript = path_str.ends_with(".ts") || path_str.ends_with(".tsx");
+    status!(render, "Analyzing {} files...", files.len());
+    let started = Instant::now();
+    let result = if args.flush_per_file {
+        analyze_flushing_per_file(analyzer, &files, &args, format, render, &mut std::io::stdout())?
+    } else {
+        analyze_with_progress(analyzer, &files, render)?
+    };
+    report_failed_files(&result.failed_files, render);
+    if args.report_unused_ignores {
+        report_unused_ignores(&result.unused_ignores, render);
+    }
 
-            if !is_in_source_dir || !is_typescript {
-                continue;
-            }
+    let mut type_errors = result.type_errors;
+    apply_error_filters(&mut type_errors, &args);
 
-            // Filter out test files unless --include-tests is set
-            if !args.include_tests {
-                // Use .ends_with() to avoid false positives like "contest.ts"
-                if path_str.ends_with(".test.ts") ||
-                   path_str.ends_with(".spec.ts") ||
-                   path_str.ends_with(".test.tsx") ||
-                   path_str.ends_with(".spec.tsx") {
-                    continue;
+    if let Some(path) = &args.baseline {
+        // Files that were skipped or not selected keep their entries untouched
+        let analyzed: HashSet<String> = files
+            .iter()
+            .filter(|file| !result.failed_files.iter().any(|failed| &failed.file == *file))
+            .map(|file| BaselineEntry::path_of(&file.to_string_lossy()).to_string())
+            .collect();
+        let covered = |file: &str| analyzed.contains(file);
+        match Baseline::load(path)? {
+            Some(baseline) if !args.update_baseline => {
+                let comparison = baseline.covering(covered).compare(type_errors);
+                type_errors = comparison.new;
+                if comparison.known > 0 {
+                    status!(render, "{} known error{} suppressed by {}.", comparison.known, if comparison.known == 1 { "" } else { "s" }, path.display());
+                }
+                if args.report_fixed {
+                    report_fixed(&comparison.fixed, path, render);
                 }
             }
-
-            files.push(path.to_path_buf());
+            existing => {
+                let written = match existing {
+                    Some(baseline) => baseline.updated(covered, &type_errors),
+                    None => Baseline::from_errors(&type_errors),
+                };
+                written.save(path)?;
+                let count = written.errors.len();
+                status!(render, "Wrote {} error{} to baseline {}.", count, if count == 1 { "" } else { "s" }, path.display());
+                return Ok(Outcome::Clean);
+            }
         }
     }
 
-    // Apply user filters if provided (OR'd together)
-    // Multiple filters: ta source foo bar → files with "foo" OR "bar" in path
-    if !args.filters.is_empty() {
-        files.retain(|f| {
-            let path_str = f.to_string_lossy();
-            // Match if ANY filter is a substring of the path
-            args.filters.iter().any(|filter| path_str.contains(filter.as_str()))
-        });
+    if args.manifest {
+        let manifest = AnalysisManifest::new(files.len(), result.failed_files.len(), &type_errors, started.elapsed());
+        println!("{}", serde_json::to_string_pretty(&manifest)?);
+        return Ok(if args.fail_on.should_fail(&type_errors) { Outcome::ErrorsFound } else { Outcome::Clean });
     }
 
-    if files.is_empty() {
-        return Err(eyre!("No source files found"));
-    }
+    // Counted before --max-errors so the total covers every file
+    let any_count = type_errors.iter().filter(|e| e.id == ANY_ERROR_ID).count();
 
-    eprintln!("Analyzing {} files...", files.len());
-    let result = analyzer.analyze_files(&files)?;
+    // Limit errors
+    if type_errors.len() > args.max_errors {
+        type_errors.truncate(args.max_errors);
+    }
 
-    let mut type_errors = result.type_errors;
+    if args.compact {
+        if !args.flush_per_file {
+            print!("{}", OutputFormatter::format_type_errors_compact(&type_errors, render));
+        }
+        return Ok(if args.fail_on.should_fail(&type_errors) { Outcome::ErrorsFound } else { Outcome::Clean });
+    }
 
-    // Apply error filters with negative filter support (for filtering errors, not files)
-    if let Some(filter) = args.error_filter {
-        if let Some(negative_filter) = filter.strip_prefix('!') {
-            // Negative filter: exclude errors matching
-            type_errors.retain(|e| {
-                !e.message.contains(negative_filter) && !e.scope.contains(negative_filter)
-            });
+    let render = render
+        .clone()
+        .with_group_by_file(args.group_by_file)
+        .with_scope_depth(args.scope_depth.map(|depth| depth as usize), &args.scope_separator);
+    // With --flush-per-file each file's errors were printed during the analysis
+    if !args.flush_per_file {
+        let output = if args.ansi_to_html {
+            // Styling is what gets converted, so colors stay on even when piped
+            let console_render = render.clone().with_force_color(true);
+            let console = OutputFormatter::format_type_errors_with_options(&type_errors, OutputFormat::Console, &console_render);
+            format!("<pre class=\"{}\">{}</pre>", render.html.class("ansi-output"), ansi_to_html(&console))
         } else {
-            // Positive filter: include errors matching
-            type_errors.retain(|e| {
-                e.message.contains(&filter) || e.scope.contains(&filter)
-            });
-        }
+            OutputFormatter::format_type_errors_with_options(&type_errors, format, &render)
+        };
+        print_report(&output, &render);
     }
 
-    // Limit errors
-    if type_errors.len() > args.max_errors {
-        type_errors.truncate(args.max_errors);
+    if args.report_any {
+        status!(render, "Found {} explicit `any` usage{}.", any_count, if any_count == 1 { "" } else { "s" });
     }
 
-    let output = OutputFormatter::format_type_errors(&type_errors, format);
-    println!("{}", output);
+    let ok_marker = status_glyphs(&render).success;
 
     // Calculate file statistics
     if !type_errors.is_empty() {
@@ -245,49 +818,224 @@ pub fn handle_source(args: SourceArgs, format: OutputFormat, verbose: bool) -> R
             for file_path in &files {
                 let file_str = file_path.to_string_lossy().to_string();
                 if !files_with_errors.contains(&file_str) {
-                    eprintln!("- ✅ {} has no type errors", file_str.green());
+                    status!(render, "- {} {} has no type errors", ok_marker, render.paint(file_str.green()));
                 }
             }
-            eprintln!();
+            status!(render);
         }
 
         // Format error count in red/bold, files-without-errors in dim/italic
-        let error_count = format!("{}", type_errors.len()).red().bold();
-        let without_errors_msg = format!(
+        let error_count = render.paint(format!("{}", type_errors.len()).red().bold());
+        let without_errors_msg = render.paint(format!(
             "{} file{} without errors",
             files_without_errors_count,
             if files_without_errors_count == 1 { "" } else { "s" }
-        ).dimmed().italic();
+        ).dimmed().italic());
 
-        eprintln!(
-            "Found {} type error{} in {} file{} ({}).",
+        status!(render, 
+            "Found {} type error{} ({}) in {} file{} ({}).",
             error_count,
             if type_errors.len() == 1 { "" } else { "s" },
+            severity_breakdown(&type_errors),
             files_with_errors_count,
             if files_with_errors_count == 1 { "" } else { "s" },
             without_errors_msg
         );
 
-        // Return exit code 1 when type errors are found (per CLI best practices)
-        std::process::exit(1);
+        // Errors reaching the --fail-on threshold exit 1 (per CLI best practices)
+        if args.fail_on.should_fail(&type_errors) {
+            return Ok(Outcome::ErrorsFound);
+        }
     } else {
         // Show individual success messages when verbose
         if verbose {
             for file_path in &files {
-                eprintln!("- ✅ {} has no type errors", file_path.to_string_lossy().green());
+                status!(render, 
+                    "- {} {} has no type errors",
+                    ok_marker,
+                    render.paint(file_path.to_string_lossy().green())
+                );
             }
-            eprintln!();
+            status!(render);
         }
 
-        let file_count = format!("{}", files.len()).bold();
+        let file_count = render.paint(format!("{}", files.len()).bold());
         let preposition = if files.len() == 1 { "in" } else { "across" };
-        eprintln!(
-            "- ✅ no type errors found {} {} file{}",
+        status!(render, 
+            "- {} no type errors found {} {} file{}",
+            ok_marker,
             preposition,
             file_count,
             if files.len() == 1 { "" } else { "s" }
         );
     }
 
-    Ok(())
+    Ok(Outcome::Clean)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc_span::Span;
+    use ta_lib::models::SpanPosition;
+
+    fn error_with_severity(severity: ErrorSeverity) -> TypeError {
+        TypeError {
+            id: "TS0000".to_string(),
+            message: "message".to_string(),
+            severity,
+            file: "test.ts".to_string(),
+            line: 1,
+            column: 1,
+            scope: "global".to_string(),
+            block: String::new(),
+            source_code: None,
+            span: Span::new(0, 1),
+            position: SpanPosition::default(),
+            related: Vec::new(),
+        }
+    }
+
+    fn error_with_code(id: &str) -> TypeError {
+        TypeError { id: id.to_string(), ..error_with_severity(ErrorSeverity::Error) }
+    }
+
+    fn filtered_codes(only: &[&str], ignore: &[&str]) -> Vec<String> {
+        let mut errors = ["TS7006", "TS2322", "TS7006", "TS2339", "PARSE"].map(error_with_code).to_vec();
+        let to_vec = |codes: &[&str]| codes.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        filter_by_code(&mut errors, &to_vec(only), &to_vec(ignore));
+        errors.into_iter().map(|e| e.id).collect()
+    }
+
+    #[test]
+    fn test_ignore_code_removes_matching_errors() {
+        assert_eq!(filtered_codes(&[], &["TS7006"]), ["TS2322", "TS2339", "PARSE"]);
+        assert_eq!(filtered_codes(&[], &["ts70", "PARSE"]), ["TS2322", "TS2339"]);
+    }
+
+    #[test]
+    fn test_only_code_keeps_matching_errors() {
+        assert_eq!(filtered_codes(&["TS2322"], &[]), ["TS2322"]);
+        assert_eq!(filtered_codes(&["TS23"], &["TS2339"]), ["TS2322"]);
+        assert!(filtered_codes(&["TS9"], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_expand_braces() {
+        assert_eq!(
+            expand_braces("{src,scripts}/*.ts").unwrap(),
+            vec!["./src/*.ts".to_string(), "./scripts/*.ts".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_expand_braces_rejects_unclosed_brace() {
+        match expand_braces("src/**/*.{ts,tsx") {
+            Err(Error::InvalidGlob { offset, reason, .. }) => {
+                assert_eq!(offset, 9);
+                assert!(reason.contains("unclosed"), "{}", reason);
+            }
+            other => panic!("expected InvalidGlob, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_expand_braces_rejects_unmatched_closing_brace() {
+        assert!(matches!(
+            expand_braces("src}/*.ts"),
+            Err(Error::InvalidGlob { offset: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_build_glob_set_reports_invalid_class() {
+        match build_glob_set("{src,lib}/[abc.ts") {
+            Err(Error::InvalidGlob { pattern, expanded, offset, .. }) => {
+                assert_eq!(pattern, "{src,lib}/[abc.ts");
+                assert_eq!(expanded, "./src/[abc.ts");
+                assert_eq!(offset, 6);
+            }
+            other => panic!("expected InvalidGlob, got {:?}", other.map(|(_, p)| p)),
+        }
+    }
+
+    #[test]
+    fn test_build_glob_set_reports_invalid_range() {
+        match build_glob_set("src/[z-a].ts") {
+            Err(Error::InvalidGlob { offset, .. }) => assert_eq!(offset, 7),
+            other => panic!("expected InvalidGlob, got {:?}", other.map(|(_, p)| p)),
+        }
+    }
+
+    #[test]
+    fn test_warning_only_run_passes_by_default() {
+        let errors = vec![error_with_severity(ErrorSeverity::Warning)];
+        assert!(!FailOn::default().should_fail(&errors));
+    }
+
+    #[test]
+    fn test_warning_only_run_fails_with_fail_on_warning() {
+        let errors = vec![error_with_severity(ErrorSeverity::Warning)];
+        assert!(FailOn::Warning.should_fail(&errors));
+        assert!(FailOn::Info.should_fail(&errors));
+    }
+
+    #[test]
+    fn test_fail_on_never() {
+        let errors = vec![error_with_severity(ErrorSeverity::Error)];
+        assert!(!FailOn::Never.should_fail(&errors));
+    }
+
+    #[test]
+    fn test_severity_breakdown() {
+        let errors = vec![
+            error_with_severity(ErrorSeverity::Error),
+            error_with_severity(ErrorSeverity::Error),
+            error_with_severity(ErrorSeverity::Warning),
+        ];
+        assert_eq!(severity_breakdown(&errors), "2 errors, 1 warning");
+    }
+
+    /// Records everything written so far at each flush
+    #[derive(Default)]
+    struct FlushRecorder {
+        written: Vec<u8>,
+        flushed: Vec<String>,
+    }
+
+    impl Write for FlushRecorder {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.flushed.push(String::from_utf8_lossy(&self.written).into_owned());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_flush_per_file_flushes_each_file_before_the_next() {
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<PathBuf> = ["first", "clean", "second"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(format!("{name}.ts"));
+                let source = if *name == "clean" { "export const ok = 1;\n".to_string() } else { format!("let {name} = 1; let {name} = 2;\n") };
+                std::fs::write(&path, source).unwrap();
+                path
+            })
+            .collect();
+        let args = SourceArgs::parse_from(["source", "--flush-per-file", "--compact"]);
+        let mut out = FlushRecorder::default();
+
+        let analyzer = Analyzer::new(AnalysisOptions::default());
+        analyze_flushing_per_file(analyzer, &files, &args, OutputFormat::Console, &RenderOptions::new(), &mut out).unwrap();
+
+        // One flush per file with errors, each holding that file's errors and none after it
+        assert_eq!(out.flushed.len(), 2, "{:?}", out.flushed);
+        assert!(out.flushed[0].contains("first.ts") && !out.flushed[0].contains("second.ts"), "{:?}", out.flushed);
+        assert!(out.flushed[1].contains("second.ts"));
+    }
 }
@@ -1,8 +1,19 @@
 use clap::Parser;
 use color_eyre::eyre::{Result, Context, eyre};
-use ta_lib::analyzer::{Analyzer, AnalysisOptions};
-use ta_lib::output::{OutputFormatter, OutputFormat};
+use ta_lib::analyzer::{normalize_source, Analyzer, AnalysisOptions};
+use ta_lib::api_diff::{diff_exported_symbols, ApiChange, ApiChangeKind, SemverImpact};
+use ta_lib::dependencies::{ReferenceKind, SymbolReference};
+use ta_lib::models::SymbolInfo;
+use ta_lib::symbols::find_missing_docs;
+use ta_lib::output::{DetailLevel, OutputFormatter, OutputFormat, PathDisplay, RenderOptions};
+use globset::{Glob, GlobSetBuilder};
 use ignore::WalkBuilder;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use crate::exit::Outcome;
+use crate::status;
+use colored::Colorize;
+use crate::utils::{Declarations, IgnoreRules, TAIGNORE_FILENAME, analyze_with_progress, git_files_at, git_show_at, is_test_path, is_typescript_path, print_report, resolve_explicit_files, retain_changed_since};
 
 /// Extract symbols from source files
 #[derive(Parser, Debug)]
@@ -11,6 +22,26 @@ pub struct SymbolsArgs {
     #[arg(value_name = "FILTER")]
     pub filters: Vec<String>,
 
+    /// Analyze exactly these files (or directories) instead of walking the project
+    #[arg(long, value_name = "PATH", num_args = 1.., conflicts_with = "filters")]
+    pub files: Vec<PathBuf>,
+
+    /// Only walk N directory levels below the project root (`src/a.ts` is at depth 2)
+    #[arg(long, value_name = "N", conflicts_with = "files")]
+    pub max_depth: Option<usize>,
+
+    /// Worker threads for parallel analysis (`0` uses all cores, `1` analyzes files serially)
+    #[arg(short = 'j', long, value_name = "N", default_value_t = 0)]
+    pub jobs: usize,
+
+    /// Print per-file analysis durations to stderr, slowest first
+    #[arg(long)]
+    pub timings: bool,
+
+    /// Only analyze files changed since this git ref (e.g. `HEAD`, `origin/main`)
+    #[arg(long, value_name = "REF", conflicts_with = "files")]
+    pub since: Option<String>,
+
     /// Filter symbol names (prefix with ! for negative match)
     #[arg(short = 'n', long = "name")]
     pub symbol_filter: Option<String>,
@@ -18,75 +49,132 @@ pub struct SymbolsArgs {
     /// Only show exported symbols
     #[arg(short, long)]
     pub exported_only: bool,
+
+    /// How much of each symbol's shape to show (`full` lists every property and method with types)
+    #[arg(long, value_enum, default_value_t = DetailLevel::Summary)]
+    pub detail: DetailLevel,
+
+    /// Show only the symbol(s) with exactly this name, in full detail; errors when none match (repeatable)
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["symbol_filter", "references", "diff", "ctags", "require_docs"])]
+    pub get: Vec<String>,
+
+    /// List imports of and references to this symbol instead of symbols (follows import aliases)
+    #[arg(long, value_name = "NAME")]
+    pub references: Option<String>,
+
+    /// Report exported symbols added, removed or changed since this git ref; exits 1 on breaking changes
+    #[arg(long, value_name = "REF", conflicts_with_all = ["files", "since", "references"])]
+    pub diff: Option<String>,
+
+    /// Print a ctags `tags` file for Vim/Emacs instead of the report (e.g. `ta symbols --ctags > tags`)
+    #[arg(long, conflicts_with_all = ["references", "diff"])]
+    pub ctags: bool,
+
+    /// Print each file once as a header with its symbols indented beneath it
+    #[arg(long)]
+    pub group_by_file: bool,
+
+    /// Show only the first sentence of each JSDoc comment in console output (JSON keeps the full text)
+    #[arg(long)]
+    pub doc_summary: bool,
+
+    /// Warn about exported symbols without a JSDoc comment; exits 1 when any are missing
+    #[arg(long, conflicts_with_all = ["references", "diff", "ctags"])]
+    pub require_docs: bool,
+
+    /// Symbol name or glob (e.g. `internal*`) that `--require-docs` skips; repeatable
+    #[arg(long, value_name = "PATTERN", requires = "require_docs")]
+    pub allow_undocumented: Vec<String>,
+
+    /// Skip declaration files (`*.d.ts`)
+    #[arg(long)]
+    pub no_declarations: bool,
+
+    /// Only include declaration files (`*.d.ts`)
+    #[arg(long, conflicts_with = "no_declarations")]
+    pub declarations_only: bool,
 }
 
-pub fn handle_symbols(args: SymbolsArgs, format: OutputFormat) -> Result<()> {
+/// BASE pattern: TypeScript files in src/ or scripts/ directories, excluding tests
+fn is_base_source_path(path_str: &str) -> bool {
+    let is_in_source_dir = path_str.contains("/src/") ||
+                            path_str.contains("/scripts/") ||
+                            path_str.starts_with("src/") ||
+                            path_str.starts_with("scripts/");
+
+    is_in_source_dir && is_typescript_path(path_str) && !is_test_path(path_str)
+}
+
+pub fn handle_symbols(args: SymbolsArgs, format: OutputFormat, render: &RenderOptions) -> Result<Outcome> {
     log::debug!("Handling symbols command with args: {:?}", args);
 
     let options = AnalysisOptions {
         parallel: true,
+        jobs: args.jobs,
+        timings: args.timings,
         exported_only: args.exported_only,
+        references_to: args.references.clone(),
         ..Default::default()
     };
 
     let analyzer = Analyzer::new(options);
 
-    // Use ignore crate to walk files, respecting .gitignore
-    // BASE pattern: all TypeScript source files in src/ and scripts/ directories
-    let walker = WalkBuilder::new(".")
-        .standard_filters(true)
-        .build();
+    // Explicit --files bypass the directory walk and filters entirely
+    let mut files = if !args.files.is_empty() {
+        resolve_explicit_files(&args.files)?
+    } else {
+        // Use ignore crate to walk files, respecting .gitignore
+        // BASE pattern: all TypeScript source files in src/ and scripts/ directories
+        let walker = WalkBuilder::new(".")
+            .standard_filters(true)
+            .add_custom_ignore_filename(TAIGNORE_FILENAME)
+            .max_depth(args.max_depth)
+            .build();
 
-    let mut files = Vec::new();
-    for entry in walker {
-        let entry = entry.wrap_err("Failed to walk directory")?;
+        let mut files = Vec::new();
+        for entry in walker {
+            let entry = entry.wrap_err("Failed to walk directory")?;
 
-        if let Some(file_type) = entry.file_type() {
-            if !file_type.is_file() {
-                continue;
+            if let Some(file_type) = entry.file_type() {
+                if !file_type.is_file() {
+                    continue;
+                }
             }
-        }
 
-        let path = entry.path();
-        let path_str = path.to_string_lossy();
-
-        // BASE pattern: TypeScript files in src/ or scripts/ directories
-        let is_in_source_dir = path_str.contains("/src/") ||
-                                path_str.contains("/scripts/") ||
-                                path_str.starts_with("src/") ||
-                                path_str.starts_with("scripts/");
-
-        let is_typescript = path_str.ends_with(".ts") || path_str.ends_with(".tsx");
-
-        if !is_in_source_dir || !is_typescript {
-            continue;
-        }
-
-        // Exclude test files
-        if path_str.ends_with(".test.ts") ||
-           path_str.ends_with(".spec.ts") ||
-           path_str.ends_with(".test.tsx") ||
-           path_str.ends_with(".spec.tsx") {
-            continue;
+            let path = entry.path();
+            if is_selected(&path.to_string_lossy(), &args) {
+                files.push(path.to_path_buf());
+            }
         }
 
-        files.push(path.to_path_buf());
-    }
+        files
+    };
 
-    // Apply file path filters if provided (OR'd together)
-    if !args.filters.is_empty() {
-        files.retain(|f| {
-            let path_str = f.to_string_lossy();
-            args.filters.iter().any(|filter| path_str.contains(filter.as_str()))
-        });
+    // Narrow to files changed since the given git ref (filters above still apply)
+    if let Some(git_ref) = &args.since {
+        retain_changed_since(&mut files, git_ref)?;
+        if files.is_empty() {
+            status!(render, "No source files changed since {}.", git_ref);
+            return Ok(Outcome::Clean);
+        }
     }
 
     if files.is_empty() {
         return Err(eyre!("No source files found"));
     }
 
-    eprintln!("Extracting symbols from {} files...", files.len());
-    let result = analyzer.analyze_files(&files)?;
+    status!(render, "Extracting symbols from {} files...", files.len());
+    let result = analyze_with_progress(analyzer, &files, render)?;
+
+    if let Some(name) = &args.references {
+        report_references(name, result.references, format, render)?;
+        return Ok(Outcome::Clean);
+    }
+
+    if let Some(git_ref) = &args.diff {
+        let previous = symbols_at(git_ref, &args, render)?;
+        return report_api_diff(git_ref, &previous, &result.symbols, format, render);
+    }
 
     let mut symbols = result.symbols;
 
@@ -101,10 +189,246 @@ pub fn handle_symbols(args: SymbolsArgs, format: OutputFormat) -> Result<()> {
         }
     }
 
-    let output = OutputFormatter::format_symbols(&symbols, format);
-    println!("{}", output);
+    if args.ctags {
+        print!("{}", OutputFormatter::format_symbols_ctags(&symbols, &tag_sources(&symbols)));
+        status!(render, "Wrote {} tags.", symbols.len());
+        return Ok(Outcome::Clean);
+    }
+
+    if args.require_docs {
+        return report_missing_docs(&symbols, &args.allow_undocumented, format, render);
+    }
+
+    if !args.get.is_empty() {
+        symbols = get_symbols(symbols, &args.get)?;
+        let files: HashSet<&str> = symbols.iter().map(|s| s.file.as_str()).collect();
+        if files.len() > 1 {
+            status!(render, "{} symbols match, defined in {} files.", symbols.len(), files.len());
+        }
+    }
+
+    let detail = if args.get.is_empty() { args.detail } else { DetailLevel::Full };
+    let render = render
+        .clone()
+        .with_detail(detail)
+        .with_jsdoc_summary(args.doc_summary)
+        .with_group_by_file(args.group_by_file);
+    let output = OutputFormatter::format_symbols_with_options(&symbols, format, &render);
+    print_report(&output, &render);
+
+    status!(render, "Found {} symbols.", symbols.len());
+
+    Ok(Outcome::Clean)
+}
+
+/// Symbols named exactly one of `names` (`ta symbols --get`), in their original order.
+///
+/// Every name must match at least one symbol; a name defined in several files
+/// keeps all of its matches.
+fn get_symbols(symbols: Vec<SymbolInfo>, names: &[String]) -> Result<Vec<SymbolInfo>> {
+    let missing: Vec<&str> = names
+        .iter()
+        .filter(|name| !symbols.iter().any(|s| &s.name == *name))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(eyre!("No symbol named {}", missing.iter().map(|n| format!("'{}'", n)).collect::<Vec<_>>().join(", ")));
+    }
+
+    Ok(symbols.into_iter().filter(|s| names.contains(&s.name)).collect())
+}
+
+/// Text of each file with a symbol, for the search patterns of `--ctags`.
+///
+/// Normalized like the analyzed text, so symbol lines index the same lines;
+/// unreadable files are left out and their tags address the line number.
+fn tag_sources(symbols: &[SymbolInfo]) -> HashMap<String, String> {
+    let files: HashSet<&str> = symbols.iter().map(|s| s.file.as_str()).collect();
+    files
+        .into_iter()
+        .filter_map(|file| {
+            let text = std::fs::read_to_string(file).ok()?;
+            Some((file.to_string(), normalize_source(&text).into_owned()))
+        })
+        .collect()
+}
+
+/// Print a warning per undocumented exported symbol (`ta symbols --require-docs`)
+fn report_missing_docs(
+    symbols: &[SymbolInfo],
+    allowed: &[String],
+    format: OutputFormat,
+    render: &RenderOptions,
+) -> Result<Outcome> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in allowed {
+        builder.add(Glob::new(pattern).wrap_err_with(|| format!("Invalid --allow-undocumented pattern '{}'", pattern))?);
+    }
+    let allowed = builder.build().wrap_err("Invalid --allow-undocumented patterns")?;
+
+    let missing = find_missing_docs(symbols, |name| allowed.is_match(name));
+    let output = OutputFormatter::format_type_errors_with_options(&missing, format, render);
+    print_report(&output, render);
+
+    if missing.is_empty() {
+        status!(render, "All exported symbols are documented.");
+        Ok(Outcome::Clean)
+    } else {
+        status!(render, "{} exported symbol{} missing JSDoc.", missing.len(), if missing.len() == 1 { " is" } else { "s are" });
+        Ok(Outcome::ErrorsFound)
+    }
+}
+
+/// Whether the directory walk analyzes `path`: the BASE pattern, narrowed by
+/// the declaration flags and the `--filter`s (OR'd together)
+fn is_selected(path: &str, args: &SymbolsArgs) -> bool {
+    is_base_source_path(path)
+        && Declarations::from_flags(false, args.no_declarations, args.declarations_only, Declarations::Include).keeps(path)
+        && (args.filters.is_empty() || args.filters.iter().any(|filter| path.contains(filter.as_str())))
+}
+
+/// Symbols of the files the directory walk would select, as committed at `git_ref`
+///
+/// Fails when one of them does not parse: skipping it would make all of its
+/// exports look removed.
+fn symbols_at(git_ref: &str, args: &SymbolsArgs, render: &RenderOptions) -> Result<Vec<SymbolInfo>> {
+    let mut ignore = IgnoreRules::new();
+    let files: Vec<PathBuf> = git_files_at(git_ref)?
+        .into_iter()
+        .filter(|f| args.max_depth.is_none_or(|depth| f.components().count() <= depth))
+        .filter(|f| !ignore.is_ignored(f) && is_selected(&f.to_string_lossy(), args))
+        .collect();
+
+    status!(render, "Extracting symbols from {} files at {}...", files.len(), git_ref);
+    let analyzer = Analyzer::new(AnalysisOptions { exported_only: true, require_parse: true, ..Default::default() });
+    let mut symbols = Vec::new();
+    for file in &files {
+        let source = git_show_at(git_ref, file)?;
+        let analysis = analyzer
+            .analyze_source(&source, &file.to_string_lossy())
+            .wrap_err_with(|| format!("Failed to analyze {} at {}", file.display(), git_ref))?;
+        symbols.extend(analysis.symbols);
+    }
+    Ok(symbols)
+}
+
+/// Print the exported API changes since `git_ref` (`ta symbols --diff`)
+fn report_api_diff(
+    git_ref: &str,
+    previous: &[SymbolInfo],
+    current: &[SymbolInfo],
+    format: OutputFormat,
+    render: &RenderOptions,
+) -> Result<Outcome> {
+    // The walk yields `./src/x.ts` while git lists `src/x.ts`
+    let current: Vec<SymbolInfo> = current
+        .iter()
+        .cloned()
+        .map(|mut symbol| {
+            if let Some(file) = symbol.file.strip_prefix("./") {
+                symbol.file = file.to_string();
+            }
+            symbol
+        })
+        .collect();
+    let changes = diff_exported_symbols(previous, &current);
+    let impact = SemverImpact::of(&changes);
+
+    match format {
+        OutputFormat::Json => {
+            println!("{}", render.to_json(&serde_json::json!({
+                "since": git_ref,
+                "impact": impact,
+                "changes": changes,
+            })));
+        }
+        _ => {
+            if changes.is_empty() {
+                println!("No changes to exported symbols since {}.", git_ref);
+            }
+            let paths = PathDisplay::new(render.path_style, changes.iter().map(|c| c.file.as_str()));
+            let (breaking, compatible): (Vec<&ApiChange>, Vec<&ApiChange>) = changes.iter().partition(|c| c.breaking);
+            for (title, group) in [("Breaking changes:", breaking), ("Compatible changes:", compatible)] {
+                if group.is_empty() {
+                    continue;
+                }
+                println!("{}", if title.starts_with("Breaking") { render.paint(title.red().bold()) } else { render.paint(title.green().bold()) });
+                for change in group {
+                    let location = render.paint(paths.display(&change.file).blue());
+                    match (change.change, &change.before, &change.after) {
+                        (ApiChangeKind::SignatureChanged, Some(before), Some(after)) => {
+                            println!("  {} {} {}", render.paint("~ changed".yellow()), change.name, location);
+                            println!("      {} {}", render.paint("-".red()), before);
+                            println!("      {} {}", render.paint("+".green()), after);
+                        }
+                        (ApiChangeKind::Removed, Some(before), _) => {
+                            println!("  {} {} {}", render.paint("- removed".red()), before, location);
+                        }
+                        (_, _, after) => {
+                            println!("  {} {} {}", render.paint("+ added".green()), after.as_deref().unwrap_or(&change.name), location);
+                        }
+                    }
+                }
+                println!();
+            }
+        }
+    }
+
+    let impact_label = match impact {
+        SemverImpact::Major => "major",
+        SemverImpact::Minor => "minor",
+        SemverImpact::None => "none",
+    };
+    status!(render, "Found {} API changes since {} (semver impact: {}).", changes.len(), git_ref, impact_label);
+
+    Ok(if impact == SemverImpact::Major { Outcome::ErrorsFound } else { Outcome::Clean })
+}
+
+/// Print the import and usage sites of one symbol (`ta symbols --references`)
+fn report_references(
+    name: &str,
+    mut references: Vec<SymbolReference>,
+    format: OutputFormat,
+    render: &RenderOptions,
+) -> Result<()> {
+    references.sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
+    let paths = PathDisplay::new(render.path_style, references.iter().map(|r| r.file.as_str()));
+
+    match format {
+        OutputFormat::Json => {
+            println!("{}", render.to_json(&references));
+        }
+        _ => {
+            if references.is_empty() {
+                println!("No references to {} found.", name);
+            }
+            let mut current_file = None;
+            for reference in &references {
+                if current_file != Some(&reference.file) {
+                    if current_file.is_some() {
+                        println!();
+                    }
+                    println!("{}:", render.paint(paths.display(&reference.file).blue()));
+                    current_file = Some(&reference.file);
+                }
+                let location = render.paint(format!("{}:{}", reference.line, reference.column).dimmed());
+                match (&reference.kind, &reference.source) {
+                    (ReferenceKind::Import, Some(source)) if reference.local != name => println!(
+                        "  {} {} {} {} {}",
+                        location, render.paint("import".magenta()), name, render.paint(format!("as {}", reference.local).dimmed()), render.paint(format!("from {}", source).dimmed())
+                    ),
+                    (ReferenceKind::Import, Some(source)) => println!(
+                        "  {} {} {} {}",
+                        location, render.paint("import".magenta()), name, render.paint(format!("from {}", source).dimmed())
+                    ),
+                    _ => println!("  {} {}", location, reference.local),
+                }
+            }
+        }
+    }
 
-    eprintln!("Found {} symbols.", symbols.len());
+    let file_count = references.iter().map(|r| r.file.as_str()).collect::<std::collections::HashSet<_>>().len();
+    status!(render, "Found {} references to {} in {} files.", references.len(), name, file_count);
 
     Ok(())
 }
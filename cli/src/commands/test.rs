@@ -1,9 +1,12 @@
 use clap::Parser;
 use color_eyre::eyre::{Result, Context, eyre};
 use ta_lib::analyzer::{Analyzer, AnalysisOptions};
-use ta_lib::output::OutputFormat;
+use ta_lib::output::{OutputFormat, PathDisplay, RenderOptions};
 use ta_lib::models::TestStatus;
 use ignore::WalkBuilder;
+use std::path::PathBuf;
+use crate::status;
+use crate::utils::{Declarations, TAIGNORE_FILENAME, analyze_with_progress, is_test_path, resolve_explicit_files};
 
 /// Detect type tests in source files
 #[derive(Parser, Debug)]
@@ -12,64 +15,101 @@ pub struct TestArgs {
     #[arg(value_name = "FILTER")]
     pub filters: Vec<String>,
 
+    /// Analyze exactly these files (or directories) instead of walking the project
+    #[arg(long, value_name = "PATH", num_args = 1.., conflicts_with = "filters")]
+    pub files: Vec<PathBuf>,
+
+    /// Only walk N directory levels below the project root (`src/a.ts` is at depth 2)
+    #[arg(long, value_name = "N", conflicts_with = "files")]
+    pub max_depth: Option<usize>,
+
+    /// Worker threads for parallel analysis (`0` uses all cores, `1` analyzes files serially)
+    #[arg(short = 'j', long, value_name = "N", default_value_t = 0)]
+    pub jobs: usize,
+
+    /// Print per-file analysis durations to stderr, slowest first
+    #[arg(long)]
+    pub timings: bool,
+
     /// Only show failing tests
     #[arg(short, long)]
     pub failing: bool,
+
+    /// Pass or fail each test by whether its `@ts-expect-error`/`$ExpectError` comments
+    /// meet an error and no other error is reported inside it. Only parser and scope
+    /// errors are seen, not type errors, so a comment above a type mismatch fails
+    #[arg(long)]
+    pub check_assertions: bool,
+
+    /// Skip declaration files (`*.d.ts`) beneath `--files` directories; the walk only
+    /// picks test files, which are never declarations
+    #[arg(long)]
+    pub no_declarations: bool,
 }
 
-pub fn handle_test(args: TestArgs, format: OutputFormat) -> Result<()> {
+pub fn handle_test(args: TestArgs, format: OutputFormat, render: &RenderOptions) -> Result<()> {
     log::debug!("Handling test command with args: {:?}", args);
 
     let options = AnalysisOptions {
         parallel: true,
+        jobs: args.jobs,
+        timings: args.timings,
+        check_type_tests: args.check_assertions,
         ..Default::default()
     };
 
     let analyzer = Analyzer::new(options);
 
-    // Use ignore crate to walk files, respecting .gitignore
-    let walker = WalkBuilder::new(".")
-        .standard_filters(true)  // Respects .gitignore, .ignore, etc.
-        .build();
+    // Explicit --files bypass the directory walk and filters entirely
+    let mut files = if !args.files.is_empty() {
+        resolve_explicit_files(&args.files)?
+    } else {
+        // Use ignore crate to walk files, respecting .gitignore
+        let walker = WalkBuilder::new(".")
+            .standard_filters(true)  // Respects .gitignore, .ignore, etc.
+            .add_custom_ignore_filename(TAIGNORE_FILENAME)
+            .max_depth(args.max_depth)
+            .build();
+
+        let mut files = Vec::new();
+        for entry in walker {
+            let entry = entry.wrap_err("Failed to walk directory")?;
+
+            if let Some(file_type) = entry.file_type() {
+                if !file_type.is_file() {
+                    continue;
+                }
+            }
 
-    let mut files = Vec::new();
-    for entry in walker {
-        let entry = entry.wrap_err("Failed to walk directory")?;
+            let path = entry.path();
+            let path_str = path.to_string_lossy();
 
-        if let Some(file_type) = entry.file_type() {
-            if !file_type.is_file() {
-                continue;
+            // Only include test files
+            if is_test_path(&path_str) {
+                files.push(path.to_path_buf());
             }
         }
 
-        let path = entry.path();
-        let path_str = path.to_string_lossy();
-
-        // Only include test files
-        if path_str.ends_with(".test.ts") ||
-           path_str.ends_with(".spec.ts") ||
-           path_str.ends_with(".test.tsx") ||
-           path_str.ends_with(".spec.tsx") {
-            files.push(path.to_path_buf());
+        // Apply user filters if provided (OR'd together)
+        // Multiple filters: ta test foo bar → files with "foo" OR "bar" in path
+        if !args.filters.is_empty() {
+            files.retain(|f| {
+                let path_str = f.to_string_lossy();
+                // Match if ANY filter is a substring of the path
+                args.filters.iter().any(|filter| path_str.contains(filter.as_str()))
+            });
         }
-    }
 
-    // Apply user filters if provided (OR'd together)
-    // Multiple filters: ta test foo bar → files with "foo" OR "bar" in path
-    if !args.filters.is_empty() {
-        files.retain(|f| {
-            let path_str = f.to_string_lossy();
-            // Match if ANY filter is a substring of the path
-            args.filters.iter().any(|filter| path_str.contains(filter.as_str()))
-        });
-    }
+        files
+    };
+    Declarations::from_flags(false, args.no_declarations, false, Declarations::Include).retain(&mut files);
 
     if files.is_empty() {
         return Err(eyre!("No test files found"));
     }
 
-    eprintln!("Scanning {} files for tests...", files.len());
-    let result = analyzer.analyze_files(&files)?;
+    status!(render, "Scanning {} files for tests...", files.len());
+    let result = analyze_with_progress(analyzer, &files, render)?;
 
     let mut tests = result.tests;
 
@@ -79,19 +119,20 @@ pub fn handle_test(args: TestArgs, format: OutputFormat) -> Result<()> {
 
     match format {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&tests).unwrap());
+            println!("{}", render.to_json(&tests));
         }
         _ => {
+            let paths = PathDisplay::new(render.path_style, tests.iter().map(|t| t.file.as_str()));
             for test in &tests {
                 println!(
-                    "[{:?}] {} > {} ({}:{})",
-                    test.status, test.describe_block, test.test_name, test.file, test.line
+                    "[{:?}] {} > {} ({}:{}:{})",
+                    test.status, test.describe_block, test.test_name, paths.display(&test.file), test.line, test.column
                 );
             }
         }
     }
 
-    eprintln!("Found {} tests.", tests.len());
+    status!(render, "Found {} tests.", tests.len());
 
     Ok(())
 }
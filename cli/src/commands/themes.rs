@@ -0,0 +1,47 @@
+use clap::Parser;
+use color_eyre::eyre::Result;
+use ta_lib::highlighting::themes::list_available_themes;
+use ta_lib::highlighting::{highlight_code, HighlightError, HighlightOptions};
+use ta_lib::output::RenderOptions;
+
+/// Snippet highlighted under each theme name by `ta list-themes --preview`
+const PREVIEW_SNIPPET: &str = "\
+interface User { name: string; age?: number }
+// Greet a user by name
+export const greet = (user: User): string => `Hello, ${user.name}!`;
+";
+
+/// List available syntax highlighting themes
+#[derive(Parser, Debug)]
+pub struct ListThemesArgs {
+    /// Show a highlighted TypeScript sample under each theme name
+    #[arg(long)]
+    pub preview: bool,
+}
+
+pub fn handle_list_themes(args: ListThemesArgs, render: &RenderOptions) -> Result<()> {
+    log::debug!("Handling list-themes command with args: {:?}", args);
+
+    // Previews are nothing but color, so NO_COLOR and --plain get the name list
+    let preview = args.preview && !render.plain && std::env::var_os("NO_COLOR").is_none();
+
+    println!("Available themes:");
+    for theme in list_available_themes() {
+        if !preview {
+            println!("  {}", theme);
+            continue;
+        }
+
+        println!();
+        println!("{}", theme);
+        let options = HighlightOptions::new("js").with_theme(&theme).with_indent(2);
+        match highlight_code(PREVIEW_SNIPPET, options) {
+            Ok(highlighted) => print!("{}", highlighted.render_console()),
+            // Some listed names are not bundled in syntect's default theme set
+            Err(HighlightError::ThemeNotFound { .. }) => println!("  (not bundled in this build)"),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(())
+}
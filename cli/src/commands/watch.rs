@@ -1,9 +1,13 @@
 use clap::Parser;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use color_eyre::eyre::{Result, Context};
-use ta_lib::watcher::{FileWatcher, WatchEvent, WatchHandler};
+use ta_lib::watcher::{FileWatcher, FilteredHandler, WatchEvent, WatchHandler};
 use ta_lib::analyzer::AnalysisOptions;
-use ta_lib::output::OutputFormat;
+use ta_lib::output::{OutputFormat, RenderOptions};
+use crate::commands::preflight::CONFIG_FILENAME;
+use crate::utils::load_rules;
 
 /// Watch for file changes and run analysis
 #[derive(Parser, Debug)]
@@ -11,6 +15,18 @@ pub struct WatchArgs {
     /// Paths to watch
     #[arg(default_value = ".")]
     pub paths: Vec<PathBuf>,
+
+    /// Worker threads for parallel analysis (`0` uses all cores, `1` analyzes files serially)
+    #[arg(short = 'j', long, value_name = "N", default_value_t = 0)]
+    pub jobs: usize,
+
+    /// Give up on a file after this many milliseconds of analysis and report it as skipped
+    #[arg(long, value_name = "MS")]
+    pub timeout_ms: Option<u64>,
+
+    /// Only report these events (comma-separated, e.g. `symbol-added,test-fixed`)
+    #[arg(long, value_name = "EVENTS", value_delimiter = ',')]
+    pub events: Vec<String>,
 }
 
 struct CliWatchHandler {
@@ -26,14 +42,17 @@ impl WatchHandler for CliWatchHandler {
             WatchEvent::SymbolRemoved { name, file } => {
                 println!("[-] Symbol Removed: {} from {}", name, file);
             }
-            WatchEvent::TestStatusChanged { file, test, status } => {
-                println!("[*] Test Status Changed: {} > {} -> {:?}", file, test, status);
+            WatchEvent::TestStatusChanged { file, test, status, line, column, .. } => {
+                println!("[*] Test Status Changed: {}:{}:{} > {} -> {:?}", file, line, column, test, status);
+            }
+            WatchEvent::NewFailingTest { file, test, line, column, .. } => {
+                println!("[!] NEW FAILING TEST: {}:{}:{} > {}", file, line, column, test);
             }
-            WatchEvent::NewFailingTest { file, test } => {
-                println!("[!] NEW FAILING TEST: {} > {}", file, test);
+            WatchEvent::TestFixed { file, test, line, column, .. } => {
+                println!("[OK] Test Fixed: {}:{}:{} > {}", file, line, column, test);
             }
-            WatchEvent::TestFixed { file, test } => {
-                println!("[OK] Test Fixed: {} > {}", file, test);
+            WatchEvent::TestRemoved { file, test, line, column, .. } => {
+                println!("[-] Test Removed: {}:{}:{} > {}", file, line, column, test);
             }
             _ => {
                 println!("[?] Other Event: {:?}", event);
@@ -43,16 +62,41 @@ impl WatchHandler for CliWatchHandler {
     }
 }
 
-pub fn handle_watch(args: WatchArgs, format: OutputFormat) -> Result<()> {
+/// Streams each event as one NDJSON line (`{"type":"SymbolAdded","data":{...}}`)
+struct JsonWatchHandler;
+
+impl WatchHandler for JsonWatchHandler {
+    fn handle_event(&self, event: &WatchEvent) -> ta_lib::Result<()> {
+        let line = serde_json::to_string(event)
+            .map_err(|e| ta_lib::Error::AnalysisError(format!("Failed to serialize watch event: {}", e)))?;
+
+        // Flush per event so consumers see it immediately even when stdout is a pipe
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}", line)?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+pub fn handle_watch(args: WatchArgs, format: OutputFormat, render: &RenderOptions) -> Result<()> {
     log::debug!("Handling watch command with args: {:?}", args);
 
     let options = AnalysisOptions {
         parallel: true,
+        jobs: args.jobs,
+        per_file_timeout: args.timeout_ms.map(Duration::from_millis),
+        rules: load_rules(Path::new(CONFIG_FILENAME))?,
         ..Default::default()
     };
 
-    let handler = Box::new(CliWatchHandler { _format: format });
-    let watcher = FileWatcher::new(options, vec![handler]);
+    let mut handler: Box<dyn WatchHandler> = match format {
+        OutputFormat::Json => Box::new(JsonWatchHandler),
+        _ => Box::new(CliWatchHandler { _format: format }),
+    };
+    if !args.events.is_empty() {
+        handler = Box::new(FilteredHandler::new(&args.events, handler)?);
+    }
+    let watcher = FileWatcher::new(options, vec![handler]).with_quiet(render.quiet);
 
     watcher.watch(&args.paths).wrap_err("File watcher failed")?;
 
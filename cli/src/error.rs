@@ -34,4 +34,25 @@ pub enum Error {
     /// logic errors involving valid but conflicting or nonsensical arguments.
     #[error("Invalid arguments: {0}")]
     InvalidArgs(String),
+
+    /// Represents a path passed explicitly (e.g. via `--files`) that does not exist.
+    #[error("Path not found: {}", .0.display())]
+    PathNotFound(std::path::PathBuf),
+
+    /// Represents a failed git invocation (e.g. not a repository or an unknown ref).
+    #[error("Git error: {0}")]
+    Git(String),
+
+    /// Represents a malformed `--glob` pattern.
+    ///
+    /// `expanded` is the brace-expanded sub-pattern that failed (equal to
+    /// `pattern` when the braces themselves are unbalanced) and `offset` is
+    /// the character offset of the failure within it.
+    #[error("Invalid glob pattern '{expanded}' (from '{pattern}') at offset {offset}: {reason}")]
+    InvalidGlob {
+        pattern: String,
+        expanded: String,
+        offset: usize,
+        reason: String,
+    },
 }
\ No newline at end of file
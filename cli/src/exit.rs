@@ -0,0 +1,48 @@
+//! Process exit codes shared by every command.
+//!
+//! Command handlers report what they found as an [`Outcome`]; `main` turns it
+//! into the process exit code so the contract lives in one place:
+//!
+//! - `0` - clean run, nothing to report
+//! - `1` - the command found problems (type errors, unresolved imports)
+//! - `2` - usage or I/O error (bad arguments, missing files, failed reads)
+//!
+//! `ta lsp` is the only exception: it exits as the LSP `exit` notification requires.
+
+/// Exit code for a clean run
+pub const EXIT_CLEAN: i32 = 0;
+
+/// Exit code when the command found problems
+pub const EXIT_ERRORS_FOUND: i32 = 1;
+
+/// Exit code for usage and I/O errors (matches clap's own usage errors)
+pub const EXIT_USAGE: i32 = 2;
+
+/// What a command found, independent of how the process should exit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Clean,
+    ErrorsFound,
+}
+
+impl Outcome {
+    /// Exit code for this outcome; `exit_zero` (`--exit-zero`) reports found errors as success.
+    pub fn exit_code(self, exit_zero: bool) -> i32 {
+        match self {
+            Outcome::ErrorsFound if !exit_zero => EXIT_ERRORS_FOUND,
+            _ => EXIT_CLEAN,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_zero_only_overrides_found_errors() {
+        assert_eq!(Outcome::Clean.exit_code(false), EXIT_CLEAN);
+        assert_eq!(Outcome::ErrorsFound.exit_code(false), EXIT_ERRORS_FOUND);
+        assert_eq!(Outcome::ErrorsFound.exit_code(true), EXIT_CLEAN);
+    }
+}
@@ -1,19 +1,28 @@
 use clap::{Parser, Subcommand};
 use color_eyre::eyre::{Result, WrapErr};
 use std::path::PathBuf;
-use ta_lib::output::OutputFormat;
+use ta_lib::highlighting::{HtmlOptions, IndentUnit};
+use ta_lib::output::{hyperlinks_supported, OutputFormat, PathStyle, RenderOptions};
 use colored::control;
 
 pub mod error;
 pub mod commands;
+pub mod exit;
 pub mod utils;
 
+use exit::{Outcome, EXIT_USAGE};
+use utils::{pager_mode, progress_enabled, select_glyphs};
 use commands::source::{handle_source, SourceArgs};
 use commands::symbols::{handle_symbols, SymbolsArgs};
 use commands::test::{handle_test, TestArgs};
 use commands::file::{handle_file, FileArgs};
 use commands::deps::{handle_deps, DepsArgs};
 use commands::watch::{handle_watch, WatchArgs};
+use commands::highlight::{handle_highlight, HighlightArgs};
+use commands::explain::{handle_explain, ExplainArgs};
+use commands::lsp::handle_lsp;
+use commands::themes::{handle_list_themes, ListThemesArgs};
+use commands::preflight::handle_preflight;
 
 #[derive(Parser)]
 #[command(name = "ta")]
@@ -24,14 +33,30 @@ pub struct Cli {
     #[arg(short, long, global = true, value_name = "PATH")]
     pub dir: Option<PathBuf>,
 
-    /// Output as JSON instead of console format
-    #[arg(long, global = true, conflicts_with = "html")]
+    /// Output format
+    #[arg(long, global = true, value_enum, value_name = "FORMAT", conflicts_with_all = ["json", "html", "markdown"])]
+    pub format: Option<OutputFormat>,
+
+    /// Deprecated alias for `--format json`
+    #[arg(long, global = true, conflicts_with_all = ["html", "markdown"])]
     pub json: bool,
 
-    /// Output as HTML instead of console format
-    #[arg(long, global = true, conflicts_with = "json")]
+    /// Deprecated alias for `--format html`
+    #[arg(long, global = true, conflicts_with_all = ["json", "markdown"])]
     pub html: bool,
 
+    /// Collapse the indentation of HTML output to single spaces (`<pre>` code blocks are kept as is)
+    #[arg(long, global = true)]
+    pub html_minify: bool,
+
+    /// Prefix every class name in HTML output (e.g. `ta-` emits `ta-error-highlight`)
+    #[arg(long, global = true, value_name = "PREFIX", default_value = "")]
+    pub html_class_prefix: String,
+
+    /// Deprecated alias for `--format markdown`
+    #[arg(long, global = true, conflicts_with_all = ["json", "html"])]
+    pub markdown: bool,
+
     /// Enable verbose logging
     #[arg(short, long, global = true)]
     pub verbose: bool,
@@ -48,10 +73,71 @@ pub struct Cli {
     #[arg(long, global = true, env = "TA_DARK_THEME")]
     pub dark_theme: Option<String>,
 
+    /// Plain ASCII console output (no colors or box-drawing), suitable for logs
+    #[arg(long, global = true)]
+    pub plain: bool,
+
+    /// ASCII markers and borders (`[X]`, `[OK]`, `+--+`) but keep colors; also set by `TA_GLYPHS=ascii`
+    #[arg(long, global = true)]
+    pub ascii: bool,
+
+    /// Only include these top-level fields in JSON output (comma-separated)
+    #[arg(long, global = true, value_name = "FIELDS", value_delimiter = ',')]
+    pub fields: Vec<String>,
+
+    /// Suppress progress indicators and status lines on stderr
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Never wrap file paths in clickable OSC8 hyperlinks
+    #[arg(long, global = true)]
+    pub no_hyperlinks: bool,
+
+    /// Indent unit for code blocks, `tab` or a number of spaces (default: detected per file)
+    #[arg(long, global = true, value_name = "N|tab")]
+    pub indent: Option<IndentUnit>,
+
+    /// Page console reports through `$PAGER` (default `less -R`) even when they fit the terminal
+    #[arg(long, global = true, conflicts_with = "no_pager")]
+    pub pager: bool,
+
+    /// Never page console reports (the default is to page reports taller than the terminal)
+    #[arg(long, global = true)]
+    pub no_pager: bool,
+
+    /// Exit 0 even when errors are found (output is still printed); usage and I/O errors still exit 2
+    #[arg(long, global = true)]
+    pub exit_zero: bool,
+
+    /// Console width in columns (default: detected terminal width, or 80)
+    #[arg(long, global = true, value_name = "N")]
+    pub width: Option<usize>,
+
+    /// How file paths are displayed in console and HTML output
+    #[arg(long, global = true, value_enum, default_value_t = PathStyle::Relative)]
+    pub path_style: PathStyle,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+impl Cli {
+    /// The output format chosen by `--format` or one of its deprecated aliases
+    pub fn output_format(&self) -> OutputFormat {
+        if let Some(format) = self.format {
+            format
+        } else if self.json {
+            OutputFormat::Json
+        } else if self.html {
+            OutputFormat::Html
+        } else if self.markdown {
+            OutputFormat::Markdown
+        } else {
+            OutputFormat::Console
+        }
+    }
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Analyze source files for type errors
@@ -66,8 +152,18 @@ pub enum Commands {
     Deps(DepsArgs),
     /// Watch for file changes and run analysis
     Watch(WatchArgs),
-    /// List available syntax highlighting themes
-    ListThemes,
+    /// Syntax-highlight code from stdin or a file (use --json for editor tokens)
+    Highlight(HighlightArgs),
+    /// Explain a TypeScript error code, e.g. `ta explain TS2322`
+    Explain(ExplainArgs),
+    /// Publish type errors as LSP diagnostics over stdio (minimal editor integration)
+    Lsp,
+    /// List available syntax highlighting themes (`--preview` shows each one)
+    ListThemes(ListThemesArgs),
+    /// List languages (and file extensions) supported by `ta highlight`
+    ListLanguages,
+    /// Validate ta.toml, tsconfig.json and themes, and time loading the highlighting data
+    Preflight,
 }
 
 fn setup_colors() {
@@ -88,7 +184,20 @@ fn main() -> Result<()> {
     setup_colors();
 
     let cli = Cli::parse();
+    let exit_zero = cli.exit_zero;
+
+    // All exit codes are decided here (see `exit`), never inside command handlers
+    let code = match run(cli) {
+        Ok(outcome) => outcome.exit_code(exit_zero),
+        Err(report) => {
+            eprintln!("Error: {:?}", report);
+            EXIT_USAGE
+        }
+    };
+    std::process::exit(code)
+}
 
+fn run(cli: Cli) -> Result<Outcome> {
     // Change directory BEFORE doing anything else (critical for monorepo support)
     if let Some(dir) = &cli.dir {
         std::env::set_current_dir(dir)
@@ -97,32 +206,58 @@ fn main() -> Result<()> {
 
     setup_logging(cli.verbose);
 
-    // Derive OutputFormat from flags
-    let format = if cli.json {
-        OutputFormat::Json
-    } else if cli.html {
-        OutputFormat::Html
-    } else {
-        OutputFormat::Console
-    };
+    let format = cli.output_format();
+
+    let glyphs = select_glyphs(cli.plain || cli.ascii, std::env::var("TA_GLYPHS").ok().as_deref());
+    let mut render = RenderOptions::new()
+        .with_glyphs(glyphs)
+        .with_plain(cli.plain)
+        .with_path_style(cli.path_style)
+        .with_hyperlinks(!cli.no_hyperlinks && hyperlinks_supported())
+        .with_fields(cli.fields.clone())
+        .with_indent(cli.indent)
+        .with_minify_html(cli.html_minify)
+        .with_html(HtmlOptions::new().with_class_prefix(cli.html_class_prefix.clone()))
+        .with_quiet(cli.quiet)
+        .with_progress(progress_enabled(format, cli.quiet, atty::is(atty::Stream::Stderr)))
+        .with_pager(pager_mode(format, cli.pager, cli.no_pager, atty::is(atty::Stream::Stdout)));
+    // An explicit --width wins over the terminal, for reproducible output
+    let width = cli.width.or_else(|| {
+        terminal_size::terminal_size().map(|(terminal_size::Width(width), _)| width as usize)
+    });
+    if let Some(width) = width {
+        render = render.with_width(width);
+    }
 
     match cli.command {
-        Commands::Source(args) => handle_source(args, format, cli.verbose)?,
-        Commands::Symbols(args) => handle_symbols(args, format)?,
-        Commands::Test(args) => handle_test(args, format)?,
-        Commands::File(args) => handle_file(args, format)?,
-        Commands::Deps(args) => handle_deps(args, format)?,
-        Commands::Watch(args) => handle_watch(args, format)?,
-        Commands::ListThemes => {
-            let themes = ta_lib::highlighting::themes::list_available_themes();
-            println!("Available themes:");
-            for theme in themes {
-                println!("  {}", theme);
+        Commands::Source(args) => return handle_source(args, format, cli.verbose, &render),
+        Commands::Symbols(args) => return handle_symbols(args, format, &render),
+        Commands::Test(args) => handle_test(args, format, &render)?,
+        Commands::File(args) => return handle_file(args, format, &render),
+        Commands::Deps(args) => return handle_deps(args, format, &render),
+        Commands::Watch(args) => handle_watch(args, format, &render)?,
+        Commands::Highlight(args) => handle_highlight(args, format, &render)?,
+        Commands::Explain(args) => handle_explain(args, format, &render)?,
+        Commands::Lsp => handle_lsp()?,
+        Commands::ListThemes(args) => handle_list_themes(args, &render)?,
+        Commands::Preflight => {
+            let themes: Vec<&str> = [&cli.theme, &cli.light_theme, &cli.dark_theme].into_iter().flatten().map(String::as_str).collect();
+            return handle_preflight(&themes, format, &render);
+        }
+        Commands::ListLanguages => {
+            let languages = ta_lib::highlighting::supported_languages();
+            if format == OutputFormat::Json {
+                println!("{}", render.to_json(&languages));
+            } else {
+                println!("Available languages:");
+                for language in languages {
+                    println!("  {:<28} {}", language.name, language.extensions.join(", "));
+                }
             }
         }
     }
 
-    Ok(())
+    Ok(Outcome::Clean)
 }
 
 fn setup_logging(_verbose: bool) {
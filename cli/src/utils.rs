@@ -1,5 +1,68 @@
 //! Utility functions for CLI argument processing
 
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::{Match, WalkBuilder};
+use indicatif::{ProgressBar, ProgressStyle};
+use ta_lib::analyzer::{AnalysisResult, Analyzer, FileTiming};
+use ta_lib::glyphs::Glyphs;
+use ta_lib::output::{OutputFormat, PagerMode, PathDisplay, RenderOptions};
+use ta_lib::rules::CustomRule;
+
+use crate::error::Error;
+
+pub use ta_lib::walk::TAIGNORE_FILENAME;
+
+/// Analyses over fewer files than this finish too quickly to need a progress bar
+pub const PROGRESS_THRESHOLD: usize = 200;
+
+/// Print a status line to stderr unless `--quiet` was given
+///
+/// Status lines ("Analyzing N files...", summaries) never carry results, so
+/// they can be dropped without changing stdout or the exit code.
+#[macro_export]
+macro_rules! status {
+    ($render:expr) => {
+        if !$render.quiet {
+            eprintln!();
+        }
+    };
+    ($render:expr, $($arg:tt)*) => {
+        if !$render.quiet {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Glyphs for stderr status markers (`✅`, `⚠` or `[OK]`, `[!]`)
+///
+/// The render glyphs, except that `NO_COLOR` also forces ASCII, matching how colors are disabled.
+pub fn status_glyphs(render: &RenderOptions) -> Glyphs {
+    if std::env::var_os("NO_COLOR").is_some() {
+        Glyphs::ASCII
+    } else {
+        render.glyphs
+    }
+}
+
+/// Glyph set chosen by `--plain`/`--ascii`, then `TA_GLYPHS` (`ascii` or `unicode`), defaulting to Unicode
+pub fn select_glyphs(ascii: bool, env_value: Option<&str>) -> Glyphs {
+    if ascii {
+        return Glyphs::ASCII;
+    }
+    match env_value {
+        Some(name) => Glyphs::from_name(name).unwrap_or_else(|| {
+            log::warn!("Unknown TA_GLYPHS value '{}' ignored (expected `ascii` or `unicode`)", name);
+            Glyphs::UNICODE
+        }),
+        None => Glyphs::UNICODE,
+    }
+}
+
 /// Normalize a user-provided pattern by adding wildcards if not already glob-like
 ///
 /// This function examines the pattern for glob syntax characters at the start
@@ -45,10 +108,542 @@ pub fn normalize_glob_pattern(pattern: &str) -> String {
     }
 }
 
+/// File extensions (without the leading dot) treated as TypeScript sources
+pub const TYPESCRIPT_EXTENSIONS: [&str; 4] = ["ts", "tsx", "mts", "cts"];
+
+/// Check whether a path names a TypeScript source file (`.ts`, `.tsx`, `.mts`, `.cts`)
+///
+/// # Examples
+///
+/// ```rust
+/// use ta::utils::is_typescript_path;
+///
+/// assert!(is_typescript_path("src/index.ts"));
+/// assert!(is_typescript_path("src/esm.mts"));
+/// assert!(!is_typescript_path("src/index.js"));
+/// ```
+pub fn is_typescript_path(path: &str) -> bool {
+    TYPESCRIPT_EXTENSIONS
+        .iter()
+        .any(|ext| path.strip_suffix(ext).is_some_and(|rest| rest.ends_with('.')))
+}
+
+/// Check whether a path names a TypeScript test file (`*.test.ts`, `*.spec.mts`, ...)
+///
+/// Uses suffix matching so that files like `contest.ts` are not treated as tests.
+///
+/// # Examples
+///
+/// ```rust
+/// use ta::utils::is_test_path;
+///
+/// assert!(is_test_path("test/foo.test.ts"));
+/// assert!(is_test_path("test/foo.spec.cts"));
+/// assert!(!is_test_path("src/contest.ts"));
+/// ```
+pub fn is_test_path(path: &str) -> bool {
+    TYPESCRIPT_EXTENSIONS.iter().any(|ext| {
+        path.ends_with(&format!(".test.{}", ext)) || path.ends_with(&format!(".spec.{}", ext))
+    })
+}
+
+/// Check whether a path names a TypeScript declaration file (`.d.ts`, `.d.mts`, `.d.cts`)
+///
+/// # Examples
+///
+/// ```rust
+/// use ta::utils::is_declaration_path;
+///
+/// assert!(is_declaration_path("src/globals.d.ts"));
+/// assert!(is_declaration_path("types/index.d.mts"));
+/// assert!(!is_declaration_path("src/build.ts"));
+/// ```
+pub fn is_declaration_path(path: &str) -> bool {
+    ["d.ts", "d.mts", "d.cts"]
+        .iter()
+        .any(|ext| path.strip_suffix(ext).is_some_and(|rest| rest.ends_with('.')))
+}
+
+/// Which declaration files (`*.d.ts`) a walk keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Declarations {
+    Include,
+    Exclude,
+    Only,
+}
+
+impl Declarations {
+    /// The filter selected by a command's flags, `default` when none was given
+    pub fn from_flags(include: bool, exclude: bool, only: bool, default: Self) -> Self {
+        if only {
+            Self::Only
+        } else if exclude {
+            Self::Exclude
+        } else if include {
+            Self::Include
+        } else {
+            default
+        }
+    }
+
+    /// Whether this filter keeps the file at `path`
+    pub fn keeps(self, path: &str) -> bool {
+        match self {
+            Self::Include => true,
+            Self::Exclude => !is_declaration_path(path),
+            Self::Only => is_declaration_path(path),
+        }
+    }
+
+    /// Drop the files this filter does not keep
+    pub fn retain(self, files: &mut Vec<PathBuf>) {
+        if self != Self::Include {
+            files.retain(|f| self.keeps(&f.to_string_lossy()));
+        }
+    }
+}
+
+/// Resolve explicitly requested paths into the files to analyze
+///
+/// Files are returned as given; directories are expanded to the TypeScript
+/// files beneath them (respecting `.gitignore`). Every path must exist.
+pub fn resolve_explicit_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        if path.is_file() {
+            files.push(path.clone());
+        } else if path.is_dir() {
+            for entry in WalkBuilder::new(path)
+                .standard_filters(true)
+                .add_custom_ignore_filename(TAIGNORE_FILENAME)
+                .build() {
+                let entry = entry.map_err(|e| Error::Io(std::io::Error::other(e.to_string())))?;
+                if entry.file_type().is_some_and(|t| t.is_file())
+                    && is_typescript_path(&entry.path().to_string_lossy())
+                {
+                    files.push(entry.into_path());
+                }
+            }
+        } else {
+            return Err(Error::PathNotFound(path.clone()));
+        }
+    }
+
+    Ok(files)
+}
+
+/// TypeScript source files under `src/` or `scripts/`, test files excluded
+///
+/// This is the BASE pattern walked by `ta file` and `ta preflight`, respecting
+/// `.gitignore` and `.taignore`. With `max_depth`, only files within that many
+/// directory levels of the project root are walked.
+pub fn base_source_files(max_depth: Option<usize>) -> Result<Vec<PathBuf>, Error> {
+    let walker = WalkBuilder::new(".")
+        .standard_filters(true)
+        .add_custom_ignore_filename(TAIGNORE_FILENAME)
+        .max_depth(max_depth)
+        .build();
+
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry = entry.map_err(|e| Error::Io(std::io::Error::other(e.to_string())))?;
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+
+        let path_str = entry.path().to_string_lossy();
+        let is_in_source_dir = path_str.contains("/src/") ||
+                                path_str.contains("/scripts/") ||
+                                path_str.starts_with("src/") ||
+                                path_str.starts_with("scripts/");
+        if is_in_source_dir && is_typescript_path(&path_str) && !is_test_path(&path_str) {
+            files.push(entry.into_path());
+        }
+    }
+
+    Ok(files)
+}
+
+/// The `[[rules]]` of the `ta.toml` at `path`; none when the file does not exist
+pub fn load_rules(path: &Path) -> Result<Vec<CustomRule>, Error> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(Error::Io(e)),
+    };
+    let invalid = |e: toml::de::Error| Error::Config(format!("{}: {}", path.display(), e.message()));
+    let mut config = text.parse::<toml::Table>().map_err(invalid)?;
+    match config.remove("rules") {
+        Some(rules) => rules.try_into().map_err(invalid),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Keep only the files changed relative to `git_ref`
+///
+/// Shells out to `git diff --name-only --relative <REF>`, so both committed and
+/// uncommitted changes to tracked files count. Paths are compared after
+/// stripping a leading `./` as produced by the directory walk.
+pub fn retain_changed_since(files: &mut Vec<PathBuf>, git_ref: &str) -> Result<(), Error> {
+    let output = Command::new("git")
+        .args(["diff", "--name-only", "--relative", git_ref, "--"])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::Git(format!(
+            "could not list files changed since '{}': {}",
+            git_ref,
+            stderr.trim()
+        )));
+    }
+
+    let changed: HashSet<PathBuf> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(PathBuf::from)
+        .collect();
+
+    files.retain(|f| changed.contains(f.strip_prefix(".").unwrap_or(f)));
+    Ok(())
+}
+
+/// Tracked files under the current directory as of `git_ref`, relative to it
+///
+/// Shells out to `git ls-tree -r --name-only <REF>`, which lists paths relative
+/// to the current directory, so the result lines up with the directory walk.
+pub fn git_files_at(git_ref: &str) -> Result<Vec<PathBuf>, Error> {
+    let output = Command::new("git")
+        .args(["ls-tree", "-r", "--name-only", git_ref])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::Git(format!("could not list files at '{}': {}", git_ref, stderr.trim())));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).lines().map(PathBuf::from).collect())
+}
+
+/// The ignore rules of the directory walks, for paths that were not walked
+///
+/// Files listed by [`git_files_at`] come from git rather than the working tree,
+/// so nothing has filtered them yet. A path is ignored when it is hidden, or
+/// when a `.taignore`, `.ignore` or `.gitignore` in the working tree ignores it
+/// or one of its directories. As in the walks, rules in deeper directories win,
+/// and within a directory `.taignore` wins over `.ignore` over `.gitignore`.
+#[derive(Default)]
+pub struct IgnoreRules {
+    /// Matchers of each directory seen so far, highest precedence first
+    dirs: HashMap<PathBuf, Vec<Gitignore>>,
+}
+
+impl IgnoreRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the walks would skip `path`, relative to the current directory
+    pub fn is_ignored(&mut self, path: &Path) -> bool {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        let hidden = path
+            .components()
+            .any(|component| component.as_os_str().to_string_lossy().starts_with('.'));
+        if hidden {
+            return true;
+        }
+
+        for dir in path.ancestors().skip(1) {
+            for matcher in self.matchers(dir) {
+                match matcher.matched_path_or_any_parents(path, false) {
+                    Match::Ignore(_) => return true,
+                    Match::Whitelist(_) => return false,
+                    Match::None => {}
+                }
+            }
+        }
+        false
+    }
+
+    fn matchers(&mut self, dir: &Path) -> &[Gitignore] {
+        self.dirs.entry(dir.to_path_buf()).or_insert_with(|| {
+            let mut files = vec![dir.join(TAIGNORE_FILENAME), dir.join(".ignore"), dir.join(".gitignore")];
+            if dir.as_os_str().is_empty() {
+                files.push(PathBuf::from(".git/info/exclude"));
+            }
+            files
+                .into_iter()
+                .filter(|file| file.is_file())
+                .filter_map(|file| {
+                    let mut builder = GitignoreBuilder::new(dir);
+                    builder.add(&file);
+                    builder.build().ok()
+                })
+                .collect()
+        })
+    }
+}
+
+/// Contents of `path` (relative to the current directory) as of `git_ref`
+pub fn git_show_at(git_ref: &str, path: &std::path::Path) -> Result<String, Error> {
+    let output = Command::new("git")
+        .arg("show")
+        .arg(format!("{}:./{}", git_ref, path.display()))
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::Git(format!(
+            "could not read {} at '{}': {}",
+            path.display(),
+            git_ref,
+            stderr.trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whether a progress bar may be drawn at all
+///
+/// Never for JSON output (which is usually piped into another tool), under
+/// `--quiet`, or when stderr is not a terminal.
+pub fn progress_enabled(format: OutputFormat, quiet: bool, stderr_is_tty: bool) -> bool {
+    format != OutputFormat::Json && !quiet && stderr_is_tty
+}
+
+/// Pager used when `$PAGER` is unset; `-R` keeps ANSI colors
+pub const DEFAULT_PAGER: &str = "less -R";
+
+/// When console reports may be paged
+///
+/// Only console output to a terminal is paged: `--no-pager`, other formats and
+/// piped stdout always print directly. `--pager` pages every report, otherwise
+/// reports are paged only when taller than the terminal.
+pub fn pager_mode(format: OutputFormat, pager: bool, no_pager: bool, stdout_is_tty: bool) -> PagerMode {
+    if no_pager || format != OutputFormat::Console || !stdout_is_tty {
+        PagerMode::Never
+    } else if pager {
+        PagerMode::Always
+    } else {
+        PagerMode::Auto
+    }
+}
+
+/// Print a report to stdout, through `$PAGER` when [`RenderOptions::pager`] allows it
+///
+/// Falls back to printing directly when the pager cannot be started, so a
+/// missing `less` never loses output. Returns once the pager exits, leaving the
+/// caller free to set the exit code afterwards.
+pub fn print_report(output: &str, render: &RenderOptions) {
+    let page = match render.pager {
+        PagerMode::Never => false,
+        PagerMode::Always => true,
+        PagerMode::Auto => terminal_size::terminal_size()
+            .is_some_and(|(_, terminal_size::Height(height))| output.lines().count() >= height as usize),
+    };
+    if page && run_pager(output).is_ok() {
+        return;
+    }
+    println!("{}", output);
+}
+
+fn run_pager(output: &str) -> std::io::Result<()> {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+
+    let mut child = Command::new(program).args(parts).stdin(Stdio::piped()).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // Quitting the pager early closes the pipe; that is not an error
+        let _ = writeln!(stdin, "{}", output);
+    }
+    child.wait()?;
+    Ok(())
+}
+
+/// Run `analyzer` over `files`, showing a progress bar on stderr for large analyses
+///
+/// The bar only appears when [`RenderOptions::progress`] is set and more than
+/// [`PROGRESS_THRESHOLD`] files are analyzed. It is cleared before returning so
+/// it never interleaves with the results or the final summary.
+pub fn analyze_with_progress(
+    analyzer: Analyzer,
+    files: &[PathBuf],
+    render: &RenderOptions,
+) -> ta_lib::Result<AnalysisResult> {
+    let result = if !render.progress || files.len() <= PROGRESS_THRESHOLD {
+        analyzer.analyze_files(files)?
+    } else {
+        analyze_with_progress_bar(analyzer, files, render)?
+    };
+
+    // Only present with `--timings`, which asked for the table explicitly, so `--quiet` does not hide it
+    if !result.timings.is_empty() {
+        eprint!("{}", format_timings(&result.timings, render));
+    }
+    Ok(result)
+}
+
+fn analyze_with_progress_bar(
+    analyzer: Analyzer,
+    files: &[PathBuf],
+    render: &RenderOptions,
+) -> ta_lib::Result<AnalysisResult> {
+
+    let (template, chars) = if render.glyphs.is_ascii() {
+        ("[{bar:30}] {pos}/{len} files ({eta})", "=> ")
+    } else {
+        ("{spinner} [{bar:30.cyan/blue}] {pos}/{len} files ({eta})", "█▉▊▋▌▍▎▏ ")
+    };
+    let style = ProgressStyle::with_template(template)
+        .unwrap_or_else(|_| ProgressStyle::default_bar())
+        .progress_chars(chars);
+    let bar = ProgressBar::new(files.len() as u64).with_style(style);
+
+    let tick = bar.clone();
+    let result = analyzer.with_progress(move |_| tick.inc(1)).analyze_files(files);
+    bar.finish_and_clear();
+    result
+}
+
+/// Milliseconds with two decimals, e.g. `12.34ms`
+fn format_millis(duration: std::time::Duration) -> String {
+    format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Renders `--timings` as a table of files, slowest first, followed by per-phase totals.
+pub fn format_timings(timings: &[FileTiming], render: &RenderOptions) -> String {
+    let mut sorted: Vec<&FileTiming> = timings.iter().collect();
+    sorted.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.file.cmp(&b.file)));
+
+    let paths = PathDisplay::new(render.path_style, sorted.iter().map(|timing| timing.file.as_str()));
+    let mut out = format!(
+        "\n{:>10} {:>10} {:>10} {:>10}  file\n",
+        "total", "parse", "semantic", "extract"
+    );
+    for timing in &sorted {
+        out.push_str(&format!(
+            "{:>10} {:>10} {:>10} {:>10}  {}\n",
+            format_millis(timing.total),
+            format_millis(timing.parse),
+            format_millis(timing.semantic),
+            format_millis(timing.extraction),
+            paths.display(&timing.file),
+        ));
+    }
+
+    let sum = |phase: fn(&FileTiming) -> std::time::Duration| timings.iter().map(phase).sum();
+    out.push_str(&format!(
+        "Total {} across {} file{} (parse {}, semantic {}, extraction {})\n",
+        format_millis(sum(|t| t.total)),
+        timings.len(),
+        if timings.len() == 1 { "" } else { "s" },
+        format_millis(sum(|t| t.parse)),
+        format_millis(sum(|t| t.semantic)),
+        format_millis(sum(|t| t.extraction)),
+    ));
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_load_rules_from_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("ta.toml");
+        assert!(load_rules(&path).unwrap().is_empty());
+
+        std::fs::write(&path, "[[rules]]\nkind = \"call\"\ncallee = \"console.log\"\ncode = \"NO_CONSOLE\"\nmessage = \"Use the logger\"\nseverity = \"error\"\n").unwrap();
+        let rules = load_rules(&path).unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].code, "NO_CONSOLE");
+
+        std::fs::write(&path, "[[rules]]\nkind = \"call\"\n").unwrap();
+        assert!(matches!(load_rules(&path), Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn test_progress_disabled_for_json_quiet_and_non_tty() {
+        assert!(progress_enabled(OutputFormat::Console, false, true));
+        assert!(progress_enabled(OutputFormat::Html, false, true));
+        assert!(!progress_enabled(OutputFormat::Json, false, true));
+        assert!(!progress_enabled(OutputFormat::Console, true, true));
+        assert!(!progress_enabled(OutputFormat::Console, false, false));
+    }
+
+    #[test]
+    fn test_timings_table_lists_slowest_file_first() {
+        use std::time::Duration;
+
+        let timing = |file: &str, millis: u64| FileTiming {
+            file: file.to_string(),
+            total: Duration::from_millis(millis),
+            parse: Duration::from_millis(millis / 2),
+            ..Default::default()
+        };
+        let render = RenderOptions::new().with_path_style(ta_lib::output::PathStyle::Absolute);
+
+        let table = format_timings(&[timing("/fast.ts", 2), timing("/slow.ts", 30)], &render);
+        let rows: Vec<&str> = table.lines().filter(|line| line.ends_with(".ts")).collect();
+
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].contains("30.00ms") && rows[0].ends_with("/slow.ts"), "{}", table);
+        assert!(rows[1].ends_with("/fast.ts"), "{}", table);
+        assert!(table.contains("Total 32.00ms across 2 files (parse 16.00ms"), "{}", table);
+    }
+
+    #[test]
+    fn test_glyph_selection() {
+        assert_eq!(select_glyphs(true, Some("unicode")), Glyphs::ASCII);
+        assert_eq!(select_glyphs(false, Some("ascii")), Glyphs::ASCII);
+        assert_eq!(select_glyphs(false, Some("bogus")), Glyphs::UNICODE);
+        assert_eq!(select_glyphs(false, None), Glyphs::UNICODE);
+    }
+
+    #[test]
+    fn test_pager_only_for_console_on_a_terminal() {
+        assert_eq!(pager_mode(OutputFormat::Console, false, false, true), PagerMode::Auto);
+        assert_eq!(pager_mode(OutputFormat::Console, true, false, true), PagerMode::Always);
+        assert_eq!(pager_mode(OutputFormat::Console, true, true, true), PagerMode::Never);
+        assert_eq!(pager_mode(OutputFormat::Console, true, false, false), PagerMode::Never);
+        assert_eq!(pager_mode(OutputFormat::Json, true, false, true), PagerMode::Never);
+    }
+
+    #[test]
+    fn test_is_typescript_path_module_extensions() {
+        assert!(is_typescript_path("src/a.ts"));
+        assert!(is_typescript_path("src/a.tsx"));
+        assert!(is_typescript_path("src/a.mts"));
+        assert!(is_typescript_path("src/a.cts"));
+        assert!(!is_typescript_path("src/a.mjs"));
+        assert!(!is_typescript_path("src/ts"));
+    }
+
+    #[test]
+    fn test_is_test_path_module_extensions() {
+        assert!(is_test_path("a.test.ts"));
+        assert!(is_test_path("a.spec.tsx"));
+        assert!(is_test_path("a.test.mts"));
+        assert!(is_test_path("a.spec.cts"));
+        assert!(!is_test_path("contest.ts"));
+        assert!(!is_test_path("a.mts"));
+    }
+
+    #[test]
+    fn test_resolve_explicit_files_rejects_missing_path() {
+        let missing = PathBuf::from("does/not/exist.ts");
+        match resolve_explicit_files(std::slice::from_ref(&missing)) {
+            Err(Error::PathNotFound(path)) => assert_eq!(path, missing),
+            other => panic!("expected PathNotFound, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_normalize_glob_pattern_no_wildcards() {
         // Pattern with no glob syntax should get wildcards added
@@ -0,0 +1,6 @@
+// Aliased import: references to `validateEmail` are used through `isEmail`
+import { validateEmail as isEmail } from './utils';
+
+export function checkContact(email: string): boolean {
+    return isEmail(email);
+}
@@ -0,0 +1,5 @@
+export function parse(input: any): string[] {
+  const data = JSON.parse(input) as any;
+  const items: Array<any> = data.items;
+  return items.map((item) => String(item));
+}
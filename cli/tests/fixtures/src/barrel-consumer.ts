@@ -0,0 +1,5 @@
+import { primaryColor, circleArea } from './barrel';
+
+export function describeCircle(radius: number): string {
+    return `${primaryColor}: ${circleArea(radius)}`;
+}
@@ -0,0 +1 @@
+export const primaryColor = '#336699';
@@ -0,0 +1,3 @@
+// Barrel re-exporting from two modules
+export { primaryColor } from './colors';
+export * from './shapes';
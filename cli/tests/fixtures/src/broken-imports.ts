@@ -0,0 +1,8 @@
+// Relative import resolution: one valid local import, one missing, one package
+import { formatDate } from './utils';
+import { missingHelper } from './does-not-exist';
+import { something } from 'some-package';
+
+export function describe(date: Date): string {
+    return `${formatDate(date)} ${missingHelper()} ${something}`;
+}
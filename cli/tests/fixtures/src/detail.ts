@@ -0,0 +1,12 @@
+/**
+ * A user account with five fields.
+ */
+export interface Account {
+  id: string;
+  name: string;
+  email: string;
+  age: number;
+  active: boolean;
+}
+
+export type Point = { x: number; y: number };
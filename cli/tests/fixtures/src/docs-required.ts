@@ -0,0 +1,18 @@
+/** Formats a price in cents as dollars */
+export function formatPrice(cents: number): string {
+  return `$${(cents / 100).toFixed(2)}`;
+}
+
+export function parsePrice(text: string): number {
+  return Math.round(parseFloat(text.replace("$", "")) * 100);
+}
+
+/** @internal */
+export const PRICE_SCALE = 100;
+
+/**
+ * @param price Cents
+ */
+export function isFree(price: number): boolean {
+  return price === 0;
+}
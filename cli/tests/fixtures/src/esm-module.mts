@@ -0,0 +1,6 @@
+export function esmHelper(value: number): number {
+  return value * 2;
+}
+
+let esmValue = 1;
+let esmValue = 2; // Redeclaration error
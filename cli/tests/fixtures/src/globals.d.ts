@@ -0,0 +1,8 @@
+/** Version string injected by the bundler at build time */
+declare const __APP_VERSION__: string;
+
+/** Settings read from the environment at startup */
+export interface AppEnv {
+    apiUrl: string;
+    debug: boolean;
+}
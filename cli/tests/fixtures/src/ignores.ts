@@ -0,0 +1,6 @@
+// ta-ignore-next-line
+const shadowed = 1;
+const shadowed = 2;
+
+// ta-ignore-next-line TS2322
+export const clean = 3;
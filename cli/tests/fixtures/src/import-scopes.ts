@@ -0,0 +1,5 @@
+import { debounce } from 'lodash';
+import { formatDate } from '@/utils';
+import type { User } from './types';
+
+export const stamp = debounce((user: User) => `${user.name} at ${formatDate(new Date())}`, 100);
@@ -0,0 +1,10 @@
+// One exported function uses the import, the other does not
+import { formatDate } from './utils';
+
+export function describeDate(date: Date): string {
+    return `Date: ${formatDate(date)}`;
+}
+
+export function answer(): number {
+    return 42;
+}
@@ -0,0 +1,10 @@
+// Import hygiene: a module imported twice, an unused name, and type-only usage
+import { formatDate } from './utils';
+import { validateEmail, DEFAULT_TIMEOUT } from './utils';
+import type { User } from './types';
+import * as types from './types';
+
+export function describeUser(user: User, joined: Date): string {
+    const method = types.HttpMethod.GET;
+    return `${user.name} ${formatDate(joined)} ${validateEmail(user.email)} ${method}`;
+}
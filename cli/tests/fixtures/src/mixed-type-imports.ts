@@ -0,0 +1,6 @@
+// Value and type-only specifiers in one import
+import { formatDate, type DateStyle } from './utils';
+
+export function describeDate(joined: Date, style: DateStyle): string {
+    return `${style}: ${formatDate(joined)}`;
+}
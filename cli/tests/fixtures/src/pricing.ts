@@ -0,0 +1,8 @@
+/** Total price of `quantity` items after a fractional discount */
+export function calculate(price: number, quantity: number, discount: number = 0): number {
+    return price * quantity * (1 - discount);
+}
+
+export function calculateTax(amount: number): number {
+    return amount * 0.2;
+}
@@ -0,0 +1,8 @@
+export function total(prices: number[]): number {
+  let sum = 0;
+  for (const price of prices) {
+    const sum = price * 2;
+    console.log(sum);
+  }
+  return sum;
+}
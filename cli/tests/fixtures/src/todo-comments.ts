@@ -0,0 +1,5 @@
+// Marker comments for `ta source --flag-comments`
+export function retryDelay(attempt: number): number {
+    // TODO: fix backoff for large attempts
+    return attempt * 100;
+}
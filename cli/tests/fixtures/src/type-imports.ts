@@ -0,0 +1,7 @@
+// A type-only import next to a value import
+import type { User } from './types';
+import { formatDate } from './utils';
+
+export function describeUser(user: User, joined: Date): string {
+    return `${user.name} joined ${formatDate(joined)}`;
+}
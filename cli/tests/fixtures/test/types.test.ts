@@ -0,0 +1,18 @@
+import { describe, it, expectTypeOf } from 'vitest';
+
+describe('expectations', () => {
+    it('expects an error that never happens', () => {
+        // @ts-expect-error
+        const count: number = 1;
+    });
+
+    it('expects a redeclaration', () => {
+        let total = 1;
+        // @ts-expect-error
+        let total = 2;
+    });
+
+    it('infers numbers', () => {
+        expectTypeOf(1).toEqualTypeOf<number>();
+    });
+});
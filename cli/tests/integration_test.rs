@@ -46,6 +46,288 @@ fn test_symbols_extraction() {
         .stdout(predicate::str::contains("class"));
 }
 
+#[test]
+fn test_symbols_detail_full_lists_all_properties() {
+    let assert = ta_cmd_in_fixtures()
+        .arg("--plain")
+        .arg("symbols")
+        .arg("detail")
+        .arg("--detail")
+        .arg("full")
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout);
+    for field in ["id: string", "name: string", "email: string", "age: number", "active: boolean"] {
+        assert!(stdout.contains(field), "missing `{}` in:\n{}", field, stdout);
+    }
+}
+
+#[test]
+fn test_symbols_summary_truncates_properties() {
+    ta_cmd_in_fixtures()
+        .arg("--plain")
+        .arg("symbols")
+        .arg("detail")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("..."))
+        .stdout(predicate::str::contains("active: boolean").not());
+}
+
+#[test]
+fn test_symbols_explicit_files_only_analyzes_given_paths() {
+    ta_cmd_in_fixtures()
+        .arg("--json")
+        .arg("symbols")
+        .arg("--files")
+        .arg("src/simple.ts")
+        .arg("src/detail.ts")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("from 2 files"))
+        .stdout(predicate::str::contains("simple.ts"))
+        .stdout(predicate::str::contains("detail.ts"))
+        .stdout(predicate::str::contains("api.ts").not())
+        .stdout(predicate::str::contains("network.ts").not());
+}
+
+#[test]
+fn test_explicit_files_missing_path_errors() {
+    ta_cmd_in_fixtures()
+        .arg("source")
+        .arg("--files")
+        .arg("src/does-not-exist.ts")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Path not found: src/does-not-exist.ts"));
+}
+
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn test_since_only_analyzes_changed_files() {
+    let repo = tempfile::tempdir().unwrap();
+    let src = repo.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(src.join("stable.ts"), "export const stable = 1;\n").unwrap();
+    std::fs::write(src.join("edited.ts"), "export const edited = 1;\n").unwrap();
+
+    git(repo.path(), &["init", "-q"]);
+    git(repo.path(), &["add", "."]);
+    git(repo.path(), &["-c", "user.name=t", "-c", "user.email=t@t", "commit", "-qm", "init"]);
+
+    std::fs::write(src.join("edited.ts"), "export const edited = 2;\nexport function added() {}\n").unwrap();
+
+    ta_cmd()
+        .arg("--dir")
+        .arg(repo.path())
+        .arg("--json")
+        .arg("symbols")
+        .arg("--since")
+        .arg("HEAD")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("from 1 files"))
+        .stdout(predicate::str::contains("edited.ts"))
+        .stdout(predicate::str::contains("stable.ts").not());
+}
+
+#[test]
+fn test_symbols_diff_reports_removed_export_as_breaking() {
+    let repo = tempfile::tempdir().unwrap();
+    let src = repo.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(src.join("api.ts"), "export function keep(): void {}\nexport function gone(): void {}\n").unwrap();
+
+    git(repo.path(), &["init", "-q"]);
+    git(repo.path(), &["add", "."]);
+    git(repo.path(), &["-c", "user.name=t", "-c", "user.email=t@t", "commit", "-qm", "init"]);
+
+    std::fs::write(src.join("api.ts"), "export function keep(): void {}\nexport function added(): void {}\n").unwrap();
+
+    let output = ta_cmd()
+        .arg("--dir")
+        .arg(repo.path())
+        .args(["--json", "symbols", "--diff", "HEAD"])
+        .output()
+        .unwrap();
+
+    // Breaking changes exit 1, like type errors
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["impact"], "Major");
+    let change = |name: &str| {
+        report["changes"].as_array().unwrap().iter().find(|c| c["name"] == name).unwrap().clone()
+    };
+    assert_eq!(change("gone")["change"], "Removed");
+    assert_eq!(change("gone")["breaking"], true);
+    assert_eq!(change("added")["change"], "Added");
+    assert_eq!(change("added")["breaking"], false);
+    assert_eq!(report["changes"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_symbols_diff_skips_taignored_files_at_the_ref() {
+    let repo = tempfile::tempdir().unwrap();
+    let src = repo.path().join("src");
+    std::fs::create_dir_all(src.join("generated")).unwrap();
+    std::fs::write(src.join("api.ts"), "export function keep(): void {}\n").unwrap();
+    std::fs::write(src.join("generated/client.ts"), "export function generated(): void {}\n").unwrap();
+    std::fs::write(repo.path().join(".taignore"), "src/generated/\n").unwrap();
+
+    git(repo.path(), &["init", "-q"]);
+    git(repo.path(), &["add", "."]);
+    git(repo.path(), &["-c", "user.name=t", "-c", "user.email=t@t", "commit", "-qm", "init"]);
+
+    let output = ta_cmd()
+        .arg("--dir")
+        .arg(repo.path())
+        .args(["--json", "symbols", "--diff", "HEAD"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["impact"], "None");
+    assert_eq!(report["changes"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn test_symbols_diff_fails_when_a_file_at_the_ref_does_not_parse() {
+    let repo = tempfile::tempdir().unwrap();
+    let src = repo.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(src.join("api.ts"), "export function keep( {\n").unwrap();
+
+    git(repo.path(), &["init", "-q"]);
+    git(repo.path(), &["add", "."]);
+    git(repo.path(), &["-c", "user.name=t", "-c", "user.email=t@t", "commit", "-qm", "init"]);
+
+    std::fs::write(src.join("api.ts"), "export function keep(): void {}\n").unwrap();
+
+    ta_cmd()
+        .arg("--dir")
+        .arg(repo.path())
+        .args(["symbols", "--diff", "HEAD"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Failed to analyze src/api.ts at HEAD"));
+}
+
+#[test]
+fn test_since_outside_git_repo_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/a.ts"), "export const a = 1;\n").unwrap();
+
+    ta_cmd()
+        .arg("--dir")
+        .arg(dir.path())
+        .arg("source")
+        .arg("--since")
+        .arg("HEAD")
+        .env("GIT_CEILING_DIRECTORIES", dir.path().parent().unwrap())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Git error: could not list files changed since 'HEAD'"));
+}
+
+#[test]
+fn test_source_warns_about_unreadable_files() {
+    ta_cmd_in_fixtures()
+        .arg("--plain")
+        .arg("source")
+        .arg("--files")
+        .arg("src/simple.ts")
+        .arg("src/barrel")
+        .arg("../../Cargo.toml")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("[!] Skipped 1 file that could not be analyzed"))
+        .stderr(predicate::str::contains("Cargo.toml (unsupported source type)"));
+}
+
+#[test]
+fn test_no_hyperlinks_flag_omits_osc8() {
+    ta_cmd_in_fixtures()
+        .env("FORCE_HYPERLINKS", "1")
+        .arg("--no-hyperlinks")
+        .arg("source")
+        .arg("with-errors-legacy")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("with-errors-legacy.ts"))
+        .stdout(predicate::str::contains("\x1b]8;;").not());
+}
+
+#[test]
+fn test_force_hyperlinks_emits_osc8() {
+    ta_cmd_in_fixtures()
+        .env("FORCE_HYPERLINKS", "1")
+        .arg("source")
+        .arg("with-errors-legacy")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("\x1b]8;;file://"));
+}
+
+#[test]
+fn test_source_group_by_file_prints_path_once() {
+    let assert = ta_cmd_in_fixtures()
+        .arg("--plain")
+        .arg("source")
+        .arg("errors.ts")
+        .arg("--group-by-file")
+        .assert()
+        .code(1);
+
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout);
+    assert_eq!(stdout.matches("errors.ts").count(), 1, "{}", stdout);
+    assert!(stdout.contains("errors.ts (2 errors)"), "{}", stdout);
+}
+
+#[test]
+fn test_json_fields_projection() {
+    let assert = ta_cmd_in_fixtures()
+        .arg("--json")
+        .arg("--fields")
+        .arg("file,message")
+        .arg("symbols")
+        .arg("detail")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Unknown field 'message'"));
+
+    let json: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    let symbols = json.as_array().unwrap();
+    assert!(!symbols.is_empty());
+    for symbol in symbols {
+        let keys: Vec<&String> = symbol.as_object().unwrap().keys().collect();
+        assert_eq!(keys, vec!["file"]);
+    }
+}
+
+#[test]
+fn test_symbols_markdown_output() {
+    ta_cmd_in_fixtures()
+        .arg("--markdown")
+        .arg("symbols")
+        .arg("detail")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| Name |"))
+        .stdout(predicate::str::contains("## Interfaces"))
+        .stdout(predicate::str::contains("| `Account` |"));
+}
+
 #[test]
 fn test_deps_analysis() {
     // Note: deps command shows symbol-level dependencies for EXPORTED symbols only
@@ -68,6 +350,113 @@ fn test_file_details() {
         .stdout(predicate::str::contains("./"));  // Should contain local imports like ./types, ./utils
 }
 
+#[test]
+fn test_file_group_imports_by_scope() {
+    ta_cmd_in_fixtures()
+        .arg("file")
+        .arg("--group-imports")
+        .arg("import-scopes")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"External \(1\)\n    → lodash \(external\)").unwrap())
+        .stdout(predicate::str::is_match(r"Alias \(1\)\n    → @/utils").unwrap())
+        .stdout(predicate::str::is_match(r"Relative \(1\)\n    → \S*src/types.ts").unwrap());
+
+    let output = ta_cmd_in_fixtures()
+        .args(["--json", "file", "import-scopes"])
+        .output()
+        .unwrap();
+    let files: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let scopes: Vec<(&str, &str)> = files[0]["imports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| (i["source"].as_str().unwrap(), i["scope"].as_str().unwrap()))
+        .collect();
+    assert_eq!(scopes, vec![("lodash", "External"), ("@/utils", "Repo"), ("./types", "Local")]);
+}
+
+#[test]
+fn test_file_lint_reports_duplicate_and_unused_imports() {
+    ta_cmd_in_fixtures()
+        .arg("file")
+        .arg("--lint")
+        .arg("lint-imports")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("'./utils' is already imported on line 2"))
+        .stdout(predicate::str::contains("Import 'DEFAULT_TIMEOUT' from './utils' is never used"))
+        .stdout(predicate::str::contains("User").not())
+        .stdout(predicate::str::contains("types").not())
+        .stderr(predicate::str::contains("Found 2 import issues in 1 file."));
+}
+
+#[test]
+fn test_file_imports_disambiguate_colliding_basenames() {
+    let project = tempfile::tempdir().unwrap();
+    let src = project.path().join("src");
+    for dir in ["a", "b"] {
+        std::fs::create_dir_all(src.join(dir)).unwrap();
+        std::fs::write(src.join(dir).join("util.ts"), "export const util = 1;\n").unwrap();
+    }
+    std::fs::write(
+        src.join("main.ts"),
+        "import { util as a } from './a/util';\nimport { util as b } from './b/util';\nexport const sum = a + b;\n",
+    )
+    .unwrap();
+
+    ta_cmd()
+        .arg("--dir")
+        .arg(project.path())
+        .args(["--plain", "--path-style", "basename", "file", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("→ util.ts (src/a)"))
+        .stdout(predicate::str::contains("→ util.ts (src/b)"));
+}
+
+#[test]
+fn test_file_lint_exit_code_reflects_issues() {
+    ta_cmd_in_fixtures()
+        .args(["file", "--lint", "simple.ts"])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains("No import issues found."));
+    ta_cmd_in_fixtures().args(["file", "--lint", "lint-imports"]).assert().code(1);
+    ta_cmd_in_fixtures().args(["--exit-zero", "file", "--lint", "lint-imports"]).assert().code(0);
+    // Without --lint the listing is informational
+    ta_cmd_in_fixtures().args(["file", "lint-imports"]).assert().code(0);
+}
+
+#[test]
+fn test_file_lint_json_only_lists_flagged_files() {
+    let output = ta_cmd_in_fixtures()
+        .arg("--json")
+        .arg("file")
+        .arg("--lint")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+
+    let files: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files = files.as_array().unwrap();
+    assert!(files.iter().all(|f| !f["issues"].as_array().unwrap().is_empty()));
+    assert!(files.iter().any(|f| f["file"].as_str().unwrap().ends_with("lint-imports.ts")));
+}
+
+#[test]
+fn test_json_output_has_no_progress_on_stderr() {
+    let output = ta_cmd_in_fixtures()
+        .arg("--json")
+        .arg("source")
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains('\r'), "unexpected progress redraw: {:?}", stderr);
+    assert!(!stderr.contains("\x1b["), "unexpected terminal control: {:?}", stderr);
+}
+
 #[test]
 fn test_json_format() {
     ta_cmd_in_fixtures()
@@ -342,3 +731,1274 @@ fn test_json_format_no_ansi_codes() {
             !s.contains("\x1b[")
         }));
 }
+
+#[test]
+fn test_fail_on_never_exits_zero_with_errors() {
+    ta_cmd_in_fixtures()
+        .arg("source")
+        .arg("with-errors-legacy")
+        .arg("--fail-on")
+        .arg("never")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Found 1 type error (1 error)"));
+}
+
+#[test]
+fn test_fail_on_default_exits_one_with_errors() {
+    ta_cmd_in_fixtures()
+        .arg("source")
+        .arg("with-errors-legacy")
+        .arg("--fail-on")
+        .arg("error")
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn test_fail_on_warning_exits_one_with_only_warnings() {
+    // Warnings alone pass under the default threshold
+    ta_cmd_in_fixtures()
+        .args(["source", "shadowing", "--lint-shadowing"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("(1 warning)"));
+
+    ta_cmd_in_fixtures()
+        .args(["source", "shadowing", "--lint-shadowing", "--fail-on", "warning"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("(1 warning)"));
+}
+
+#[test]
+fn test_plain_output_is_ascii_only() {
+    let output = ta_cmd_in_fixtures()
+        .env("CLICOLOR_FORCE", "1")
+        .arg("--plain")
+        .arg("source")
+        .arg("with-errors-legacy")
+        .arg("--fail-on")
+        .arg("never")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_ascii(), "stdout: {}", String::from_utf8_lossy(&output.stdout));
+    assert!(output.stderr.is_ascii(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("[X] "));
+}
+
+#[test]
+fn test_plain_output_has_no_colors_even_when_forced() {
+    for args in [&["symbols", "src"][..], &["highlight", "src/api.ts"], &["explain", "TS2322"]] {
+        let output = ta_cmd_in_fixtures()
+            .env("CLICOLOR_FORCE", "1")
+            .arg("--plain")
+            .args(args)
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{:?}", args);
+        assert!(!output.stdout.contains(&b'\x1b'), "{:?} stdout: {}", args, String::from_utf8_lossy(&output.stdout));
+        assert!(!output.stderr.contains(&b'\x1b'), "{:?} stderr: {}", args, String::from_utf8_lossy(&output.stderr));
+    }
+}
+
+#[test]
+fn test_mts_files_are_analyzed() {
+    ta_cmd_in_fixtures()
+        .arg("source")
+        .arg("esm-module")
+        .arg("--fail-on")
+        .arg("never")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Analyzing 1 files"))
+        .stderr(predicate::str::contains("Found 1 type error"))
+        .stdout(predicate::str::contains("esm-module.mts"));
+}
+
+#[test]
+fn test_deps_binds_imports_to_using_symbols_only() {
+    let output = ta_cmd_in_fixtures()
+        .arg("--json")
+        .arg("deps")
+        .arg("import-usage")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let deps: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let depends_on = |name: &str| {
+        deps.as_array()
+            .unwrap()
+            .iter()
+            .find(|d| d["symbol"]["name"] == name)
+            .map(|d| d["depends_on"].as_array().unwrap().clone())
+            .unwrap()
+    };
+
+    let describe = depends_on("describeDate");
+    assert_eq!(describe.len(), 1);
+    assert_eq!(describe[0]["symbol"], "formatDate");
+    assert!(depends_on("answer").is_empty());
+}
+
+fn deps_of(filter: &str, flag: Option<&str>) -> Vec<serde_json::Value> {
+    let mut cmd = ta_cmd_in_fixtures();
+    cmd.arg("--json").arg("deps").arg(filter);
+    if let Some(flag) = flag {
+        cmd.arg(flag);
+    }
+    let output = cmd.output().unwrap();
+
+    assert!(output.status.success());
+    let deps: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    deps[0]["depends_on"].as_array().unwrap().clone()
+}
+
+#[test]
+fn test_deps_marks_type_only_imports() {
+    let depends_on = deps_of("type-imports", None);
+    let type_only = |name: &str| {
+        depends_on
+            .iter()
+            .find(|d| d["symbol"] == name)
+            .map(|d| d["type_only"].clone())
+            .unwrap()
+    };
+
+    assert_eq!(type_only("User"), true);
+    assert_eq!(type_only("formatDate"), false);
+}
+
+#[test]
+fn test_deps_type_only_and_value_only_filters() {
+    let symbols = |flag: &str| -> Vec<String> {
+        deps_of("type-imports", Some(flag))
+            .iter()
+            .map(|d| d["symbol"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    assert_eq!(symbols("--type-only"), vec!["User"]);
+    assert_eq!(symbols("--value-only"), vec!["formatDate"]);
+}
+
+#[test]
+fn test_deps_type_only_is_per_symbol_in_mixed_imports() {
+    let symbols = |flag: Option<&str>| -> Vec<(String, bool)> {
+        deps_of("mixed-type-imports", flag)
+            .iter()
+            .map(|d| (d["symbol"].as_str().unwrap().to_string(), d["type_only"].as_bool().unwrap()))
+            .collect()
+    };
+
+    assert_eq!(symbols(None), vec![("formatDate".to_string(), false), ("DateStyle".to_string(), true)]);
+    assert_eq!(symbols(Some("--type-only")), vec![("DateStyle".to_string(), true)]);
+    assert_eq!(symbols(Some("--value-only")), vec![("formatDate".to_string(), false)]);
+}
+
+#[test]
+fn test_deps_resolve_barrels_follows_reexports() {
+    let output = ta_cmd_in_fixtures()
+        .arg("--json")
+        .arg("deps")
+        .arg("barrel-consumer")
+        .arg("--resolve-barrels")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let deps: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let depends_on = deps[0]["depends_on"].as_array().unwrap();
+    let from = |name: &str| {
+        depends_on
+            .iter()
+            .find(|d| d["symbol"] == name)
+            .unwrap()
+            .clone()
+    };
+
+    let color = from("primaryColor");
+    assert!(color["from"].as_str().unwrap().ends_with("barrel/colors.ts"));
+    assert!(color["via"].as_str().unwrap().ends_with("barrel/index.ts"));
+    assert!(from("circleArea")["from"].as_str().unwrap().ends_with("barrel/shapes.ts"));
+}
+
+#[test]
+fn test_deps_without_resolve_barrels_reports_barrel() {
+    ta_cmd_in_fixtures()
+        .arg("deps")
+        .arg("barrel-consumer")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("barrel/index.ts"))
+        .stdout(predicate::str::contains("shapes.ts").not());
+}
+
+#[test]
+fn test_context_lines_zero_shows_single_code_line() {
+    let output = ta_cmd_in_fixtures()
+        .arg("--json")
+        .arg("source")
+        .arg("/errors.ts")
+        .arg("--context-lines")
+        .arg("0")
+        .output()
+        .unwrap();
+
+    let errors: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let errors = errors.as_array().unwrap();
+    assert!(!errors.is_empty());
+    for error in errors {
+        let display_code = error["source_code"]["display_code"].as_str().unwrap();
+        assert_eq!(display_code.lines().count(), 1, "Got: {}", display_code);
+    }
+}
+
+#[test]
+fn test_path_style_basename_shows_file_name_only() {
+    ta_cmd_in_fixtures()
+        .arg("--path-style")
+        .arg("basename")
+        .arg("symbols")
+        .arg("jsdoc-test")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("jsdoc-test.ts:"))
+        .stdout(predicate::str::contains("src/jsdoc-test.ts").not());
+}
+
+#[test]
+fn test_highlight_json_from_stdin() {
+    let output = ta_cmd()
+        .arg("highlight")
+        .arg("--lang")
+        .arg("ts")
+        .arg("--json")
+        .write_stdin("const x=1;")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let highlighted: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let segments = highlighted["segments"].as_array().unwrap();
+    assert!(!segments.is_empty());
+    assert!(segments[0]["line"].is_number());
+    assert!(segments[0]["column"].is_number());
+    assert!(segments[0]["style"].is_object());
+}
+
+#[test]
+fn test_highlight_unsupported_language_exit_code() {
+    ta_cmd()
+        .arg("highlight")
+        .arg("--lang")
+        .arg("cobol")
+        .arg("--json")
+        .write_stdin("DISPLAY 'HI'.")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Unsupported language: cobol"));
+}
+
+#[test]
+fn test_watch_json_streams_symbol_added() {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("watched.ts");
+    std::fs::write(&file, "export const existing = 1;\n").unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ta"))
+        .arg("watch")
+        .arg("--json")
+        .arg(dir.path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // Only change the file once the baseline has been recorded
+    let stderr = BufReader::new(child.stderr.take().unwrap());
+    let (ready_tx, ready_rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in stderr.lines().map_while(Result::ok) {
+            if line.contains("Watching for changes") {
+                let _ = ready_tx.send(());
+            }
+        }
+    });
+    ready_rx.recv_timeout(Duration::from_secs(10)).expect("watcher did not start");
+
+    std::fs::write(&file, "export const existing = 1;\nexport function added() {}\n").unwrap();
+
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    let (line_tx, line_rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in stdout.lines().map_while(Result::ok) {
+            if line_tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let line = line_rx.recv_timeout(Duration::from_secs(15));
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    let event: serde_json::Value = serde_json::from_str(&line.expect("no watch event")).unwrap();
+    assert_eq!(event["type"], "SymbolAdded");
+    assert_eq!(event["data"]["name"], "added");
+    assert_eq!(event["data"]["kind"], "Function");
+}
+
+#[test]
+fn test_taignore_skips_directories_and_applies_to_subtrees() {
+    let dir = tempfile::tempdir().unwrap();
+    let write = |path: &str, contents: &str| {
+        let path = dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    };
+    write("src/kept.ts", "export const kept = 1;");
+    write("src/local.ts", "export const topLocal = 1;");
+    write("src/generated/skipped.ts", "export const generated = 1;");
+    write("src/nested/local.ts", "export const nestedLocal = 1;");
+    write("src/nested/other.ts", "export const nestedOther = 1;");
+    write(".taignore", "src/generated/\n");
+    // Patterns in a nested .taignore are relative to its own directory
+    write("src/nested/.taignore", "local.ts\n");
+
+    ta_cmd()
+        .arg("--dir")
+        .arg(dir.path())
+        .arg("symbols")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("kept"))
+        .stdout(predicate::str::contains("topLocal"))
+        .stdout(predicate::str::contains("nestedOther"))
+        .stdout(predicate::str::contains("generated").not())
+        .stdout(predicate::str::contains("nestedLocal").not());
+}
+
+#[test]
+fn test_indent_flag_validates_unit() {
+    ta_cmd_in_fixtures()
+        .arg("--indent")
+        .arg("wide")
+        .arg("source")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected 'tab' or a positive number of spaces"));
+
+    ta_cmd_in_fixtures()
+        .arg("--indent")
+        .arg("tab")
+        .arg("source")
+        .assert()
+        .stdout(predicate::str::contains("\n\t"));
+}
+
+#[test]
+fn test_list_languages() {
+    ta_cmd()
+        .arg("list-languages")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Available languages:"))
+        .stdout(predicate::str::contains("Rust"))
+        .stdout(predicate::str::contains("TypeScript"));
+
+    let output = ta_cmd().arg("--json").arg("list-languages").output().unwrap();
+    let languages: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(languages
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|l| l["token"] == "js"));
+}
+
+#[test]
+fn test_explain_known_and_unknown_codes() {
+    let output = ta_cmd().args(["explain", "TS2322"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("TS2322"));
+    assert!(stdout.contains("not assignable"), "Got: {}", stdout);
+    assert!(stdout.contains("https://"));
+
+    ta_cmd()
+        .args(["explain", "TS99999"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no explanation available."));
+}
+
+#[test]
+fn test_quiet_suppresses_stderr_but_keeps_exit_code() {
+    ta_cmd_in_fixtures()
+        .args(["--quiet", "source", "errors.ts"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::is_empty())
+        .stdout(predicate::str::is_empty().not());
+}
+
+#[test]
+fn test_no_color_uses_ascii_status_markers() {
+    ta_cmd_in_fixtures()
+        .env("NO_COLOR", "1")
+        .args(["source", "simple.ts"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("[OK]"))
+        .stderr(predicate::str::contains("✅").not());
+}
+
+#[test]
+fn test_width_flag_sizes_markdown_separators() {
+    let output = ta_cmd()
+        .args(["--plain", "--width", "20", "highlight", "--lang", "md"])
+        .write_stdin("Intro\n\n---\n\n```ts Example\nconst x = 1;\n```\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let separators: Vec<&str> = stdout.lines().filter(|l| l.starts_with(['+', '-'])).collect();
+    assert_eq!(separators.len(), 3, "Got: {}", stdout);
+    for line in separators {
+        assert_eq!(line.chars().count(), 20, "Bad width: {:?}", line);
+    }
+}
+
+#[test]
+fn test_ignore_code_drops_errors_from_output() {
+    // Fixture errors are uncoded, so they carry the fallback id `error`
+    ta_cmd_in_fixtures()
+        .args(["--json", "source", "errors.ts", "--ignore-code", "err"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"id\"").not());
+
+    ta_cmd_in_fixtures()
+        .args(["--json", "source", "errors.ts", "--only-code", "error"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("\"id\": \"error\""));
+}
+
+#[test]
+fn test_ta_ignore_comments_suppress_and_report_unused() {
+    ta_cmd_in_fixtures()
+        .args(["source", "ignores.ts"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("no type errors"))
+        .stderr(predicate::str::contains("Unused").not());
+
+    ta_cmd_in_fixtures()
+        .args(["--plain", "source", "ignores.ts", "--report-unused-ignores"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("[!] Unused ta-ignore-next-line TS2322 directive at"))
+        .stderr(predicate::str::contains("ignores.ts:5"));
+}
+
+#[test]
+fn test_deps_check_reports_unresolved_relative_imports() {
+    let output = ta_cmd_in_fixtures()
+        .arg("--json")
+        .arg("deps")
+        .arg("broken-imports")
+        .arg("--check")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let flagged: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let issues = flagged[0]["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["kind"], "UnresolvedImport");
+    assert_eq!(issues[0]["source"], "./does-not-exist");
+    assert_eq!(issues[0]["line"], 3);
+}
+
+#[test]
+fn test_deps_check_passes_when_relative_imports_resolve() {
+    ta_cmd_in_fixtures()
+        .arg("deps")
+        .arg("import-usage")
+        .arg("--check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No import issues found."));
+}
+
+#[test]
+fn test_lsp_publishes_diagnostics_for_opened_document() {
+    let frame = |message: serde_json::Value| {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    };
+    let input = [
+        frame(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} })),
+        frame(serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": {
+                "uri": "file:///project/src/a.ts",
+                "languageId": "typescript",
+                "version": 1,
+                "text": "const a = 1;\nconst a = 2;\n"
+            } }
+        })),
+        frame(serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" })),
+        frame(serde_json::json!({ "jsonrpc": "2.0", "method": "exit" })),
+    ]
+    .concat();
+
+    let output = ta_cmd().arg("lsp").write_stdin(input).output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let messages: Vec<serde_json::Value> = stdout
+        .split("Content-Length: ")
+        .filter_map(|frame| frame.split_once("\r\n\r\n"))
+        .map(|(_, body)| serde_json::from_str(body).unwrap())
+        .collect();
+
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0]["id"], 1);
+    let publish = &messages[1];
+    assert_eq!(publish["method"], "textDocument/publishDiagnostics");
+    let diagnostics = publish["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["source"], "ta");
+    assert_eq!(messages[2]["id"], 2);
+}
+
+#[test]
+fn test_symbols_references_reports_import_and_aliased_usage() {
+    let output = ta_cmd_in_fixtures()
+        .arg("--json")
+        .arg("symbols")
+        .arg("aliased-import")
+        .arg("--references")
+        .arg("validateEmail")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let references: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let references = references.as_array().unwrap();
+    assert_eq!(references.len(), 2);
+
+    assert_eq!(references[0]["kind"], "Import");
+    assert_eq!(references[0]["local"], "isEmail");
+    assert_eq!(references[0]["source"], "./utils");
+    assert_eq!(references[0]["line"], 2);
+
+    assert_eq!(references[1]["kind"], "Usage");
+    assert_eq!(references[1]["local"], "isEmail");
+    assert_eq!(references[1]["line"], 5);
+}
+
+#[test]
+fn test_symbols_get_returns_only_the_named_symbol() {
+    let output = ta_cmd_in_fixtures()
+        .arg("--json")
+        .arg("symbols")
+        .arg("--get")
+        .arg("calculate")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let symbols: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let symbols = symbols.as_array().unwrap();
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(symbols[0]["name"], "calculate");
+    assert_eq!(symbols[0]["kind"], "Function");
+    let parameters: Vec<_> = symbols[0]["parameters"].as_array().unwrap().iter().map(|p| p["name"].as_str().unwrap()).collect();
+    assert_eq!(parameters, vec!["price", "quantity", "discount"]);
+
+    ta_cmd_in_fixtures()
+        .arg("symbols")
+        .arg("--get")
+        .arg("noSuchSymbol")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No symbol named 'noSuchSymbol'"));
+}
+
+#[test]
+fn test_jobs_one_matches_default_results() {
+    let run = |extra: &[&str]| {
+        let output = ta_cmd_in_fixtures()
+            .arg("--json")
+            .arg("source")
+            .args(extra)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let default = run(&[]);
+
+    assert!(default.contains("\"id\""));
+    assert_eq!(run(&["--jobs", "1"]), default);
+    assert_eq!(run(&["--jobs", "2"]), default);
+}
+
+#[test]
+fn test_flag_comments_reports_todo_as_info() {
+    let output = ta_cmd_in_fixtures()
+        .arg("--json")
+        .arg("source")
+        .arg("todo-comments")
+        .arg("--flag-comments")
+        .arg("todo,FIXME")
+        .output()
+        .unwrap();
+
+    // Info diagnostics do not fail the run unless --fail-on info is given
+    assert!(output.status.success());
+    let errors: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let errors = errors.as_array().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0]["severity"], "info");
+    assert_eq!(errors[0]["id"], "TODO");
+    assert_eq!(errors[0]["start_line"], 3);
+    assert!(errors[0]["message"].as_str().unwrap().starts_with("TODO: fix"));
+
+    ta_cmd_in_fixtures()
+        .arg("source")
+        .arg("todo-comments")
+        .arg("--flag-comments")
+        .arg("TODO")
+        .arg("--fail-on")
+        .arg("info")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("(1 info)"));
+}
+
+#[test]
+fn test_no_pager_prints_directly_and_keeps_exit_code() {
+    // A pager that would swallow the report proves it is never started off a TTY
+    ta_cmd_in_fixtures()
+        .env("PAGER", "false")
+        .arg("--no-pager")
+        .arg("source")
+        .arg("errors")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("has already been declared"));
+
+    ta_cmd_in_fixtures()
+        .env("PAGER", "false")
+        .arg("--pager")
+        .arg("source")
+        .arg("errors")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("has already been declared"));
+}
+
+#[test]
+fn test_exit_zero_succeeds_with_errors_and_still_prints_them() {
+    ta_cmd_in_fixtures()
+        .arg("--exit-zero")
+        .arg("source")
+        .arg("errors")
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains("has already been declared"));
+
+    ta_cmd_in_fixtures()
+        .args(["--exit-zero", "deps", "broken-imports", "--check"])
+        .assert()
+        .code(0);
+}
+
+#[test]
+fn test_usage_and_io_errors_exit_two() {
+    // `--exit-zero` only covers found errors, never a failed run
+    ta_cmd_in_fixtures()
+        .args(["--exit-zero", "source", "no-such-file-anywhere"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("No source files found"));
+
+    ta_cmd_in_fixtures()
+        .args(["source", "--no-such-flag"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_timings_prints_a_row_per_analyzed_file() {
+    let output = ta_cmd_in_fixtures()
+        .args(["--json", "symbols", "utils", "types", "--timings"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let rows: Vec<&str> = stderr.lines().filter(|line| line.ends_with(".ts")).collect();
+    assert_eq!(rows.len(), 2, "{}", stderr);
+    assert!(rows.iter().all(|row| row.split_whitespace().next().unwrap().ends_with("ms")));
+    assert!(stderr.contains("across 2 files"), "{}", stderr);
+    // The table goes to stderr, so JSON on stdout stays parseable
+    serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap();
+}
+
+#[test]
+fn test_ascii_glyphs_use_bracket_markers_and_no_unicode() {
+    let output = ta_cmd_in_fixtures()
+        .env("TA_GLYPHS", "ascii")
+        .args(["source", "errors"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[X] "), "{}", stdout);
+    assert!(stdout.chars().all(|c| c <= '\u{7F}'), "{}", stdout);
+
+    ta_cmd_in_fixtures()
+        .args(["--ascii", "source", "utils"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("[OK] no type errors found"));
+}
+
+#[test]
+fn test_preflight_passes_in_valid_repo() {
+    ta_cmd_in_fixtures()
+        .arg("--plain")
+        .arg("preflight")
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains("[OK] highlighting"))
+        .stdout(predicate::str::contains("tsconfig.json  valid"))
+        .stderr(predicate::str::contains("Preflight passed."));
+}
+
+#[test]
+fn test_preflight_fails_on_invalid_config() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/a.ts"), "export const a = 1;\n").unwrap();
+    std::fs::write(dir.path().join("ta.toml"), "[analysis\njobs = 4\n").unwrap();
+
+    let output = ta_cmd()
+        .arg("--dir")
+        .arg(dir.path())
+        .arg("--json")
+        .arg("preflight")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let checks: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let config = checks.as_array().unwrap().iter().find(|c| c["name"] == "ta.toml").unwrap();
+    assert_eq!(config["status"], "error");
+}
+
+#[test]
+fn test_symbols_ctags_lines() {
+    let output = ta_cmd_in_fixtures()
+        .args(["symbols", "--ctags", "utils"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.starts_with("!_TAG_FILE_FORMAT"));
+    assert!(
+        stdout.contains("formatDate\tsrc/utils.ts\t/^export function formatDate(date: Date): string {$/;\"\tf\n"),
+        "{}",
+        stdout
+    );
+
+    let names: Vec<&str> = stdout.lines().filter(|l| !l.starts_with('!')).map(|l| l.split('\t').next().unwrap()).collect();
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+}
+
+#[test]
+fn test_symbols_require_docs_flags_undocumented_exports() {
+    let output = ta_cmd_in_fixtures()
+        .args(["--json", "symbols", "--require-docs", "docs-required"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let errors: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let errors = errors.as_array().unwrap();
+    // `PRICE_SCALE` and `isFree` have tag-only JSDoc, which still counts as documented
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0]["id"], "DOCS");
+    assert_eq!(errors[0]["severity"], "warning");
+    assert_eq!(errors[0]["scope"], "parsePrice");
+    assert_eq!(errors[0]["line"], 6);
+
+    let output = ta_cmd_in_fixtures()
+        .args(["symbols", "--require-docs", "docs-required", "--allow-undocumented", "parse*"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_lint_shadowing_warns_at_inner_declaration() {
+    let output = ta_cmd_in_fixtures()
+        .args(["--json", "source", "shadowing", "--lint-shadowing"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let errors: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let errors = errors.as_array().unwrap();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0]["id"], "SHADOW");
+    assert_eq!(errors[0]["severity"], "warning");
+    assert_eq!(errors[0]["start_line"], 4);
+    assert_eq!(errors[0]["message"], "'sum' shadows an outer declaration");
+    assert_eq!(errors[0]["related"][0]["message"], "outer declaration");
+    assert_eq!(errors[0]["related"][0]["start_line"], 2);
+
+    ta_cmd_in_fixtures()
+        .args(["--plain", "source", "shadowing", "--lint-shadowing"])
+        .assert()
+        .stdout(predicate::str::contains("  outer declaration on line 2\n"));
+
+    ta_cmd_in_fixtures()
+        .args(["source", "shadowing"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("SHADOW").not());
+}
+
+#[test]
+fn test_scope_separator_and_module_scopes() {
+    let scopes = |args: &[&str]| -> Vec<String> {
+        let output = ta_cmd_in_fixtures()
+            .args(["--json", "source", "errors"])
+            .args(args)
+            .output()
+            .unwrap();
+        let errors: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        errors.as_array().unwrap().iter().map(|e| e["scope"].as_str().unwrap().to_string()).collect()
+    };
+
+    assert!(scopes(&[]).contains(&"processUser".to_string()));
+    let module_scopes = scopes(&["--module-scopes", "--scope-separator", "::"]);
+    assert!(module_scopes.contains(&"src/errors::processUser".to_string()), "{:?}", module_scopes);
+}
+
+#[test]
+fn test_include_sfc_reports_vue_script_errors_at_file_lines() {
+    // Without --include-sfc components are not walked at all
+    ta_cmd_in_fixtures()
+        .args(["source", "Counter"])
+        .assert()
+        .code(2);
+
+    let output = ta_cmd_in_fixtures()
+        .args(["--json", "source", "Counter", "--include-sfc"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let errors: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let errors = errors.as_array().unwrap();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0]["file"], "./src/Counter.vue");
+    // `const step = 1;` is on line 11 of the component, line 7 of its script
+    assert_eq!(errors[0]["line"], 11);
+    assert_eq!(errors[0]["scope"], "increment");
+}
+
+#[test]
+fn test_baseline_reports_only_new_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let baseline = dir.path().join("baseline.json");
+    let baseline_arg = baseline.to_str().unwrap();
+
+    // A missing baseline is written and the run passes
+    ta_cmd_in_fixtures()
+        .args(["source", "errors", "--baseline", baseline_arg])
+        .assert()
+        .success();
+    ta_cmd_in_fixtures()
+        .args(["source", "errors", "--baseline", baseline_arg])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("3 known errors"));
+
+    // Drop one known error and add one that has since been fixed
+    let mut written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&baseline).unwrap()).unwrap();
+    let entries = written["errors"].as_array_mut().unwrap();
+    let dropped = entries.remove(0);
+    entries.push(serde_json::json!({ "file": "src/errors.ts", "id": "error", "message": "Already fixed" }));
+    std::fs::write(&baseline, written.to_string()).unwrap();
+
+    let output = ta_cmd_in_fixtures()
+        .args(["--json", "source", "errors", "--baseline", baseline_arg, "--report-fixed"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let errors: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let errors = errors.as_array().unwrap();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0]["message"], dropped["message"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Already fixed"));
+
+    // --update-baseline accepts the current errors again
+    ta_cmd_in_fixtures()
+        .args(["source", "errors", "--baseline", baseline_arg, "--update-baseline"])
+        .assert()
+        .success();
+    ta_cmd_in_fixtures()
+        .args(["source", "errors", "--baseline", baseline_arg])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_baseline_only_covers_the_analyzed_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let baseline = dir.path().join("baseline.json");
+    let baseline_arg = baseline.to_str().unwrap();
+    let entries = || -> usize {
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&baseline).unwrap()).unwrap();
+        written["errors"].as_array().unwrap().len()
+    };
+
+    ta_cmd_in_fixtures().args(["source", "errors", "--baseline", baseline_arg]).assert().success();
+    assert_eq!(entries(), 3);
+
+    // Another file's run neither reports errors.ts as fixed nor drops it on update
+    ta_cmd_in_fixtures()
+        .args(["source", "--files", "src/simple.ts", "--baseline", baseline_arg, "--report-fixed"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No errors in"));
+    ta_cmd_in_fixtures()
+        .args(["source", "--files", "src/simple.ts", "--baseline", baseline_arg, "--update-baseline"])
+        .assert()
+        .success();
+    assert_eq!(entries(), 3);
+
+    // Error filters would leave the baseline with only some of a file's errors
+    ta_cmd_in_fixtures()
+        .args(["source", "errors", "--baseline", baseline_arg, "--ignore-code", "TS"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_declaration_files_are_filtered() {
+    // `source` skips declarations unless asked for them
+    ta_cmd_in_fixtures()
+        .args(["source", "globals"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("No source files found"));
+    ta_cmd_in_fixtures()
+        .args(["source", "globals", "--declarations-only"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Analyzing 1 files"));
+    ta_cmd_in_fixtures()
+        .args(["source", "--declarations-only"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Analyzing 1 files"));
+
+    let symbol_files = |args: &[&str]| -> Vec<String> {
+        let output = ta_cmd_in_fixtures()
+            .args(["--json", "symbols"])
+            .args(args)
+            .output()
+            .unwrap();
+        let symbols: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let mut files: Vec<String> = symbols.as_array().unwrap().iter().map(|s| s["file"].as_str().unwrap().to_string()).collect();
+        files.dedup();
+        files
+    };
+
+    assert!(symbol_files(&[]).contains(&"./src/globals.d.ts".to_string()));
+    assert!(!symbol_files(&["--no-declarations"]).contains(&"./src/globals.d.ts".to_string()));
+    assert_eq!(symbol_files(&["--declarations-only"]), vec!["./src/globals.d.ts"]);
+
+    // Every walker accepts --no-declarations, a no-op where it is the default
+    ta_cmd_in_fixtures()
+        .args(["source", "globals", "--no-declarations"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("No source files found"));
+    ta_cmd_in_fixtures().args(["test", "--no-declarations"]).assert().success();
+    // Directories given to `test --files` expand to every TypeScript file, declarations included
+    let scanned = |extra: &[&str]| {
+        let output = ta_cmd_in_fixtures().args(["test", "--files", "src"]).args(extra).output().unwrap();
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        stderr.lines().find(|line| line.starts_with("Scanning")).unwrap().to_string()
+    };
+    let all = scanned(&[]);
+    let without_declarations = scanned(&["--no-declarations"]);
+    let count = |line: &str| line.split_whitespace().nth(1).unwrap().parse::<usize>().unwrap();
+    assert_eq!(count(&without_declarations), count(&all) - 1, "{} / {}", all, without_declarations);
+    for command in ["file", "deps"] {
+        ta_cmd_in_fixtures()
+            .args([command, "globals", "--no-declarations"])
+            .assert()
+            .code(2)
+            .stderr(predicate::str::contains("No source files found"));
+        ta_cmd_in_fixtures()
+            .args([command, "--declarations-only"])
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("for 1 files"));
+    }
+}
+
+#[test]
+fn test_report_any_lists_explicit_any_with_count() {
+    let output = ta_cmd_in_fixtures()
+        .args(["--json", "source", "any-usage", "--report-any"])
+        .output()
+        .unwrap();
+
+    // Info diagnostics do not fail the run by default
+    assert!(output.status.success());
+    let errors: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let errors = errors.as_array().unwrap();
+    let lines: Vec<u64> = errors.iter().map(|e| e["line"].as_u64().unwrap()).collect();
+    assert_eq!(lines, vec![1, 2, 3]);
+    assert!(errors.iter().all(|e| e["id"] == "ANY" && e["severity"] == "info"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Found 3 explicit `any` usages."));
+
+    ta_cmd_in_fixtures()
+        .args(["source", "any-usage"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("any` type").not());
+}
+
+#[test]
+fn test_manifest_summarizes_the_run() {
+    // package.json is not a source file, so it is skipped rather than analyzed
+    let output = ta_cmd_in_fixtures()
+        .args(["source", "--manifest", "--files", "src/errors.ts", "src/simple.ts", "package.json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+
+    let manifest: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let keys: Vec<&str> = manifest.as_object().unwrap().keys().map(String::as_str).collect();
+    for key in ["total_files", "analyzed_files", "skipped_files", "error_count", "warning_count", "duration_ms", "ta_version"] {
+        assert!(keys.contains(&key), "missing {}: {}", key, manifest);
+    }
+    assert_eq!(manifest["total_files"], 3);
+    assert_eq!(manifest["skipped_files"], 1);
+    assert_eq!(
+        manifest["analyzed_files"].as_u64().unwrap() + manifest["skipped_files"].as_u64().unwrap(),
+        manifest["total_files"].as_u64().unwrap()
+    );
+    assert_eq!(manifest["error_count"], 2);
+    assert_eq!(manifest["ta_version"], env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn test_ansi_to_html_converts_colored_console_report() {
+    let output = ta_cmd_in_fixtures()
+        .args(["source", "--ansi-to-html", "--files", "src/errors.ts"])
+        // Only the converted report is styled; colors stay off everywhere else
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("<pre class=\"ansi-output\">"), "{}", stdout);
+    assert!(stdout.contains("<span style=\"color: rgb("), "{}", stdout);
+    // The bold error message comes from the report itself, not the code highlighting
+    assert!(stdout.contains("font-weight: bold"), "{}", stdout);
+    assert!(!stdout.contains('\x1b'), "{}", stdout);
+}
+
+#[test]
+fn test_compact_prints_one_location_line_per_error() {
+    let output = ta_cmd_in_fixtures()
+        .args(["source", "--compact", "--files", "src/errors.ts", "src/simple.ts"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains('\x1b'), "{}", stdout);
+    let mut locations = Vec::new();
+    for line in stdout.lines() {
+        let (file, rest) = line.split_once(':').unwrap();
+        let mut fields = rest.splitn(3, ':');
+        let line_number: usize = fields.next().unwrap().parse().unwrap();
+        let column: usize = fields.next().unwrap().parse().unwrap();
+        let diagnostic = fields.next().unwrap();
+        assert!(line_number > 0 && column > 0, "{}", line);
+        assert!(diagnostic.starts_with(" [") && diagnostic.contains("] "), "{}", line);
+        locations.push((file.to_string(), line_number, column));
+    }
+    assert_eq!(locations.len(), 2);
+    assert!(locations.iter().all(|(file, _, _)| file.ends_with("src/errors.ts")));
+    assert!(locations.is_sorted());
+}
+
+#[test]
+fn test_max_depth_skips_deeply_nested_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let error = "let x = 1;\nlet x = 2;\n";
+    std::fs::create_dir_all(dir.path().join("lib/nested")).unwrap();
+    std::fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+    for file in ["top.ts", "lib/nested/deep.ts", "src/shallow.ts", "src/nested/deep.ts"] {
+        std::fs::write(dir.path().join(file), error).unwrap();
+    }
+    let analyzed = |args: &[&str]| -> Vec<String> {
+        let output = ta_cmd().arg("--dir").arg(dir.path()).arg("source").arg("--compact").args(args).output().unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        stdout.lines().map(|line| line.split(':').next().unwrap().trim_start_matches("./").to_string()).collect()
+    };
+
+    assert_eq!(analyzed(&["--glob", "**/*.ts", "--max-depth", "1"]), vec!["top.ts"]);
+
+    // Files under the base `src/` directory start at depth 2
+    assert_eq!(analyzed(&["--max-depth", "2"]), vec!["src/shallow.ts"]);
+    assert_eq!(analyzed(&[]), vec!["src/nested/deep.ts", "src/shallow.ts"]);
+}
+
+#[test]
+fn test_explain_selection_gives_each_file_a_verdict() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("src/generated")).unwrap();
+    std::fs::create_dir_all(dir.path().join("lib")).unwrap();
+    for file in ["src/app.ts", "src/app.test.ts", "src/types.d.ts", "src/generated/api.ts", "lib/util.ts"] {
+        std::fs::write(dir.path().join(file), "export const x = 1;\n").unwrap();
+    }
+    std::fs::write(dir.path().join("src/.taignore"), "generated/\n").unwrap();
+
+    let output = ta_cmd().arg("--dir").arg(dir.path()).args(["source", "--explain-selection"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "lib/util.ts skipped: not in src/ or scripts/",
+            "src/app.test.ts skipped: test file",
+            "src/app.ts included",
+            "src/generated skipped: gitignored",
+            "src/types.d.ts skipped: declaration file",
+        ]
+    );
+
+    let output = ta_cmd().arg("--dir").arg(dir.path()).args(["--json", "source", "--explain-selection", "types"]).output().unwrap();
+    let verdicts: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let app = verdicts.as_array().unwrap().iter().find(|v| v["file"] == "src/app.ts").unwrap();
+    assert_eq!(app["verdict"], "skipped: filter");
+}
+
+#[test]
+fn test_format_flag_matches_deprecated_aliases() {
+    let run = |args: &[&str]| ta_cmd_in_fixtures().args(args).output().unwrap();
+
+    let aliased = run(&["--json", "symbols"]);
+    let formatted = run(&["--format", "json", "symbols"]);
+    assert!(formatted.status.success());
+    assert_eq!(formatted.status.code(), aliased.status.code());
+    assert_eq!(formatted.stdout, aliased.stdout);
+    serde_json::from_slice::<serde_json::Value>(&formatted.stdout).unwrap();
+
+    ta_cmd_in_fixtures()
+        .args(["--format", "html", "--json", "symbols"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_check_assertions_sets_status_from_expected_errors() {
+    let statuses = |args: &[&str]| -> Vec<(String, String)> {
+        let output = ta_cmd_in_fixtures()
+            .args(["--json", "test", "--files", "test/types.test.ts"])
+            .args(args)
+            .output()
+            .unwrap();
+        let tests: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        tests
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| (t["test_name"].as_str().unwrap().to_string(), t["status"].as_str().unwrap().to_string()))
+            .collect()
+    };
+    let named = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+        pairs.iter().map(|(name, status)| (name.to_string(), status.to_string())).collect()
+    };
+
+    assert_eq!(
+        statuses(&["--check-assertions"]),
+        named(&[
+            ("expects an error that never happens", "Failing"),
+            ("expects a redeclaration", "Passing"),
+            ("infers numbers", "Passing"),
+        ])
+    );
+    // Without the flag, expect-error comments are not cross-referenced
+    assert_eq!(
+        statuses(&[]),
+        named(&[
+            ("expects an error that never happens", "NoTypeCases"),
+            ("expects a redeclaration", "NoTypeCases"),
+            ("infers numbers", "Passing"),
+        ])
+    );
+}
+
+#[test]
+fn test_flush_per_file_prints_the_batched_report_file_by_file() {
+    let stdout = |args: &[&str]| -> String {
+        let output = ta_cmd_in_fixtures().args(["--plain", "source"]).args(args).output().unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert_eq!(stdout(&["--flush-per-file"]), stdout(&[]));
+
+    // One flush per file with errors, each holding all of that file's lines
+    let flushed = stdout(&["--compact", "--flush-per-file"]);
+    let mut files: Vec<&str> = flushed.lines().map(|line| line.split(':').next().unwrap()).collect();
+    files.dedup();
+    let distinct: std::collections::HashSet<&str> = files.iter().copied().collect();
+    assert!(files.len() >= 3, "{}", flushed);
+    assert_eq!(files.len(), distinct.len(), "{}", flushed);
+
+    let mut flushed_lines: Vec<&str> = flushed.lines().collect();
+    flushed_lines.sort();
+    let batched = stdout(&["--compact"]);
+    let mut batched_lines: Vec<&str> = batched.lines().collect();
+    batched_lines.sort();
+    assert_eq!(flushed_lines, batched_lines);
+
+    ta_cmd_in_fixtures()
+        .args(["--json", "source", "--flush-per-file"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--flush-per-file only writes console"));
+}
@@ -85,3 +85,55 @@ fn test_theme_env_vars() {
 
     cmd.assert().code(1);  // Exit code 1 when type errors are found
 }
+
+#[test]
+#[allow(deprecated)]
+fn test_list_themes_preview_highlights_each_theme() {
+    let mut cmd = Command::cargo_bin("ta").unwrap();
+
+    let output = cmd
+        .env("COLORTERM", "truecolor")
+        .env_remove("NO_COLOR")
+        .arg("list-themes")
+        .arg("--preview")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    // Each theme name sits on its own unindented line, followed by its preview
+    let mut previews: Vec<(&str, String)> = Vec::new();
+    for line in stdout.lines().skip(1) {
+        if !line.is_empty() && !line.starts_with(' ') && !line.starts_with('\x1b') {
+            previews.push((line, String::new()));
+        } else if let Some((_, preview)) = previews.last_mut() {
+            preview.push_str(line);
+        }
+    }
+
+    assert!(previews.iter().any(|(name, _)| *name == "Dracula"));
+    for (name, preview) in &previews {
+        if preview.contains("not bundled") {
+            continue;
+        }
+        assert!(preview.contains("\x1b[38;2;"), "no truecolor preview for {}", name);
+        assert!(preview.contains("greet"), "no snippet for {}", name);
+    }
+    let highlighted = previews.iter().filter(|(_, preview)| preview.contains("\x1b[38;2;")).count();
+    assert!(highlighted >= 2, "expected the default themes to be previewed");
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_list_themes_preview_respects_no_color() {
+    let mut cmd = Command::cargo_bin("ta").unwrap();
+
+    cmd.env("NO_COLOR", "1")
+        .arg("list-themes")
+        .arg("--preview")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("  Dracula"))
+        .stdout(predicate::str::contains("\x1b[").not());
+}
@@ -0,0 +1,88 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use ta_lib::analyzer::{AnalysisOptions, AnalysisResult, Analyzer};
+
+/// System allocator that tracks the current and peak number of live bytes
+struct PeakAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAlloc = PeakAlloc;
+
+/// Write `count` files with a few errors, symbols, imports and tests each
+fn generate_files(dir: &std::path::Path, count: usize) -> Vec<PathBuf> {
+    (0..count)
+        .map(|i| {
+            let path = dir.join(format!("file{}.ts", i));
+            let mut source = format!("import {{ helper }} from './file{}';\n", (i + 1) % count);
+            for j in 0..20 {
+                source.push_str(&format!(
+                    "export function f{j}(value: string): string {{ let x = helper(value); let x = value; return x; }}\n\
+                     export interface Shape{j} {{ id: number; name: string }}\n\
+                     test('f{j}', () => {{}});\n"
+                ));
+            }
+            std::fs::write(&path, source).unwrap();
+            path
+        })
+        .collect()
+}
+
+/// Peak live bytes above the baseline while `analyze` runs, with its result
+fn peak_bytes(analyze: impl FnOnce() -> AnalysisResult) -> (usize, AnalysisResult) {
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let result = analyze();
+    (PEAK.load(Ordering::Relaxed) - baseline, result)
+}
+
+fn benchmark_analyze_files(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let files = generate_files(dir.path(), 2_000);
+    let analyzer = Analyzer::new(AnalysisOptions { parallel: true, ..Default::default() });
+
+    let (folding, folded) = peak_bytes(|| analyzer.analyze_files(&files).unwrap());
+    drop(folded);
+    let (in_order, ordered) = peak_bytes(|| analyzer.analyze_files_each(&files, |_| {}).unwrap());
+    drop(ordered);
+    println!(
+        "peak allocation for {} files: analyze_files {:.1} MiB, analyze_files_each {:.1} MiB",
+        files.len(),
+        folding as f64 / (1024.0 * 1024.0),
+        in_order as f64 / (1024.0 * 1024.0)
+    );
+
+    let mut group = c.benchmark_group("analyze_files");
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("folding", files.len()), |b| {
+        b.iter(|| analyzer.analyze_files(&files).unwrap())
+    });
+    group.bench_function(BenchmarkId::new("in_order", files.len()), |b| {
+        b.iter(|| analyzer.analyze_files_each(&files, |_| {}).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_analyze_files);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_semantic::SemanticBuilder;
+use oxc_span::SourceType;
+use ta_lib::type_errors::{extract_type_errors, merge_diagnostics};
+
+/// Generate a clean (diagnostic-free) TypeScript file with a specified number of lines
+fn generate_clean_typescript(lines: usize) -> String {
+    let mut code = String::new();
+
+    for i in 0..lines {
+        match i % 4 {
+            0 => code.push_str(&format!("export const value{}: number = {};\n", i, i)),
+            1 => code.push_str(&format!(
+                "export function double{}(x: number): number {{ return x * 2; }}\n",
+                i
+            )),
+            2 => code.push_str(&format!("export interface Shape{} {{ id: number; name: string }}\n", i)),
+            3 => code.push_str(&format!(
+                "export class Box{} {{ private value = {}; get(): number {{ return this.value; }} }}\n",
+                i, i
+            )),
+            _ => unreachable!(),
+        }
+    }
+
+    code
+}
+
+fn benchmark_clean_file(c: &mut Criterion) {
+    let mut group = c.benchmark_group("extract_type_errors_clean");
+
+    for size in [1_000, 10_000].iter() {
+        let source = generate_clean_typescript(*size);
+        let allocator = Allocator::default();
+        let parse_ret = Parser::new(&allocator, &source, SourceType::ts()).parse();
+        let semantic_ret = SemanticBuilder::new(&source).build(&parse_ret.program);
+        let diagnostics = merge_diagnostics(parse_ret.errors, semantic_ret.errors);
+        assert!(diagnostics.is_empty(), "benchmark input must be clean");
+
+        group.bench_function(BenchmarkId::new("lines", size), |b| {
+            b.iter(|| {
+                extract_type_errors(
+                    black_box(&source),
+                    &semantic_ret.semantic,
+                    &diagnostics,
+                    &parse_ret.program,
+                    "clean.ts".to_string(),
+                    None,
+                )
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_clean_file);
+criterion_main!(benches);
@@ -1,4 +1,8 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 use rayon::prelude::*;
 use oxc_allocator::Allocator;
 use oxc_parser::Parser;
@@ -6,18 +10,65 @@ use oxc_semantic::SemanticBuilder;
 use oxc_span::SourceType;
 use crate::models::{TypeError, SymbolInfo, TypeTest};
 use crate::{Error, Result};
-use crate::type_errors::extract_type_errors;
+use crate::module_format::{find_module_mismatches, ModuleFormat, PackageTypes};
+use crate::sfc::{extract_scripts, is_sfc_path};
+use crate::explicit_any::find_explicit_any;
+use crate::empty_catch::find_empty_catches;
+use crate::shadowing::find_shadowed_bindings;
+use crate::rules::{find_rule_violations, CustomRule};
+use crate::type_errors::{extract_type_errors_with_ignores, find_comment_markers, merge_diagnostics, IgnoreDirective, ScopeNaming};
 use crate::symbols::extract_symbols;
-use crate::dependencies::{extract_dependencies, extract_imports};
-use crate::visitors::dependency_visitor::ImportInfo;
-use crate::tests::extract_tests;
+use crate::dependencies::{classify_import, extract_module_dependencies, find_references, find_unresolved_imports, lint_imports, ImportIssue, SymbolReference};
+use crate::visitors::dependency_visitor::{ImportInfo, ReexportInfo};
+use crate::tests::{check_type_tests, extract_tests};
+use crate::watchdog::Watchdog;
 
 #[derive(Default, Clone)]
 pub struct AnalysisOptions {
     pub include_patterns: Vec<String>,
     pub exclude_patterns: Vec<String>,
     pub parallel: bool,
+    /// Worker threads used when `parallel` is set: `0` uses rayon's global pool
+    /// (all cores), `1` analyzes files serially, `n` runs in a dedicated `n`-thread pool
+    pub jobs: usize,
     pub exported_only: bool,
+    /// Lines of code shown around each type error (`Some(0)` = only the error line);
+    /// `None` uses the default scope-based truncation
+    pub context_lines: Option<usize>,
+    /// Check each file for duplicate and unused imports
+    pub lint_imports: bool,
+    /// Check each file for relative imports that resolve to no file
+    pub check_imports: bool,
+    /// Warn about bindings that shadow one in an enclosing scope
+    pub lint_shadowing: bool,
+    /// Report each explicit `any` as an info diagnostic, see [`crate::explicit_any`]
+    pub report_any: bool,
+    /// Warn about `catch` blocks with no statements, see [`crate::empty_catch`]
+    pub lint_empty_catch: bool,
+    /// Custom rules from `[[rules]]` in `ta.toml`, see [`crate::rules`]
+    pub rules: Vec<CustomRule>,
+    /// How each type error's `scope` is named
+    pub scope_naming: ScopeNaming,
+    /// Analyze the `<script>` blocks of `.vue` and `.svelte` files, see [`crate::sfc`]
+    pub include_sfc: bool,
+    /// Collect imports of and references to the symbol with this name
+    pub references_to: Option<String>,
+    /// Report comments containing these markers (e.g. `TODO`) as info diagnostics
+    pub comment_markers: Vec<String>,
+    /// Record how long each file takes to analyze, see [`FileTiming`]
+    pub timings: bool,
+    /// tsconfig `paths` patterns (e.g. `@/*`) whose imports are classified as
+    /// [`DependencyScope::Repo`](crate::models::DependencyScope::Repo), see [`load_path_aliases`](crate::dependencies::load_path_aliases)
+    pub path_aliases: Vec<String>,
+    /// Give up on a file whose analysis takes longer than this, recording it in
+    /// [`AnalysisResult::failed_files`] instead of waiting for it
+    pub per_file_timeout: Option<Duration>,
+    /// Decide each type test's status from the errors its `@ts-expect-error`
+    /// comments expect, see [`check_type_tests`]
+    pub check_type_tests: bool,
+    /// Fail a file with syntax errors with [`Error::ParseError`] instead of
+    /// reporting them as type errors, for callers that need its whole AST
+    pub require_parse: bool,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -30,6 +81,11 @@ pub struct FileDependency {
 pub struct FileImports {
     pub file: String,
     pub imports: Vec<ImportInfo>,
+    pub reexports: Vec<ReexportInfo>,
+    /// Import hygiene problems, only collected with [`AnalysisOptions::lint_imports`]
+    /// or [`AnalysisOptions::check_imports`]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<ImportIssue>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -39,6 +95,56 @@ pub struct SymbolDependency {
     pub used_in: Vec<String>,
 }
 
+/// Why a file could not be analyzed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum FailureKind {
+    /// The file could not be read
+    Io,
+    /// The extension is not a TypeScript/JavaScript source type
+    UnsupportedSourceType,
+    /// Analysis exceeded [`AnalysisOptions::per_file_timeout`]
+    Timeout,
+    /// Any other analysis failure
+    Other,
+}
+
+/// A file that was skipped during analysis, with the reason
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FailedFile {
+    pub file: PathBuf,
+    pub kind: FailureKind,
+    pub message: String,
+}
+
+impl FailedFile {
+    fn new(file: &Path, error: &Error) -> Self {
+        let kind = match error {
+            Error::IoError(_) => FailureKind::Io,
+            Error::InvalidSourceType(_) => FailureKind::UnsupportedSourceType,
+            Error::Timeout(_) => FailureKind::Timeout,
+            _ => FailureKind::Other,
+        };
+        Self {
+            file: file.to_path_buf(),
+            kind,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Wall-clock time spent analyzing one file, only recorded with [`AnalysisOptions::timings`].
+///
+/// `total` also covers reading the file, so it is at least the sum of the phases.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct FileTiming {
+    pub file: String,
+    pub total: Duration,
+    pub parse: Duration,
+    pub semantic: Duration,
+    /// Error, symbol, dependency and test extraction
+    pub extraction: Duration,
+}
+
 #[derive(Default, Debug, serde::Serialize)]
 pub struct AnalysisResult {
     pub type_errors: Vec<TypeError>,
@@ -47,6 +153,74 @@ pub struct AnalysisResult {
     pub file_imports: Vec<FileImports>,
     pub tests: Vec<TypeTest>,
     pub total_files: usize,
+    /// Files that could not be analyzed (unreadable or unsupported)
+    pub failed_files: Vec<FailedFile>,
+    /// `ta-ignore` comments that did not suppress any error
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unused_ignores: Vec<IgnoreDirective>,
+    /// Reference sites, only collected with [`AnalysisOptions::references_to`]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<SymbolReference>,
+    /// Per-file durations in input order, only collected with [`AnalysisOptions::timings`]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub timings: Vec<FileTiming>,
+}
+
+impl AnalysisResult {
+    /// Merge one file's analysis, or record it as failed
+    fn add_file(&mut self, path: &Path, file_analysis: Result<FileAnalysis>) {
+        let file_analysis = match file_analysis {
+            Ok(analysis) => analysis,
+            Err(error) => {
+                self.failed_files.push(FailedFile::new(path, &error));
+                return;
+            }
+        };
+
+        self.type_errors.extend(file_analysis.type_errors);
+        self.unused_ignores.extend(file_analysis.unused_ignores);
+        self.references.extend(file_analysis.references);
+        self.timings.extend(file_analysis.timing);
+        self.symbols.extend(file_analysis.symbols);
+
+        // Preserve file context for dependencies
+        if !file_analysis.dependencies.is_empty() {
+            self.dependencies.push(FileDependency {
+                file: file_analysis.file_path.to_string_lossy().to_string(),
+                imports: file_analysis.dependencies,
+            });
+        }
+
+        // Preserve imports with symbols, and re-exports so barrels stay visible
+        if !file_analysis.imports.is_empty()
+            || !file_analysis.reexports.is_empty()
+            || !file_analysis.import_issues.is_empty()
+        {
+            self.file_imports.push(FileImports {
+                file: file_analysis.file_path.to_string_lossy().to_string(),
+                imports: file_analysis.imports,
+                reexports: file_analysis.reexports,
+                issues: file_analysis.import_issues,
+            });
+        }
+
+        self.tests.extend(file_analysis.tests);
+    }
+
+    /// `self` followed by `other`, for results of adjacent runs of files
+    fn append(mut self, other: AnalysisResult) -> AnalysisResult {
+        self.type_errors.extend(other.type_errors);
+        self.symbols.extend(other.symbols);
+        self.dependencies.extend(other.dependencies);
+        self.file_imports.extend(other.file_imports);
+        self.tests.extend(other.tests);
+        self.total_files += other.total_files;
+        self.failed_files.extend(other.failed_files);
+        self.unused_ignores.extend(other.unused_ignores);
+        self.references.extend(other.references);
+        self.timings.extend(other.timings);
+        self
+    }
 }
 
 pub struct FileAnalysis {
@@ -55,79 +229,297 @@ pub struct FileAnalysis {
     pub symbols: Vec<SymbolInfo>,
     pub dependencies: Vec<String>,
     pub imports: Vec<ImportInfo>,
+    pub reexports: Vec<ReexportInfo>,
+    pub import_issues: Vec<ImportIssue>,
     pub tests: Vec<TypeTest>,
+    /// `ta-ignore` comments that did not suppress any error
+    pub unused_ignores: Vec<IgnoreDirective>,
+    pub references: Vec<SymbolReference>,
+    pub timing: Option<FileTiming>,
 }
 
+/// Called once for every file [`Analyzer::analyze_files`] finishes, possibly from worker threads
+pub type ProgressCallback = Box<dyn Fn(&Path) + Send + Sync>;
+
 pub struct Analyzer {
-    options: AnalysisOptions,
+    /// Shared with the [`Watchdog`] threads that analyze under a deadline
+    options: Arc<AnalysisOptions>,
+    on_file_done: Option<ProgressCallback>,
+    /// Only started with [`AnalysisOptions::per_file_timeout`]
+    watchdog: Option<Watchdog>,
+    /// Cleared at the start of every batch, so a long-lived analyzer (the
+    /// watcher's) sees `package.json` edits on its next run
+    package_types: Arc<PackageTypes>,
 }
 
 impl Analyzer {
     pub fn new(options: AnalysisOptions) -> Self {
-        Self { options }
+        let watchdog = options.per_file_timeout.map(|_| Watchdog::new());
+        Self {
+            options: Arc::new(options),
+            on_file_done: None,
+            watchdog,
+            package_types: Arc::default(),
+        }
+    }
+
+    /// Report each completed file (successful or failed) to `callback`, e.g. to drive a progress bar
+    pub fn with_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Path) + Send + Sync + 'static,
+    {
+        self.on_file_done = Some(Box::new(callback));
+        self
+    }
+
+    fn analyze_and_report(&self, path: &Path) -> Result<FileAnalysis> {
+        let analysis = match (&self.watchdog, self.options.per_file_timeout) {
+            (Some(watchdog), Some(timeout)) => self.analyze_with_timeout(watchdog, path, timeout),
+            _ => self.analyze_single_file(path),
+        };
+        if let Some(callback) = &self.on_file_done {
+            callback(path);
+        }
+        analysis
     }
 
+    /// Analyze `files`, with results in the order of `files` regardless of thread scheduling.
+    ///
+    /// Each worker folds the files it finishes straight into its own partial
+    /// [`AnalysisResult`], so besides the merged output only one [`FileAnalysis`]
+    /// per worker is alive at a time, instead of one per file. Partial results
+    /// cover adjacent runs of `files` and are joined in order.
     pub fn analyze_files(&self, files: &[PathBuf]) -> Result<AnalysisResult> {
+        self.package_types.clear();
+        let fold = |mut result: AnalysisResult, path: &PathBuf| {
+            result.add_file(path, self.analyze_and_report(path));
+            result
+        };
+        let mut result = self.run_workers(
+            || {
+                files.par_iter()
+                    .fold(AnalysisResult::default, fold)
+                    .reduce(AnalysisResult::default, AnalysisResult::append)
+            },
+            || files.iter().fold(AnalysisResult::default(), fold),
+        )?;
+        result.total_files = files.len();
+        Ok(result)
+    }
+
+    /// [`Self::analyze_files`], also handing each file's analysis to `on_file` as it is produced.
+    ///
+    /// Files finish in any order across workers; each analysis is held back
+    /// until every file before it has been handed over, so `on_file` sees the
+    /// files in the order of `files` and the result is identical to
+    /// [`Self::analyze_files`]. Failed files are only recorded in the result.
+    pub fn analyze_files_each(&self, files: &[PathBuf], mut on_file: impl FnMut(&FileAnalysis)) -> Result<AnalysisResult> {
+        self.package_types.clear();
         let mut result = AnalysisResult {
             total_files: files.len(),
             ..Default::default()
         };
 
-        let file_analyses: Vec<FileAnalysis> = if self.options.parallel {
-            files.par_iter()
-                .filter_map(|path| self.analyze_single_file(path).ok())
-                .collect()
-        } else {
-            files.iter()
-                .filter_map(|path| self.analyze_single_file(path).ok())
-                .collect()
-        };
+        let (sender, receiver) = mpsc::channel();
+        std::thread::scope(|scope| {
+            let workers = scope.spawn(move || {
+                self.run_workers(
+                    || {
+                        files.par_iter().enumerate().for_each_with(sender.clone(), |sender, (index, path)| {
+                            let _ = sender.send((index, self.analyze_and_report(path)));
+                        })
+                    },
+                    || {
+                        for (index, path) in files.iter().enumerate() {
+                            let _ = sender.send((index, self.analyze_and_report(path)));
+                        }
+                    },
+                )
+            });
 
-        for file_analysis in file_analyses {
-            result.type_errors.extend(file_analysis.type_errors);
-            result.symbols.extend(file_analysis.symbols);
-
-            // Preserve file context for dependencies
-            if !file_analysis.dependencies.is_empty() {
-                result.dependencies.push(FileDependency {
-                    file: file_analysis.file_path.to_string_lossy().to_string(),
-                    imports: file_analysis.dependencies,
-                });
+            let mut pending = BTreeMap::new();
+            let mut next = 0;
+            // Ends once the workers are done and drop their senders
+            for (index, file_analysis) in receiver {
+                pending.insert(index, file_analysis);
+                while let Some(file_analysis) = pending.remove(&next) {
+                    if let Ok(analysis) = &file_analysis {
+                        on_file(analysis);
+                    }
+                    result.add_file(&files[next], file_analysis);
+                    next += 1;
+                }
             }
+            workers.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })?;
 
-            // Preserve imports with symbols
-            if !file_analysis.imports.is_empty() {
-                result.file_imports.push(FileImports {
-                    file: file_analysis.file_path.to_string_lossy().to_string(),
-                    imports: file_analysis.imports,
-                });
-            }
+        Ok(result)
+    }
 
-            result.tests.extend(file_analysis.tests);
+    /// Run `parallel` as configured by [`AnalysisOptions::parallel`] and
+    /// [`AnalysisOptions::jobs`], or `serial` when analysis is single-threaded
+    fn run_workers<T: Send>(&self, parallel: impl FnOnce() -> T + Send, serial: impl FnOnce() -> T) -> Result<T> {
+        match (self.options.parallel, self.options.jobs) {
+            (true, 0) => Ok(parallel()),
+            (true, jobs) if jobs > 1 => {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(jobs)
+                    .build()
+                    .map_err(|e| Error::AnalysisError(format!("Failed to start {} worker threads: {}", jobs, e)))?;
+                Ok(pool.install(parallel))
+            }
+            _ => Ok(serial()),
         }
+    }
 
-        Ok(result)
+    /// [`Self::analyze_single_file`] on a `watchdog` thread, failing with
+    /// [`Error::Timeout`] once `timeout` passes.
+    ///
+    /// A thread cannot be cancelled, so a timed-out analysis runs on in the
+    /// background and its result is dropped; the caller moves on immediately.
+    /// See [`Watchdog::run`] for how those analyses are tracked and capped.
+    fn analyze_with_timeout(&self, watchdog: &Watchdog, path: &Path, timeout: Duration) -> Result<FileAnalysis> {
+        let analyzer = Analyzer {
+            options: Arc::clone(&self.options),
+            on_file_done: None,
+            watchdog: None,
+            package_types: Arc::clone(&self.package_types),
+        };
+        let owned_path = path.to_path_buf();
+        watchdog.run(path, timeout, move || analyzer.analyze_single_file(&owned_path))
     }
 
+    /// Analyze one file on disk.
+    ///
+    /// The source is normalized with [`normalize_source`] first, so spans,
+    /// lines and columns refer to the text without a BOM or `\r\n` endings.
     pub fn analyze_single_file(&self, path: &Path) -> Result<FileAnalysis> {
+        let start = Instant::now();
         let source_code = std::fs::read_to_string(path)?;
+        let source_code = normalize_source(&source_code);
+        let mut analysis = self.analyze_source_as(&source_code, &path.to_string_lossy(), ModuleFormat::resolve(path, &self.package_types))?;
+        analysis.file_path = path.to_path_buf();
+        // Include the read so the total matches what the file costs end to end
+        if let Some(timing) = &mut analysis.timing {
+            timing.total = start.elapsed();
+        }
+        Ok(analysis)
+    }
+
+    /// Analyze in-memory source text without touching the filesystem.
+    ///
+    /// `filename` determines the source type (`.ts`, `.tsx`, ...) and is used as
+    /// the `file` of every extracted error, symbol, and test.
+    ///
+    /// Without a filesystem there is no `package.json` to consult, so the module
+    /// format (ESM or CommonJS) is only known for `.mts`/`.cts`-style extensions.
+    ///
+    /// Like [`Self::analyze_single_file`], the source is normalized with
+    /// [`normalize_source`] first.
+    pub fn analyze_source(&self, source: &str, filename: &str) -> Result<FileAnalysis> {
+        let source = normalize_source(source);
+        self.analyze_source_as(&source, filename, ModuleFormat::from_extension(Path::new(filename)))
+    }
+
+    /// [`Self::analyze_source`] for a file whose module format is already resolved
+    fn analyze_source_as(&self, source: &str, filename: &str, format: Option<ModuleFormat>) -> Result<FileAnalysis> {
+        let path = Path::new(filename);
         let allocator = Allocator::default();
-        let source_type = SourceType::from_path(path).map_err(|_| Error::InvalidSourceType(path.to_string_lossy().to_string()))?;
-        
-        let parser = Parser::new(&allocator, &source_code, source_type);
+
+        // Components are analyzed as their scripts, blanked around but in place;
+        // bundlers decide their module format, so it is not checked
+        let sfc_script;
+        let (source, source_type, format) = if self.options.include_sfc && is_sfc_path(path) {
+            sfc_script = extract_scripts(source);
+            (sfc_script.source.as_str(), sfc_script.source_type, None)
+        } else {
+            let source_type = SourceType::from_path(path).map_err(|_| Error::InvalidSourceType(filename.to_string()))?;
+            (source, source_type, format)
+        };
+
+        let parse_start = Instant::now();
+        let parser = Parser::new(&allocator, source, source_type);
         let parse_ret = parser.parse();
+        let parse_time = parse_start.elapsed();
+        if self.options.require_parse {
+            if let Some(error) = parse_ret.errors.first() {
+                return Err(Error::ParseError { file: filename.to_string(), message: error.to_string() });
+            }
+        }
 
-        let semantic_ret = SemanticBuilder::new(&source_code).build(&parse_ret.program);
+        // Trivias carry the comments that `ta-ignore` directives are read from
+        let semantic_start = Instant::now();
+        let semantic_ret = SemanticBuilder::new(source)
+            .with_trivias(parse_ret.trivias.clone())
+            .build(&parse_ret.program);
         let semantic = semantic_ret.semantic;
-        let diagnostics = semantic_ret.errors;
-        
-        let file_path_str = path.to_string_lossy().to_string();
+        let semantic_time = semantic_start.elapsed();
+
+        let extraction_start = Instant::now();
+        // Syntax errors leave an empty/partial AST, so they must be reported too
+        let mut diagnostics = merge_diagnostics(parse_ret.errors, semantic_ret.errors);
+        if !self.options.comment_markers.is_empty() {
+            diagnostics.extend(find_comment_markers(source, semantic.trivias(), &self.options.comment_markers));
+        }
+        if self.options.lint_shadowing {
+            diagnostics.extend(find_shadowed_bindings(&semantic));
+        }
+        if self.options.report_any {
+            diagnostics.extend(find_explicit_any(&semantic));
+        }
+        if self.options.lint_empty_catch {
+            diagnostics.extend(find_empty_catches(source, &semantic));
+        }
+        diagnostics.extend(find_rule_violations(&parse_ret.program, &self.options.rules));
+        if let Some(format) = format {
+            diagnostics.extend(find_module_mismatches(&parse_ret.program, &semantic, source_type, format));
+        }
+
+        let file_path_str = filename.to_string();
+
+        let (type_errors, unused_ignores) = extract_type_errors_with_ignores(
+            source,
+            &semantic,
+            &diagnostics,
+            &parse_ret.program,
+            file_path_str.clone(),
+            self.options.context_lines,
+            &self.options.scope_naming,
+        );
+        let symbols = extract_symbols(source, &parse_ret.program, file_path_str.clone(), self.options.exported_only);
+        let (dependencies, mut imports, reexports) = extract_module_dependencies(&parse_ret.program, path.to_path_buf());
+        if !self.options.path_aliases.is_empty() {
+            for import in &mut imports {
+                import.scope = classify_import(&import.source, &self.options.path_aliases);
+            }
+        }
+        let mut tests = extract_tests(source, &parse_ret.program, file_path_str);
+        if self.options.check_type_tests {
+            check_type_tests(&mut tests, source, semantic.trivias(), &diagnostics);
+        }
+        let mut import_issues = if self.options.lint_imports {
+            lint_imports(source, &parse_ret.program)
+        } else {
+            Vec::new()
+        };
+        if self.options.check_imports {
+            import_issues.extend(find_unresolved_imports(source, &parse_ret.program, path));
+        }
+        let references = match &self.options.references_to {
+            Some(name) => find_references(source, &parse_ret.program, filename, name),
+            None => Vec::new(),
+        };
 
-        let type_errors = extract_type_errors(&source_code, &semantic, &diagnostics, &parse_ret.program, file_path_str.clone());
-        let symbols = extract_symbols(&source_code, &parse_ret.program, file_path_str.clone(), self.options.exported_only);
-        let dependencies = extract_dependencies(&parse_ret.program, path.to_path_buf());
-        let imports = extract_imports(&parse_ret.program, path.to_path_buf());
-        let tests = extract_tests(&parse_ret.program, file_path_str);
+        let timing = self.options.timings.then(|| {
+            let extraction = extraction_start.elapsed();
+            FileTiming {
+                file: filename.to_string(),
+                total: parse_time + semantic_time + extraction,
+                parse: parse_time,
+                semantic: semantic_time,
+                extraction,
+            }
+        });
 
         Ok(FileAnalysis {
             file_path: path.to_path_buf(),
@@ -135,7 +527,413 @@ impl Analyzer {
             symbols,
             dependencies,
             imports,
+            reexports,
+            import_issues,
             tests,
+            unused_ignores,
+            references,
+            timing,
         })
     }
-}
\ No newline at end of file
+}
+
+/// Strips a leading UTF-8 BOM and converts `\r\n` line endings to `\n`.
+///
+/// Files authored on Windows would otherwise carry a `\r` into every highlighted
+/// line, and a BOM shifts every column on the first line by one.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::analyzer::normalize_source;
+///
+/// assert_eq!(normalize_source("\u{feff}let a = 1;\r\nlet b = 2;\r\n"), "let a = 1;\nlet b = 2;\n");
+/// assert_eq!(normalize_source("let a = 1;\n"), "let a = 1;\n");
+/// ```
+pub fn normalize_source(source: &str) -> Cow<'_, str> {
+    let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+    if source.contains("\r\n") {
+        Cow::Owned(source.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// `(id, line, column)` of each error in `source` written to disk as `name`
+    fn file_errors(source: &str, name: &str) -> Vec<(String, usize, usize)> {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(name);
+        fs::write(&path, source).unwrap();
+        Analyzer::new(AnalysisOptions::default())
+            .analyze_single_file(&path)
+            .unwrap()
+            .type_errors
+            .into_iter()
+            .map(|e| (e.id, e.line, e.column))
+            .collect()
+    }
+
+    #[test]
+    fn test_crlf_file_reports_logical_positions() {
+        let lf = "export function f() {\n  let x = 1;\n  let x = 2;\n}\n";
+        let expected = file_errors(lf, "lf.ts");
+        assert_eq!(expected.len(), 1);
+        assert_eq!((expected[0].1, expected[0].2), (2, 6));
+
+        let errors = file_errors(&lf.replace('\n', "\r\n"), "crlf.ts");
+        assert_eq!(errors, expected);
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("crlf.ts");
+        fs::write(&path, lf.replace('\n', "\r\n")).unwrap();
+        let analysis = Analyzer::new(AnalysisOptions::default()).analyze_single_file(&path).unwrap();
+        let code = &analysis.type_errors[0].source_code.as_ref().unwrap().display_code;
+        assert!(!code.contains('\r'), "{:?}", code);
+    }
+
+    #[test]
+    fn test_bom_file_reports_logical_positions() {
+        let source = "let x = 1; let x = 2;\n";
+        let expected = file_errors(source, "plain.ts");
+        assert_eq!(expected.len(), 1);
+
+        assert_eq!(file_errors(&format!("\u{feff}{}", source), "bom.ts"), expected);
+    }
+
+    #[test]
+    fn test_analyze_source_normalizes_bom_and_crlf() {
+        let analyzer = Analyzer::new(AnalysisOptions::default());
+        let positions = |source: &str| -> Vec<(usize, usize)> {
+            let analysis = analyzer.analyze_source(source, "in-memory.ts").unwrap();
+            analysis.type_errors.iter().map(|e| (e.line, e.column)).collect()
+        };
+        let source = "let x = 1;\nlet x = 2;\n";
+
+        assert_eq!(positions(source), vec![(1, 4)]);
+        assert_eq!(positions(&format!("\u{feff}{}", source)), vec![(1, 4)]);
+        assert_eq!(positions(&format!("\u{feff}{}", source.replace('\n', "\r\n"))), vec![(1, 4)]);
+    }
+
+    #[test]
+    fn test_analyze_source_reports_errors_without_filesystem() {
+        let analyzer = Analyzer::new(AnalysisOptions::default());
+        let source = "export function foo() { let x = 1; let x = 2; }";
+
+        let analysis = analyzer.analyze_source(source, "virtual/in-memory.ts").unwrap();
+
+        assert_eq!(analysis.file_path, PathBuf::from("virtual/in-memory.ts"));
+        assert!(!analysis.type_errors.is_empty());
+        assert_eq!(analysis.type_errors[0].file, "virtual/in-memory.ts");
+        assert_eq!(analysis.type_errors[0].scope, "foo");
+        assert!(analysis.symbols.iter().any(|s| s.name == "foo" && s.exported));
+    }
+
+    #[test]
+    fn test_parallel_analysis_is_deterministic() {
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<PathBuf> = (0..24)
+            .map(|i| {
+                let path = dir.path().join(format!("file{}.ts", i));
+                let source = format!(
+                    "export function f{i}() {{ let x = 1; let x = 2; }}\nexport interface I{i} {{ a: string }}\n"
+                );
+                std::fs::write(&path, source).unwrap();
+                path
+            })
+            .collect();
+
+        let analyzer = Analyzer::new(AnalysisOptions { parallel: true, ..Default::default() });
+        let to_json = |result: &AnalysisResult| {
+            serde_json::to_string(&(&result.type_errors, &result.symbols)).unwrap()
+        };
+
+        let first = analyzer.analyze_files(&files).unwrap();
+        let second = analyzer.analyze_files(&files).unwrap();
+
+        assert_eq!(to_json(&first), to_json(&second));
+        let files_in_order: Vec<&str> = first.type_errors.iter().map(|e| e.file.as_str()).collect();
+        let expected: Vec<String> = files.iter().map(|f| f.to_string_lossy().to_string()).collect();
+        assert_eq!(files_in_order, expected);
+    }
+
+    #[test]
+    fn test_jobs_setting_does_not_change_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<PathBuf> = (0..12)
+            .map(|i| {
+                let path = dir.path().join(format!("file{}.ts", i));
+                std::fs::write(&path, format!("export function f{i}() {{ let x = 1; let x = 2; }}\n")).unwrap();
+                path
+            })
+            .collect();
+        let analyze = |jobs: usize| {
+            let analyzer = Analyzer::new(AnalysisOptions { parallel: true, jobs, ..Default::default() });
+            let result = analyzer.analyze_files(&files).unwrap();
+            serde_json::to_string(&(&result.type_errors, &result.symbols)).unwrap()
+        };
+
+        let default = analyze(0);
+
+        assert_eq!(analyze(1), default);
+        assert_eq!(analyze(3), default);
+    }
+
+    #[test]
+    fn test_folded_analysis_matches_analysis_in_input_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files: Vec<PathBuf> = (0..30)
+            .map(|i| {
+                let path = dir.path().join(format!("file{}.ts", i));
+                let source = format!(
+                    "import {{ helper }} from './file{next}';\nexport function f{i}() {{ let x = helper(); let x = 2; }}\ntest('f{i}', () => {{}});\n",
+                    next = (i + 1) % 30
+                );
+                std::fs::write(&path, source).unwrap();
+                path
+            })
+            .collect();
+        files.insert(7, dir.path().join("missing.ts"));
+
+        for (parallel, jobs) in [(true, 0), (true, 3), (false, 0)] {
+            let analyzer = Analyzer::new(AnalysisOptions { parallel, jobs, ..Default::default() });
+            // `analyze_files_each` adds the files one at a time in input order
+            let in_order = analyzer.analyze_files_each(&files, |_| {}).unwrap();
+            let folded = analyzer.analyze_files(&files).unwrap();
+
+            assert_eq!(folded.total_files, 31);
+            assert_eq!(folded.failed_files.len(), 1);
+            assert_eq!(serde_json::to_value(&folded).unwrap(), serde_json::to_value(&in_order).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_each_file_is_handed_over_in_input_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files: Vec<PathBuf> = (0..20)
+            .map(|i| {
+                let path = dir.path().join(format!("file{}.ts", i));
+                std::fs::write(&path, format!("export function f{i}() {{ let x = 1; let x = 2; }}\n")).unwrap();
+                path
+            })
+            .collect();
+        files.insert(4, dir.path().join("missing.ts"));
+
+        for (parallel, jobs) in [(true, 0), (true, 3), (false, 0)] {
+            let analyzer = Analyzer::new(AnalysisOptions { parallel, jobs, ..Default::default() });
+            let mut seen = Vec::new();
+            let each = analyzer.analyze_files_each(&files, |analysis| seen.push(analysis.file_path.clone())).unwrap();
+
+            let analyzed: Vec<PathBuf> = files.iter().filter(|path| path.exists()).cloned().collect();
+            assert_eq!(seen, analyzed);
+            let collected = analyzer.analyze_files(&files).unwrap();
+            assert_eq!(serde_json::to_value(&each).unwrap(), serde_json::to_value(&collected).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_progress_callback_sees_every_file() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<PathBuf> = (0..8)
+            .map(|i| {
+                let path = dir.path().join(format!("file{}.ts", i));
+                std::fs::write(&path, "export const a = 1;").unwrap();
+                path
+            })
+            .chain(std::iter::once(dir.path().join("missing.ts")))
+            .collect();
+
+        let completed = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&completed);
+        let analyzer = Analyzer::new(AnalysisOptions { parallel: true, ..Default::default() })
+            .with_progress(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+
+        analyzer.analyze_files(&files).unwrap();
+
+        assert_eq!(completed.load(Ordering::SeqCst), files.len());
+    }
+
+    #[test]
+    fn test_failed_files_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = dir.path().join("good.ts");
+        let unsupported = dir.path().join("notes.txt");
+        let missing = dir.path().join("missing.ts");
+        std::fs::write(&good, "export const a = 1;").unwrap();
+        std::fs::write(&unsupported, "hello").unwrap();
+
+        let analyzer = Analyzer::new(AnalysisOptions::default());
+        let result = analyzer
+            .analyze_files(&[good, unsupported.clone(), missing.clone()])
+            .unwrap();
+
+        assert_eq!(result.symbols.len(), 1);
+        assert_eq!(result.failed_files.len(), 2);
+        assert_eq!(result.failed_files[0].file, unsupported);
+        assert_eq!(result.failed_files[0].kind, FailureKind::UnsupportedSourceType);
+        assert_eq!(result.failed_files[1].file, missing);
+        assert_eq!(result.failed_files[1].kind, FailureKind::Io);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_per_file_timeout_records_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let small = dir.path().join("small.ts");
+        std::fs::write(&small, "export const a = 1;\n").unwrap();
+        // Reading a named pipe blocks until something opens it for writing,
+        // so its analysis hangs until the end of the test
+        let slow = dir.path().join("slow.ts");
+        let mkfifo = std::process::Command::new("mkfifo").arg(&slow).status().unwrap();
+        assert!(mkfifo.success());
+
+        let options = AnalysisOptions { per_file_timeout: Some(Duration::from_secs(1)), ..Default::default() };
+        let analyzer = Analyzer::new(options);
+        let started = Instant::now();
+        let result = analyzer.analyze_files(&[slow.clone(), small]).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(30));
+        assert_eq!(result.failed_files.len(), 1);
+        assert_eq!(result.failed_files[0].file, slow);
+        assert_eq!(result.failed_files[0].kind, FailureKind::Timeout);
+        assert_eq!(result.failed_files[0].message, "Analysis timed out after 1000 ms");
+        assert_eq!(result.symbols.len(), 1);
+
+        // The slow analysis is still running, so the next run does not start it again
+        let result = analyzer.analyze_files(std::slice::from_ref(&slow)).unwrap();
+        assert_eq!(result.failed_files[0].kind, FailureKind::Timeout);
+        assert_eq!(analyzer.watchdog.as_ref().unwrap().abandoned(), vec![slow.clone()]);
+
+        // Opening the pipe for writing and closing it lets the abandoned read finish
+        drop(std::fs::OpenOptions::new().write(true).open(&slow).unwrap());
+    }
+
+    #[test]
+    fn test_timings_recorded_per_file_only_when_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<PathBuf> = ["a.ts", "b.ts"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(name);
+                std::fs::write(&path, "export function f() { return 1; }").unwrap();
+                path
+            })
+            .collect();
+
+        let untimed = Analyzer::new(AnalysisOptions::default()).analyze_files(&files).unwrap();
+        assert!(untimed.timings.is_empty());
+
+        let timed = Analyzer::new(AnalysisOptions { timings: true, ..Default::default() })
+            .analyze_files(&files)
+            .unwrap();
+        assert_eq!(timed.timings.len(), 2);
+        for (timing, path) in timed.timings.iter().zip(&files) {
+            assert_eq!(timing.file, path.to_string_lossy());
+            assert!(timing.total >= timing.parse + timing.semantic + timing.extraction);
+        }
+    }
+
+    #[test]
+    fn test_ta_ignore_next_line_suppresses_error() {
+        let analyzer = Analyzer::new(AnalysisOptions::default());
+        let source = "// ta-ignore-next-line\nconst a = 1;\nconst a = 2;\n";
+        let analysis = analyzer.analyze_source(source, "ignored.ts").unwrap();
+
+        assert!(analysis.type_errors.is_empty(), "Got: {:?}", analysis.type_errors);
+        assert!(analysis.unused_ignores.is_empty());
+    }
+
+    #[test]
+    fn test_ta_ignore_matches_any_labelled_line() {
+        let analyzer = Analyzer::new(AnalysisOptions::default());
+        // The redeclaration error starts at the first `a` and is also labelled at the second
+        let source = "const a = 1;\nconst a = 2; // ta-ignore\n";
+        let analysis = analyzer.analyze_source(source, "ignored.ts").unwrap();
+        assert!(analysis.type_errors.is_empty(), "Got: {:?}", analysis.type_errors);
+        assert!(analysis.unused_ignores.is_empty());
+
+        // Only the first line of a multi-line label counts: the import below is
+        // labelled from `import` to `;`
+        let source = "import {\n  // ta-ignore-next-line\n  readFile,\n} from 'fs';\n";
+        let analysis = analyzer.analyze_source(source, "legacy.cjs").unwrap();
+        assert_eq!(analysis.type_errors.len(), 1);
+        assert_eq!(analysis.type_errors[0].position.end_line, 4);
+        assert_eq!(analysis.unused_ignores.len(), 1);
+    }
+
+    #[test]
+    fn test_unused_ta_ignore_directives_are_reported() {
+        let analyzer = Analyzer::new(AnalysisOptions::default());
+        // The code doesn't match the error's id, and line 5 has no error at all
+        let source = "// ta-ignore-next-line TS2322\nconst a = 1;\nconst a = 2;\n\nconst b = 1; // ta-ignore\n";
+        let analysis = analyzer.analyze_source(source, "unused.ts").unwrap();
+
+        assert_eq!(analysis.type_errors.len(), 1);
+        let unused: Vec<(usize, usize)> = analysis.unused_ignores.iter().map(|d| (d.line, d.target_line)).collect();
+        assert_eq!(unused, [(1, 2), (5, 5)]);
+        assert_eq!(analysis.unused_ignores[0].codes, ["TS2322"]);
+        assert_eq!(analysis.unused_ignores[0].file, "unused.ts");
+    }
+
+    #[test]
+    fn test_vue_script_errors_use_component_lines() {
+        let source = "<template>\n  <button @click=\"count++\">{{ count }}</button>\n</template>\n\n<script setup lang=\"ts\">\nlet count = 0;\nlet count = 1;\n</script>\n";
+
+        let plain = Analyzer::new(AnalysisOptions::default()).analyze_source(source, "Counter.vue");
+        assert!(matches!(plain, Err(Error::InvalidSourceType(_))));
+
+        let analysis = Analyzer::new(AnalysisOptions { include_sfc: true, ..Default::default() })
+            .analyze_source(source, "Counter.vue")
+            .unwrap();
+        assert_eq!(analysis.type_errors.len(), 1);
+        let error = &analysis.type_errors[0];
+        assert_eq!((error.line, error.position.start_column), (6, 5));
+        assert_eq!(&source[error.span.start as usize..error.span.end as usize], "count");
+    }
+
+    #[test]
+    fn test_flagged_comment_is_info_diagnostic_on_its_line() {
+        let analyzer = Analyzer::new(AnalysisOptions {
+            comment_markers: vec!["TODO".to_string(), "FIXME".to_string()],
+            ..Default::default()
+        });
+        let source = "export function f() {\n  // TODO: fix\n  return 1;\n}\n";
+
+        let analysis = analyzer.analyze_source(source, "todo.ts").unwrap();
+
+        assert_eq!(analysis.type_errors.len(), 1);
+        let error = &analysis.type_errors[0];
+        assert_eq!(error.severity, crate::highlighting::ErrorSeverity::Info);
+        assert_eq!(error.id, "TODO");
+        assert_eq!(error.message, "TODO: fix");
+        assert_eq!((error.position.start_line, error.position.start_column), (2, 6));
+        assert_eq!(error.scope, "f");
+    }
+
+    #[test]
+    fn test_analyze_source_rejects_unknown_extension() {
+        let analyzer = Analyzer::new(AnalysisOptions::default());
+        let result = analyzer.analyze_source("let x = 1;", "notes.txt");
+        assert!(matches!(result, Err(Error::InvalidSourceType(_))));
+    }
+
+    #[test]
+    fn test_require_parse_fails_on_syntax_errors_only() {
+        let analyzer = Analyzer::new(AnalysisOptions { require_parse: true, ..Default::default() });
+        let result = analyzer.analyze_source("export function broken( {", "broken.ts");
+        assert!(matches!(result, Err(Error::ParseError { file, .. }) if file == "broken.ts"));
+
+        // Semantic errors still parse
+        let analysis = analyzer.analyze_source("let x = 1; let x = 2;", "redeclared.ts").unwrap();
+        assert!(!analysis.type_errors.is_empty());
+    }
+}
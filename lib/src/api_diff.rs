@@ -0,0 +1,164 @@
+//! Public API surface comparison between two snapshots of exported symbols.
+//!
+//! Symbols are matched by file and name, so moving a symbol to another file
+//! reads as a removal plus an addition. Signatures are compared through
+//! [`SymbolInfo::display_signature`], which covers parameters, return types,
+//! properties and heritage clauses.
+
+use serde::Serialize;
+
+use crate::models::{SymbolInfo, SymbolKind};
+
+/// How an exported symbol changed between the two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ApiChangeKind {
+    Added,
+    Removed,
+    SignatureChanged,
+}
+
+/// One change to the exported API
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiChange {
+    pub change: ApiChangeKind,
+    pub name: String,
+    /// Kind of the symbol after the change (before it, for removals)
+    pub kind: SymbolKind,
+    pub file: String,
+    /// Signature in the old snapshot (`None` for additions)
+    pub before: Option<String>,
+    /// Signature in the new snapshot (`None` for removals)
+    pub after: Option<String>,
+    /// Removals and signature changes can break consumers; additions cannot
+    pub breaking: bool,
+}
+
+/// Semantic versioning bump implied by a set of changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum SemverImpact {
+    None,
+    Minor,
+    Major,
+}
+
+impl SemverImpact {
+    /// `Major` for any breaking change, `Minor` for additions only, otherwise `None`
+    pub fn of(changes: &[ApiChange]) -> Self {
+        if changes.iter().any(|c| c.breaking) {
+            SemverImpact::Major
+        } else if changes.is_empty() {
+            SemverImpact::None
+        } else {
+            SemverImpact::Minor
+        }
+    }
+}
+
+/// Compares the exported symbols of `old` and `new`.
+///
+/// Non-exported symbols are ignored. Changes are ordered by file and name,
+/// so the report is stable regardless of analysis order.
+pub fn diff_exported_symbols(old: &[SymbolInfo], new: &[SymbolInfo]) -> Vec<ApiChange> {
+    let same_symbol = |a: &SymbolInfo, b: &SymbolInfo| a.file == b.file && a.name == b.name;
+    let old: Vec<&SymbolInfo> = old.iter().filter(|s| s.exported).collect();
+    let new: Vec<&SymbolInfo> = new.iter().filter(|s| s.exported).collect();
+    let mut changes = Vec::new();
+
+    for new_sym in &new {
+        match old.iter().find(|s| same_symbol(s, new_sym)) {
+            Some(old_sym) => {
+                let before = old_sym.display_signature();
+                let after = new_sym.display_signature();
+                if before != after {
+                    changes.push(ApiChange {
+                        change: ApiChangeKind::SignatureChanged,
+                        name: new_sym.name.clone(),
+                        kind: new_sym.kind.clone(),
+                        file: new_sym.file.clone(),
+                        before: Some(before),
+                        after: Some(after),
+                        breaking: true,
+                    });
+                }
+            }
+            None => changes.push(ApiChange {
+                change: ApiChangeKind::Added,
+                name: new_sym.name.clone(),
+                kind: new_sym.kind.clone(),
+                file: new_sym.file.clone(),
+                before: None,
+                after: Some(new_sym.display_signature()),
+                breaking: false,
+            }),
+        }
+    }
+
+    for old_sym in &old {
+        if !new.iter().any(|s| same_symbol(s, old_sym)) {
+            changes.push(ApiChange {
+                change: ApiChangeKind::Removed,
+                name: old_sym.name.clone(),
+                kind: old_sym.kind.clone(),
+                file: old_sym.file.clone(),
+                before: Some(old_sym.display_signature()),
+                after: None,
+                breaking: true,
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| (&a.file, &a.name).cmp(&(&b.file, &b.name)));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisOptions, Analyzer};
+
+    fn symbols(source: &str) -> Vec<SymbolInfo> {
+        Analyzer::new(AnalysisOptions::default())
+            .analyze_source(source, "src/api.ts")
+            .unwrap()
+            .symbols
+    }
+
+    #[test]
+    fn test_removed_and_changed_exports_are_breaking() {
+        let old = symbols(
+            "export function keep(a: string): void {}\n\
+             export function gone(): void {}\n\
+             export function widen(a: string): void {}\n",
+        );
+        let new = symbols(
+            "export function keep(a: string): void {}\n\
+             export function widen(a: string, b: number): void {}\n\
+             export function added(): void {}\n\
+             function internal(): void {}\n",
+        );
+
+        let changes = diff_exported_symbols(&old, &new);
+        let summary: Vec<_> = changes.iter().map(|c| (c.name.as_str(), c.change, c.breaking)).collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("added", ApiChangeKind::Added, false),
+                ("gone", ApiChangeKind::Removed, true),
+                ("widen", ApiChangeKind::SignatureChanged, true),
+            ]
+        );
+        assert_eq!(SemverImpact::of(&changes), SemverImpact::Major);
+    }
+
+    #[test]
+    fn test_additions_only_are_minor() {
+        let old = symbols("export const a = 1;\n");
+        let new = symbols("export const a = 1;\nexport const b = 2;\n");
+
+        let changes = diff_exported_symbols(&old, &new);
+
+        assert_eq!(SemverImpact::of(&changes), SemverImpact::Minor);
+        assert_eq!(SemverImpact::of(&diff_exported_symbols(&old, &old)), SemverImpact::None);
+    }
+}
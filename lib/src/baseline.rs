@@ -0,0 +1,245 @@
+//! Baselines of known type errors (`ta source --baseline`).
+//!
+//! A baseline records the errors of a codebase at one point in time so that
+//! later runs only report errors introduced since. Errors are matched by file,
+//! code and message but not position, so edits that shift an error to another
+//! line do not make it new. Identical errors in one file are counted: a file
+//! with two recorded `TS2322: Type 'string' is not assignable...` errors may
+//! still have two, and a third is new.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::TypeError;
+use crate::{Error, Result};
+
+/// Format version written to new baselines
+pub const BASELINE_VERSION: u32 = 1;
+
+/// One known error
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Path as reported, without a leading `./`
+    pub file: String,
+    pub id: String,
+    pub message: String,
+}
+
+impl BaselineEntry {
+    pub fn of(error: &TypeError) -> Self {
+        Self {
+            file: Self::path_of(&error.file).to_string(),
+            id: error.id.clone(),
+            message: error.message.clone(),
+        }
+    }
+
+    /// `file` as recorded in entries, without a leading `./`
+    pub fn path_of(file: &str) -> &str {
+        file.strip_prefix("./").unwrap_or(file)
+    }
+}
+
+/// The errors known when the baseline was written
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    /// Sorted by file, code and message so rewrites produce small diffs
+    pub errors: Vec<BaselineEntry>,
+}
+
+/// Errors of a run split against a [`Baseline`]
+#[derive(Debug, Clone, Default)]
+pub struct BaselineComparison {
+    /// Errors not in the baseline
+    pub new: Vec<TypeError>,
+    /// Number of errors the baseline already knew about
+    pub known: usize,
+    /// Baseline entries no longer reported, i.e. fixed since
+    pub fixed: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// A baseline of exactly `errors`
+    pub fn from_errors(errors: &[TypeError]) -> Self {
+        let mut entries: Vec<BaselineEntry> = errors.iter().map(BaselineEntry::of).collect();
+        entries.sort();
+        Self { version: BASELINE_VERSION, errors: entries }
+    }
+
+    /// Reads the baseline at `path`, or `None` when there is no file there.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidBaseline`] when the file is not a baseline and
+    /// [`Error::IoError`] when it cannot be read.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_str(&text).map(Some).map_err(|e| Error::InvalidBaseline {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    /// Writes the baseline as pretty JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| Error::AnalysisError(e.to_string()))?;
+        std::fs::write(path, json + "\n")?;
+        Ok(())
+    }
+
+    /// The entries of files for which `covered` holds, e.g. the files a run analyzed.
+    ///
+    /// Compare against this instead of the whole baseline when a run only
+    /// looked at some files, so the others' entries are not reported as fixed.
+    pub fn covering(&self, covered: impl Fn(&str) -> bool) -> Self {
+        Self {
+            version: self.version,
+            errors: self.errors.iter().filter(|entry| covered(&entry.file)).cloned().collect(),
+        }
+    }
+
+    /// This baseline with the entries of files for which `covered` holds
+    /// replaced by `errors`, keeping the entries of all other files.
+    pub fn updated(&self, covered: impl Fn(&str) -> bool, errors: &[TypeError]) -> Self {
+        let mut updated = Self::from_errors(errors);
+        updated.errors.extend(self.errors.iter().filter(|entry| !covered(&entry.file)).cloned());
+        updated.errors.sort();
+        updated
+    }
+
+    /// Splits `errors` into those new since the baseline and those it knew,
+    /// and lists the baseline entries that are no longer reported.
+    pub fn compare(&self, errors: Vec<TypeError>) -> BaselineComparison {
+        let mut remaining: HashMap<&BaselineEntry, usize> = HashMap::new();
+        for entry in &self.errors {
+            *remaining.entry(entry).or_default() += 1;
+        }
+
+        let mut comparison = BaselineComparison::default();
+        for error in errors {
+            match remaining.get_mut(&BaselineEntry::of(&error)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    comparison.known += 1;
+                }
+                _ => comparison.new.push(error),
+            }
+        }
+
+        // Walk the sorted entries so fixed errors are listed in baseline order
+        for entry in &self.errors {
+            if let Some(count) = remaining.get_mut(entry) {
+                if *count > 0 {
+                    *count -= 1;
+                    comparison.fixed.push(entry.clone());
+                }
+            }
+        }
+        comparison
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighting::ErrorSeverity;
+    use crate::models::SpanPosition;
+    use oxc_span::Span;
+    use tempfile::TempDir;
+
+    fn error(file: &str, id: &str, message: &str, line: usize) -> TypeError {
+        TypeError {
+            id: id.to_string(),
+            message: message.to_string(),
+            severity: ErrorSeverity::Error,
+            file: file.to_string(),
+            line,
+            column: 1,
+            scope: "global".to_string(),
+            block: String::new(),
+            source_code: None,
+            span: Span::default(),
+            position: SpanPosition::default(),
+            related: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_new_errors_are_detected_despite_line_shifts() {
+        let baseline = Baseline::from_errors(&[
+            error("./src/a.ts", "TS2322", "Type mismatch", 3),
+            error("./src/a.ts", "TS2322", "Type mismatch", 9),
+        ]);
+
+        let comparison = baseline.compare(vec![
+            // Both known errors moved down after an edit above them
+            error("src/a.ts", "TS2322", "Type mismatch", 5),
+            error("src/a.ts", "TS2322", "Type mismatch", 11),
+            // A third identical error is new, as is one in another file
+            error("src/a.ts", "TS2322", "Type mismatch", 20),
+            error("src/b.ts", "TS2322", "Type mismatch", 3),
+        ]);
+
+        assert_eq!(comparison.known, 2);
+        let new: Vec<(&str, usize)> = comparison.new.iter().map(|e| (e.file.as_str(), e.line)).collect();
+        assert_eq!(new, vec![("src/a.ts", 20), ("src/b.ts", 3)]);
+        assert!(comparison.fixed.is_empty());
+    }
+
+    #[test]
+    fn test_fixed_errors_are_reported() {
+        let baseline = Baseline::from_errors(&[
+            error("src/a.ts", "TS2322", "Type mismatch", 3),
+            error("src/a.ts", "TS2304", "Cannot find name 'x'", 7),
+        ]);
+
+        let comparison = baseline.compare(vec![error("src/a.ts", "TS2322", "Type mismatch", 3)]);
+
+        assert!(comparison.new.is_empty());
+        assert_eq!(comparison.fixed, vec![BaselineEntry {
+            file: "src/a.ts".to_string(),
+            id: "TS2304".to_string(),
+            message: "Cannot find name 'x'".to_string(),
+        }]);
+    }
+
+    #[test]
+    fn test_files_outside_a_run_are_neither_fixed_nor_dropped() {
+        let baseline = Baseline::from_errors(&[
+            error("src/a.ts", "TS2322", "Type mismatch", 3),
+            error("src/b.ts", "TS2304", "Cannot find name 'x'", 7),
+        ]);
+        let only_a = |file: &str| file == "src/a.ts";
+
+        let comparison = baseline.covering(only_a).compare(Vec::new());
+        let fixed: Vec<&str> = comparison.fixed.iter().map(|e| e.file.as_str()).collect();
+        assert_eq!(fixed, vec!["src/a.ts"]);
+
+        let updated = baseline.updated(only_a, &[error("./src/a.ts", "TS1", "New", 1)]);
+        let entries: Vec<(&str, &str)> = updated.errors.iter().map(|e| (e.file.as_str(), e.id.as_str())).collect();
+        assert_eq!(entries, vec![("src/a.ts", "TS1"), ("src/b.ts", "TS2304")]);
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".ta-baseline.json");
+        assert_eq!(Baseline::load(&path).unwrap(), None);
+
+        let baseline = Baseline::from_errors(&[error("src/b.ts", "PARSE", "b", 1), error("src/a.ts", "TS1", "a", 1)]);
+        baseline.save(&path).unwrap();
+        let loaded = Baseline::load(&path).unwrap().unwrap();
+        assert_eq!(loaded, baseline);
+        assert_eq!(loaded.errors[0].file, "src/a.ts");
+
+        std::fs::write(&path, "{ not json").unwrap();
+        assert!(matches!(Baseline::load(&path), Err(Error::InvalidBaseline { .. })));
+    }
+}
@@ -1,7 +1,17 @@
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use oxc_ast::ast::Program;
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{Program, Statement};
 use oxc_ast::visit::Visit;
-use crate::visitors::dependency_visitor::{DependencyVisitor, ImportInfo};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use serde::Serialize;
+use crate::models::DependencyScope;
+use crate::source_map::SourceMap;
+use crate::symbols::extract_symbols;
+use crate::visitors::dependency_visitor::{
+    DependencyVisitor, IdentifierReferenceVisitor, ImportInfo, ReexportInfo,
+};
 
 pub fn extract_dependencies(
     program: &Program<'_>,
@@ -21,7 +31,381 @@ pub fn extract_imports(
     visitor.imports
 }
 
-/// Resolve a TypeScript import specifier to an actual file path
+pub fn extract_reexports(
+    program: &Program<'_>,
+    file_path: PathBuf,
+) -> Vec<ReexportInfo> {
+    let mut visitor = DependencyVisitor::new(file_path);
+    visitor.visit_program(program);
+    visitor.reexports
+}
+
+/// [`extract_dependencies`], [`extract_imports`] and [`extract_reexports`] from one walk of `program`
+pub fn extract_module_dependencies(
+    program: &Program<'_>,
+    file_path: PathBuf,
+) -> (Vec<String>, Vec<ImportInfo>, Vec<ReexportInfo>) {
+    let mut visitor = DependencyVisitor::new(file_path);
+    visitor.visit_program(program);
+    (visitor.dependencies, visitor.imports, visitor.reexports)
+}
+
+/// The kind of import hygiene problem found by [`lint_imports`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ImportIssueKind {
+    /// The same module is imported by more than one declaration
+    DuplicateImport,
+    /// An imported name is never referenced in the file
+    UnusedImport,
+    /// A relative import does not resolve to any file on disk
+    UnresolvedImport,
+}
+
+/// An import hygiene problem in a single file
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportIssue {
+    pub kind: ImportIssueKind,
+    /// Module specifier of the offending import
+    pub source: String,
+    /// Local name that is never used (`None` for duplicate imports)
+    pub name: Option<String>,
+    pub line: usize,
+    pub message: String,
+}
+
+/// Find duplicate import sources and imported names with zero references.
+///
+/// A name counts as used when it is referenced anywhere in the file, including
+/// type positions, so a type-only import used only in annotations is not reported.
+/// Namespace imports are used when the namespace itself is referenced (`ns.foo`).
+/// `import type` and value imports of the same module are checked separately,
+/// since splitting them is a common and intentional style.
+pub fn lint_imports(source_text: &str, program: &Program<'_>) -> Vec<ImportIssue> {
+    let mut imports = DependencyVisitor::new(PathBuf::new());
+    imports.visit_program(program);
+    let mut references = IdentifierReferenceVisitor::default();
+    references.visit_program(program);
+    let referenced: HashSet<&str> = references
+        .references
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    let source_map = SourceMap::new(source_text);
+    let mut issues = Vec::new();
+    let mut first_seen: HashMap<(&str, bool), usize> = HashMap::new();
+    for statement in &imports.statements {
+        let line = source_map.line(statement.span.start as usize);
+        match first_seen.get(&(statement.source.as_str(), statement.type_only)) {
+            Some(first_line) => issues.push(ImportIssue {
+                kind: ImportIssueKind::DuplicateImport,
+                source: statement.source.clone(),
+                name: None,
+                line,
+                message: format!(
+                    "'{}' is already imported on line {}",
+                    statement.source, first_line
+                ),
+            }),
+            None => {
+                first_seen.insert((statement.source.as_str(), statement.type_only), line);
+            }
+        }
+
+        for binding in &statement.bindings {
+            if referenced.contains(binding.local.as_str()) {
+                continue;
+            }
+            let what = if binding.namespace {
+                "Namespace import"
+            } else if binding.type_only {
+                "Type import"
+            } else {
+                "Import"
+            };
+            issues.push(ImportIssue {
+                kind: ImportIssueKind::UnusedImport,
+                source: statement.source.clone(),
+                name: Some(binding.local.clone()),
+                line: source_map.line(binding.span.start as usize),
+                message: format!(
+                    "{} '{}' from '{}' is never used",
+                    what, binding.local, statement.source
+                ),
+            });
+        }
+    }
+
+    issues
+}
+
+/// Find relative imports (`./x`, `../x`) that resolve to no file on disk.
+///
+/// Resolution follows [`resolve_import_path`], so `.ts`/`.tsx` extensions and
+/// `index` files are tried. Bare package specifiers are never reported.
+pub fn find_unresolved_imports(
+    source_text: &str,
+    program: &Program<'_>,
+    file_path: &Path,
+) -> Vec<ImportIssue> {
+    let mut imports = DependencyVisitor::new(file_path.to_path_buf());
+    imports.visit_program(program);
+
+    let source_map = SourceMap::new(source_text);
+    imports
+        .statements
+        .iter()
+        .filter(|statement| statement.source.starts_with('.'))
+        .filter(|statement| resolve_import_path(&statement.source, file_path).is_none())
+        .map(|statement| ImportIssue {
+            kind: ImportIssueKind::UnresolvedImport,
+            source: statement.source.clone(),
+            name: None,
+            line: source_map.line(statement.span.start as usize),
+            message: format!("Cannot resolve '{}'", statement.source),
+        })
+        .collect()
+}
+
+/// How a [`SymbolReference`] refers to the symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ReferenceKind {
+    /// An import specifier that brings the symbol into the file
+    Import,
+    /// An identifier that refers to the symbol (or its import alias)
+    Usage,
+}
+
+/// One place a symbol is imported or referenced
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolReference {
+    pub kind: ReferenceKind,
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    /// Name at the reference site, which is the alias for `import { a as b }`
+    pub local: String,
+    /// Module specifier, for [`ReferenceKind::Import`] only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Find every import of `name` and every identifier referring to it in one file.
+///
+/// Aliased imports (`import { name as alias }`) are followed, so uses of `alias`
+/// are reported. Outside imports, references are matched by name: `name` is
+/// tracked unless the file imports some other symbol under that local name.
+pub fn find_references(
+    source_text: &str,
+    program: &Program<'_>,
+    file: &str,
+    name: &str,
+) -> Vec<SymbolReference> {
+    let mut imports = DependencyVisitor::new(PathBuf::from(file));
+    imports.visit_program(program);
+
+    let source_map = SourceMap::new(source_text);
+    let mut references = Vec::new();
+    let mut tracked: HashSet<&str> = HashSet::new();
+    let mut shadowed = false;
+    for statement in &imports.statements {
+        for binding in &statement.bindings {
+            if binding.imported == name {
+                let (line, column) = source_map.line_col(binding.span.start as usize);
+                references.push(SymbolReference {
+                    kind: ReferenceKind::Import,
+                    file: file.to_string(),
+                    line,
+                    column,
+                    local: binding.local.clone(),
+                    source: Some(statement.source.clone()),
+                });
+                tracked.insert(binding.local.as_str());
+            } else if binding.local == name {
+                shadowed = true;
+            }
+        }
+    }
+    if !shadowed {
+        tracked.insert(name);
+    }
+
+    let mut identifiers = IdentifierReferenceVisitor::default();
+    identifiers.visit_program(program);
+    for (identifier, span) in &identifiers.references {
+        if !tracked.contains(identifier.as_str()) {
+            continue;
+        }
+        let (line, column) = source_map.line_col(span.start as usize);
+        references.push(SymbolReference {
+            kind: ReferenceKind::Usage,
+            file: file.to_string(),
+            line,
+            column,
+            local: identifier.clone(),
+            source: None,
+        });
+    }
+
+    references
+}
+
+/// Where a (possibly re-exported) symbol is ultimately defined
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ReexportOrigin {
+    /// Defining file, or the package specifier for external modules
+    pub file: String,
+    /// Name of the symbol in the defining file (`*` for a namespace re-export)
+    pub symbol: String,
+}
+
+/// Exported names and re-exports of a single module, as seen by [`BarrelResolver`]
+#[derive(Debug, Default)]
+struct ModuleExports {
+    /// Exported name to the name it is defined under in the module
+    exported: HashMap<String, String>,
+    reexports: Vec<ReexportInfo>,
+}
+
+/// Follows re-export chains through barrel files to the module that defines a symbol.
+///
+/// Modules are parsed lazily from disk and cached, and each `(file, name)` pair is
+/// visited at most once per lookup so re-export cycles terminate.
+#[derive(Debug, Default)]
+pub struct BarrelResolver {
+    modules: HashMap<PathBuf, ModuleExports>,
+}
+
+impl BarrelResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve `name` as exported from `file` to its defining module.
+    ///
+    /// Returns `None` when the name cannot be found or only leads back into a cycle.
+    pub fn resolve(&mut self, file: &Path, name: &str) -> Option<ReexportOrigin> {
+        let mut visited = HashSet::new();
+        self.resolve_inner(file, name, &mut visited)
+    }
+
+    fn resolve_inner(
+        &mut self,
+        file: &Path,
+        name: &str,
+        visited: &mut HashSet<(PathBuf, String)>,
+    ) -> Option<ReexportOrigin> {
+        if !visited.insert((file.to_path_buf(), name.to_string())) {
+            log::debug!("Re-export cycle detected at {}:{}", file.display(), name);
+            return None;
+        }
+
+        let module = self.load(file);
+        if let Some(local) = module.exported.get(name) {
+            return Some(ReexportOrigin {
+                file: file.to_string_lossy().to_string(),
+                symbol: local.clone(),
+            });
+        }
+
+        let reexports = module.reexports.clone();
+
+        // Explicitly named re-exports take precedence over `export *`
+        if let Some(reexport) = reexports.iter().find(|r| r.exported == name) {
+            let Some(target) = resolve_import_path(&reexport.source, file) else {
+                return Some(ReexportOrigin {
+                    file: reexport.source.clone(),
+                    symbol: reexport.imported.clone(),
+                });
+            };
+
+            if reexport.imported == "*" {
+                return Some(ReexportOrigin {
+                    file: target.to_string_lossy().to_string(),
+                    symbol: reexport.imported.clone(),
+                });
+            }
+
+            return self.resolve_inner(&target, &reexport.imported, visited);
+        }
+
+        reexports
+            .iter()
+            .filter(|r| r.exported == "*")
+            .filter_map(|r| resolve_import_path(&r.source, file))
+            .find_map(|target| self.resolve_inner(&target, name, visited))
+    }
+
+    fn load(&mut self, file: &Path) -> &ModuleExports {
+        self.modules
+            .entry(file.to_path_buf())
+            .or_insert_with(|| parse_module_exports(file).unwrap_or_default())
+    }
+}
+
+fn parse_module_exports(file: &Path) -> Option<ModuleExports> {
+    let source = std::fs::read_to_string(file).ok()?;
+    let source_type = SourceType::from_path(file).ok()?;
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, &source, source_type).parse();
+
+    let file_path = file.to_string_lossy().to_string();
+    let mut exported: HashMap<String, String> = extract_symbols(&source, &ret.program, file_path, true)
+        .into_iter()
+        .map(|symbol| (symbol.name.clone(), symbol.name))
+        .collect();
+    let mut visitor = DependencyVisitor::new(file.to_path_buf());
+    visitor.visit_program(&ret.program);
+    let mut reexports = visitor.reexports;
+
+    // `export { x as y }` without a source passes on an imported binding, or
+    // exports a name defined here
+    for (local, name) in local_export_specifiers(&ret.program) {
+        let import = visitor.statements.iter().find_map(|statement| {
+            let binding = statement.bindings.iter().find(|binding| binding.local == local)?;
+            Some(ReexportInfo {
+                source: statement.source.clone(),
+                exported: name.clone(),
+                imported: binding.imported.clone(),
+                type_only: statement.type_only || binding.type_only,
+            })
+        });
+        match import {
+            Some(reexport) => reexports.push(reexport),
+            None => {
+                exported.insert(name, local);
+            }
+        }
+    }
+
+    Some(ModuleExports { exported, reexports })
+}
+
+/// (local, exported) names of `export { local as exported }` declarations without a `from`
+fn local_export_specifiers(program: &Program<'_>) -> Vec<(String, String)> {
+    program
+        .body
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::ExportNamedDeclaration(decl) if decl.source.is_none() => Some(&decl.specifiers),
+            _ => None,
+        })
+        .flatten()
+        .map(|spec| (spec.local.name().to_string(), spec.exported.name().to_string()))
+        .collect()
+}
+
+/// Extensions tried, in order, for an import that omits one
+const RESOLVE_EXTENSIONS: [&str; 5] = [".ts", ".tsx", ".mts", ".cts", ".d.ts"];
+
+/// Resolve a TypeScript import specifier to an actual file path.
+///
+/// Relative and absolute specifiers are tried, in order, as the exact file,
+/// with each of `.ts`, `.tsx`, `.mts`, `.cts` and `.d.ts` appended, and as a
+/// directory's `index` file with the same extensions. So `./utils` finds
+/// `utils.ts` before `utils/index.ts`. Specifiers that can only name a
+/// directory (`.`, `..`, or ending in `/`) go straight to the index files.
+/// Package imports resolve to `None`.
 pub fn resolve_import_path(import_specifier: &str, importing_file: &Path) -> Option<PathBuf> {
     // External packages (don't start with . or /)
     if !import_specifier.starts_with('.') && !import_specifier.starts_with('/') {
@@ -31,34 +415,147 @@ pub fn resolve_import_path(import_specifier: &str, importing_file: &Path) -> Opt
     let importing_dir = importing_file.parent()?;
     let base_path = importing_dir.join(import_specifier);
 
-    // Try various extensions
-    let extensions = [".ts", ".tsx", ".d.ts"];
-
-    // Try with exact name first (already has extension)
-    if base_path.exists() {
+    // Exact name first (already has an extension); directories fall through to index files
+    if base_path.is_file() {
         return Some(normalize_path(&base_path));
     }
 
-    // Try adding extensions
-    for ext in extensions {
-        let mut candidate = base_path.clone();
-        let file_name = candidate.file_name()?.to_string_lossy().to_string();
-        candidate.set_file_name(format!("{}{}", file_name, ext));
+    let names_directory = import_specifier.ends_with('/')
+        || matches!(import_specifier.rsplit('/').next(), Some("." | ".."));
+    if !names_directory {
+        if let Some(file_name) = base_path.file_name().map(|name| name.to_string_lossy().to_string()) {
+            for ext in RESOLVE_EXTENSIONS {
+                let candidate = base_path.with_file_name(format!("{}{}", file_name, ext));
+                if candidate.is_file() {
+                    return Some(normalize_path(&candidate));
+                }
+            }
+        }
+    }
 
-        if candidate.exists() {
-            return Some(normalize_path(&candidate));
+    RESOLVE_EXTENSIONS
+        .iter()
+        .map(|ext| base_path.join(format!("index{}", ext)))
+        .find(|candidate| candidate.is_file())
+        .map(|candidate| normalize_path(&candidate))
+}
+
+/// Classify an import specifier by where it resolves.
+///
+/// Relative and absolute paths are [`DependencyScope::Local`], specifiers
+/// matching one of the tsconfig `paths` patterns in `aliases` (see
+/// [`load_path_aliases`]) are [`DependencyScope::Repo`], and any other bare
+/// specifier is an [`DependencyScope::External`] package.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::dependencies::classify_import;
+/// use ta_lib::models::DependencyScope;
+///
+/// let aliases = vec!["@/*".to_string()];
+/// assert_eq!(classify_import("./y", &aliases), DependencyScope::Local);
+/// assert_eq!(classify_import("@/x", &aliases), DependencyScope::Repo);
+/// assert_eq!(classify_import("lodash", &aliases), DependencyScope::External);
+/// ```
+pub fn classify_import(specifier: &str, aliases: &[String]) -> DependencyScope {
+    if specifier.starts_with('.') || specifier.starts_with('/') {
+        return DependencyScope::Local;
+    }
+
+    let is_alias = aliases.iter().any(|pattern| match pattern.split_once('*') {
+        // A bare `*` catch-all would claim every package
+        Some(("", "")) => false,
+        Some((prefix, suffix)) => {
+            specifier.len() >= prefix.len() + suffix.len()
+                && specifier.starts_with(prefix)
+                && specifier.ends_with(suffix)
         }
+        None => specifier == pattern,
+    });
+
+    if is_alias {
+        DependencyScope::Repo
+    } else {
+        DependencyScope::External
     }
+}
+
+/// Read the `compilerOptions.paths` patterns (e.g. `@/*`) from `dir/tsconfig.json`.
+///
+/// Comments and trailing commas are tolerated. `extends` is not followed, and
+/// a missing or unreadable tsconfig yields no aliases.
+pub fn load_path_aliases(dir: &Path) -> Vec<String> {
+    let Some(Ok(config)) = read_tsconfig(dir) else {
+        return Vec::new();
+    };
+
+    config["compilerOptions"]["paths"]
+        .as_object()
+        .map(|paths| paths.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Parse `dir/tsconfig.json`, tolerating comments and trailing commas.
+///
+/// `None` when there is no readable tsconfig, `Some(Err(_))` when it is not valid JSON.
+pub fn read_tsconfig(dir: &Path) -> Option<serde_json::Result<serde_json::Value>> {
+    let text = std::fs::read_to_string(dir.join("tsconfig.json")).ok()?;
+    Some(serde_json::from_str(&strip_jsonc(&text)))
+}
 
-    // Try index files in directory
-    for ext in extensions {
-        let candidate = base_path.join(format!("index{}", ext));
-        if candidate.exists() {
-            return Some(normalize_path(&candidate));
+/// Remove `//` and `/* */` comments and trailing commas, leaving strings intact
+fn strip_jsonc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push(c);
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            (']' | '}', _) => {
+                let trimmed = out.trim_end().len();
+                if out[..trimmed].ends_with(',') {
+                    out.truncate(trimmed - 1);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
         }
     }
 
-    None
+    out
 }
 
 /// Normalize path by removing redundant . and .. components
@@ -88,3 +585,262 @@ fn normalize_path(path: &Path) -> PathBuf {
 
     components.iter().collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, contents: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_classify_import_scopes() {
+        let aliases = vec!["@/*".to_string(), "~utils".to_string()];
+
+        assert_eq!(classify_import("lodash", &aliases), DependencyScope::External);
+        assert_eq!(classify_import("@scope/pkg", &aliases), DependencyScope::External);
+        assert_eq!(classify_import("@/x", &aliases), DependencyScope::Repo);
+        assert_eq!(classify_import("~utils", &aliases), DependencyScope::Repo);
+        assert_eq!(classify_import("./y", &aliases), DependencyScope::Local);
+        assert_eq!(classify_import("../z", &[]), DependencyScope::Local);
+        assert_eq!(classify_import("@/x", &["*".to_string()]), DependencyScope::External);
+    }
+
+    #[test]
+    fn test_load_path_aliases_tolerates_jsonc() {
+        let dir = TempDir::new().unwrap();
+        write(
+            &dir,
+            "tsconfig.json",
+            "{\n  // aliases\n  \"compilerOptions\": {\n    /* src root */\n    \"paths\": { \"@/*\": [\"src/*\"], \"#lib\": [\"lib/index.ts\"], },\n  },\n}\n",
+        );
+
+        let mut aliases = load_path_aliases(dir.path());
+        aliases.sort();
+        assert_eq!(aliases, vec!["#lib", "@/*"]);
+        assert!(load_path_aliases(&dir.path().join("missing")).is_empty());
+    }
+
+    fn unresolved(file: &Path) -> Vec<ImportIssue> {
+        let source = fs::read_to_string(file).unwrap();
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, &source, SourceType::default().with_typescript(true)).parse();
+        find_unresolved_imports(&source, &ret.program, file)
+    }
+
+    fn lint(source: &str) -> Vec<ImportIssue> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::default().with_typescript(true)).parse();
+        lint_imports(source, &ret.program)
+    }
+
+    #[test]
+    fn test_lint_reports_duplicate_import() {
+        let issues = lint(
+            "import { a } from './x';\nimport { b } from './x';\nexport const c = a + b;",
+        );
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, ImportIssueKind::DuplicateImport);
+        assert_eq!(issues[0].source, "./x");
+        assert_eq!(issues[0].line, 2);
+        assert!(issues[0].message.contains("line 1"));
+    }
+
+    #[test]
+    fn test_lint_reports_unused_named_import() {
+        let issues = lint("import { used, unused } from './x';\nexport const c = used();");
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, ImportIssueKind::UnusedImport);
+        assert_eq!(issues[0].name.as_deref(), Some("unused"));
+        assert_eq!(issues[0].line, 1);
+    }
+
+    #[test]
+    fn test_lint_counts_type_and_namespace_usage() {
+        let issues = lint(
+            "import type { User } from './types';\n\
+             import { type Role } from './types';\n\
+             import * as ns from './ns';\n\
+             import * as idle from './idle';\n\
+             export function f(u: User): Role { return ns.role(u); }",
+        );
+
+        assert_eq!(issues.len(), 1, "{:?}", issues);
+        assert_eq!(issues[0].name.as_deref(), Some("idle"));
+        assert!(issues[0].message.starts_with("Namespace import"));
+    }
+
+    #[test]
+    fn test_barrel_resolves_names_from_two_modules() {
+        let dir = TempDir::new().unwrap();
+        let a = write(&dir, "a.ts", "export function alpha() {}");
+        let b = write(&dir, "b.ts", "export const beta = 1;");
+        let index = write(&dir, "index.ts", "export { alpha } from './a';\nexport * from './b';");
+
+        let mut resolver = BarrelResolver::new();
+
+        let alpha = resolver.resolve(&index, "alpha").unwrap();
+        assert_eq!(PathBuf::from(alpha.file), normalize_path(&a));
+        assert_eq!(alpha.symbol, "alpha");
+
+        let beta = resolver.resolve(&index, "beta").unwrap();
+        assert_eq!(PathBuf::from(beta.file), normalize_path(&b));
+
+        assert!(resolver.resolve(&index, "gamma").is_none());
+    }
+
+    #[test]
+    fn test_barrel_follows_aliased_chain() {
+        let dir = TempDir::new().unwrap();
+        let a = write(&dir, "a.ts", "export class Original {}");
+        write(&dir, "mid.ts", "export { Original as Renamed } from './a';");
+        let index = write(&dir, "index.ts", "export * from './mid';");
+
+        let origin = BarrelResolver::new().resolve(&index, "Renamed").unwrap();
+        assert_eq!(PathBuf::from(origin.file), normalize_path(&a));
+        assert_eq!(origin.symbol, "Original");
+    }
+
+    #[test]
+    fn test_barrel_counts_local_export_specifiers() {
+        let dir = TempDir::new().unwrap();
+        let a = write(&dir, "a.ts", "const alpha = 1;\nfunction beta() {}\nexport { alpha, beta as renamed };");
+        let b = write(&dir, "b.ts", "export const gamma = 1;");
+        write(&dir, "mid.ts", "import { gamma } from './b';\nexport { gamma as passed };");
+        let index = write(&dir, "index.ts", "export * from './a';\nexport * from './mid';");
+
+        let mut resolver = BarrelResolver::new();
+
+        let alpha = resolver.resolve(&index, "alpha").unwrap();
+        assert_eq!(PathBuf::from(alpha.file), normalize_path(&a));
+        // The origin names the binding as defined in `a.ts`
+        assert_eq!(resolver.resolve(&index, "renamed").unwrap().symbol, "beta");
+        assert!(resolver.resolve(&index, "beta").is_none());
+
+        // An imported binding exported again is followed to where it is defined
+        let passed = resolver.resolve(&index, "passed").unwrap();
+        assert_eq!(PathBuf::from(passed.file), normalize_path(&b));
+        assert_eq!(passed.symbol, "gamma");
+    }
+
+    #[test]
+    fn test_barrel_reexport_cycle_terminates() {
+        let dir = TempDir::new().unwrap();
+        let one = write(&dir, "one.ts", "export * from './two';");
+        write(&dir, "two.ts", "export * from './one';");
+
+        assert!(BarrelResolver::new().resolve(&one, "missing").is_none());
+    }
+
+    #[test]
+    fn test_unresolved_relative_imports_are_reported() {
+        let dir = TempDir::new().unwrap();
+        write(&dir, "present.ts", "export const a = 1;");
+        fs::create_dir(dir.path().join("lib")).unwrap();
+        write(&dir, "lib/index.ts", "export const b = 2;");
+        let main = write(
+            &dir,
+            "main.ts",
+            "import { a } from './present';\nimport { b } from './lib';\nimport { c } from './missing';\nimport { d } from 'some-package';\nexport const e = a + b + c + d;",
+        );
+
+        let issues = unresolved(&main);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, ImportIssueKind::UnresolvedImport);
+        assert_eq!(issues[0].source, "./missing");
+        assert_eq!(issues[0].line, 3);
+    }
+
+    #[test]
+    fn test_resolve_extensionless_file_import() {
+        let dir = TempDir::new().unwrap();
+        let main = write(&dir, "main.ts", "");
+        let utils = write(&dir, "utils.ts", "");
+        let view = write(&dir, "view.tsx", "");
+
+        assert_eq!(resolve_import_path("./utils", &main), Some(normalize_path(&utils)));
+        assert_eq!(resolve_import_path("./utils.ts", &main), Some(normalize_path(&utils)));
+        assert_eq!(resolve_import_path("./view", &main), Some(normalize_path(&view)));
+        assert_eq!(resolve_import_path("./missing", &main), None);
+        assert_eq!(resolve_import_path("lodash", &main), None);
+    }
+
+    #[test]
+    fn test_resolve_directory_index_import() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("lib/nested")).unwrap();
+        let index = write(&dir, "lib/index.ts", "");
+        let nested = write(&dir, "lib/nested/index.tsx", "");
+        let inner = write(&dir, "lib/nested/inner.ts", "");
+
+        let main = dir.path().join("main.ts");
+        assert_eq!(resolve_import_path("./lib", &main), Some(normalize_path(&index)));
+        assert_eq!(resolve_import_path("./lib/", &main), Some(normalize_path(&index)));
+        assert_eq!(resolve_import_path("./lib/nested", &main), Some(normalize_path(&nested)));
+        assert_eq!(resolve_import_path("..", &inner), Some(normalize_path(&index)));
+        assert_eq!(resolve_import_path(".", &inner), Some(normalize_path(&nested)));
+    }
+
+    #[test]
+    fn test_resolve_prefers_file_over_directory_index() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("utils")).unwrap();
+        write(&dir, "utils/index.ts", "");
+        let utils = write(&dir, "utils.ts", "");
+
+        assert_eq!(resolve_import_path("./utils", &dir.path().join("main.ts")), Some(normalize_path(&utils)));
+    }
+
+    fn references(source: &str, name: &str) -> Vec<SymbolReference> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::default().with_typescript(true)).parse();
+        find_references(source, &ret.program, "main.ts", name)
+    }
+
+    #[test]
+    fn test_references_follow_import_alias() {
+        let refs = references(
+            "import { formatDate as fmt } from './utils';\n\nexport const today = fmt(new Date());\nconst formatDate = 1;\n",
+            "formatDate",
+        );
+
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].kind, ReferenceKind::Import);
+        assert_eq!(refs[0].local, "fmt");
+        assert_eq!(refs[0].source.as_deref(), Some("./utils"));
+        assert_eq!((refs[0].line, refs[0].column), (1, 24));
+        assert_eq!(refs[1].kind, ReferenceKind::Usage);
+        assert_eq!(refs[1].local, "fmt");
+        assert_eq!(refs[1].line, 3);
+    }
+
+    #[test]
+    fn test_references_in_declaring_file() {
+        let refs = references(
+            "export function helper() {}\nexport function caller() { return helper(); }\n",
+            "helper",
+        );
+
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].kind, ReferenceKind::Usage);
+        assert_eq!(refs[0].line, 2);
+    }
+
+    #[test]
+    fn test_references_skip_other_symbol_imported_under_same_name() {
+        let refs = references(
+            "import { other as helper } from './x';\nhelper();\n",
+            "helper",
+        );
+
+        assert!(refs.is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+//! Empty `catch` blocks (`ta source --lint-empty-catch`).
+//!
+//! `try { ... } catch {}` silently swallows every error thrown in the `try`.
+//! A catch that is empty on purpose is marked with an `// intentionally-empty`
+//! comment on the `catch` line or inside its braces.
+
+use oxc_ast::AstKind;
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_semantic::Semantic;
+use oxc_span::Span;
+
+/// Error id for an empty catch block
+pub const EMPTY_CATCH_ERROR_ID: &str = "EMPTY_CATCH";
+
+/// Comment text that marks an empty catch block as intended
+pub const EMPTY_CATCH_SUPPRESSION: &str = "intentionally-empty";
+
+/// Warn about each `catch` clause whose block has no statements.
+///
+/// The warning is labelled at the `catch` keyword. Clauses with an
+/// [`EMPTY_CATCH_SUPPRESSION`] comment on their first line or inside their
+/// block are not reported.
+pub fn find_empty_catches(source: &str, semantic: &Semantic<'_>) -> Vec<OxcDiagnostic> {
+    let mut diagnostics: Vec<OxcDiagnostic> = semantic
+        .nodes()
+        .iter()
+        .filter_map(|node| {
+            let AstKind::CatchClause(clause) = node.kind() else {
+                return None;
+            };
+            if !clause.body.body.is_empty() {
+                return None;
+            }
+
+            let start = clause.span.start as usize;
+            let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = source[start..].find('\n').map_or(source.len(), |i| start + i);
+            let end = line_end.max(clause.body.span.end as usize);
+            let suppressed = semantic.trivias().comments().any(|comment| {
+                (comment.span.start as usize) < end
+                    && comment.span.end as usize > line_start
+                    && comment.span.source_text(source).contains(EMPTY_CATCH_SUPPRESSION)
+            });
+            if suppressed {
+                return None;
+            }
+
+            Some(
+                OxcDiagnostic::warn("Empty catch block swallows errors")
+                    .with_error_code_scope(EMPTY_CATCH_ERROR_ID)
+                    .with_label(Span::new(clause.span.start, clause.span.start + "catch".len() as u32)),
+            )
+        })
+        .collect();
+
+    diagnostics.sort_by_key(|d| d.labels.as_ref().and_then(|l| l.first()).map(|l| l.offset()));
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisOptions, Analyzer};
+    use crate::highlighting::ErrorSeverity;
+    use crate::models::TypeError;
+
+    fn empty_catches(source: &str) -> Vec<TypeError> {
+        Analyzer::new(AnalysisOptions { lint_empty_catch: true, ..Default::default() })
+            .analyze_source(source, "catch.ts")
+            .unwrap()
+            .type_errors
+            .into_iter()
+            .filter(|e| e.id == EMPTY_CATCH_ERROR_ID)
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_catch_is_reported() {
+        let source = "export function load() {\n  try {\n    run();\n  } catch (e) {}\n  try { run(); } catch { log(); }\n}\n";
+        let errors = empty_catches(source);
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].line, errors[0].column), (4, 4));
+        assert_eq!(errors[0].severity, ErrorSeverity::Warning);
+        assert_eq!(errors[0].scope, "load");
+    }
+
+    #[test]
+    fn test_intentionally_empty_comment_suppresses() {
+        let source = "try { run(); } catch { // intentionally-empty\n}\ntry {\n  run();\n} catch {\n  /* intentionally-empty: best effort */\n}\n";
+        assert!(empty_catches(source).is_empty());
+
+        // A marker on another catch does not cover this one
+        let source = "try { run(); } catch {} // intentionally-empty\ntry { run(); } catch {}\n";
+        assert_eq!(empty_catches(source).iter().map(|e| e.line).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        let errors = Analyzer::new(AnalysisOptions::default())
+            .analyze_source("try { run(); } catch {}\n", "catch.ts")
+            .unwrap()
+            .type_errors;
+        assert!(errors.is_empty());
+    }
+}
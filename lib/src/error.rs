@@ -45,4 +45,38 @@ pub enum Error {
     /// This can be used for logical errors during the analysis pipeline.
     #[error("Analysis error: {0}")]
     AnalysisError(String),
-}
\ No newline at end of file
+
+    /// Represents a malformed message on the LSP connection.
+    ///
+    /// This occurs when a message lacks a valid `Content-Length` header or
+    /// its body is not valid JSON.
+    #[error("LSP protocol error: {0}")]
+    ProtocolError(String),
+
+    /// Represents a baseline file that exists but cannot be parsed.
+    ///
+    /// Baselines are written by `ta source --baseline`; see [`crate::baseline`].
+    #[error("Invalid baseline {path}: {message}")]
+    InvalidBaseline {
+        /// The path of the baseline file.
+        path: String,
+        /// Why the file is not a valid baseline.
+        message: String,
+    },
+
+    /// Represents a `ta watch --events` name that is not a watch event.
+    ///
+    /// See [`crate::watcher::WATCH_EVENT_NAMES`] for the accepted names.
+    #[error("Unknown watch event '{name}' (expected one of: {valid})")]
+    UnknownWatchEvent {
+        /// The name as given.
+        name: String,
+        /// The accepted names, comma-separated.
+        valid: String,
+    },
+
+    /// Represents a file whose analysis did not finish within
+    /// [`AnalysisOptions::per_file_timeout`](crate::analyzer::AnalysisOptions::per_file_timeout).
+    #[error("Analysis timed out after {} ms", .0.as_millis())]
+    Timeout(std::time::Duration),
+}
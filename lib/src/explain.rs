@@ -0,0 +1,195 @@
+//! Short explanations for common TypeScript diagnostic codes.
+//!
+//! The table is embedded so `ta explain` works offline; each entry is a
+//! one-paragraph summary with a pointer to fuller upstream documentation.
+
+use serde::Serialize;
+
+/// A canned explanation of one diagnostic code.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Explanation {
+    /// Normalized code, e.g. `TS2322`
+    pub code: &'static str,
+    /// The diagnostic's message template
+    pub title: &'static str,
+    /// What usually causes it and how to fix it
+    pub description: &'static str,
+}
+
+impl Explanation {
+    /// Link to the upstream write-up for this code.
+    pub fn docs_url(&self) -> String {
+        docs_url(self.code)
+    }
+}
+
+const EXPLANATIONS: &[Explanation] = &[
+    Explanation {
+        code: "TS1005",
+        title: "'{0}' expected.",
+        description: "The parser reached a token it did not expect, usually because of a missing comma, semicolon, bracket or closing parenthesis just before the reported position.",
+    },
+    Explanation {
+        code: "TS2300",
+        title: "Duplicate identifier '{0}'.",
+        description: "The same name is declared twice in one scope. Rename one declaration, or merge them if they were meant to describe the same thing.",
+    },
+    Explanation {
+        code: "TS2304",
+        title: "Cannot find name '{0}'.",
+        description: "The name is not declared in any enclosing scope. Check for typos, add the missing import, or install the type definitions that declare it.",
+    },
+    Explanation {
+        code: "TS2307",
+        title: "Cannot find module '{0}' or its corresponding type declarations.",
+        description: "The import path does not resolve. Check the relative path and file extension, install the package, or add its `@types/*` package.",
+    },
+    Explanation {
+        code: "TS2322",
+        title: "Type '{0}' is not assignable to type '{1}'.",
+        description: "A value is assigned, returned or passed where a different type is required. Change the value, widen the declared type, or narrow the value with a type guard first.",
+    },
+    Explanation {
+        code: "TS2339",
+        title: "Property '{0}' does not exist on type '{1}'.",
+        description: "The property is not part of the value's declared type. Check the spelling, add the property to the type, or narrow a union to the member that has it.",
+    },
+    Explanation {
+        code: "TS2345",
+        title: "Argument of type '{0}' is not assignable to parameter of type '{1}'.",
+        description: "A call passes an argument whose type does not match the parameter. Convert the argument or adjust the function's parameter type.",
+    },
+    Explanation {
+        code: "TS2355",
+        title: "A function whose declared type is neither 'undefined', 'void', nor 'any' must return a value.",
+        description: "The function declares a return type but some code path falls off the end. Add the missing `return` or change the declared return type.",
+    },
+    Explanation {
+        code: "TS2365",
+        title: "Operator '{0}' cannot be applied to types '{1}' and '{2}'.",
+        description: "The operands do not support the operator, such as adding an object to a number. Convert the operands to compatible types first.",
+    },
+    Explanation {
+        code: "TS2451",
+        title: "Cannot redeclare block-scoped variable '{0}'.",
+        description: "A `let` or `const` name is declared twice in the same block, or clashes with a global. Rename one of the declarations or move it into its own block.",
+    },
+    Explanation {
+        code: "TS2531",
+        title: "Object is possibly 'null'.",
+        description: "Strict null checks found a value that may be `null` where it is used. Check for `null` first, use optional chaining, or assert non-null with `!` when you are certain.",
+    },
+    Explanation {
+        code: "TS2532",
+        title: "Object is possibly 'undefined'.",
+        description: "Strict null checks found a value that may be `undefined` where it is used. Check it first, supply a default with `??`, or use optional chaining.",
+    },
+    Explanation {
+        code: "TS2554",
+        title: "Expected {0} arguments, but got {1}.",
+        description: "A call passes the wrong number of arguments. Add or remove arguments, or mark extra parameters optional in the function signature.",
+    },
+    Explanation {
+        code: "TS2588",
+        title: "Cannot assign to '{0}' because it is a constant.",
+        description: "A `const` binding is reassigned. Declare it with `let` if it must change, or assign to a new name instead.",
+    },
+    Explanation {
+        code: "TS2741",
+        title: "Property '{0}' is missing in type '{1}' but required in type '{2}'.",
+        description: "An object literal or value lacks a required property of the target type. Add the property or make it optional in the type.",
+    },
+    Explanation {
+        code: "TS2769",
+        title: "No overload matches this call.",
+        description: "None of the function's overload signatures accept the given arguments. Read the per-overload errors below the message to see which argument is wrong.",
+    },
+    Explanation {
+        code: "TS6133",
+        title: "'{0}' is declared but its value is never read.",
+        description: "A variable, parameter or import is unused. Remove it, or prefix a parameter with `_` to mark it intentionally unused.",
+    },
+    Explanation {
+        code: "TS7006",
+        title: "Parameter '{0}' implicitly has an 'any' type.",
+        description: "With `noImplicitAny`, every parameter needs a type that can be inferred or is written out. Add a type annotation to the parameter.",
+    },
+    Explanation {
+        code: "TS18048",
+        title: "'{0}' is possibly 'undefined'.",
+        description: "Strict null checks found a named value that may be `undefined`. Narrow it with a check, supply a default, or use optional chaining.",
+    },
+];
+
+/// Normalizes `TS2322`, `ts2322` or `2322` to `TS2322`.
+///
+/// Returns `None` for anything that is not a TypeScript diagnostic code.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::explain::normalize_code;
+///
+/// assert_eq!(normalize_code("ts2322"), Some("TS2322".to_string()));
+/// assert_eq!(normalize_code("2322"), Some("TS2322".to_string()));
+/// assert_eq!(normalize_code("PARSE"), None);
+/// ```
+pub fn normalize_code(code: &str) -> Option<String> {
+    let code = code.trim();
+    let digits = if code.len() > 2 && code[..2].eq_ignore_ascii_case("ts") {
+        &code[2..]
+    } else {
+        code
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("TS{}", digits))
+}
+
+/// Looks up the canned explanation for a diagnostic code.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::explain::explain;
+///
+/// assert!(explain("TS2322").is_some());
+/// assert!(explain("TS99999").is_none());
+/// ```
+pub fn explain(code: &str) -> Option<&'static Explanation> {
+    let code = normalize_code(code)?;
+    EXPLANATIONS.iter().find(|explanation| explanation.code == code)
+}
+
+/// Upstream documentation link for any TypeScript diagnostic code.
+pub fn docs_url(code: &str) -> String {
+    let code = normalize_code(code).unwrap_or_else(|| code.to_string());
+    format!("https://typescript.tv/errors/#{}", code.to_lowercase())
+}
+
+/// The `(run \`ta explain TSxxxx\`)` hint shown next to an error id, when the id is a TS code.
+pub fn explain_hint(code: &str) -> Option<String> {
+    normalize_code(code).map(|code| format!("{} (run `ta explain {}`)", code, code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_codes_are_normalized_and_unique() {
+        for (i, explanation) in EXPLANATIONS.iter().enumerate() {
+            assert_eq!(normalize_code(explanation.code).as_deref(), Some(explanation.code));
+            assert!(!explanation.description.is_empty());
+            assert!(EXPLANATIONS[i + 1..].iter().all(|other| other.code != explanation.code));
+        }
+    }
+
+    #[test]
+    fn test_explain_hint_only_for_ts_codes() {
+        assert_eq!(explain_hint("TS2322").as_deref(), Some("TS2322 (run `ta explain TS2322`)"));
+        assert_eq!(explain_hint("PARSE"), None);
+        assert_eq!(docs_url("2322"), "https://typescript.tv/errors/#ts2322");
+    }
+}
@@ -0,0 +1,99 @@
+//! Explicit `any` usage (`ta source --report-any`).
+//!
+//! Only the `any` keyword written in the source is reported: annotations
+//! (`x: any`), assertions (`as any`) and type arguments (`Array<any>`).
+//! Implicit `any`, a binding left without an annotation, needs type inference
+//! and is not detected.
+
+use oxc_ast::AstKind;
+use oxc_diagnostics::{OxcDiagnostic, Severity};
+use oxc_semantic::Semantic;
+
+/// Error id for an explicit `any`
+pub const ANY_ERROR_ID: &str = "ANY";
+
+/// Create an info-level diagnostic for each `any` keyword in the program.
+///
+/// The message names where the `any` appears, and the label covers the keyword.
+pub fn find_explicit_any(semantic: &Semantic<'_>) -> Vec<OxcDiagnostic> {
+    let nodes = semantic.nodes();
+    let mut diagnostics: Vec<OxcDiagnostic> = nodes
+        .iter()
+        .filter_map(|node| {
+            let AstKind::TSAnyKeyword(keyword) = node.kind() else {
+                return None;
+            };
+            let message = match nodes.parent_kind(node.id()) {
+                Some(AstKind::TSAsExpression(_)) => "`as any` assertion",
+                Some(AstKind::TSTypeParameterInstantiation(_)) => "Explicit `any` type argument",
+                Some(AstKind::TSTypeAnnotation(_)) => "Explicit `any` type annotation",
+                _ => "Explicit `any` type",
+            };
+            Some(
+                OxcDiagnostic::warn(message)
+                    .with_severity(Severity::Advice)
+                    .with_error_code_scope(ANY_ERROR_ID)
+                    .with_label(keyword.span),
+            )
+        })
+        .collect();
+
+    diagnostics.sort_by_key(|d| d.labels.as_ref().and_then(|l| l.first()).map(|l| l.offset()));
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisOptions, Analyzer};
+    use crate::highlighting::ErrorSeverity;
+
+    fn any_reports(source: &str) -> Vec<(usize, String)> {
+        Analyzer::new(AnalysisOptions { report_any: true, ..Default::default() })
+            .analyze_source(source, "any.ts")
+            .unwrap()
+            .type_errors
+            .into_iter()
+            .filter(|e| e.id == ANY_ERROR_ID)
+            .map(|e| (e.line, e.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_any_parameter_is_reported_once() {
+        let source = "export function log(value: any): void {\n  console.log(value);\n}\n";
+        assert_eq!(any_reports(source), vec![(1, "Explicit `any` type annotation".to_string())]);
+
+        let errors = Analyzer::new(AnalysisOptions { report_any: true, ..Default::default() })
+            .analyze_source(source, "any.ts")
+            .unwrap()
+            .type_errors;
+        assert_eq!(errors[0].severity, ErrorSeverity::Info);
+        assert_eq!(errors[0].scope, "log");
+    }
+
+    #[test]
+    fn test_assertions_and_type_arguments() {
+        let source = "const items: Array<any> = [];\nconst raw = JSON.parse('{}') as any;\nlet loose: string | any[];\n";
+        assert_eq!(
+            any_reports(source),
+            vec![
+                (1, "Explicit `any` type argument".to_string()),
+                (2, "`as any` assertion".to_string()),
+                (3, "Explicit `any` type".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_implicit_any_and_disabled_by_default() {
+        // Unannotated parameters are implicitly `any` but carry no keyword
+        assert!(any_reports("function f(x) { return x; }\n").is_empty());
+
+        let errors = Analyzer::new(AnalysisOptions::default())
+            .analyze_source("let x: any;\n", "any.ts")
+            .unwrap()
+            .type_errors;
+        assert!(errors.is_empty());
+    }
+}
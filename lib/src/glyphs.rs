@@ -0,0 +1,110 @@
+//! Symbols used to decorate console output.
+//!
+//! Renderers take their markers, borders and bullets from a [`Glyphs`] set
+//! instead of hardcoding them, so terminals and fonts without good Unicode
+//! coverage can switch to [`Glyphs::ASCII`].
+
+/// One set of console decorations.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::glyphs::Glyphs;
+///
+/// assert_eq!(Glyphs::default(), Glyphs::UNICODE);
+/// assert_eq!(Glyphs::ASCII.error, "[X]");
+/// assert_eq!(Glyphs::from_name("ascii"), Some(Glyphs::ASCII));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyphs {
+    /// Marker in front of each error message
+    pub error: &'static str,
+    /// Marker for files and runs without errors
+    pub success: &'static str,
+    /// Marker for skipped files and other warnings
+    pub warning: &'static str,
+    /// Surrounds the "N lines omitted" note in truncated code
+    pub omitted: &'static str,
+    /// Horizontal rule and code block border segment
+    pub horizontal: &'static str,
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    /// List item bullet
+    pub bullet: &'static str,
+    /// Marks text cut short, e.g. the outer segments of a truncated scope
+    pub ellipsis: &'static str,
+}
+
+impl Glyphs {
+    /// Emoji markers and box-drawing borders (the default)
+    pub const UNICODE: Glyphs = Glyphs {
+        error: "[❌]",
+        success: "✅",
+        warning: "⚠",
+        omitted: "┄┄┄",
+        horizontal: "─",
+        top_left: "┌",
+        top_right: "┐",
+        bottom_left: "└",
+        bottom_right: "┘",
+        bullet: "•",
+        ellipsis: "…",
+    };
+
+    /// Printable ASCII only, for limited terminals, fonts and log files
+    pub const ASCII: Glyphs = Glyphs {
+        error: "[X]",
+        success: "[OK]",
+        warning: "[!]",
+        omitted: "...",
+        horizontal: "-",
+        top_left: "+",
+        top_right: "+",
+        bottom_left: "+",
+        bottom_right: "+",
+        bullet: "-",
+        ellipsis: "...",
+    };
+
+    /// The set named `name` (`unicode` or `ascii`, case-insensitive), as accepted by `TA_GLYPHS`
+    pub fn from_name(name: &str) -> Option<Glyphs> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "unicode" => Some(Glyphs::UNICODE),
+            "ascii" => Some(Glyphs::ASCII),
+            _ => None,
+        }
+    }
+
+    /// True for the ASCII preset
+    pub fn is_ascii(&self) -> bool {
+        *self == Glyphs::ASCII
+    }
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Glyphs::UNICODE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_glyphs_are_ascii() {
+        let g = Glyphs::ASCII;
+        for glyph in [g.error, g.success, g.warning, g.omitted, g.horizontal, g.top_left, g.top_right, g.bottom_left, g.bottom_right, g.bullet] {
+            assert!(glyph.is_ascii(), "{:?}", glyph);
+        }
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Glyphs::from_name("ASCII"), Some(Glyphs::ASCII));
+        assert_eq!(Glyphs::from_name("unicode"), Some(Glyphs::UNICODE));
+        assert_eq!(Glyphs::from_name("emoji"), None);
+    }
+}
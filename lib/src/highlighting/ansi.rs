@@ -1,8 +1,9 @@
+use std::borrow::Cow;
 use std::env;
 
 /// ANSI escape code builder for terminal text formatting.
 ///
-/// Supports 24-bit RGB colors, bold, italic, and underline styles.
+/// Supports 24-bit RGB colors, bold, italic, underline, and strikethrough styles.
 ///
 /// # Examples
 ///
@@ -98,6 +99,34 @@ impl AnsiBuilder {
         self
     }
 
+    /// Strikes through the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_lib::highlighting::ansi::AnsiBuilder;
+    /// let code = AnsiBuilder::new().strikethrough().build();
+    /// assert_eq!(code, "\x1b[9m");
+    /// ```
+    pub fn strikethrough(mut self) -> Self {
+        self.codes.push("9".to_string());
+        self
+    }
+
+    /// Dims the text (faint intensity).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_lib::highlighting::ansi::AnsiBuilder;
+    /// let code = AnsiBuilder::new().dim().build();
+    /// assert_eq!(code, "\x1b[2m");
+    /// ```
+    pub fn dim(mut self) -> Self {
+        self.codes.push("2".to_string());
+        self
+    }
+
     /// Builds the ANSI escape sequence.
     ///
     /// Returns an empty string if no codes were added.
@@ -176,10 +205,314 @@ pub fn detect_terminal_capabilities() -> TerminalCapabilities {
     }
 }
 
+/// Removes ANSI escape sequences from `text`.
+///
+/// Handles CSI sequences (`ESC [ ... m` colors, cursor movement), OSC
+/// sequences (`ESC ] ... BEL`, e.g. hyperlinks) and two-byte escapes. Text
+/// without an `ESC` is returned borrowed.
+///
+/// # Examples
+///
+/// ```
+/// # use ta_lib::highlighting::ansi::strip_ansi;
+/// assert_eq!(strip_ansi("\x1b[31mred\x1b[0m text"), "red text");
+/// assert_eq!(strip_ansi("plain"), "plain");
+/// ```
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1b') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameter and intermediate bytes up to a final byte in `@`..=`~`
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: terminated by BEL or `ESC \`
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Text style accumulated from SGR (`ESC [ ... m`) sequences
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct SgrStyle {
+    foreground: Option<(u8, u8, u8)>,
+    background: Option<(u8, u8, u8)>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+}
+
+impl SgrStyle {
+    /// Applies the `;`-separated parameters of one SGR sequence
+    fn apply(&mut self, params: &str) {
+        let codes: Vec<u16> = params.split(';').map(|code| code.parse().unwrap_or(0)).collect();
+        let mut codes = codes.iter().copied();
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                9 => self.strikethrough = true,
+                22 => (self.bold, self.dim) = (false, false),
+                23 => self.italic = false,
+                24 => self.underline = false,
+                29 => self.strikethrough = false,
+                30..=37 => self.foreground = Some(palette_color(code - 30)),
+                90..=97 => self.foreground = Some(palette_color(code - 90 + 8)),
+                40..=47 => self.background = Some(palette_color(code - 40)),
+                100..=107 => self.background = Some(palette_color(code - 100 + 8)),
+                39 => self.foreground = None,
+                49 => self.background = None,
+                38 | 48 => {
+                    let color = match codes.next() {
+                        Some(2) => match (codes.next(), codes.next(), codes.next()) {
+                            (Some(r), Some(g), Some(b)) => Some((r as u8, g as u8, b as u8)),
+                            _ => None,
+                        },
+                        Some(5) => codes.next().map(palette_color),
+                        _ => None,
+                    };
+                    if code == 38 {
+                        self.foreground = color;
+                    } else {
+                        self.background = color;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Inline CSS for the style; empty when unstyled
+    fn css(&self) -> String {
+        let mut rules = Vec::new();
+        if let Some((r, g, b)) = self.foreground {
+            rules.push(format!("color: rgb({}, {}, {})", r, g, b));
+        }
+        if let Some((r, g, b)) = self.background {
+            rules.push(format!("background-color: rgb({}, {}, {})", r, g, b));
+        }
+        if self.bold {
+            rules.push("font-weight: bold".to_string());
+        }
+        if self.dim {
+            rules.push("opacity: 0.5".to_string());
+        }
+        if self.italic {
+            rules.push("font-style: italic".to_string());
+        }
+        let decorations: Vec<&str> = [(self.underline, "underline"), (self.strikethrough, "line-through")]
+            .into_iter()
+            .filter_map(|(on, decoration)| on.then_some(decoration))
+            .collect();
+        if !decorations.is_empty() {
+            rules.push(format!("text-decoration: {}", decorations.join(" ")));
+        }
+        rules.join("; ")
+    }
+}
+
+/// RGB of a 256-color palette index (the first 16 are the xterm defaults)
+fn palette_color(index: u16) -> (u8, u8, u8) {
+    const BASIC: [(u8, u8, u8); 16] = [
+        (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0), (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+        (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0), (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+    ];
+    match index {
+        0..=15 => BASIC[index as usize],
+        16..=231 => {
+            let level = |n: u16| if n == 0 { 0 } else { (55 + n * 40) as u8 };
+            let n = index - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        _ => {
+            let gray = (8 + (index.min(255) - 232) * 10) as u8;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Converts ANSI-styled terminal output into HTML.
+///
+/// SGR sequences (RGB, 256 and 16-color foregrounds and backgrounds, bold,
+/// dim, italic, underline, strikethrough and resets) become inline-styled
+/// `<span>`s, and OSC8 hyperlinks become `<a href>`s. Codes may be stacked and
+/// reset in any order: the text is split wherever the combined style changes,
+/// so spans never overlap. Other escape sequences are dropped and the text is
+/// HTML-escaped.
+///
+/// # Examples
+///
+/// ```
+/// # use ta_lib::highlighting::ansi::ansi_to_html;
+/// assert_eq!(ansi_to_html("\x1b[1mbold\x1b[0m <tag>"), r#"<span style="font-weight: bold">bold</span> &lt;tag&gt;"#);
+/// ```
+pub fn ansi_to_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut style = SgrStyle::default();
+    let mut open_css = String::new();
+    let mut in_link = false;
+    let mut text = String::new();
+
+    // Writes pending text inside a span matching the current style
+    let flush = |out: &mut String, text: &mut String, open_css: &mut String, style: &SgrStyle| {
+        if text.is_empty() {
+            return;
+        }
+        let css = style.css();
+        if css != *open_css {
+            if !open_css.is_empty() {
+                out.push_str("</span>");
+            }
+            if !css.is_empty() {
+                out.push_str(&format!(r#"<span style="{}">"#, css));
+            }
+            *open_css = css;
+        }
+        out.push_str(&html_escape::encode_text(text));
+        text.clear();
+    };
+    let close_span = |out: &mut String, open_css: &mut String| {
+        if !open_css.is_empty() {
+            out.push_str("</span>");
+            open_css.clear();
+        }
+    };
+
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                let mut params = String::new();
+                let mut last = None;
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        last = Some(c);
+                        break;
+                    }
+                    params.push(c);
+                }
+                if last == Some('m') {
+                    flush(&mut out, &mut text, &mut open_css, &style);
+                    style.apply(&params);
+                }
+            }
+            Some(']') => {
+                let mut body = String::new();
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                    body.push(c);
+                }
+                // OSC8: `8;params;uri` opens a link, an empty uri closes it
+                if let Some(uri) = body.strip_prefix("8;").and_then(|rest| rest.split_once(';')).map(|(_, uri)| uri) {
+                    flush(&mut out, &mut text, &mut open_css, &style);
+                    close_span(&mut out, &mut open_css);
+                    if in_link {
+                        out.push_str("</a>");
+                        in_link = false;
+                    }
+                    if !uri.is_empty() {
+                        out.push_str(&format!(r#"<a href="{}">"#, html_escape::encode_double_quoted_attribute(uri)));
+                        in_link = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    flush(&mut out, &mut text, &mut open_css, &style);
+    close_span(&mut out, &mut open_css);
+    if in_link {
+        out.push_str("</a>");
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_strip_ansi_removes_csi_and_osc_sequences() {
+        let styled = format!("{}bold{} \x1b]8;;https://example.com\x07link\x1b]8;;\x1b\\ \x1b[2Kdone", AnsiBuilder::new().bold().fg_rgb(1, 2, 3).build(), AnsiBuilder::RESET);
+        assert_eq!(strip_ansi(&styled), "bold link done");
+        assert!(matches!(strip_ansi("no escapes"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_ansi_to_html_rgb_foreground() {
+        assert_eq!(ansi_to_html("\x1b[38;2;255;0;0mX\x1b[0m"), r#"<span style="color: rgb(255, 0, 0)">X</span>"#);
+        assert_eq!(ansi_to_html("plain & simple"), "plain &amp; simple");
+    }
+
+    #[test]
+    fn test_ansi_to_html_stacked_and_partial_resets() {
+        let input = "\x1b[1m\x1b[34mA\x1b[3mB\x1b[22mC\x1b[39;23mD\x1b[0m\x1b[2mE\x1b[0m";
+        assert_eq!(
+            ansi_to_html(input),
+            concat!(
+                r#"<span style="color: rgb(0, 0, 238); font-weight: bold">A</span>"#,
+                r#"<span style="color: rgb(0, 0, 238); font-weight: bold; font-style: italic">B</span>"#,
+                r#"<span style="color: rgb(0, 0, 238); font-style: italic">C</span>"#,
+                "D",
+                r#"<span style="opacity: 0.5">E</span>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_ansi_to_html_links_and_other_escapes() {
+        let input = "\x1b[4msee \x1b]8;;file:///a.ts\x1b\\a.ts\x1b]8;;\x1b\\ now\x1b[0m\x1b[2K";
+        assert_eq!(
+            ansi_to_html(input),
+            concat!(
+                r#"<span style="text-decoration: underline">see </span>"#,
+                r#"<a href="file:///a.ts"><span style="text-decoration: underline">a.ts</span></a>"#,
+                r#"<span style="text-decoration: underline"> now</span>"#,
+            )
+        );
+    }
+
     #[test]
     fn test_empty_builder() {
         let code = AnsiBuilder::new().build();
@@ -216,6 +549,12 @@ mod tests {
         assert_eq!(code, "\x1b[4m");
     }
 
+    #[test]
+    fn test_strikethrough() {
+        let code = AnsiBuilder::new().strikethrough().build();
+        assert_eq!(code, "\x1b[9m");
+    }
+
     #[test]
     fn test_combined_styles() {
         let code = AnsiBuilder::new()
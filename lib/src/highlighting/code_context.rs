@@ -1,14 +1,57 @@
-/// Context-aware code extraction for error highlighting.
-///
-/// This module provides functionality to extract relevant code context around
-/// type errors, with smart truncation based on scope (function/method/type/module).
+//! Context-aware code extraction for error highlighting.
+//!
+//! This module provides functionality to extract relevant code context around
+//! type errors, with smart truncation based on scope (function/method/type/module).
 
 use oxc_span::Span;
 use oxc_semantic::Semantic;
 use serde::Serialize;
 
+use crate::glyphs::Glyphs;
+use crate::scope_names::{enclosing_scope_names, join_scope_names};
+use crate::source_map::SourceMap;
+use crate::type_errors::DEFAULT_SCOPE_SEPARATOR;
 use crate::highlighting::error::{HighlightError, Result};
 
+/// Marker surrounding the "lines omitted" note in truncated code.
+///
+/// Extraction always emits this marker; renderers swap it for their
+/// [`Glyphs::omitted`] with [`truncation_markers`].
+pub const TRUNCATION_MARKER: &str = Glyphs::UNICODE.omitted;
+
+/// ASCII replacement for [`TRUNCATION_MARKER`] used by plain renderers.
+pub const PLAIN_TRUNCATION_MARKER: &str = Glyphs::ASCII.omitted;
+
+/// Replaces truncation markers with the ones from `glyphs`.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::glyphs::Glyphs;
+/// use ta_lib::highlighting::code_context::truncation_markers;
+///
+/// let code = "fn a() {\n┄┄┄ (3 lines omitted) ┄┄┄\n}";
+/// assert_eq!(truncation_markers(code, &Glyphs::UNICODE), code);
+/// assert_eq!(truncation_markers(code, &Glyphs::ASCII), "fn a() {\n... (3 lines omitted) ...\n}");
+/// ```
+pub fn truncation_markers(display_code: &str, glyphs: &Glyphs) -> String {
+    display_code.replace(TRUNCATION_MARKER, glyphs.omitted)
+}
+
+/// Replaces box-drawing truncation markers with their ASCII equivalent.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::highlighting::code_context::plain_truncation_markers;
+///
+/// let code = "fn a() {\n┄┄┄ (3 lines omitted) ┄┄┄\n}";
+/// assert_eq!(plain_truncation_markers(code), "fn a() {\n... (3 lines omitted) ...\n}");
+/// ```
+pub fn plain_truncation_markers(display_code: &str) -> String {
+    truncation_markers(display_code, &Glyphs::ASCII)
+}
+
 /// Represents the extracted code context around an error.
 ///
 /// # Examples
@@ -35,7 +78,7 @@ pub struct CodeContext {
     /// The type of scope containing the error.
     pub scope_type: ScopeType,
 
-    /// The name of the scope (e.g., function name, class::method).
+    /// The scope name, as in [`crate::models::TypeError::scope`] (e.g. `Class.method`).
     pub scope_name: String,
 
     /// Information about truncation, if any was applied.
@@ -55,6 +98,9 @@ pub enum ScopeType {
     /// Error in a type utility or type alias.
     TypeUtility,
 
+    /// Error inside a top-level variable declaration (e.g. a `const` config object).
+    Variable,
+
     /// Error at module/file level (not in any specific scope).
     ModuleLevel,
 }
@@ -107,16 +153,34 @@ pub fn extract_code_context(
     source: &str,
     error_span: Span,
     semantic: &Semantic,
+) -> Result<CodeContext> {
+    extract_code_context_with_lines(source, error_span, semantic, None)
+}
+
+/// Extracts code context around an error span with an explicit context window.
+///
+/// When `context_lines` is `Some(n)`, the display code is limited to `n` lines
+/// before and after the error line (`0` shows only the error line), replacing the
+/// default truncation heuristics. Module-level boundary detection still applies,
+/// with its scan capped at `n` lines. `None` behaves like [`extract_code_context`].
+///
+/// # Errors
+///
+/// Returns `HighlightError::InvalidSpan` if the span is out of bounds.
+pub fn extract_code_context_with_lines(
+    source: &str,
+    error_span: Span,
+    semantic: &Semantic,
+    context_lines: Option<usize>,
 ) -> Result<CodeContext> {
     // CRITICAL: Validate span bounds FIRST to prevent panics
     if error_span.end as usize > source.len() {
-        let line = calculate_line_number(source, error_span.start as usize);
-        let column = calculate_column_number(source, error_span.start as usize);
+        let (line, column) = SourceMap::new(source).line_col(error_span.start as usize);
         return Err(HighlightError::InvalidSpan { line, column });
     }
 
     // Find the scope containing the error
-    let scope_info = find_containing_scope(source, error_span, semantic)?;
+    let scope_info = find_containing_scope(source, error_span)?;
 
     // Extract the code for this scope
     let scope_span = scope_info.span;
@@ -128,31 +192,28 @@ pub fn extract_code_context(
         error_span,
         scope_span,
         scope_info.scope_type,
+        context_lines,
     );
 
     Ok(CodeContext {
         full_code,
         display_code,
         scope_type: scope_info.scope_type,
-        scope_name: scope_info.name,
+        scope_name: join_scope_names(&enclosing_scope_names(semantic, error_span), DEFAULT_SCOPE_SEPARATOR, None),
         truncation_info,
     })
 }
 
-/// Information about a detected scope.
+/// The code shown for an error: its span and kind. Its name comes from
+/// [`crate::scope_names`] like the error's own scope.
 #[derive(Debug)]
 struct ScopeInfo {
     span: Span,
     scope_type: ScopeType,
-    name: String,
 }
 
 /// Finds the scope containing the given error span.
-fn find_containing_scope(
-    source: &str,
-    error_span: Span,
-    _semantic: &Semantic,
-) -> Result<ScopeInfo> {
+fn find_containing_scope(source: &str, error_span: Span) -> Result<ScopeInfo> {
     use oxc_ast::visit::{Visit, walk};
     use oxc_ast::ast::*;
 
@@ -168,17 +229,12 @@ fn find_containing_scope(
                 return;
             }
 
-            // If we have a name, use it
-            if let Some(id) = &func.id {
-                let name = id.name.to_string();
-                // Only update if we don't have a result yet, or this is more specific (smaller span)
-                if self.result.is_none() || self.result.as_ref().unwrap().span.size() > func.span.size() {
-                    self.result = Some(ScopeInfo {
-                        span: func.span,
-                        scope_type: ScopeType::Function,
-                        name,
-                    });
-                }
+            // Only named functions; update if we don't have a result yet, or this is more specific (smaller span)
+            if func.id.is_some() && (self.result.is_none() || self.result.as_ref().unwrap().span.size() > func.span.size()) {
+                self.result = Some(ScopeInfo {
+                    span: func.span,
+                    scope_type: ScopeType::Function,
+                });
             }
 
             // Continue walking to find nested scopes
@@ -191,15 +247,11 @@ fn find_containing_scope(
             }
 
             // Update class scope
-            if let Some(id) = &class.id {
-                let class_name = id.name.to_string();
-                if self.result.is_none() || self.result.as_ref().unwrap().span.size() > class.span.size() {
-                    self.result = Some(ScopeInfo {
-                        span: class.span,
-                        scope_type: ScopeType::Method, // Will be refined if method found
-                        name: class_name.clone(),
-                    });
-                }
+            if class.id.is_some() && (self.result.is_none() || self.result.as_ref().unwrap().span.size() > class.span.size()) {
+                self.result = Some(ScopeInfo {
+                    span: class.span,
+                    scope_type: ScopeType::Method, // Will be refined if method found
+                });
             }
 
             // Continue to find methods
@@ -213,28 +265,9 @@ fn find_containing_scope(
                 return;
             }
 
-            // Get method name
-            let method_name = match &method.key {
-                PropertyKey::StaticIdentifier(id) => id.name.to_string(),
-                PropertyKey::PrivateIdentifier(id) => format!("#{}", id.name),
-                _ => "method".to_string(),
-            };
-
-            // Get class name from previous scope (if any)
-            let full_name = if let Some(ref prev_scope) = self.result {
-                if prev_scope.span.contains_inclusive(method_span) {
-                    format!("{}::{}", prev_scope.name, method_name)
-                } else {
-                    method_name
-                }
-            } else {
-                method_name
-            };
-
             self.result = Some(ScopeInfo {
                 span: method_span,
                 scope_type: ScopeType::Method,
-                name: full_name,
             });
 
             walk::walk_method_definition(self, method);
@@ -245,12 +278,10 @@ fn find_containing_scope(
                 return;
             }
 
-            let name = decl.id.name.to_string();
             if self.result.is_none() || self.result.as_ref().unwrap().span.size() > decl.span.size() {
                 self.result = Some(ScopeInfo {
                     span: decl.span,
                     scope_type: ScopeType::TypeUtility,
-                    name,
                 });
             }
 
@@ -262,12 +293,10 @@ fn find_containing_scope(
                 return;
             }
 
-            let name = decl.id.name.to_string();
             if self.result.is_none() || self.result.as_ref().unwrap().span.size() > decl.span.size() {
                 self.result = Some(ScopeInfo {
                     span: decl.span,
                     scope_type: ScopeType::TypeUtility,
-                    name,
                 });
             }
 
@@ -284,9 +313,11 @@ fn find_containing_scope(
     let source_type = SourceType::default().with_typescript(true);
     let parse_result = Parser::new(&allocator, source, source_type).parse();
 
+    // Top-level variables are the outermost candidate; nested scopes found by the
+    // visitor are smaller and replace it
     let mut finder = ScopeFinder {
         error_span,
-        result: None,
+        result: find_top_level_variable(&parse_result.program, error_span),
     };
 
     finder.visit_program(&parse_result.program);
@@ -295,10 +326,40 @@ fn find_containing_scope(
     Ok(finder.result.unwrap_or(ScopeInfo {
         span: Span::new(0, source.len() as u32),
         scope_type: ScopeType::ModuleLevel,
-        name: "global".to_string(),
     }))
 }
 
+/// Finds the top-level `const`/`let`/`var` declaration containing the error span.
+///
+/// Only declarators bound to a plain identifier count; destructuring
+/// patterns fall through to module level.
+fn find_top_level_variable(program: &oxc_ast::ast::Program, error_span: Span) -> Option<ScopeInfo> {
+    use oxc_ast::ast::{BindingPatternKind, Declaration, Statement};
+
+    program.body.iter().find_map(|statement| {
+        let decl = match statement {
+            Statement::VariableDeclaration(decl) => decl,
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::VariableDeclaration(decl)) => decl,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        if !decl.span.contains_inclusive(error_span) {
+            return None;
+        }
+
+        let declarator = decl
+            .declarations
+            .iter()
+            .find(|declarator| declarator.span.contains_inclusive(error_span))?;
+        matches!(declarator.id.kind, BindingPatternKind::BindingIdentifier(_)).then_some(ScopeInfo {
+            span: decl.span,
+            scope_type: ScopeType::Variable,
+        })
+    })
+}
+
 /// Extracts text for a given span with bounds checking.
 fn extract_span_text(source: &str, span: Span) -> Result<String> {
     let start = span.start as usize;
@@ -306,38 +367,42 @@ fn extract_span_text(source: &str, span: Span) -> Result<String> {
 
     // Check for invalid span ordering
     if start > end {
-        let line = calculate_line_number(source, start.min(source.len()));
-        let column = calculate_column_number(source, start.min(source.len()));
+        let (line, column) = SourceMap::new(source).line_col(start);
         return Err(HighlightError::InvalidSpan { line, column });
     }
 
     if end > source.len() || start > source.len() {
-        let line = calculate_line_number(source, start.min(source.len()));
-        let column = calculate_column_number(source, start.min(source.len()));
+        let (line, column) = SourceMap::new(source).line_col(start);
         return Err(HighlightError::InvalidSpan { line, column });
     }
 
     // Ensure we're at valid UTF-8 char boundaries
     if !source.is_char_boundary(start) || !source.is_char_boundary(end) {
-        let line = calculate_line_number(source, start);
-        let column = calculate_column_number(source, start);
+        let (line, column) = SourceMap::new(source).line_col(start);
         return Err(HighlightError::InvalidSpan { line, column });
     }
 
     Ok(source[start..end].to_string())
 }
 
+/// Default number of lines scanned before/after an error at module level.
+const MODULE_CONTEXT_LINES: usize = 3;
+
 /// Applies truncation logic to large code blocks.
 ///
 /// Truncation rules:
 /// - Function/method/type <15 lines: Show full definition
 /// - Function/method/type ≥15 lines: Show signature + context around error + closing
 /// - Module-level: Smart boundary detection (stop at blank lines/closing braces)
+///
+/// With `Some(n)`, function/method/type scopes show exactly `n` lines around the
+/// error and module-level boundary detection scans at most `n` lines each way.
 fn apply_truncation(
     full_code: &str,
     error_span: Span,
     scope_span: Span,
     scope_type: ScopeType,
+    context_lines: Option<usize>,
 ) -> (String, Option<TruncationInfo>) {
     let lines: Vec<&str> = full_code.lines().collect();
     let line_count = lines.len();
@@ -346,22 +411,64 @@ fn apply_truncation(
     let error_line = calculate_relative_line_number(full_code, scope_span, error_span);
 
     // Apply truncation based on scope type
-    match scope_type {
-        ScopeType::Function | ScopeType::Method | ScopeType::TypeUtility => {
+    match (scope_type, context_lines) {
+        (ScopeType::ModuleLevel, context_lines) => {
+            // Always apply boundary detection for module-level scope
+            // (even for short files, to avoid showing unrelated code)
+            let max_lines = context_lines.unwrap_or(MODULE_CONTEXT_LINES);
+            truncate_module_scope(&lines, error_line, line_count, max_lines)
+        }
+        (_, Some(context_lines)) => truncate_to_window(&lines, error_line, context_lines),
+        (_, None) => {
             // Short code: no truncation for function/method/type scopes
             if line_count < 15 {
                 return (full_code.to_string(), None);
             }
             truncate_function_scope(&lines, error_line, line_count)
         }
-        ScopeType::ModuleLevel => {
-            // Always apply boundary detection for module-level scope
-            // (even for short files, to avoid showing unrelated code)
-            truncate_module_scope(&lines, error_line, line_count)
-        }
     }
 }
 
+/// Shows only `context_lines` lines before and after the error line.
+///
+/// Used when the caller overrides the context window; no truncation markers are
+/// added so `0` yields exactly the error line.
+fn truncate_to_window(
+    lines: &[&str],
+    error_line: usize,
+    context_lines: usize,
+) -> (String, Option<TruncationInfo>) {
+    if lines.is_empty() {
+        return (String::new(), None);
+    }
+
+    let error_line = error_line.min(lines.len() - 1);
+    let start = error_line.saturating_sub(context_lines);
+    let end = error_line.saturating_add(context_lines).min(lines.len() - 1);
+
+    let display_code = lines[start..=end].join("\n");
+
+    let mut truncated_sections = Vec::new();
+    if start > 0 {
+        truncated_sections.push((0, start - 1));
+    }
+    if end < lines.len() - 1 {
+        truncated_sections.push((end + 1, lines.len() - 1));
+    }
+
+    let truncation_info = if truncated_sections.is_empty() {
+        None
+    } else {
+        Some(TruncationInfo {
+            original_line_count: lines.len(),
+            displayed_line_count: end - start + 1,
+            truncated_sections,
+        })
+    };
+
+    (display_code, truncation_info)
+}
+
 /// Truncates a function/method/type scope.
 ///
 /// Shows: signature + ... + context around error + ... + closing
@@ -380,7 +487,7 @@ fn truncate_function_scope(
     let context_start = error_line.saturating_sub(2).max(1);
     if context_start > 1 {
         let omitted = context_start - 1;
-        displayed_lines.push(format!("┄┄┄ ({} lines omitted) ┄┄┄", omitted));
+        displayed_lines.push(format!("{} ({} lines omitted) {}", TRUNCATION_MARKER, omitted, TRUNCATION_MARKER));
         truncated_sections.push((1, context_start - 1));
     }
 
@@ -397,7 +504,7 @@ fn truncate_function_scope(
     let last_line_idx = total_lines - 1;
     if error_end < last_line_idx - 1 {
         let omitted = last_line_idx - error_end - 1;
-        displayed_lines.push(format!("┄┄┄ ({} lines omitted) ┄┄┄", omitted));
+        displayed_lines.push(format!("{} ({} lines omitted) {}", TRUNCATION_MARKER, omitted, TRUNCATION_MARKER));
         truncated_sections.push((error_end + 1, last_line_idx - 1));
     }
 
@@ -419,7 +526,7 @@ fn truncate_function_scope(
 /// Truncates module-level code with smart boundary detection.
 ///
 /// Rules:
-/// - Maximum 3 lines before/after error
+/// - Maximum `max_lines` lines before/after error (3 by default)
 /// - Stop at blank lines (immediate termination)
 /// - Stop at closing braces `}` (block boundaries)
 /// - Stop at opening braces for new blocks
@@ -428,12 +535,13 @@ fn truncate_module_scope(
     lines: &[&str],
     error_line: usize,
     total_lines: usize,
+    max_lines: usize,
 ) -> (String, Option<TruncationInfo>) {
     // Find context start by scanning upward from error, stopping at boundaries
-    let context_start = find_context_start(lines, error_line);
+    let context_start = find_context_start(lines, error_line, max_lines);
 
     // Find context end by scanning downward from error, stopping at boundaries
-    let context_end = find_context_end(lines, error_line, total_lines);
+    let context_end = find_context_end(lines, error_line, total_lines, max_lines);
 
     // Extract just the relevant lines (no truncation markers)
     let mut displayed_lines = Vec::new();
@@ -452,17 +560,15 @@ fn truncate_module_scope(
 /// Finds the start of context by scanning upward from error line.
 ///
 /// Stops at:
-/// - 3 lines before error (maximum)
+/// - `max_lines` lines before error (maximum)
 /// - Blank lines
 /// - Closing braces `}` (include the brace, then stop)
-fn find_context_start(lines: &[&str], error_line: usize) -> usize {
-    const MAX_LINES_BEFORE: usize = 3;
-
+fn find_context_start(lines: &[&str], error_line: usize, max_lines: usize) -> usize {
     let mut start = error_line;
     let mut lines_seen = 0;
 
     // Scan upward from error_line - 1
-    while start > 0 && lines_seen < MAX_LINES_BEFORE {
+    while start > 0 && lines_seen < max_lines {
         let prev_line = start - 1;
         let line_content = lines[prev_line].trim();
 
@@ -487,17 +593,15 @@ fn find_context_start(lines: &[&str], error_line: usize) -> usize {
 /// Finds the end of context by scanning downward from error line.
 ///
 /// Stops at:
-/// - 3 lines after error (maximum)
+/// - `max_lines` lines after error (maximum)
 /// - Blank lines
 /// - Opening braces for new blocks (function/class definitions)
-fn find_context_end(lines: &[&str], error_line: usize, total_lines: usize) -> usize {
-    const MAX_LINES_AFTER: usize = 3;
-
+fn find_context_end(lines: &[&str], error_line: usize, total_lines: usize, max_lines: usize) -> usize {
     let mut end = error_line;
     let mut lines_seen = 0;
 
     // Scan downward from error_line + 1
-    while end < total_lines - 1 && lines_seen < MAX_LINES_AFTER {
+    while end < total_lines - 1 && lines_seen < max_lines {
         let next_line = end + 1;
         let line_content = lines[next_line].trim();
 
@@ -547,51 +651,6 @@ fn is_block_start(line: &str) -> bool {
     false
 }
 
-/// Calculates the line number (1-indexed) for a byte offset.
-fn calculate_line_number(source: &str, byte_offset: usize) -> usize {
-    if byte_offset > source.len() {
-        return 1;
-    }
-
-    // Adjust to nearest valid char boundary if not already on one
-    let safe_offset = if !source.is_char_boundary(byte_offset) {
-        // Find the previous valid boundary
-        (0..=byte_offset)
-            .rev()
-            .find(|&i| source.is_char_boundary(i))
-            .unwrap_or(0)
-    } else {
-        byte_offset
-    };
-
-    source[..safe_offset].chars().filter(|&c| c == '\n').count() + 1
-}
-
-/// Calculates the column number (1-indexed) for a byte offset.
-fn calculate_column_number(source: &str, byte_offset: usize) -> usize {
-    if byte_offset > source.len() {
-        return 1;
-    }
-
-    // Adjust to nearest valid char boundary if not already on one
-    let safe_offset = if !source.is_char_boundary(byte_offset) {
-        // Find the previous valid boundary
-        (0..=byte_offset)
-            .rev()
-            .find(|&i| source.is_char_boundary(i))
-            .unwrap_or(0)
-    } else {
-        byte_offset
-    };
-
-    let line_start = source[..safe_offset]
-        .rfind('\n')
-        .map(|pos| pos + 1)
-        .unwrap_or(0);
-
-    source[line_start..safe_offset].chars().count() + 1
-}
-
 /// Calculates the line number of error_span relative to scope_span start.
 ///
 /// Returns the 0-indexed line number within the scope's extracted text.
@@ -618,6 +677,45 @@ fn calculate_relative_line_number(scope_text: &str, scope_span: Span, error_span
         .count()
 }
 
+/// Calculates the line number (1-indexed) for a byte offset.
+///
+/// Superseded by [`SourceMap::line`](crate::source_map::SourceMap::line);
+/// kept as the scanning oracle its property tests compare against.
+#[cfg(test)]
+pub(crate) fn calculate_line_number(source: &str, byte_offset: usize) -> usize {
+    if byte_offset > source.len() {
+        return 1;
+    }
+
+    let safe_offset = if !source.is_char_boundary(byte_offset) {
+        (0..=byte_offset).rev().find(|&i| source.is_char_boundary(i)).unwrap_or(0)
+    } else {
+        byte_offset
+    };
+
+    source[..safe_offset].chars().filter(|&c| c == '\n').count() + 1
+}
+
+/// Calculates the column number (1-indexed) for a byte offset.
+///
+/// Superseded by [`SourceMap::line_col`](crate::source_map::SourceMap::line_col);
+/// kept as the scanning oracle its property tests compare against.
+#[cfg(test)]
+pub(crate) fn calculate_column_number(source: &str, byte_offset: usize) -> usize {
+    if byte_offset > source.len() {
+        return 1;
+    }
+
+    let safe_offset = if !source.is_char_boundary(byte_offset) {
+        (0..=byte_offset).rev().find(|&i| source.is_char_boundary(i)).unwrap_or(0)
+    } else {
+        byte_offset
+    };
+
+    let line_start = source[..safe_offset].rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+    source[line_start..safe_offset].chars().count() + 1
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -642,6 +740,57 @@ mod tests {
         assert_ne!(ScopeType::Function, ScopeType::Method);
         assert_ne!(ScopeType::Method, ScopeType::TypeUtility);
         assert_ne!(ScopeType::TypeUtility, ScopeType::ModuleLevel);
+        assert_ne!(ScopeType::Variable, ScopeType::ModuleLevel);
+    }
+
+    fn context_at(source: &str, needle: &str) -> CodeContext {
+        use oxc_allocator::Allocator;
+        use oxc_parser::Parser;
+        use oxc_semantic::SemanticBuilder;
+        use oxc_span::SourceType;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::default().with_typescript(true)).parse();
+        let semantic = SemanticBuilder::new(source).build(&ret.program).semantic;
+        let start = source.find(needle).unwrap() as u32;
+        let span = Span::new(start, start + needle.len() as u32);
+        extract_code_context(source, span, &semantic).unwrap()
+    }
+
+    #[test]
+    fn test_error_in_top_level_object_literal_reports_variable() {
+        let entries: String = (0..20).map(|i| format!("  key{}: {},\n", i, i)).collect();
+        let source = format!(
+            "interface Config {{ [key: string]: number }}\n\nexport const config = {{\n{}  broken: \"oops\",\n}} satisfies Config;\n",
+            entries
+        );
+
+        let context = context_at(&source, "\"oops\"");
+
+        assert_eq!(context.scope_type, ScopeType::Variable);
+        // The variable's code is shown, but like the error's scope it is named global
+        assert_eq!(context.scope_name, "global");
+        assert!(context.full_code.starts_with("const config"));
+    }
+
+    #[test]
+    fn test_function_inside_variable_is_more_specific() {
+        let source = "const handlers = {\n  run: function run() {\n    return missing;\n  },\n};\n";
+
+        let context = context_at(source, "missing");
+
+        assert_eq!(context.scope_type, ScopeType::Function);
+        assert_eq!(context.scope_name, "run");
+    }
+
+    #[test]
+    fn test_statement_outside_declarations_stays_module_level() {
+        let source = "const a = 1;\n\nconsole.log(missing);\n";
+
+        let context = context_at(source, "missing");
+
+        assert_eq!(context.scope_type, ScopeType::ModuleLevel);
+        assert_eq!(context.scope_name, "global");
     }
 
     #[test]
@@ -673,25 +822,6 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_calculate_line_number() {
-        let source = "line 1\nline 2\nline 3";
-
-        assert_eq!(calculate_line_number(source, 0), 1);
-        assert_eq!(calculate_line_number(source, 7), 2);
-        assert_eq!(calculate_line_number(source, 14), 3);
-    }
-
-    #[test]
-    fn test_calculate_column_number() {
-        let source = "line 1\nline 2\nline 3";
-
-        assert_eq!(calculate_column_number(source, 0), 1);
-        assert_eq!(calculate_column_number(source, 5), 6);
-        assert_eq!(calculate_column_number(source, 7), 1);
-        assert_eq!(calculate_column_number(source, 10), 4);
-    }
-
     #[test]
     fn test_extract_span_text_valid() {
         let source = "function test() { return 42; }";
@@ -717,7 +847,7 @@ mod tests {
         let error_span = Span::new(7, 13);
         let scope_span = Span::new(0, 20);
 
-        let (display, info) = apply_truncation(code, error_span, scope_span, ScopeType::Function);
+        let (display, info) = apply_truncation(code, error_span, scope_span, ScopeType::Function, None);
 
         assert_eq!(display, code);
         assert!(info.is_none());
@@ -730,7 +860,7 @@ mod tests {
         let error_span = Span::new(100, 110); // Somewhere in the middle
         let scope_span = Span::new(0, code.len() as u32);
 
-        let (display, info) = apply_truncation(&code, error_span, scope_span, ScopeType::Function);
+        let (display, info) = apply_truncation(&code, error_span, scope_span, ScopeType::Function, None);
 
         assert!(info.is_some());
         if let Some(truncation_info) = info {
@@ -748,7 +878,7 @@ mod tests {
         let error_span = Span::new(100, 110);
         let scope_span = Span::new(0, code.len() as u32);
 
-        let (display, info) = apply_truncation(&code, error_span, scope_span, ScopeType::ModuleLevel);
+        let (display, info) = apply_truncation(&code, error_span, scope_span, ScopeType::ModuleLevel, None);
 
         assert!(info.is_some());
         if let Some(truncation_info) = info {
@@ -774,13 +904,78 @@ mod tests {
         let lines: Vec<&str> = (1..=30).map(|_| "code").collect();
         let error_line = 15;
 
-        let (display, info) = truncate_module_scope(&lines, error_line, 30);
+        let (display, info) = truncate_module_scope(&lines, error_line, 30, MODULE_CONTEXT_LINES);
 
         assert!(display.contains("┄┄┄"));
         assert!(display.contains("lines omitted"));
         assert!(info.is_some());
     }
 
+    #[test]
+    fn test_context_lines_zero_shows_only_error_line() {
+        let lines: Vec<String> = (1..=30).map(|i| format!("line {}", i)).collect();
+        let code = lines.join("\n");
+        let start = code.find("line 20").unwrap() as u32;
+        let error_span = Span::new(start, start + 7);
+        let scope_span = Span::new(0, code.len() as u32);
+
+        for scope_type in [ScopeType::Function, ScopeType::ModuleLevel] {
+            let (display, _) =
+                apply_truncation(&code, error_span, scope_span, scope_type, Some(0));
+            assert_eq!(display, "line 20");
+        }
+    }
+
+    #[test]
+    fn test_context_lines_window() {
+        let lines: Vec<String> = (1..=30).map(|i| format!("line {}", i)).collect();
+        let code = lines.join("\n");
+        let start = code.find("line 20").unwrap() as u32;
+        let error_span = Span::new(start, start + 7);
+        let scope_span = Span::new(0, code.len() as u32);
+
+        let (display, info) =
+            apply_truncation(&code, error_span, scope_span, ScopeType::Function, Some(1));
+        assert_eq!(display, "line 19\nline 20\nline 21");
+        let info = info.unwrap();
+        assert_eq!(info.displayed_line_count, 3);
+        assert_eq!(info.truncated_sections, vec![(0, 17), (21, 29)]);
+
+        // Module-level scan is capped at N, not the default 3
+        let (display, _) =
+            apply_truncation(&code, error_span, scope_span, ScopeType::ModuleLevel, Some(1));
+        assert_eq!(display, "line 19\nline 20\nline 21");
+    }
+
+    #[test]
+    fn test_plain_truncation_markers_are_ascii() {
+        let lines: Vec<&str> = (1..=30).map(|_| "code").collect();
+        let (display, _) = truncate_function_scope(&lines, 15, 30);
+
+        let plain = plain_truncation_markers(&display);
+        assert!(plain.contains("... (12 lines omitted) ..."));
+        assert!(plain.is_ascii());
+    }
+
+    #[test]
+    fn test_calculate_line_number() {
+        let source = "line 1\nline 2\nline 3";
+
+        assert_eq!(calculate_line_number(source, 0), 1);
+        assert_eq!(calculate_line_number(source, 7), 2);
+        assert_eq!(calculate_line_number(source, 14), 3);
+    }
+
+    #[test]
+    fn test_calculate_column_number() {
+        let source = "line 1\nline 2\nline 3";
+
+        assert_eq!(calculate_column_number(source, 0), 1);
+        assert_eq!(calculate_column_number(source, 5), 6);
+        assert_eq!(calculate_column_number(source, 7), 1);
+        assert_eq!(calculate_column_number(source, 10), 4);
+    }
+
     #[test]
     fn test_calculate_line_number_boundary() {
         let source = "a\nb\nc";
@@ -821,7 +1016,7 @@ mod tests {
         let error_span = Span::new(50, 60);
         let scope_span = Span::new(0, code.len() as u32);
 
-        let (_display, info) = apply_truncation(&code, error_span, scope_span, ScopeType::Function);
+        let (_display, info) = apply_truncation(&code, error_span, scope_span, ScopeType::Function, None);
 
         // At exactly 15 lines, should apply truncation
         assert!(info.is_some());
@@ -837,7 +1032,7 @@ mod tests {
         let error_span = Span::new(50, 60);
         let scope_span = Span::new(0, code.len() as u32);
 
-        let (display, info) = apply_truncation(&code, error_span, scope_span, ScopeType::Function);
+        let (display, info) = apply_truncation(&code, error_span, scope_span, ScopeType::Function, None);
 
         // At 14 lines, should NOT apply truncation
         assert!(info.is_none());
@@ -914,7 +1109,8 @@ mod proptests {
                 &code,
                 error_span,
                 scope_span,
-                ScopeType::Function
+                ScopeType::Function,
+                None
             );
 
             // Display should never be empty
@@ -934,7 +1130,8 @@ mod proptests {
                 &code,
                 error_span,
                 scope_span,
-                ScopeType::Function
+                ScopeType::Function,
+                None
             );
 
             if let Some(truncation_info) = info {
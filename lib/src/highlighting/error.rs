@@ -62,6 +62,10 @@ pub enum HighlightError {
     /// This is a catch-all for unexpected syntect errors.
     #[error("Syntax highlighting failed: {0}")]
     SyntectError(String),
+
+    /// Highlighted code could not be serialized to JSON.
+    #[error("Failed to serialize highlighted code: {0}")]
+    SerializationError(#[from] serde_json::Error),
 }
 
 /// A specialized `Result` type for highlighting operations.
@@ -1,14 +1,18 @@
-/// Error annotation module for TypeScript Analyzer highlighting.
-///
-/// This module provides error annotation capabilities for code blocks,
-/// including "red squigglies" visual indicators and error messages.
-/// Uses `Span` as the single source of truth for error positions.
+//! Error annotation module for TypeScript Analyzer highlighting.
+//!
+//! This module provides error annotation capabilities for code blocks,
+//! including "red squigglies" visual indicators and error messages.
+//! Uses `Span` as the single source of truth for error positions.
 
 use oxc_span::Span;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use unicode_width::UnicodeWidthChar;
 
 use crate::highlighting::ansi::AnsiBuilder;
+use crate::highlighting::options::HtmlOptions;
+use crate::highlighting::syntect_highlighter::{HighlightedCode, HTML_CLOSE};
+use crate::source_map::SourceMap;
 
 /// Severity level for error annotations.
 ///
@@ -24,7 +28,7 @@ use crate::highlighting::ansi::AnsiBuilder;
 /// assert_eq!(severity, ErrorSeverity::Error);
 /// ```
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ErrorSeverity {
     /// Critical error that prevents compilation.
@@ -49,6 +53,26 @@ impl ErrorSeverity {
         }
     }
 
+    /// Returns the lowercase label for this severity level.
+    ///
+    /// Matches the serialized form (`"error"`, `"warning"`, `"info"`).
+    pub fn label(&self) -> &'static str {
+        match self {
+            ErrorSeverity::Error => "error",
+            ErrorSeverity::Warning => "warning",
+            ErrorSeverity::Info => "info",
+        }
+    }
+
+    /// Returns the capitalized severity word used in accessible names (`"Error"`, ...).
+    pub fn title(&self) -> &'static str {
+        match self {
+            ErrorSeverity::Error => "Error",
+            ErrorSeverity::Warning => "Warning",
+            ErrorSeverity::Info => "Info",
+        }
+    }
+
     /// Returns the CSS class name for this severity level.
     pub fn css_class(&self) -> &'static str {
         match self {
@@ -59,6 +83,19 @@ impl ErrorSeverity {
     }
 }
 
+impl From<miette::Severity> for ErrorSeverity {
+    /// Maps diagnostic severities reported by OXC onto annotation severities.
+    ///
+    /// Miette's `Advice` level is treated as `Info`.
+    fn from(severity: miette::Severity) -> Self {
+        match severity {
+            miette::Severity::Error => ErrorSeverity::Error,
+            miette::Severity::Warning => ErrorSeverity::Warning,
+            miette::Severity::Advice => ErrorSeverity::Info,
+        }
+    }
+}
+
 /// Error annotation with position and message.
 ///
 /// Uses `Span` as the single source of truth for error positions.
@@ -138,8 +175,8 @@ impl ErrorAnnotation {
 
     /// Computes the 1-based line number where the error starts.
     ///
-    /// This is computed from the span's start position by counting newlines
-    /// in the source text before that position.
+    /// Computed with a [`SourceMap`] of `source`; callers looking up many
+    /// positions in one file should build the map once and use it directly.
     ///
     /// # Arguments
     ///
@@ -169,21 +206,12 @@ impl ErrorAnnotation {
     /// assert_eq!(annotation.line(source), 2);
     /// ```
     pub fn line(&self, source: &str) -> usize {
-        let start = self.span.start as usize;
-        if start > source.len() {
-            panic!(
-                "Span start {} is out of bounds for source length {}",
-                start,
-                source.len()
-            );
-        }
-        source[..start].chars().filter(|&c| c == '\n').count() + 1
+        self.start_position(&SourceMap::new(source)).0
     }
 
     /// Computes the 1-based column number where the error starts.
     ///
-    /// This is computed from the span's start position by finding the last
-    /// newline before it and counting characters from there.
+    /// Columns count characters, not bytes.
     ///
     /// # Arguments
     ///
@@ -213,25 +241,13 @@ impl ErrorAnnotation {
     /// assert_eq!(annotation.column(source), 7);
     /// ```
     pub fn column(&self, source: &str) -> usize {
-        let start = self.span.start as usize;
-        if start > source.len() {
-            panic!(
-                "Span start {} is out of bounds for source length {}",
-                start,
-                source.len()
-            );
-        }
-        let line_start = source[..start]
-            .rfind('\n')
-            .map(|pos| pos + 1)
-            .unwrap_or(0);
-        source[line_start..start].chars().count() + 1
+        self.start_position(&SourceMap::new(source)).1
     }
 
     /// Computes the 1-based line number where the error ends.
     ///
-    /// This is computed from the span's end position by counting newlines
-    /// in the source text before that position.
+    /// Computed with a [`SourceMap`] of `source`; callers looking up many
+    /// positions in one file should build the map once and use it directly.
     ///
     /// # Arguments
     ///
@@ -245,21 +261,12 @@ impl ErrorAnnotation {
     ///
     /// Panics if the span is out of bounds for the source text.
     pub fn end_line(&self, source: &str) -> usize {
-        let end = self.span.end as usize;
-        if end > source.len() {
-            panic!(
-                "Span end {} is out of bounds for source length {}",
-                end,
-                source.len()
-            );
-        }
-        source[..end].chars().filter(|&c| c == '\n').count() + 1
+        self.end_position(&SourceMap::new(source)).0
     }
 
     /// Computes the 1-based column number where the error ends.
     ///
-    /// This is computed from the span's end position by finding the last
-    /// newline before it and counting characters from there.
+    /// Columns count characters, not bytes.
     ///
     /// # Arguments
     ///
@@ -273,16 +280,25 @@ impl ErrorAnnotation {
     ///
     /// Panics if the span is out of bounds for the source text.
     pub fn end_column(&self, source: &str) -> usize {
-        let end = self.span.end as usize;
-        if end > source.len() {
-            panic!(
-                "Span end {} is out of bounds for source length {}",
-                end,
-                source.len()
-            );
+        self.end_position(&SourceMap::new(source)).1
+    }
+
+    /// Line and column where the span starts, panicking when it is past the end of the source
+    fn start_position(&self, map: &SourceMap<'_>) -> (usize, usize) {
+        Self::position(map, self.span.start, "start")
+    }
+
+    /// Line and column where the span ends, panicking when it is past the end of the source
+    fn end_position(&self, map: &SourceMap<'_>) -> (usize, usize) {
+        Self::position(map, self.span.end, "end")
+    }
+
+    fn position(map: &SourceMap<'_>, offset: u32, edge: &str) -> (usize, usize) {
+        let len = map.source().len();
+        if offset as usize > len {
+            panic!("Span {} {} is out of bounds for source length {}", edge, offset, len);
         }
-        let line_start = source[..end].rfind('\n').map(|pos| pos + 1).unwrap_or(0);
-        source[line_start..end].chars().count() + 1
+        map.line_col(offset as usize)
     }
 
     /// Renders this error annotation for console output with ANSI escape codes.
@@ -318,35 +334,39 @@ impl ErrorAnnotation {
     /// assert!(output.contains("\x1b[")); // ANSI escape code
     /// ```
     pub fn render_console(&self, source: &str) -> String {
-        let line_num = self.line(source);
-        let col = self.column(source);
-        let end_col = self.end_column(source);
-
-        // Extract the line containing the error
-        let lines: Vec<&str> = source.lines().collect();
-        let error_line = if line_num > 0 && line_num <= lines.len() {
-            lines[line_num - 1]
-        } else {
-            ""
-        };
+        let map = SourceMap::new(source);
+        let (line_num, col) = self.start_position(&map);
+        let (end_line, end_col) = self.end_position(&map);
+        let error_line = map.line_text(line_num).unwrap_or("");
 
         // Build the underline (red squiggly)
         let (r, g, b) = self.severity.color();
         let underline_code = AnsiBuilder::new().fg_rgb(r, g, b).underline().build();
 
-        // Calculate the span of the underline
+        // Columns count chars, but the terminal lays out glyphs: wide (CJK) chars
+        // take two cells and combining marks none, so pad by display width
         let underline_start = col - 1;
-        let underline_length = if line_num == self.end_line(source) {
+        let span_chars = if line_num == end_line {
             end_col - col
         } else {
-            error_line.chars().count() - underline_start
+            error_line.chars().count().saturating_sub(underline_start)
         };
-
-        // Build the underline string
         let mut underline = String::new();
-        for _ in 0..underline_start {
-            underline.push(' ');
+        for c in error_line.chars().take(underline_start) {
+            // Tabs are kept so the underline expands to the same tab stops
+            if c == '\t' {
+                underline.push('\t');
+            } else {
+                underline.extend(std::iter::repeat_n(' ', char_width(c)));
+            }
         }
+        let underline_length: usize = error_line
+            .chars()
+            .skip(underline_start)
+            .take(span_chars)
+            .map(char_width)
+            .sum();
+
         underline.push_str(&underline_code);
         for _ in 0..underline_length.max(1) {
             underline.push('^');
@@ -362,9 +382,11 @@ impl ErrorAnnotation {
     /// Renders this error annotation for HTML output with popover API.
     ///
     /// Creates semantic HTML with:
-    /// - A `<span>` element wrapping the error text
-    /// - ARIA attributes for accessibility
-    /// - A popover div with the error message
+    /// - A keyboard-focusable (`tabindex="0"`) `<span>` wrapping the error text,
+    ///   so `.error-highlight:focus-visible` can style a focus ring
+    /// - An accessible name that starts with the severity (`"Error: ..."`)
+    /// - A `<span class="visually-hidden">` giving the line and column for screen readers
+    /// - A live popover div (`aria-live`/`aria-atomic`) with the error message
     ///
     /// # Arguments
     ///
@@ -391,8 +413,27 @@ impl ErrorAnnotation {
     /// let html = annotation.render_html(source, 1);
     /// assert!(html.contains("popovertarget"));
     /// assert!(html.contains("aria-describedby"));
+    /// assert!(html.contains(r#"aria-label="Error: Type error""#));
+    /// assert!(html.contains("line 1, column 11"));
     /// ```
     pub fn render_html(&self, source: &str, error_id: usize) -> String {
+        self.render_html_with_options(source, error_id, &HtmlOptions::default())
+    }
+
+    /// [`Self::render_html`] with every class name taken from `html`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use oxc_span::Span;
+    /// use ta_lib::highlighting::HtmlOptions;
+    /// use ta_lib::highlighting::error_annotations::{ErrorAnnotation, ErrorSeverity};
+    ///
+    /// let annotation = ErrorAnnotation::new(Span::new(10, 17), "Type error".to_string(), ErrorSeverity::Error);
+    /// let html = annotation.render_html_with_options("const x = 'hello';", 1, &HtmlOptions::new().with_class_prefix("ta-"));
+    /// assert!(html.contains(r#"class="ta-error-highlight ta-error""#));
+    /// ```
+    pub fn render_html_with_options(&self, source: &str, error_id: usize, html: &HtmlOptions) -> String {
         let start = self.span.start as usize;
         let end = self.span.end as usize;
 
@@ -401,22 +442,30 @@ impl ErrorAnnotation {
         }
 
         let error_text = &source[start..end];
-        let severity_class = self.severity.css_class();
         let popover_id = format!("error-{}", error_id);
+        let accessible_name = format!("{}: {}", self.severity.title(), self.message);
+        let (line, column) = self.start_position(&SourceMap::new(source));
+        let position = format!("{} at line {}, column {}", self.severity.title(), line, column);
 
         format!(
-            r#"<span class="error-highlight {}" popovertarget="{}" aria-describedby="{}">
-  <span class="squiggle" aria-label="{}">{}</span>
+            r#"<span class="{} {}" tabindex="0" popovertarget="{}" aria-describedby="{}">
+  <span class="{}" aria-label="{}">{}</span>
+  <span class="{}">{}</span>
 </span>
-<div id="{}" popover role="alert">
-  <div class="error-message">{}</div>
+<div id="{}" popover role="alert" aria-live="assertive" aria-atomic="true">
+  <div class="{}">{}</div>
 </div>"#,
-            severity_class,
+            html.class("error-highlight"),
+            html.class(self.severity.css_class()),
             popover_id,
             popover_id,
-            self.severity.css_class(),
+            html.class("squiggle"),
+            html_escape::encode_double_quoted_attribute(&accessible_name),
             html_escape::encode_text(error_text),
+            html.class("visually-hidden"),
+            position,
             popover_id,
+            html.class("error-message"),
             html_escape::encode_text(&self.message)
         )
     }
@@ -505,6 +554,107 @@ pub fn render_errors_html(
     html_map
 }
 
+/// Renders highlighted code as HTML with each error wrapped inline around its text.
+///
+/// Unlike [`render_errors_html`], which renders each error on its own, the
+/// annotations' spans are byte offsets into the code the segments were
+/// highlighted from. Segments are split at every span boundary, so an error
+/// can start or end mid-token and cross any number of tokens or lines: each
+/// piece of its text is wrapped in an `error-highlight` span (numbered like
+/// [`render_errors_html`], in `data-error-id`) around the piece's syntax span,
+/// nesting when errors overlap. Empty spans become empty markers. Class names
+/// follow the code's [`HtmlOptions`](crate::highlighting::HtmlOptions).
+///
+/// # Examples
+///
+/// ```
+/// use oxc_span::Span;
+/// use ta_lib::highlighting::{highlight_code, HighlightOptions};
+/// use ta_lib::highlighting::error_annotations::{render_highlighted_html_with_errors, ErrorAnnotation, ErrorSeverity};
+///
+/// let code = highlight_code("let total = count + 1;", HighlightOptions::new("js")).unwrap();
+/// let errors = [ErrorAnnotation::new(Span::new(12, 17), "Unknown name".to_string(), ErrorSeverity::Error)];
+///
+/// let html = render_highlighted_html_with_errors(&code, &errors);
+/// assert!(html.contains(r#"data-error-id="1""#));
+/// ```
+pub fn render_highlighted_html_with_errors(code: &HighlightedCode, annotations: &[ErrorAnnotation]) -> String {
+    let indent = code.indent_unit.repeat(code.indent_spaces);
+    let mut output = code.html_open();
+    let mut line_start = true;
+    let mut offset = 0;
+
+    for segment in &code.segments {
+        let segment_end = offset + segment.text.len();
+        let mut boundaries = vec![offset, segment_end];
+        for annotation in annotations {
+            for boundary in [annotation.span.start as usize, annotation.span.end as usize] {
+                if boundary > offset && boundary < segment_end && segment.text.is_char_boundary(boundary - offset) {
+                    boundaries.push(boundary);
+                }
+            }
+        }
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        for piece in boundaries.windows(2) {
+            let (start, end) = (piece[0], piece[1]);
+            if line_start && code.indent_spaces > 0 {
+                output.push_str(&html_escape::encode_text(&indent));
+                line_start = false;
+            }
+            output.push_str(&empty_error_markers(annotations, start, &code.html));
+
+            let text = &segment.text[start - offset..end - offset];
+            let mut html = code.token_html(segment, &html_escape::encode_text(text));
+            // The first annotation ends up outermost
+            for (index, annotation) in annotations.iter().enumerate().rev() {
+                let span = (annotation.span.start as usize, annotation.span.end as usize);
+                if span.0 <= start && end <= span.1 && span.0 < span.1 {
+                    html = error_highlight_span(annotation, index + 1, &html, &code.html);
+                }
+            }
+            output.push_str(&html);
+
+            if text.ends_with('\n') {
+                line_start = true;
+            }
+        }
+        offset = segment_end;
+    }
+
+    output.push_str(&empty_error_markers(annotations, offset, &code.html));
+    output.push_str(HTML_CLOSE);
+    output
+}
+
+/// Inline wrapper around (part of) the text of the `error_id`th annotation
+fn error_highlight_span(annotation: &ErrorAnnotation, error_id: usize, inner: &str, html: &HtmlOptions) -> String {
+    format!(
+        r#"<span class="{} {}" data-error-id="{}" title="{}">{}</span>"#,
+        html.class("error-highlight"),
+        html.class(annotation.severity.css_class()),
+        error_id,
+        html_escape::encode_double_quoted_attribute(&annotation.message),
+        inner
+    )
+}
+
+/// Markers for the zero-length annotations at `offset`
+fn empty_error_markers(annotations: &[ErrorAnnotation], offset: usize, html: &HtmlOptions) -> String {
+    annotations
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| a.span.start == a.span.end && a.span.start as usize == offset)
+        .map(|(index, annotation)| error_highlight_span(annotation, index + 1, "", html))
+        .collect()
+}
+
+/// Terminal cells taken by `c`; control characters count as one cell
+fn char_width(c: char) -> usize {
+    c.width().unwrap_or(1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -523,6 +673,20 @@ mod tests {
         assert_eq!(ErrorSeverity::Info.css_class(), "info");
     }
 
+    #[test]
+    fn test_error_severity_label() {
+        assert_eq!(ErrorSeverity::Error.label(), "error");
+        assert_eq!(ErrorSeverity::Warning.label(), "warning");
+        assert_eq!(ErrorSeverity::Info.label(), "info");
+    }
+
+    #[test]
+    fn test_error_severity_from_miette() {
+        assert_eq!(ErrorSeverity::from(miette::Severity::Error), ErrorSeverity::Error);
+        assert_eq!(ErrorSeverity::from(miette::Severity::Warning), ErrorSeverity::Warning);
+        assert_eq!(ErrorSeverity::from(miette::Severity::Advice), ErrorSeverity::Info);
+    }
+
     #[test]
     fn test_error_annotation_new() {
         let span = Span::new(10, 15);
@@ -625,6 +789,39 @@ mod tests {
         assert!(output.contains("^")); // Underline character
     }
 
+    #[test]
+    fn test_console_underline_aligns_under_wide_characters() {
+        // `名前` and `数値` are two terminal cells per char
+        let source = "const 名前 = 数値;";
+        let start = source.find("数値").unwrap() as u32;
+        let annotation = ErrorAnnotation::new(
+            Span::new(start, start + "数値".len() as u32),
+            "Error".to_string(),
+            ErrorSeverity::Error,
+        );
+
+        let output = annotation.render_console(source);
+        let underline = output.lines().nth(1).unwrap();
+
+        let padding = underline.chars().take_while(|c| *c == ' ').count();
+        assert_eq!(padding, "const 名前 = ".chars().count() + 2);
+        assert_eq!(underline.matches('^').count(), 4);
+    }
+
+    #[test]
+    fn test_console_underline_skips_combining_marks_and_keeps_tabs() {
+        // `e\u{301}` renders as one cell: é
+        let source = "\tlet e\u{301} = x;";
+        let start = source.find('x').unwrap() as u32;
+        let annotation = ErrorAnnotation::new(Span::new(start, start + 1), "Error".to_string(), ErrorSeverity::Error);
+
+        let output = annotation.render_console(source);
+        let underline = output.lines().nth(1).unwrap();
+
+        assert!(underline.starts_with("\t        \x1b["), "{:?}", underline);
+        assert_eq!(underline.matches('^').count(), 1);
+    }
+
     #[test]
     fn test_html_rendering_has_popover() {
         let source = "const x = 'hello';";
@@ -651,7 +848,24 @@ mod tests {
         );
 
         let html = annotation.render_html(source, 1);
-        assert!(html.contains("aria-label=\"warning\""));
+        assert!(html.contains("aria-label=\"Warning: Unused\""));
+    }
+
+    #[test]
+    fn test_html_rendering_is_keyboard_and_screen_reader_accessible() {
+        let source = "let a = 1;\nconst x = \"hi\";";
+        let annotation = ErrorAnnotation::new(
+            Span::new(21, 25),
+            "Type \"string\" is not assignable".to_string(),
+            ErrorSeverity::Error,
+        );
+
+        let html = annotation.render_html(source, 7);
+        assert!(html.contains("tabindex=\"0\""));
+        assert!(html.contains("aria-live=\"assertive\""));
+        assert!(html.contains("aria-atomic=\"true\""));
+        assert!(html.contains("aria-label=\"Error: Type &quot;string&quot; is not assignable\""));
+        assert!(html.contains("<span class=\"visually-hidden\">Error at line 2, column 11</span>"));
     }
 
     #[test]
@@ -726,4 +940,59 @@ mod tests {
         assert!(json.is_ok());
         assert_eq!(json.unwrap(), "\"warning\"");
     }
+
+    fn highlighted(source: &str) -> HighlightedCode {
+        crate::highlighting::highlight_code(source, crate::highlighting::HighlightOptions::new("js")).unwrap()
+    }
+
+    /// The merged HTML with all tags removed
+    fn strip_tags(html: &str) -> String {
+        let mut text = String::new();
+        let mut in_tag = false;
+        for c in html.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                c if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        text
+    }
+
+    #[test]
+    fn test_errors_are_merged_into_highlighted_tokens() {
+        let source = "const total: number = 'ten';\n";
+        let code = highlighted(source);
+        let errors = [ErrorAnnotation::new(Span::new(22, 27), "Not a number".to_string(), ErrorSeverity::Error)];
+
+        let html = render_highlighted_html_with_errors(&code, &errors);
+
+        assert!(html.contains("<span style=\"color: rgb("), "{}", html);
+        let start = html.find(r#"<span class="error-highlight error" data-error-id="1""#).unwrap();
+        let wrapped = &html[start..];
+        assert!(strip_tags(wrapped).starts_with("'ten'"), "{}", html);
+        assert_eq!(html_escape::decode_html_entities(&strip_tags(&html)), source);
+    }
+
+    #[test]
+    fn test_error_crossing_segments_and_lines_is_split() {
+        let source = "let a = 1;\nlet b = a + c;\n";
+        let code = highlighted(source);
+        // `1;\nlet b` starts mid-line and crosses several tokens and a newline
+        let errors = [
+            ErrorAnnotation::new(Span::new(8, 16), "Spans lines".to_string(), ErrorSeverity::Warning),
+            ErrorAnnotation::new(Span::new(15, 20), "Overlaps".to_string(), ErrorSeverity::Error),
+            ErrorAnnotation::new(Span::new(24, 24), "Empty".to_string(), ErrorSeverity::Info),
+        ];
+
+        let html = render_highlighted_html_with_errors(&code, &errors);
+
+        assert_eq!(html_escape::decode_html_entities(&strip_tags(&html)), source);
+        assert!(html.matches(r#"data-error-id="1""#).count() > 1, "{}", html);
+        // `b` is covered by both errors, with the first one outermost
+        assert!(html.contains(r#"title="Spans lines"><span class="error-highlight error" data-error-id="2""#), "{}", html);
+        assert!(html.contains(r#"<span class="error-highlight info" data-error-id="3" title="Empty"></span>"#));
+        assert_eq!(html.matches("<span").count(), html.matches("</span>").count());
+    }
 }
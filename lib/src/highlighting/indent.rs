@@ -0,0 +1,156 @@
+//! Indentation style detection for code display.
+//!
+//! Code blocks are nested under error and symbol headers by one indent unit.
+//! Using the file's own unit keeps tab-indented code from being mixed with
+//! a hard-coded two-space prefix.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+/// A single level of indentation: one tab or a fixed number of spaces.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::highlighting::IndentUnit;
+///
+/// assert_eq!(IndentUnit::Tab.as_string(), "\t");
+/// assert_eq!(IndentUnit::Spaces(4).repeat(2), "        ");
+/// assert_eq!("tab".parse::<IndentUnit>(), Ok(IndentUnit::Tab));
+/// assert_eq!("4".parse::<IndentUnit>(), Ok(IndentUnit::Spaces(4)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndentUnit {
+    /// One tab character per level.
+    Tab,
+    /// The given number of spaces per level.
+    Spaces(usize),
+}
+
+impl Default for IndentUnit {
+    /// Two spaces, used when a file has no indented lines to learn from.
+    fn default() -> Self {
+        IndentUnit::Spaces(2)
+    }
+}
+
+impl IndentUnit {
+    /// The whitespace for a single level.
+    pub fn as_string(&self) -> String {
+        self.repeat(1)
+    }
+
+    /// The whitespace for `levels` levels.
+    pub fn repeat(&self, levels: usize) -> String {
+        match self {
+            IndentUnit::Tab => "\t".repeat(levels),
+            IndentUnit::Spaces(width) => " ".repeat(width * levels),
+        }
+    }
+}
+
+impl fmt::Display for IndentUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndentUnit::Tab => write!(f, "tab"),
+            IndentUnit::Spaces(width) => write!(f, "{}", width),
+        }
+    }
+}
+
+impl FromStr for IndentUnit {
+    type Err = String;
+
+    /// Parses `tab` or a positive number of spaces (the `--indent <N|tab>` syntax).
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.eq_ignore_ascii_case("tab") {
+            return Ok(IndentUnit::Tab);
+        }
+        match value.parse::<usize>() {
+            Ok(width) if width > 0 => Ok(IndentUnit::Spaces(width)),
+            _ => Err(format!("expected 'tab' or a positive number of spaces, got '{}'", value)),
+        }
+    }
+}
+
+/// Detects the predominant indentation unit of `source`.
+///
+/// Lines indented with tabs are compared against lines indented with spaces;
+/// the majority wins. For spaces, the unit is the most common increase in
+/// indentation between consecutive indented lines (ties prefer the smaller
+/// width). Falls back to [`IndentUnit::default`] when nothing is indented.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::highlighting::{detect_indent_unit, IndentUnit};
+///
+/// assert_eq!(detect_indent_unit("if (a) {\n\treturn;\n}"), IndentUnit::Tab);
+/// assert_eq!(detect_indent_unit("if (a) {\n    if (b) {\n        return;\n    }\n}"), IndentUnit::Spaces(4));
+/// assert_eq!(detect_indent_unit("const a = 1;"), IndentUnit::Spaces(2));
+/// ```
+pub fn detect_indent_unit(source: &str) -> IndentUnit {
+    let mut tab_lines = 0;
+    let mut space_lines = 0;
+    let mut steps: HashMap<usize, usize> = HashMap::new();
+    let mut previous = 0;
+
+    for line in source.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let indent = &line[..line.len() - line.trim_start().len()];
+        if indent.starts_with('\t') {
+            tab_lines += 1;
+            continue;
+        }
+
+        let width = indent.chars().take_while(|&c| c == ' ').count();
+        if width > 0 {
+            space_lines += 1;
+        }
+        if width > previous {
+            *steps.entry(width - previous).or_default() += 1;
+        }
+        previous = width;
+    }
+
+    if tab_lines > space_lines {
+        return IndentUnit::Tab;
+    }
+
+    steps
+        .into_iter()
+        .max_by(|(a_width, a_count), (b_width, b_count)| a_count.cmp(b_count).then(b_width.cmp(a_width)))
+        .map(|(width, _)| IndentUnit::Spaces(width))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_two_space_indent_with_continuation_lines() {
+        let source = "function a() {\n  if (x) {\n    call(\n        arg);\n  }\n}\n";
+        assert_eq!(detect_indent_unit(source), IndentUnit::Spaces(2));
+    }
+
+    #[test]
+    fn test_detect_tabs_outvote_stray_spaces() {
+        let source = "class A {\n\tm() {\n\t\treturn 1;\n\t}\n  // aligned comment\n}\n";
+        assert_eq!(detect_indent_unit(source), IndentUnit::Tab);
+    }
+
+    #[test]
+    fn test_parse_rejects_zero_and_words() {
+        assert!("0".parse::<IndentUnit>().is_err());
+        assert!("wide".parse::<IndentUnit>().is_err());
+        assert_eq!("TAB".parse::<IndentUnit>(), Ok(IndentUnit::Tab));
+    }
+}
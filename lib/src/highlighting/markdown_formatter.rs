@@ -4,9 +4,13 @@
 //! with the code highlighting system to provide syntax-highlighted code blocks
 //! with language indicators, titles, and visual separators.
 
-use pulldown_cmark::{CodeBlockKind, CowStr, Event, Parser, Tag, TagEnd};
+use std::collections::BTreeMap;
 
-use crate::highlighting::{highlight_code, HighlightOptions, MarkdownOptions, Result};
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+
+use crate::glyphs::Glyphs;
+use crate::highlighting::ansi::AnsiBuilder;
+use crate::highlighting::{highlight_code, HighlightOptions, MarkdownOptions, Result, MIN_SEPARATOR_WIDTH};
 use crate::output::OutputFormat;
 
 /// Formatted markdown with embedded code highlighting.
@@ -20,6 +24,10 @@ pub struct FormattedMarkdown {
 
     /// Number of code blocks found and highlighted.
     pub code_block_count: usize,
+
+    /// `key: value` pairs from leading YAML front matter, which is not rendered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub front_matter: Option<BTreeMap<String, String>>,
 }
 
 impl FormattedMarkdown {
@@ -29,9 +37,16 @@ impl FormattedMarkdown {
             output,
             format,
             code_block_count,
+            front_matter: None,
         }
     }
 
+    /// Attaches the parsed front matter.
+    pub fn with_front_matter(mut self, front_matter: Option<BTreeMap<String, String>>) -> Self {
+        self.front_matter = front_matter;
+        self
+    }
+
     /// Returns the formatted output as a string.
     pub fn as_str(&self) -> &str {
         &self.output
@@ -46,6 +61,9 @@ impl FormattedMarkdown {
 /// - Optional code block titles from info strings
 /// - Visual separators (box-drawing characters for console output)
 /// - Fallback to plain text for unknown languages
+/// - GFM strikethrough (`~~text~~`) and task list items (`- [ ] task`)
+/// - Leading YAML front matter, stripped from the output and parsed into
+///   [`FormattedMarkdown::front_matter`] (see [`split_front_matter`])
 ///
 /// # Examples
 ///
@@ -67,7 +85,12 @@ impl FormattedMarkdown {
 ///
 /// Returns an error if code highlighting fails for a code block.
 pub fn format_markdown(text: &str, options: MarkdownOptions) -> Result<FormattedMarkdown> {
-    let parser = Parser::new(text);
+    let mut parser_options = Options::empty();
+    parser_options.insert(Options::ENABLE_STRIKETHROUGH);
+    parser_options.insert(Options::ENABLE_TASKLISTS);
+
+    let (front_matter, body) = split_front_matter(text);
+    let parser = Parser::new_ext(body, parser_options);
     let mut formatter = MarkdownFormatter::new(options);
 
     for event in parser {
@@ -78,7 +101,60 @@ pub fn format_markdown(text: &str, options: MarkdownOptions) -> Result<Formatted
     let format = formatter.options.output_format;
     let output = formatter.finalize();
 
-    Ok(FormattedMarkdown::new(output, format, code_block_count))
+    Ok(FormattedMarkdown::new(output, format, code_block_count).with_front_matter(front_matter))
+}
+
+/// Splits leading YAML front matter from a markdown document.
+///
+/// Front matter must open with a `---` line at the very start of the document
+/// and close with a `---` (or `...`) line; anything else, including a `---`
+/// later in the document, is left as markdown. Only flat `key: value` pairs are
+/// parsed: surrounding quotes are removed from values, and comments, list items
+/// and nested (indented) lines are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::highlighting::markdown_formatter::split_front_matter;
+///
+/// let (front_matter, body) = split_front_matter("---\ntitle: \"Intro\"\n---\n# Hello\n");
+/// assert_eq!(front_matter.unwrap()["title"], "Intro");
+/// assert_eq!(body, "# Hello\n");
+///
+/// let (front_matter, body) = split_front_matter("# Hello\n\n---\n");
+/// assert!(front_matter.is_none());
+/// assert_eq!(body, "# Hello\n\n---\n");
+/// ```
+pub fn split_front_matter(text: &str) -> (Option<BTreeMap<String, String>>, &str) {
+    let Some(rest) = text.strip_prefix("---\n").or_else(|| text.strip_prefix("---\r\n")) else {
+        return (None, text);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\r', '\n']);
+        if content == "---" || content == "..." {
+            let front_matter = rest[..offset]
+                .lines()
+                .filter(|line| !line.starts_with([' ', '\t', '#', '-']))
+                .filter_map(|line| line.split_once(':'))
+                .map(|(key, value)| {
+                    let value = value.trim();
+                    let unquoted = value
+                        .strip_prefix('"')
+                        .and_then(|v| v.strip_suffix('"'))
+                        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                        .unwrap_or(value);
+                    (key.trim().to_string(), unquoted.to_string())
+                })
+                .collect();
+            return (Some(front_matter), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+
+    // No closing delimiter: the leading `---` is an ordinary rule
+    (None, text)
 }
 
 /// Internal markdown formatter state machine.
@@ -145,6 +221,7 @@ impl MarkdownFormatter {
             Event::SoftBreak => self.output.push(' '),
             Event::HardBreak => self.output.push('\n'),
             Event::Rule => self.handle_rule(),
+            Event::TaskListMarker(checked) => self.handle_task_list_marker(checked),
             _ => {}
         }
         Ok(())
@@ -172,18 +249,29 @@ impl MarkdownFormatter {
                 self.output.push('\n');
             }
             Tag::Item => {
-                self.output.push_str("\n  • ");
+                // Non-console formats keep Markdown's own list marker
+                let bullet = if self.options.output_format == OutputFormat::Console {
+                    self.options.glyphs.bullet
+                } else {
+                    "•"
+                };
+                self.output.push_str(&format!("\n  {} ", bullet));
             }
-            Tag::Emphasis => {
-                if self.options.output_format == OutputFormat::Html {
-                    self.output.push_str("<em>");
-                }
+            Tag::Emphasis if self.options.output_format == OutputFormat::Html => {
+                self.output.push_str("<em>");
             }
-            Tag::Strong => {
-                if self.options.output_format == OutputFormat::Html {
-                    self.output.push_str("<strong>");
-                }
+            Tag::Strong if self.options.output_format == OutputFormat::Html => {
+                self.output.push_str("<strong>");
             }
+            Tag::Strikethrough => match self.options.output_format {
+                OutputFormat::Console if self.options.plain => self.output.push_str("~~"),
+                OutputFormat::Console => {
+                    self.output
+                        .push_str(&AnsiBuilder::new().strikethrough().build());
+                }
+                OutputFormat::Html => self.output.push_str("<del>"),
+                OutputFormat::Json | OutputFormat::Markdown => self.output.push_str("~~"),
+            },
             _ => {}
         }
         Ok(())
@@ -206,16 +294,18 @@ impl MarkdownFormatter {
                 self.output.push('\n');
                 self.state = FormatterState::Prose;
             }
-            TagEnd::Emphasis => {
-                if self.options.output_format == OutputFormat::Html {
-                    self.output.push_str("</em>");
-                }
+            TagEnd::Emphasis if self.options.output_format == OutputFormat::Html => {
+                self.output.push_str("</em>");
             }
-            TagEnd::Strong => {
-                if self.options.output_format == OutputFormat::Html {
-                    self.output.push_str("</strong>");
-                }
+            TagEnd::Strong if self.options.output_format == OutputFormat::Html => {
+                self.output.push_str("</strong>");
             }
+            TagEnd::Strikethrough => match self.options.output_format {
+                OutputFormat::Console if self.options.plain => self.output.push_str("~~"),
+                OutputFormat::Console => self.output.push_str(AnsiBuilder::RESET),
+                OutputFormat::Html => self.output.push_str("</del>"),
+                OutputFormat::Json | OutputFormat::Markdown => self.output.push_str("~~"),
+            },
             _ => {}
         }
         Ok(())
@@ -238,7 +328,7 @@ impl MarkdownFormatter {
 
     fn handle_inline_code(&mut self, code: CowStr) {
         match self.options.output_format {
-            OutputFormat::Console => {
+            OutputFormat::Console | OutputFormat::Markdown => {
                 self.output.push('`');
                 self.output.push_str(&code);
                 self.output.push('`');
@@ -255,15 +345,38 @@ impl MarkdownFormatter {
         }
     }
 
+    fn handle_task_list_marker(&mut self, checked: bool) {
+        match self.options.output_format {
+            OutputFormat::Console | OutputFormat::Json | OutputFormat::Markdown => {
+                self.output.push_str(if checked { "[x] " } else { "[ ] " });
+            }
+            OutputFormat::Html => {
+                if checked {
+                    self.output
+                        .push_str("<input type=\"checkbox\" disabled checked> ");
+                } else {
+                    self.output.push_str("<input type=\"checkbox\" disabled> ");
+                }
+            }
+        }
+    }
+
+    /// Columns spanned by console separators
+    fn separator_width(&self) -> usize {
+        self.options.width.max(MIN_SEPARATOR_WIDTH)
+    }
+
     fn handle_rule(&mut self) {
+        let line = self.options.glyphs.horizontal;
         match self.options.output_format {
             OutputFormat::Console => {
-                self.output.push_str("\n\n───────────────────────────────────────\n\n");
+                let rule = line.repeat(self.separator_width());
+                self.output.push_str(&format!("\n\n{}\n\n", rule));
             }
             OutputFormat::Html => {
                 self.output.push_str("\n<hr>\n");
             }
-            OutputFormat::Json => {
+            OutputFormat::Json | OutputFormat::Markdown => {
                 self.output.push_str("\n---\n");
             }
         }
@@ -271,7 +384,7 @@ impl MarkdownFormatter {
 
     fn format_heading(&mut self, text: &str, level: usize) {
         match self.options.output_format {
-            OutputFormat::Console => {
+            OutputFormat::Console | OutputFormat::Markdown => {
                 let prefix = "#".repeat(level);
                 self.output.push_str(&prefix);
                 self.output.push(' ');
@@ -309,7 +422,7 @@ impl MarkdownFormatter {
                 self.output.push('\n');
                 self.render_code_block_html(&code, &language, title.as_deref())?;
             }
-            OutputFormat::Json => {
+            OutputFormat::Json | OutputFormat::Markdown => {
                 self.output.push_str("\n```");
                 self.output.push_str(&language);
                 if let Some(t) = title {
@@ -338,23 +451,38 @@ impl MarkdownFormatter {
             .with_line_numbers(self.options.show_line_numbers)
             .for_format(OutputFormat::Console);
 
+        let plain = self.options.plain;
+
         // Try to highlight, fall back to plain text on error
-        let highlighted = match highlight_code(code, highlight_opts) {
-            Ok(h) => h.render_console(),
-            Err(_) => code.to_string(),
+        let highlighted = if plain {
+            code.to_string()
+        } else {
+            match highlight_code(code, highlight_opts) {
+                Ok(h) => h.render_console(),
+                Err(_) => code.to_string(),
+            }
         };
 
         // Render visual separator with language and title
-        let header = format_code_block_header_console(language, title);
+        let width = self.separator_width();
+        let glyphs = self.options.glyphs;
+        let header = format_code_block_header_console(language, title, &glyphs, width);
         self.output.push_str(&header);
         self.output.push('\n');
 
         // Add the highlighted code
         self.output.push_str(&highlighted);
+        if plain && !highlighted.ends_with('\n') {
+            self.output.push('\n');
+        }
 
         // Bottom border
-        self.output
-            .push_str("└───────────────────────────────────────────────────────────┘\n");
+        self.output.push_str(&format!(
+            "{}{}{}\n",
+            glyphs.bottom_left,
+            glyphs.horizontal.repeat(width.saturating_sub(2)),
+            glyphs.bottom_right
+        ));
 
         Ok(())
     }
@@ -367,6 +495,7 @@ impl MarkdownFormatter {
     ) -> Result<()> {
         let highlight_opts = HighlightOptions::new(language)
             .with_line_numbers(self.options.show_line_numbers)
+            .with_html(self.options.html.clone())
             .for_format(OutputFormat::Html);
 
         // Try to highlight, fall back to plain text on error
@@ -379,21 +508,27 @@ impl MarkdownFormatter {
         };
 
         // Render code block with header
-        self.output.push_str("<div class=\"code-block\">\n");
+        let html = &self.options.html;
+        self.output.push_str(&format!("<div class=\"{}\">\n", html.class("code-block")));
 
         if title.is_some() || !language.is_empty() {
             self.output
-                .push_str("  <div class=\"code-block__header\">\n");
+                .push_str(&format!("  <div class=\"{}\">\n", html.class("code-block__header")));
 
             if let Some(t) = title {
-                self.output
-                    .push_str(&format!("    <span class=\"code-block__title\">{}</span>\n", t));
+                self.output.push_str(&format!(
+                    "    <span class=\"{}\">{}</span>\n",
+                    html.class("code-block__title"),
+                    t
+                ));
             }
 
             if !language.is_empty() {
                 self.output.push_str(&format!(
-                    "    <span class=\"code-block__language\" data-lang=\"{}\">{}</span>\n",
-                    language, language
+                    "    <span class=\"{}\" data-lang=\"{}\">{}</span>\n",
+                    html.class("code-block__language"),
+                    language,
+                    language
                 ));
             }
 
@@ -449,8 +584,13 @@ pub fn parse_code_block_info(kind: CodeBlockKind) -> (Option<String>, Option<Str
     }
 }
 
-/// Formats a code block header for console output using box-drawing characters.
-fn format_code_block_header_console(language: &str, title: Option<&str>) -> String {
+/// Formats a code block header for console output with the borders from `glyphs`.
+///
+/// The header is `width` columns wide unless the language and title alone are
+/// longer.
+fn format_code_block_header_console(language: &str, title: Option<&str>, glyphs: &Glyphs, width: usize) -> String {
+    let (corner_left, line, corner_right) = (glyphs.top_left, glyphs.horizontal, glyphs.top_right);
+
     let lang_part = if !language.is_empty() {
         format!(" {} ", language)
     } else {
@@ -458,22 +598,24 @@ fn format_code_block_header_console(language: &str, title: Option<&str>) -> Stri
     };
 
     let title_part = if let Some(t) = title {
-        format!(" ─ {} ", t)
+        format!(" {} {} ", line, t)
     } else {
         String::new()
     };
 
     let header_content = format!("{}{}", lang_part, title_part);
-    let padding_needed = 60_usize.saturating_sub(header_content.len() + 2);
-    let padding = "─".repeat(padding_needed);
+    // Two corners plus the line segment on either side of the content
+    let padding_needed = width.saturating_sub(header_content.chars().count() + 4);
+    let padding = line.repeat(padding_needed);
 
-    format!("┌─{}{}─┐", header_content, padding)
+    format!("{}{}{}{}{}{}", corner_left, line, header_content, padding, line, corner_right)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::output::OutputFormat;
+    use crate::highlighting::HtmlOptions;
 
     #[test]
     fn test_parse_code_block_info_with_language_and_title() {
@@ -646,6 +788,42 @@ Text after"#;
         assert!(result.output.contains("Text after"));
     }
 
+    #[test]
+    fn test_format_markdown_strips_front_matter() {
+        let markdown = "---\ntitle: Getting Started\nauthor: 'Ada'\ntags:\n  - intro\n---\n# Welcome\n\nBody text.\n";
+        let options = MarkdownOptions::new().with_plain(true);
+        let result = format_markdown(markdown, options).unwrap();
+
+        let front_matter = result.front_matter.unwrap();
+        assert_eq!(front_matter["title"], "Getting Started");
+        assert_eq!(front_matter["author"], "Ada");
+        assert_eq!(front_matter["tags"], "");
+        assert_eq!(front_matter.len(), 3);
+        assert!(result.output.contains("# Welcome"));
+        assert!(result.output.contains("Body text."));
+        assert!(!result.output.contains("title"));
+        assert!(!result.output.contains("---"));
+    }
+
+    #[test]
+    fn test_mid_document_rule_is_not_front_matter() {
+        let markdown = "Intro\n\n---\n\ntitle: not front matter\n";
+        let options = MarkdownOptions::new().for_format(OutputFormat::Html);
+        let result = format_markdown(markdown, options).unwrap();
+
+        assert!(result.front_matter.is_none());
+        assert!(result.output.contains("<hr>"));
+        assert!(result.output.contains("title: not front matter"));
+    }
+
+    #[test]
+    fn test_unclosed_front_matter_is_left_as_markdown() {
+        let (front_matter, body) = split_front_matter("---\ntitle: x\n");
+
+        assert!(front_matter.is_none());
+        assert_eq!(body, "---\ntitle: x\n");
+    }
+
     #[test]
     fn test_format_markdown_with_line_numbers() {
         let markdown = r#"```ts
@@ -664,7 +842,7 @@ function test() {
 
     #[test]
     fn test_format_code_block_header_console() {
-        let header = format_code_block_header_console("ts", Some("My Function"));
+        let header = format_code_block_header_console("ts", Some("My Function"), &Glyphs::UNICODE, 60);
         assert!(header.starts_with("┌─"));
         assert!(header.ends_with("─┐"));
         assert!(header.contains("ts"));
@@ -673,12 +851,70 @@ function test() {
 
     #[test]
     fn test_format_code_block_header_console_no_title() {
-        let header = format_code_block_header_console("js", None);
+        let header = format_code_block_header_console("js", None, &Glyphs::UNICODE, 60);
         assert!(header.starts_with("┌─"));
         assert!(header.ends_with("─┐"));
         assert!(header.contains("js"));
     }
 
+    #[test]
+    fn test_format_code_block_header_console_plain() {
+        let header = format_code_block_header_console("ts", Some("My Function"), &Glyphs::ASCII, 60);
+        assert!(header.starts_with("+-"));
+        assert!(header.ends_with("-+"));
+        assert!(header.is_ascii());
+    }
+
+    #[test]
+    fn test_separators_span_configured_width() {
+        let markdown = "Intro\n\n---\n\n```ts Example\nconst x = 1;\n```";
+
+        for plain in [false, true] {
+            let options = MarkdownOptions::new().with_plain(plain).with_width(20);
+            let result = format_markdown(markdown, options).unwrap();
+
+            let separators: Vec<&str> = result
+                .output
+                .lines()
+                .filter(|line| line.starts_with(['┌', '└', '─', '+', '-']))
+                .collect();
+            assert_eq!(separators.len(), 3, "Got: {}", result.output);
+            for line in separators {
+                assert_eq!(line.chars().count(), 20, "Bad width: {:?}", line);
+            }
+        }
+    }
+
+    #[test]
+    fn test_separator_width_is_clamped() {
+        let options = MarkdownOptions::new().with_plain(true).with_width(3);
+        let result = format_markdown("---", options).unwrap();
+        assert_eq!(result.output, "-".repeat(MIN_SEPARATOR_WIDTH));
+    }
+
+    #[test]
+    fn test_format_markdown_plain_is_ascii() {
+        let markdown = "# Title\n\n- item\n- ~~old~~\n\n---\n\n```ts Example\nconst x = 1;\n```";
+        let options = MarkdownOptions::new().with_plain(true);
+        let result = format_markdown(markdown, options).unwrap();
+
+        assert!(result.output.is_ascii(), "Got: {}", result.output);
+        assert!(result.output.contains("- ~~old~~"));
+        assert!(result.output.contains("const x = 1;"));
+    }
+
+    #[test]
+    fn test_ascii_glyphs_keep_colors() {
+        let markdown = "- item\n\n```js\nconst x = 1;\n```";
+        let options = MarkdownOptions::new().with_glyphs(Glyphs::ASCII).with_width(20);
+        let result = format_markdown(markdown, options).unwrap();
+
+        assert!(result.output.starts_with("- item"), "Got: {}", result.output);
+        assert!(result.output.contains("+- js -"), "Got: {}", result.output);
+        assert!(result.output.contains("\x1b["), "Got: {}", result.output);
+        assert!(!result.output.contains(['┌', '└', '─', '•']), "Got: {}", result.output);
+    }
+
     #[test]
     fn test_formatted_markdown_accessors() {
         let fm = FormattedMarkdown::new("output".to_string(), OutputFormat::Console, 3);
@@ -687,6 +923,63 @@ function test() {
         assert_eq!(fm.format, OutputFormat::Console);
     }
 
+    #[test]
+    fn test_format_markdown_task_list_console() {
+        let markdown = "- [x] done\n- [ ] todo";
+        let options = MarkdownOptions::default();
+        let result = format_markdown(markdown, options).unwrap();
+
+        assert!(result.output.contains("[x] done"));
+        assert!(result.output.contains("[ ] todo"));
+    }
+
+    #[test]
+    fn test_format_markdown_html_class_prefix() {
+        let markdown = "```ts Example\nconst x = 1;\n```\n";
+        let options = MarkdownOptions::new()
+            .with_html(HtmlOptions::new().with_class_prefix("docs-"))
+            .for_format(OutputFormat::Html);
+        let result = format_markdown(markdown, options).unwrap();
+
+        assert!(result.output.contains(r#"<div class="docs-code-block">"#), "{}", result.output);
+        assert!(result.output.contains(r#"<span class="docs-code-block__title">Example</span>"#));
+        assert!(result.output.contains(r#"class="docs-code-block__language""#));
+        assert!(!result.output.contains(r#"class="code-block"#));
+    }
+
+    #[test]
+    fn test_format_markdown_task_list_html() {
+        let markdown = "- [x] done\n- [ ] todo";
+        let options = MarkdownOptions::new().for_format(OutputFormat::Html);
+        let result = format_markdown(markdown, options).unwrap();
+
+        assert!(result
+            .output
+            .contains("<input type=\"checkbox\" disabled checked> done"));
+        assert!(result
+            .output
+            .contains("<input type=\"checkbox\" disabled> todo"));
+    }
+
+    #[test]
+    fn test_format_markdown_strikethrough_console() {
+        let markdown = "This is ~~gone~~ now.";
+        let options = MarkdownOptions::default();
+        let result = format_markdown(markdown, options).unwrap();
+
+        assert!(result.output.contains("\x1b[9mgone\x1b[0m"));
+        assert!(!result.output.contains("~~"));
+    }
+
+    #[test]
+    fn test_format_markdown_strikethrough_html() {
+        let markdown = "This is ~~gone~~ now.";
+        let options = MarkdownOptions::new().for_format(OutputFormat::Html);
+        let result = format_markdown(markdown, options).unwrap();
+
+        assert!(result.output.contains("<del>gone</del>"));
+    }
+
     #[test]
     fn test_malformed_markdown_graceful() {
         // Unclosed emphasis should be handled gracefully
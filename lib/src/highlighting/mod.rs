@@ -15,23 +15,36 @@ pub mod error_annotations;
 // Phase 3: Context-Aware Code Extraction
 pub mod code_context;
 
+// Indentation style detection for code display
+pub mod indent;
+
 // Phase 4: Markdown Parsing with Code Blocks
 pub mod markdown_formatter;
 
 // Re-export commonly used types from Phase 1
+pub use ansi::ansi_to_html;
 pub use error::{HighlightError, Result};
-pub use options::{HighlightOptions, MarkdownOptions};
-pub use syntect_highlighter::{highlight_code, HighlightedCode, HighlightSegment, RgbColor, SegmentStyle};
+pub use options::{HighlightOptions, HtmlOptions, MarkdownOptions, MIN_SEPARATOR_WIDTH};
+pub use syntect_highlighter::{
+    highlight_code, highlight_code_incremental, highlight_to_json, supported_languages, syntax_language, theme_stylesheet,
+    theme_stylesheet_with_options,
+    warm_caches, HighlightedCode, HighlightSegment, LanguageInfo, LineCache, RgbColor, SegmentStyle,
+};
 pub use themes::{BuiltinTheme, ThemeSource};
 
 // Re-export Phase 2 types
-pub use error_annotations::{ErrorAnnotation, ErrorSeverity, render_errors_console, render_errors_html};
+pub use error_annotations::{ErrorAnnotation, ErrorSeverity, render_errors_console, render_errors_html, render_highlighted_html_with_errors};
 
 // Re-export Phase 3 types
-pub use code_context::{extract_code_context, CodeContext, ScopeType, TruncationInfo};
+pub use code_context::{
+    extract_code_context, extract_code_context_with_lines, plain_truncation_markers, truncation_markers, CodeContext,
+    ScopeType, TruncationInfo,
+};
+
+pub use indent::{detect_indent_unit, IndentUnit};
 
 // Re-export Phase 4 types
-pub use markdown_formatter::{format_markdown, parse_code_block_info, FormattedMarkdown};
+pub use markdown_formatter::{format_markdown, parse_code_block_info, split_front_matter, FormattedMarkdown};
 
 #[cfg(test)]
 mod tests {
@@ -1,3 +1,5 @@
+use crate::glyphs::Glyphs;
+use crate::highlighting::IndentUnit;
 use crate::output::OutputFormat;
 
 /// Options for highlighting code with syntax highlighting and error annotations.
@@ -26,15 +28,27 @@ pub struct HighlightOptions {
     /// Whether to show line numbers in the output.
     pub show_line_numbers: bool,
 
-    /// Number of spaces to indent the entire code block.
+    /// Number of times [`Self::indent_unit`] prefixes each line of the code block.
     pub indent_spaces: usize,
 
+    /// Whitespace repeated `indent_spaces` times before each line (one space by default).
+    pub indent_unit: IndentUnit,
+
     /// Error spans to annotate in the code (populated in Phase 2).
     /// For Phase 1, this is a placeholder Vec<()>.
     pub error_spans: Vec<()>, // TODO: Replace with Vec<ErrorAnnotation> in Phase 2
 
     /// The output format (Console, HTML, or JSON).
     pub output_format: OutputFormat,
+
+    /// Emit `class="tok-*"` spans instead of inline styles in HTML output.
+    pub css_classes: bool,
+
+    /// Display columns after which each line is cut and marked with `…` (unset by default).
+    pub max_line_length: Option<usize>,
+
+    /// Class names used in HTML output.
+    pub html: HtmlOptions,
 }
 
 impl Default for HighlightOptions {
@@ -56,8 +70,12 @@ impl Default for HighlightOptions {
             dark_theme: None,  // Will use "base16-ocean.dark"
             show_line_numbers: false,
             indent_spaces: 0,  // No indentation by default
+            indent_unit: IndentUnit::Spaces(1),
             error_spans: Vec::new(),
             output_format: OutputFormat::Console,
+            css_classes: false,
+            max_line_length: None,
+            html: HtmlOptions::default(),
         }
     }
 }
@@ -136,6 +154,27 @@ impl HighlightOptions {
         self
     }
 
+    /// Sets the unit repeated [`Self::with_indent`] times before each line.
+    ///
+    /// Use the analyzed file's own unit (e.g. a tab) with an indent of `1` so the
+    /// block prefix matches the code's indentation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_lib::highlighting::{HighlightOptions, IndentUnit};
+    ///
+    /// let options = HighlightOptions::new("js")
+    ///     .with_indent(1)
+    ///     .with_indent_unit(IndentUnit::Tab);
+    ///
+    /// assert_eq!(options.indent_unit.repeat(options.indent_spaces), "\t");
+    /// ```
+    pub fn with_indent_unit(mut self, unit: IndentUnit) -> Self {
+        self.indent_unit = unit;
+        self
+    }
+
     /// Sets the output format.
     ///
     /// # Examples
@@ -153,6 +192,57 @@ impl HighlightOptions {
         self.output_format = format;
         self
     }
+
+    /// Sets whether HTML output uses CSS classes (`tok-keyword`, `tok-string`, ...)
+    /// instead of inline styles, so it can be themed with a stylesheet
+    /// (see [`theme_stylesheet`](crate::highlighting::theme_stylesheet)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_lib::highlighting::HighlightOptions;
+    ///
+    /// let options = HighlightOptions::new("js").with_css_classes(true);
+    /// assert!(options.css_classes);
+    /// ```
+    pub fn with_css_classes(mut self, enabled: bool) -> Self {
+        self.css_classes = enabled;
+        self
+    }
+
+    /// Sets the display width at which each rendered line is truncated.
+    ///
+    /// Longer lines (e.g. minified code) are cut at `max` columns and end in a
+    /// dimmed `…`. Only the code is measured: the indent and any escape codes or
+    /// markup added by styling are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_lib::highlighting::HighlightOptions;
+    ///
+    /// let options = HighlightOptions::new("js").with_max_line_length(Some(120));
+    /// assert_eq!(options.max_line_length, Some(120));
+    /// ```
+    pub fn with_max_line_length(mut self, max: Option<usize>) -> Self {
+        self.max_line_length = max;
+        self
+    }
+
+    /// Sets the class names used in HTML output (e.g. a prefix for every class).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_lib::highlighting::{HighlightOptions, HtmlOptions};
+    ///
+    /// let options = HighlightOptions::new("js").with_html(HtmlOptions::new().with_class_prefix("ta-"));
+    /// assert_eq!(options.html.class("tok-keyword"), "ta-tok-keyword");
+    /// ```
+    pub fn with_html(mut self, html: HtmlOptions) -> Self {
+        self.html = html;
+        self
+    }
 }
 
 /// Options for formatting markdown with embedded code highlighting.
@@ -178,10 +268,27 @@ pub struct MarkdownOptions {
     /// Whether to show line numbers in code blocks.
     pub show_line_numbers: bool,
 
+    /// Render console output as plain ASCII (no colors or box-drawing characters).
+    pub plain: bool,
+
+    /// Borders, rules and bullets drawn in console output.
+    pub glyphs: Glyphs,
+
     /// The output format (Console, HTML, or JSON).
     pub output_format: OutputFormat,
+
+    /// Console width in columns that code block borders and rules span.
+    ///
+    /// Values below [`MIN_SEPARATOR_WIDTH`] are raised to it when rendering.
+    pub width: usize,
+
+    /// Class names used in HTML output, including highlighted code blocks.
+    pub html: HtmlOptions,
 }
 
+/// Narrowest console separator drawn, so headers always fit their corners and language.
+pub const MIN_SEPARATOR_WIDTH: usize = 20;
+
 impl Default for MarkdownOptions {
     /// Creates default markdown formatting options.
     ///
@@ -198,7 +305,11 @@ impl Default for MarkdownOptions {
             code_light_theme: None,
             code_dark_theme: None,
             show_line_numbers: false,
+            plain: false,
+            glyphs: Glyphs::UNICODE,
             output_format: OutputFormat::Console,
+            width: crate::output::DEFAULT_WIDTH,
+            html: HtmlOptions::default(),
         }
     }
 }
@@ -253,6 +364,69 @@ impl MarkdownOptions {
         self
     }
 
+    /// Sets whether console output should be plain ASCII.
+    ///
+    /// Plain output skips syntax highlighting and replaces box-drawing
+    /// separators and bullets with ASCII equivalents, which keeps log files
+    /// and CI transcripts readable. Enabling it also selects [`Glyphs::ASCII`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_lib::glyphs::Glyphs;
+    /// use ta_lib::highlighting::MarkdownOptions;
+    ///
+    /// let options = MarkdownOptions::new()
+    ///     .with_plain(true);
+    ///
+    /// assert!(options.plain);
+    /// assert_eq!(options.glyphs, Glyphs::ASCII);
+    /// ```
+    pub fn with_plain(mut self, plain: bool) -> Self {
+        self.plain = plain;
+        if plain {
+            self.glyphs = Glyphs::ASCII;
+        }
+        self
+    }
+
+    /// Sets the borders, rules and bullets drawn in console output.
+    ///
+    /// Unlike [`Self::with_plain`], colors and syntax highlighting are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_lib::glyphs::Glyphs;
+    /// use ta_lib::highlighting::MarkdownOptions;
+    ///
+    /// let options = MarkdownOptions::new()
+    ///     .with_glyphs(Glyphs::ASCII);
+    ///
+    /// assert!(!options.plain);
+    /// ```
+    pub fn with_glyphs(mut self, glyphs: Glyphs) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Sets the console width that code block borders and rules span.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_lib::highlighting::MarkdownOptions;
+    ///
+    /// let options = MarkdownOptions::new()
+    ///     .with_width(120);
+    ///
+    /// assert_eq!(options.width, 120);
+    /// ```
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
     /// Sets the output format.
     ///
     /// # Examples
@@ -270,6 +444,62 @@ impl MarkdownOptions {
         self.output_format = format;
         self
     }
+
+    /// Sets the class names used in HTML output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_lib::highlighting::{HtmlOptions, MarkdownOptions};
+    ///
+    /// let options = MarkdownOptions::new()
+    ///     .with_html(HtmlOptions::new().with_class_prefix("docs-"));
+    ///
+    /// assert_eq!(options.html.class_prefix, "docs-");
+    /// ```
+    pub fn with_html(mut self, html: HtmlOptions) -> Self {
+        self.html = html;
+        self
+    }
+}
+
+/// Options shared by every HTML renderer.
+///
+/// The classes emitted in HTML output (`error-highlight`, `code-block`,
+/// `symbol-name`, ...) are generic enough to collide with a host page's
+/// styles; a prefix namespaces all of them.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::highlighting::HtmlOptions;
+///
+/// let html = HtmlOptions::new().with_class_prefix("ta-");
+/// assert_eq!(html.class("code-block"), "ta-code-block");
+/// assert_eq!(HtmlOptions::default().class("code-block"), "code-block");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlOptions {
+    /// Prepended to every emitted class name (empty by default).
+    pub class_prefix: String,
+}
+
+impl HtmlOptions {
+    /// Creates HTML options that emit class names unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the prefix prepended to every emitted class name.
+    pub fn with_class_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.class_prefix = prefix.into();
+        self
+    }
+
+    /// `name` with the class prefix applied.
+    pub fn class(&self, name: &str) -> String {
+        format!("{}{}", self.class_prefix, name)
+    }
 }
 
 #[cfg(test)]
@@ -354,6 +584,13 @@ mod tests {
         assert!(options.show_line_numbers);
     }
 
+    #[test]
+    fn test_markdown_options_with_plain() {
+        let options = MarkdownOptions::new().with_plain(true);
+        assert!(options.plain);
+        assert!(!MarkdownOptions::default().plain);
+    }
+
     #[test]
     fn test_markdown_options_for_format() {
         let options = MarkdownOptions::new().for_format(OutputFormat::Html);
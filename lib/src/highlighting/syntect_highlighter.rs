@@ -1,13 +1,15 @@
 use crate::highlighting::ansi::AnsiBuilder;
 use crate::highlighting::error::{HighlightError, Result};
-use crate::highlighting::options::HighlightOptions;
+use crate::highlighting::options::{HighlightOptions, HtmlOptions};
+use crate::highlighting::indent::IndentUnit;
 use crate::highlighting::themes::get_theme_by_name;
 use crate::output::OutputFormat;
 use serde::Serialize;
-use syntect::easy::HighlightLines;
-use syntect::highlighting::{Color, FontStyle, Style};
-use syntect::parsing::SyntaxSet;
+use std::sync::OnceLock;
+use syntect::highlighting::{Color, FontStyle, Highlighter, Style};
+use syntect::parsing::{ParseState, Scope, ScopeStack, SyntaxReference, SyntaxSet};
 use syntect::util::LinesWithEndings;
+use unicode_width::UnicodeWidthChar;
 
 /// A segment of highlighted code with styling information.
 ///
@@ -26,6 +28,10 @@ pub struct HighlightSegment {
 
     /// The column number (1-indexed).
     pub column: usize,
+
+    /// Token class derived from the scope stack (e.g. `tok-keyword`), if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
 }
 
 /// Style information for a code segment.
@@ -83,8 +89,49 @@ pub struct HighlightedCode {
     /// The theme used for highlighting.
     pub theme: String,
 
-    /// Number of spaces to indent each line.
+    /// Number of indent units prefixed to each line.
     pub indent_spaces: usize,
+
+    /// Whitespace repeated `indent_spaces` times before each line.
+    #[serde(skip)]
+    pub indent_unit: IndentUnit,
+
+    /// Render HTML with `tok-*` classes instead of inline styles.
+    #[serde(skip)]
+    pub css_classes: bool,
+
+    /// Display columns after which rendered lines are cut, see [`HighlightOptions::with_max_line_length`].
+    #[serde(skip)]
+    pub max_line_length: Option<usize>,
+
+    /// Class names used by [`Self::render_html`].
+    #[serde(skip)]
+    pub html: HtmlOptions,
+
+    /// Per-line parser state, letting [`highlight_code_incremental`] resume mid-file.
+    #[serde(skip)]
+    pub line_cache: Option<LineCache>,
+}
+
+/// The source lines of a [`HighlightedCode`] and syntect's state at the start of each.
+///
+/// syntect highlights line by line, carrying a parse state and scope stack from
+/// one line to the next, so a line's highlighting depends on everything above it.
+/// Keeping the state at every line boundary lets an edit be re-highlighted from
+/// the first changed line instead of from the top.
+#[derive(Debug, Clone)]
+pub struct LineCache {
+    /// Each line, including its line ending
+    lines: Vec<String>,
+    /// State before each line, plus the state after the last one (`lines.len() + 1` entries)
+    states: Vec<LineState>,
+}
+
+/// syntect's state between two lines.
+#[derive(Debug, Clone, PartialEq)]
+struct LineState {
+    parse_state: ParseState,
+    stack: ScopeStack,
 }
 
 impl HighlightedCode {
@@ -99,14 +146,21 @@ impl HighlightedCode {
     /// #     line_count: 1,
     /// #     language: "typescript".to_string(),
     /// #     theme: "Solarized (light)".to_string(),
+    /// #     indent_spaces: 0,
+    /// #     indent_unit: ta_lib::highlighting::IndentUnit::Spaces(1),
+    /// #     css_classes: false,
+    /// #     max_line_length: None,
+    /// #     html: Default::default(),
+    /// #     line_cache: None,
     /// # };
     /// let console_output = code.render_console();
     /// // Contains ANSI escape codes like \x1b[38;2;R;G;Bm
     /// ```
     pub fn render_console(&self) -> String {
         let mut output = String::new();
-        let indent = " ".repeat(self.indent_spaces);
+        let indent = self.indent_unit.repeat(self.indent_spaces);
         let mut line_start = true;
+        let mut clipper = LineClipper::new(self.max_line_length);
 
         for segment in &self.segments {
             // Add indentation at the start of each new line
@@ -115,7 +169,9 @@ impl HighlightedCode {
                 line_start = false;
             }
 
-            if let Some(fg) = segment.style.foreground {
+            // Segments past the cut are dropped along with their styling
+            let clipped = clipper.clip(&segment.text);
+            if let (Some(fg), false) = (segment.style.foreground, clipped.text.is_empty()) {
                 let mut builder = AnsiBuilder::new().fg_rgb(fg.r, fg.g, fg.b);
 
                 if segment.style.bold {
@@ -129,11 +185,17 @@ impl HighlightedCode {
                 }
 
                 output.push_str(&builder.build());
-                output.push_str(&segment.text);
+                output.push_str(clipped.text);
                 output.push_str(AnsiBuilder::RESET);
             } else {
-                output.push_str(&segment.text);
+                output.push_str(clipped.text);
+            }
+            if clipped.cut {
+                output.push_str(&AnsiBuilder::new().dim().build());
+                output.push_str(ELLIPSIS);
+                output.push_str(AnsiBuilder::RESET);
             }
+            output.push_str(clipped.line_end);
 
             // Check if this segment ends with a newline
             if segment.text.ends_with('\n') {
@@ -155,51 +217,37 @@ impl HighlightedCode {
     /// #     line_count: 1,
     /// #     language: "typescript".to_string(),
     /// #     theme: "Solarized (light)".to_string(),
+    /// #     indent_spaces: 0,
+    /// #     indent_unit: ta_lib::highlighting::IndentUnit::Spaces(1),
+    /// #     css_classes: false,
+    /// #     max_line_length: None,
+    /// #     html: Default::default(),
+    /// #     line_cache: None,
     /// # };
     /// let html_output = code.render_html();
     /// // Contains <span> elements with inline styles
     /// ```
     pub fn render_html(&self) -> String {
-        let indent = " ".repeat(self.indent_spaces);
-        let mut output = String::from("<pre><code>");
+        let indent = self.indent_unit.repeat(self.indent_spaces);
+        let mut output = self.html_open();
         let mut line_start = true;
+        let mut clipper = LineClipper::new(self.max_line_length);
 
         for segment in &self.segments {
-            let text = html_escape::encode_text(&segment.text);
-
             // Add indentation at line start
             if line_start && self.indent_spaces > 0 {
                 output.push_str(&html_escape::encode_text(&indent));
                 line_start = false;
             }
 
-            if segment.style.foreground.is_some() || segment.style.bold || segment.style.italic {
-                let mut style_parts = Vec::new();
-
-                if let Some(fg) = segment.style.foreground {
-                    style_parts.push(format!("color: rgb({}, {}, {})", fg.r, fg.g, fg.b));
-                }
-
-                if segment.style.bold {
-                    style_parts.push("font-weight: bold".to_string());
-                }
-
-                if segment.style.italic {
-                    style_parts.push("font-style: italic".to_string());
-                }
-
-                if segment.style.underline {
-                    style_parts.push("text-decoration: underline".to_string());
-                }
-
-                output.push_str(&format!(
-                    r#"<span style="{}">{}</span>"#,
-                    style_parts.join("; "),
-                    text
-                ));
-            } else {
-                output.push_str(text.as_ref());
+            let clipped = clipper.clip(&segment.text);
+            if !clipped.text.is_empty() {
+                output.push_str(&self.token_html(segment, &html_escape::encode_text(clipped.text)));
+            }
+            if clipped.cut {
+                output.push_str(&format!(r#"<span style="opacity: 0.5">{}</span>"#, ELLIPSIS));
             }
+            output.push_str(clipped.line_end);
 
             // Check if this segment ends with a newline
             if segment.text.ends_with('\n') {
@@ -207,9 +255,200 @@ impl HighlightedCode {
             }
         }
 
-        output.push_str("</code></pre>");
+        output.push_str(HTML_CLOSE);
         output
     }
+
+    /// Opening `<pre><code>` of [`Self::render_html`], with the stylesheet root
+    /// class in CSS-class mode.
+    pub(crate) fn html_open(&self) -> String {
+        if self.css_classes {
+            format!(r#"<pre class="{}"><code>"#, self.html.class(STYLESHEET_ROOT_CLASS))
+        } else {
+            String::from("<pre><code>")
+        }
+    }
+
+    /// `text` (already HTML-escaped, possibly part of `segment`) wrapped in the
+    /// segment's inline style, or its `tok-*` class in CSS-class mode.
+    pub(crate) fn token_html(&self, segment: &HighlightSegment, text: &str) -> String {
+        if self.css_classes {
+            return match &segment.class {
+                Some(class) => format!(r#"<span class="{}">{}</span>"#, self.html.class(class), text),
+                None => text.to_string(),
+            };
+        }
+
+        if segment.style.foreground.is_none() && !segment.style.bold && !segment.style.italic {
+            return text.to_string();
+        }
+
+        let mut style_parts = Vec::new();
+
+        if let Some(fg) = segment.style.foreground {
+            style_parts.push(format!("color: rgb({}, {}, {})", fg.r, fg.g, fg.b));
+        }
+
+        if segment.style.bold {
+            style_parts.push("font-weight: bold".to_string());
+        }
+
+        if segment.style.italic {
+            style_parts.push("font-style: italic".to_string());
+        }
+
+        if segment.style.underline {
+            style_parts.push("text-decoration: underline".to_string());
+        }
+
+        format!(r#"<span style="{}">{}</span>"#, style_parts.join("; "), text)
+    }
+}
+
+/// Marker appended to lines cut by [`HighlightOptions::with_max_line_length`]
+const ELLIPSIS: &str = "…";
+
+/// Cuts rendered lines at a maximum display width, tracking the width of the
+/// current line across the segments it is split into.
+struct LineClipper {
+    max: Option<usize>,
+    /// Display columns of the current line rendered so far; past `max` once cut
+    column: usize,
+}
+
+/// The part of one segment that is rendered
+struct Clipped<'t> {
+    /// Text within the limit, without the line ending when the line is clipped
+    text: &'t str,
+    /// The line is cut inside this segment, so the ellipsis follows `text`
+    cut: bool,
+    /// The line ending that ends this segment (empty when unlimited, as it stays in `text`)
+    line_end: &'t str,
+}
+
+impl LineClipper {
+    fn new(max: Option<usize>) -> Self {
+        Self { max, column: 0 }
+    }
+
+    /// `text` is one highlighted segment, which never spans more than the end of a line
+    fn clip<'t>(&mut self, text: &'t str) -> Clipped<'t> {
+        let Some(max) = self.max else {
+            return Clipped { text, cut: false, line_end: "" };
+        };
+        let body = text.trim_end_matches(['\r', '\n']);
+        let line_end = &text[body.len()..];
+
+        let mut end = body.len();
+        let mut cut = false;
+        if self.column > max {
+            end = 0;
+        } else {
+            for (index, c) in body.char_indices() {
+                let width = c.width().unwrap_or(0);
+                if self.column + width > max {
+                    end = index;
+                    cut = true;
+                    self.column = max + 1;
+                    break;
+                }
+                self.column += width;
+            }
+        }
+        if !line_end.is_empty() {
+            self.column = 0;
+        }
+
+        Clipped { text: &body[..end], cut, line_end }
+    }
+}
+
+/// Closing tags of [`HighlightedCode::render_html`]
+pub(crate) const HTML_CLOSE: &str = "</code></pre>";
+
+/// Class on the `<pre>` emitted in CSS-class mode; scopes [`theme_stylesheet`] rules.
+pub const STYLESHEET_ROOT_CLASS: &str = "ta-highlight";
+
+/// Top-level TextMate scope names mapped to `tok-<name>` classes.
+const TOKEN_CATEGORIES: [&str; 11] = [
+    "comment", "string", "constant", "keyword", "storage", "entity",
+    "variable", "support", "punctuation", "invalid", "markup",
+];
+
+/// Class for the innermost scope on the stack whose top-level name is a known category.
+fn token_class(stack: &ScopeStack) -> Option<String> {
+    stack.as_slice().iter().rev().find_map(|scope| {
+        let name = scope.build_string();
+        let category = name.split('.').next()?;
+        TOKEN_CATEGORIES.contains(&category).then(|| format!("tok-{}", category))
+    })
+}
+
+/// Generates a stylesheet for CSS-class highlighting output using a theme's colors.
+///
+/// Rules are scoped under `.ta-highlight` and cover every `tok-*` class that
+/// [`HighlightOptions::with_css_classes`] can emit.
+///
+/// # Errors
+///
+/// Returns `HighlightError` if the theme cannot be found.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::highlighting::theme_stylesheet;
+///
+/// let css = theme_stylesheet("Solarized (light)")?;
+/// assert!(css.contains(".ta-highlight .tok-keyword"));
+/// # Ok::<(), ta_lib::highlighting::error::HighlightError>(())
+/// ```
+pub fn theme_stylesheet(theme_name: &str) -> Result<String> {
+    theme_stylesheet_with_options(theme_name, &HtmlOptions::default())
+}
+
+/// [`theme_stylesheet`] with its selectors matching HTML rendered with `html`
+/// (e.g. `.ta-ta-highlight .ta-tok-keyword` for a `ta-` class prefix).
+///
+/// # Errors
+///
+/// Returns `HighlightError` if the theme cannot be found.
+pub fn theme_stylesheet_with_options(theme_name: &str, html: &HtmlOptions) -> Result<String> {
+    let root_class = html.class(STYLESHEET_ROOT_CLASS);
+    let theme = get_theme_by_name(theme_name)?;
+    let highlighter = Highlighter::new(&theme);
+    let css_color = |c: Color| format!("rgb({}, {}, {})", c.r, c.g, c.b);
+
+    let mut css = String::new();
+    let mut root = Vec::new();
+    if let Some(fg) = theme.settings.foreground {
+        root.push(format!("color: {}", css_color(fg)));
+    }
+    if let Some(bg) = theme.settings.background {
+        root.push(format!("background-color: {}", css_color(bg)));
+    }
+    css.push_str(&format!(".{} {{ {} }}\n", root_class, root.join("; ")));
+
+    for category in TOKEN_CATEGORIES {
+        let scope = Scope::new(category).map_err(|e| HighlightError::SyntectError(e.to_string()))?;
+        let style = convert_style(highlighter.style_for_stack(&[scope]));
+
+        let mut rules = Vec::new();
+        if let Some(fg) = style.foreground {
+            rules.push(format!("color: rgb({}, {}, {})", fg.r, fg.g, fg.b));
+        }
+        if style.bold {
+            rules.push("font-weight: bold".to_string());
+        }
+        if style.italic {
+            rules.push("font-style: italic".to_string());
+        }
+        if style.underline {
+            rules.push("text-decoration: underline".to_string());
+        }
+        css.push_str(&format!(".{} .{} {{ {} }}\n", root_class, html.class(&format!("tok-{}", category)), rules.join("; ")));
+    }
+
+    Ok(css)
 }
 
 /// Highlights code using syntect with the given options.
@@ -236,7 +475,131 @@ impl HighlightedCode {
 /// # Ok::<(), ta_lib::highlighting::error::HighlightError>(())
 /// ```
 pub fn highlight_code(code: &str, options: HighlightOptions) -> Result<HighlightedCode> {
-    // Enforce maximum code block size
+    check_code_size(code)?;
+    let (syntax, theme_name) = resolve_syntax_and_theme(&options)?;
+    let theme = get_theme_by_name(theme_name)?;
+
+    // Highlight the code, tracking the scope stack so each segment gets both
+    // its themed style and a token class
+    let highlighter = Highlighter::new(&theme);
+    let mut state = LineState {
+        parse_state: ParseState::new(syntax),
+        stack: ScopeStack::new(),
+    };
+    let mut segments = Vec::new();
+    let mut cache = LineCache { lines: Vec::new(), states: Vec::new() };
+
+    for (line_idx, line) in LinesWithEndings::from(code).enumerate() {
+        cache.lines.push(line.to_string());
+        cache.states.push(state.clone());
+        highlight_line(line, line_idx + 1, &mut state, &highlighter, &mut segments)?;
+    }
+    cache.states.push(state);
+
+    Ok(finish(segments, cache, theme_name, &options))
+}
+
+/// Re-highlights `code` after an edit, reusing the unchanged parts of `previous`.
+///
+/// Lines before the first changed line keep their segments, and syntect resumes
+/// from the parse state cached at that line. After the edit, once a line matches
+/// the corresponding line of `previous` *and* syntect's state there is the same
+/// as it was, the rest of `previous` is reused with its line numbers shifted.
+/// The state check matters: an edit that opens a block comment or string changes
+/// how every following line is highlighted even though their text is the same.
+///
+/// Falls back to a full [`highlight_code`] when `previous` was highlighted with
+/// a different language or theme, or carries no line cache.
+///
+/// # Errors
+///
+/// Returns the same errors as [`highlight_code`].
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::highlighting::{highlight_code, highlight_code_incremental, HighlightOptions};
+///
+/// let before = highlight_code("const a = 1;\nconst b = 2;\n", HighlightOptions::new("js"))?;
+/// let after = highlight_code_incremental(&before, "const a = 1;\nlet b = 3;\n", HighlightOptions::new("js"))?;
+/// assert_eq!(after.line_count, 2);
+/// # Ok::<(), ta_lib::highlighting::error::HighlightError>(())
+/// ```
+pub fn highlight_code_incremental(
+    previous: &HighlightedCode,
+    code: &str,
+    options: HighlightOptions,
+) -> Result<HighlightedCode> {
+    check_code_size(code)?;
+    let (_, theme_name) = resolve_syntax_and_theme(&options)?;
+    let prev_cache = match &previous.line_cache {
+        Some(cache) if previous.language == options.language && previous.theme == theme_name => cache,
+        _ => return highlight_code(code, options),
+    };
+
+    let lines: Vec<&str> = LinesWithEndings::from(code).collect();
+    let prev_lines = &prev_cache.lines;
+
+    // First line that differs; everything before it is reused as-is
+    let first_changed = lines
+        .iter()
+        .zip(prev_lines)
+        .take_while(|(new, old)| *new == *old)
+        .count();
+    // Lines at or past `resume_from` are identical to the tail of `previous`
+    let common_suffix = lines[first_changed..]
+        .iter()
+        .rev()
+        .zip(prev_lines[first_changed..].iter().rev())
+        .take_while(|(new, old)| **new == old.as_str())
+        .count();
+    let resume_from = lines.len() - common_suffix;
+
+    let theme = get_theme_by_name(theme_name)?;
+    let highlighter = Highlighter::new(&theme);
+
+    let mut segments: Vec<HighlightSegment> = previous
+        .segments
+        .iter()
+        .take_while(|segment| segment.line <= first_changed)
+        .cloned()
+        .collect();
+    let mut cache = LineCache {
+        lines: prev_lines[..first_changed].to_vec(),
+        states: prev_cache.states[..first_changed].to_vec(),
+    };
+    let mut state = prev_cache.states[first_changed].clone();
+
+    for (line_idx, line) in lines.iter().enumerate().skip(first_changed) {
+        if line_idx >= resume_from {
+            // Same text as `previous` from here on; reuse it if syntect agrees on the state
+            let prev_idx = line_idx + prev_lines.len() - lines.len();
+            if prev_cache.states[prev_idx] == state {
+                let shift = |line: usize| line + line_idx - prev_idx;
+                segments.extend(
+                    previous
+                        .segments
+                        .iter()
+                        .filter(|segment| segment.line > prev_idx)
+                        .map(|segment| HighlightSegment { line: shift(segment.line), ..segment.clone() }),
+                );
+                cache.lines.extend_from_slice(&prev_lines[prev_idx..]);
+                cache.states.extend_from_slice(&prev_cache.states[prev_idx..]);
+                return Ok(finish(segments, cache, theme_name, &options));
+            }
+        }
+
+        cache.lines.push(line.to_string());
+        cache.states.push(state.clone());
+        highlight_line(line, line_idx + 1, &mut state, &highlighter, &mut segments)?;
+    }
+    cache.states.push(state);
+
+    Ok(finish(segments, cache, theme_name, &options))
+}
+
+/// Rejects code blocks over the 10,000 line limit.
+fn check_code_size(code: &str) -> Result<()> {
     let line_count = code.lines().count();
     if line_count > 10_000 {
         return Err(HighlightError::CodeBlockTooLarge {
@@ -244,9 +607,12 @@ pub fn highlight_code(code: &str, options: HighlightOptions) -> Result<Highlight
             max: 10_000,
         });
     }
+    Ok(())
+}
 
-    // Load syntax set
-    let syntax_set = SyntaxSet::load_defaults_newlines();
+/// Finds the syntax for `options.language` and the theme name for its output format.
+fn resolve_syntax_and_theme(options: &HighlightOptions) -> Result<(&'static SyntaxReference, &str)> {
+    let syntax_set = syntax_set();
 
     // Find syntax for the language
     // Try extension first (e.g., "ts", "rs", "py"), then token (e.g., "TypeScript")
@@ -255,9 +621,8 @@ pub fn highlight_code(code: &str, options: HighlightOptions) -> Result<Highlight
         .or_else(|| syntax_set.find_syntax_by_token(&options.language))
         .ok_or_else(|| HighlightError::UnsupportedLanguage(options.language.clone()))?;
 
-    // Load theme
     let theme_name = match options.output_format {
-        OutputFormat::Console | OutputFormat::Json => {
+        OutputFormat::Console | OutputFormat::Json | OutputFormat::Markdown => {
             options.dark_theme.as_deref().unwrap_or("base16-ocean.dark")
         }
         OutputFormat::Html => {
@@ -265,40 +630,184 @@ pub fn highlight_code(code: &str, options: HighlightOptions) -> Result<Highlight
         }
     };
 
-    let theme = get_theme_by_name(theme_name)?;
-
-    // Highlight the code
-    let mut highlighter = HighlightLines::new(syntax, &theme);
-    let mut segments = Vec::new();
-
-    for (line_idx, line) in LinesWithEndings::from(code).enumerate() {
-        let line_num = line_idx + 1;
-
-        let highlighted = highlighter
-            .highlight_line(line, &syntax_set)
-            .map_err(|e| HighlightError::SyntectError(e.to_string()))?;
+    Ok((syntax, theme_name))
+}
 
-        let mut column = 1;
-        for (style, text) in highlighted {
-            let segment = HighlightSegment {
+/// Highlights a single line, advancing `state` to the start of the next line.
+fn highlight_line(
+    line: &str,
+    line_num: usize,
+    state: &mut LineState,
+    highlighter: &Highlighter,
+    segments: &mut Vec<HighlightSegment>,
+) -> Result<()> {
+    let ops = state
+        .parse_state
+        .parse_line(line, syntax_set())
+        .map_err(|e| HighlightError::SyntectError(e.to_string()))?;
+
+    let mut column = 1;
+    let mut pos = 0;
+    let boundaries = ops.iter().map(|(i, op)| (*i, Some(op))).chain(std::iter::once((line.len(), None)));
+    for (end, op) in boundaries {
+        if end > pos {
+            let text = &line[pos..end];
+            segments.push(HighlightSegment {
                 text: text.to_string(),
-                style: convert_style(style),
+                style: convert_style(highlighter.style_for_stack(state.stack.as_slice())),
                 line: line_num,
                 column,
-            };
-
+                class: token_class(&state.stack),
+            });
             column += text.chars().count();
-            segments.push(segment);
+            pos = end;
+        }
+        if let Some(op) = op {
+            state.stack.apply(op).map_err(|e| HighlightError::SyntectError(e.to_string()))?;
         }
     }
 
-    Ok(HighlightedCode {
+    Ok(())
+}
+
+fn finish(segments: Vec<HighlightSegment>, cache: LineCache, theme_name: &str, options: &HighlightOptions) -> HighlightedCode {
+    HighlightedCode {
         segments,
-        line_count,
+        line_count: cache.lines.len(),
         language: options.language.clone(),
         theme: theme_name.to_string(),
         indent_spaces: options.indent_spaces,
-    })
+        indent_unit: options.indent_unit,
+        css_classes: options.css_classes,
+        max_line_length: options.max_line_length,
+        html: options.html.clone(),
+        line_cache: Some(cache),
+    }
+}
+
+/// The bundled syntax definitions, loaded once and shared by every call.
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// Loads the bundled syntax definitions and themes now instead of on the first
+/// highlight, so later highlighting in this process does not pay for it.
+///
+/// Nothing is persisted: every process loads them again. `ta preflight` calls
+/// this to time the load.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::highlighting::warm_caches;
+///
+/// warm_caches();
+/// ```
+pub fn warm_caches() {
+    syntax_set();
+    super::themes::default_theme_set();
+}
+
+/// A language that [`highlight_code`] can highlight.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct LanguageInfo {
+    /// Display name of the grammar (e.g. `"Rust"`).
+    pub name: String,
+    /// File extensions the grammar is selected by, without the leading dot.
+    pub extensions: Vec<String>,
+    /// Value to pass as the highlighting language (e.g. `HighlightOptions::new(token)`).
+    pub token: String,
+}
+
+/// Lists every language the highlighter supports, sorted by name.
+///
+/// Backed by the same syntax set as [`highlight_code`]. TypeScript is listed
+/// with its own extensions even though it is highlighted with the JavaScript
+/// grammar (see [`syntax_language`]).
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::highlighting::supported_languages;
+///
+/// let languages = supported_languages();
+/// let rust = languages.iter().find(|l| l.name == "Rust").unwrap();
+/// assert_eq!(rust.token, "rs");
+/// assert!(languages.iter().any(|l| l.extensions.contains(&"ts".to_string())));
+/// ```
+pub fn supported_languages() -> Vec<LanguageInfo> {
+    let mut languages: Vec<LanguageInfo> = syntax_set()
+        .syntaxes()
+        .iter()
+        .filter(|syntax| !syntax.hidden)
+        .map(|syntax| LanguageInfo {
+            name: syntax.name.clone(),
+            extensions: syntax.file_extensions.clone(),
+            token: syntax
+                .file_extensions
+                .first()
+                .cloned()
+                .unwrap_or_else(|| syntax.name.to_lowercase()),
+        })
+        .collect();
+
+    if !languages.iter().any(|l| l.extensions.iter().any(|ext| ext == "ts")) {
+        languages.push(LanguageInfo {
+            name: "TypeScript".to_string(),
+            extensions: ["ts", "tsx", "mts", "cts"].map(String::from).to_vec(),
+            token: "ts".to_string(),
+        });
+    }
+
+    languages.sort_by_key(|l| l.name.to_lowercase());
+    languages
+}
+
+/// Maps a user-facing language name to the syntax name syntect understands.
+///
+/// TypeScript variants map to `js`; other names pass through unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::highlighting::syntect_highlighter::syntax_language;
+///
+/// assert_eq!(syntax_language("tsx"), "js");
+/// assert_eq!(syntax_language("rs"), "rs");
+/// ```
+pub fn syntax_language(language: &str) -> &str {
+    match language {
+        "ts" | "tsx" | "mts" | "cts" | "typescript" => "js",
+        other => other,
+    }
+}
+
+/// Highlights code and returns the [`HighlightedCode`] as pretty-printed JSON.
+///
+/// This is the entry point for editors that want token segments (line, column,
+/// style) rather than rendered output. TypeScript variants (`ts`, `tsx`, `mts`,
+/// `cts`) use the JavaScript syntax, since syntect does not bundle a TypeScript
+/// grammar.
+///
+/// # Errors
+///
+/// Returns `HighlightError` if the language is unsupported, the code exceeds
+/// the maximum size, or serialization fails.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::highlighting::syntect_highlighter::highlight_to_json;
+///
+/// let json = highlight_to_json("const x = 1;", "ts")?;
+/// assert!(json.contains("\"segments\""));
+/// # Ok::<(), ta_lib::highlighting::error::HighlightError>(())
+/// ```
+pub fn highlight_to_json(code: &str, language: &str) -> Result<String> {
+    let options = HighlightOptions::new(syntax_language(language)).for_format(OutputFormat::Json);
+    let highlighted = highlight_code(code, options)?;
+    Ok(serde_json::to_string_pretty(&highlighted)?)
 }
 
 /// Converts a syntect `Style` to our `SegmentStyle`.
@@ -355,6 +864,21 @@ mod tests {
         assert!(!highlighted.segments.is_empty());
     }
 
+    #[test]
+    fn test_supported_languages_include_rust_and_js() {
+        let languages = supported_languages();
+
+        let rust = languages.iter().find(|l| l.name == "Rust").unwrap();
+        assert!(rust.extensions.contains(&"rs".to_string()));
+        assert!(languages.iter().any(|l| l.token == "js"));
+
+        // Every listed token must actually be accepted by the highlighter
+        for language in &languages {
+            let options = HighlightOptions::new(syntax_language(&language.token));
+            assert!(highlight_code("x", options).is_ok(), "{} is not highlightable", language.token);
+        }
+    }
+
     #[test]
     fn test_highlight_unsupported_language() {
         let code = "some code";
@@ -407,6 +931,56 @@ mod tests {
         assert!(html_output.contains("<span"));
     }
 
+    #[test]
+    fn test_render_html_css_classes() {
+        let code = "const s = 'hi'; // note";
+        let options = HighlightOptions::new("js")
+            .for_format(OutputFormat::Html)
+            .with_css_classes(true);
+
+        let html = highlight_code(code, options).unwrap().render_html();
+
+        assert!(html.contains(r#"class="tok-"#), "{}", html);
+        assert!(html.contains(r#"class="tok-string""#));
+        assert!(html.contains(r#"class="tok-comment""#));
+        assert!(!html.contains("style="));
+    }
+
+    #[test]
+    fn test_inline_styles_remain_default() {
+        let options = HighlightOptions::new("js").for_format(OutputFormat::Html);
+        let html = highlight_code("const x = 1;", options).unwrap().render_html();
+        assert!(html.contains("style="));
+        assert!(!html.contains("class=\"tok-"));
+    }
+
+    #[test]
+    fn test_theme_stylesheet_covers_token_classes() {
+        let css = theme_stylesheet("base16-ocean.dark").unwrap();
+        for category in TOKEN_CATEGORIES {
+            assert!(css.contains(&format!(".ta-highlight .tok-{} {{", category)));
+        }
+        assert!(css.contains("background-color: rgb("));
+        assert!(theme_stylesheet("No Such Theme").is_err());
+    }
+
+    #[test]
+    fn test_class_prefix_on_token_classes_and_stylesheet() {
+        let html_options = HtmlOptions::new().with_class_prefix("x-");
+        let options = HighlightOptions::new("js")
+            .for_format(OutputFormat::Html)
+            .with_css_classes(true)
+            .with_html(html_options.clone());
+
+        let html = highlight_code("const s = 'hi';", options).unwrap().render_html();
+        assert!(html.starts_with(r#"<pre class="x-ta-highlight">"#), "{}", html);
+        assert!(html.contains(r#"class="x-tok-string""#), "{}", html);
+        assert!(!html.contains(r#"class="tok-"#));
+
+        let css = theme_stylesheet_with_options("base16-ocean.dark", &html_options).unwrap();
+        assert!(css.contains(".x-ta-highlight .x-tok-string {"), "{}", css);
+    }
+
     #[test]
     fn test_rgb_color_from_syntect() {
         let color = Color { r: 255, g: 128, b: 64, a: 255 };
@@ -552,4 +1126,103 @@ mod tests {
         let json = serde_json::to_string(&highlighted);
         assert!(json.is_ok());
     }
+
+    #[test]
+    fn test_highlight_to_json_typescript_segments() {
+        let json = highlight_to_json("const x=1;", "ts").unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let segments = value["segments"].as_array().unwrap();
+        assert!(!segments.is_empty());
+        assert_eq!(segments[0]["line"], 1);
+        assert_eq!(segments[0]["column"], 1);
+        assert!(segments[0]["style"].is_object());
+    }
+
+    #[test]
+    fn test_highlight_to_json_unsupported_language() {
+        let result = highlight_to_json("x", "cobol");
+        assert!(matches!(result, Err(HighlightError::UnsupportedLanguage(_))));
+    }
+
+    const INCREMENTAL_BASE: &str = "import { a } from './a';\n\nfunction add(x: number, y: number) {\n  return x + y;\n}\n\nconst label = 'sum';\nexport default add;\n";
+
+    /// Asserts that re-highlighting `before` into `after` matches highlighting `after` from scratch
+    fn assert_incremental_matches_full(before: &str, after: &str) {
+        let previous = highlight_code(before, HighlightOptions::new("js")).unwrap();
+        let incremental = highlight_code_incremental(&previous, after, HighlightOptions::new("js")).unwrap();
+        let full = highlight_code(after, HighlightOptions::new("js")).unwrap();
+
+        assert_eq!(incremental.line_count, full.line_count);
+        assert_eq!(
+            serde_json::to_value(&incremental.segments).unwrap(),
+            serde_json::to_value(&full.segments).unwrap(),
+            "before: {:?}\nafter: {:?}",
+            before,
+            after
+        );
+        assert_eq!(
+            incremental.line_cache.as_ref().unwrap().states,
+            full.line_cache.as_ref().unwrap().states
+        );
+    }
+
+    #[test]
+    fn test_incremental_edit_at_top_matches_full() {
+        let after = INCREMENTAL_BASE.replace("import { a }", "import { a, b }");
+        assert_incremental_matches_full(INCREMENTAL_BASE, &after);
+    }
+
+    #[test]
+    fn test_incremental_edit_in_middle_matches_full() {
+        let after = INCREMENTAL_BASE.replace("return x + y;", "const total = x + y;\n  return total;");
+        assert_incremental_matches_full(INCREMENTAL_BASE, &after);
+
+        let after = INCREMENTAL_BASE.replace("  return x + y;\n", "");
+        assert_incremental_matches_full(INCREMENTAL_BASE, &after);
+    }
+
+    #[test]
+    fn test_incremental_edit_at_bottom_matches_full() {
+        let after = INCREMENTAL_BASE.replace("export default add;\n", "export default add;\nexport { label };\n");
+        assert_incremental_matches_full(INCREMENTAL_BASE, &after);
+    }
+
+    #[test]
+    fn test_incremental_rehighlights_lines_whose_state_changed() {
+        // Opening a comment changes every following line even though their text is untouched
+        let after = INCREMENTAL_BASE.replace("function add", "/* function add");
+        assert_incremental_matches_full(INCREMENTAL_BASE, &after);
+        assert_incremental_matches_full(&after, INCREMENTAL_BASE);
+    }
+
+    #[test]
+    fn test_incremental_falls_back_when_language_changes() {
+        let previous = highlight_code(INCREMENTAL_BASE, HighlightOptions::new("js")).unwrap();
+        let incremental = highlight_code_incremental(&previous, "fn main() {}\n", HighlightOptions::new("rs")).unwrap();
+        assert_eq!(incremental.language, "rs");
+        assert_eq!(incremental.line_count, 1);
+    }
+
+    #[test]
+    fn test_max_line_length_truncates_long_lines() {
+        let long_line = format!("const data = [{}];", "1,".repeat(92));
+        assert_eq!(long_line.len(), 200);
+        let source = format!("{}\nconst ok = 1;\n", long_line);
+        let code = highlight_code(&source, HighlightOptions::new("js").with_max_line_length(Some(40))).unwrap();
+
+        let console = code.render_console();
+        let plain = crate::highlighting::ansi::strip_ansi(&console);
+        let lines: Vec<&str> = plain.lines().collect();
+        assert_eq!(lines, vec![format!("{}…", &long_line[..40]).as_str(), "const ok = 1;"]);
+        assert!(console.contains("\x1b[2m…\x1b[0m"));
+
+        let html = code.render_html();
+        assert!(html.contains(r#"<span style="opacity: 0.5">…</span>"#));
+        assert!(!html.contains("1,1,1,1,1,1,1,1,1,1,1,1,1,1,"));
+        assert!(html.contains("\n"));
+
+        let unlimited = highlight_code(&source, HighlightOptions::new("js")).unwrap();
+        assert_eq!(crate::highlighting::ansi::strip_ansi(&unlimited.render_console()), source);
+    }
 }
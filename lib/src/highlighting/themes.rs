@@ -1,5 +1,6 @@
 use crate::highlighting::error::{HighlightError, Result};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use syntect::highlighting::ThemeSet;
 
 /// Built-in theme options available in syntect.
@@ -146,6 +147,12 @@ pub fn get_default_theme_set() -> ThemeSet {
     ThemeSet::load_defaults()
 }
 
+/// The built-in themes, loaded once and shared by every theme lookup.
+pub(crate) fn default_theme_set() -> &'static ThemeSet {
+    static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+    THEME_SET.get_or_init(ThemeSet::load_defaults)
+}
+
 /// Loads a theme from a custom file path.
 ///
 /// # Security
@@ -204,7 +211,7 @@ pub fn load_theme_from_file(path: &Path) -> Result<syntect::highlighting::Theme>
 /// assert_eq!(theme.name, Some("Solarized (light)".to_string()));
 /// ```
 pub fn get_theme_by_name(name: &str) -> Result<syntect::highlighting::Theme> {
-    let theme_set = get_default_theme_set();
+    let theme_set = default_theme_set();
 
     // Try exact match first
     if let Some(theme) = theme_set.themes.get(name) {
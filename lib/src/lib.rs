@@ -2,14 +2,29 @@ pub mod error;
 pub mod models;
 pub mod output;
 pub mod colorize;
+pub mod glyphs;
 pub mod visitors;
 pub mod analyzer;
 pub mod type_errors;
+pub mod scope_names;
+pub mod source_map;
 pub mod symbols;
+pub mod api_diff;
+pub mod baseline;
 pub mod dependencies;
+pub mod module_format;
+pub mod shadowing;
+pub mod explicit_any;
+pub mod empty_catch;
+pub mod rules;
+pub mod sfc;
 pub mod tests;
+pub mod walk;
 pub mod watcher;
+pub mod watchdog;
 pub mod highlighting;
+pub mod explain;
+pub mod lsp;
 
 pub use error::Error;
 pub type Result<T> = std::result::Result<T, Error>;
\ No newline at end of file
@@ -0,0 +1,426 @@
+//! Minimal Language Server Protocol adapter that publishes type errors as diagnostics.
+//!
+//! This is not a full language server: it understands `initialize`, document
+//! open/change/close notifications and the shutdown handshake, and answers
+//! every edit by re-analyzing the document and sending
+//! `textDocument/publishDiagnostics`. Documents are synced in full.
+
+use std::io::{BufRead, Write};
+
+use serde_json::{json, Value};
+
+use crate::analyzer::{AnalysisOptions, Analyzer};
+use crate::highlighting::ErrorSeverity;
+use crate::models::TypeError;
+use crate::source_map::SourceMap;
+use crate::{Error, Result};
+
+/// `TextDocumentSyncKind.Full`: clients send the whole document on every change
+const TEXT_DOCUMENT_SYNC_FULL: u8 = 1;
+
+/// JSON-RPC error code for requests the adapter does not implement
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Reads one `Content-Length` framed JSON-RPC message.
+///
+/// Returns `Ok(None)` at end of input.
+///
+/// # Errors
+///
+/// Returns [`Error::ProtocolError`] for a missing `Content-Length` header or a
+/// body that is not valid JSON, and [`Error::IoError`] if reading fails.
+pub fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                let length = value.trim().parse::<usize>().map_err(|_| {
+                    Error::ProtocolError(format!("invalid Content-Length: {}", value.trim()))
+                })?;
+                content_length = Some(length);
+            }
+        }
+    }
+
+    let length = content_length
+        .ok_or_else(|| Error::ProtocolError("missing Content-Length header".to_string()))?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| Error::ProtocolError(format!("invalid JSON body: {}", e)))
+}
+
+/// Writes `message` with a `Content-Length` header and flushes.
+pub fn write_message<W: Write>(writer: &mut W, message: &Value) -> Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// How the `character` of an LSP position is counted, agreed on in `initialize`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PositionEncoding {
+    /// UTF-16 code units, the LSP default
+    #[default]
+    Utf16,
+    /// Characters (Unicode scalar values), used when the client offers `utf-32`
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// The client's preferred encoding from its `general.positionEncodings`
+    /// capability, if it offers one of ours
+    fn negotiate(capabilities: &Value) -> Self {
+        let offered = capabilities["general"]["positionEncodings"].as_array();
+        match offered.into_iter().flatten().filter_map(Value::as_str).find(|e| matches!(*e, "utf-16" | "utf-32")) {
+            Some("utf-32") => PositionEncoding::Utf32,
+            _ => PositionEncoding::Utf16,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            PositionEncoding::Utf16 => "utf-16",
+            PositionEncoding::Utf32 => "utf-32",
+        }
+    }
+
+    /// `character` of the 1-indexed character `column` on `line`.
+    ///
+    /// `column` counts characters of the analyzed text, which has no byte order
+    /// mark (see [`crate::analyzer::normalize_source`]), while `map` is the document as the
+    /// client has it. A BOM is skipped when counting and then added back, so
+    /// the result points into the client's text.
+    fn character(self, map: &SourceMap<'_>, line: usize, column: usize) -> usize {
+        let text = map.line_text(line).unwrap_or_default();
+        let (bom, text) = match text.strip_prefix('\u{feff}') {
+            Some(rest) if line == 1 => (1, rest),
+            _ => (0, text),
+        };
+        let before = text.chars().take(column.saturating_sub(1));
+        // U+FEFF is one UTF-16 code unit and one character
+        bom + match self {
+            PositionEncoding::Utf16 => before.map(char::len_utf16).sum::<usize>(),
+            PositionEncoding::Utf32 => before.count(),
+        }
+    }
+}
+
+/// Converts a type error in the document mapped by `map` to an LSP `Diagnostic`.
+///
+/// LSP positions are 0-indexed, so the 1-indexed line/column span is shifted by
+/// one, and columns are counted in `encoding`.
+pub fn to_diagnostic(error: &TypeError, map: &SourceMap<'_>, encoding: PositionEncoding) -> Value {
+    let severity = match error.severity {
+        ErrorSeverity::Error => 1,
+        ErrorSeverity::Warning => 2,
+        ErrorSeverity::Info => 3,
+    };
+    let position = error.position;
+    json!({
+        "range": {
+            "start": {
+                "line": position.start_line.saturating_sub(1),
+                "character": encoding.character(map, position.start_line, position.start_column),
+            },
+            "end": {
+                "line": position.end_line.saturating_sub(1),
+                "character": encoding.character(map, position.end_line, position.end_column),
+            },
+        },
+        "severity": severity,
+        "code": error.id,
+        "source": "ta",
+        "message": error.message,
+    })
+}
+
+/// Builds a `textDocument/publishDiagnostics` notification for `uri`, whose text is `source`.
+pub fn publish_diagnostics(uri: &str, source: &str, errors: &[TypeError], encoding: PositionEncoding) -> Value {
+    let map = SourceMap::new(source);
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {
+            "uri": uri,
+            "diagnostics": errors.iter().map(|error| to_diagnostic(error, &map, encoding)).collect::<Vec<_>>(),
+        },
+    })
+}
+
+/// File name used for analysis: the URI's decoded path, so its extension picks the source type.
+fn uri_to_filename(uri: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri).as_bytes();
+    let mut decoded = Vec::with_capacity(path.len());
+    let mut i = 0;
+    while i < path.len() {
+        let escaped = path.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (path[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// State of one editor connection.
+///
+/// Feed every incoming message to [`LspSession::handle`] and send back what it returns.
+pub struct LspSession {
+    analyzer: Analyzer,
+    encoding: PositionEncoding,
+    shutdown_requested: bool,
+    exited: bool,
+}
+
+impl Default for LspSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LspSession {
+    pub fn new() -> Self {
+        Self {
+            analyzer: Analyzer::new(AnalysisOptions::default()),
+            encoding: PositionEncoding::default(),
+            shutdown_requested: false,
+            exited: false,
+        }
+    }
+
+    /// True once the client sent `exit`; the server should stop reading.
+    pub fn exited(&self) -> bool {
+        self.exited
+    }
+
+    /// Process exit code required by LSP: 0 when `shutdown` preceded `exit`.
+    pub fn exit_code(&self) -> i32 {
+        if self.shutdown_requested { 0 } else { 1 }
+    }
+
+    /// Handles one incoming message and returns the responses and notifications to send.
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let id = message.get("id").cloned();
+        let params = &message["params"];
+
+        match method {
+            "initialize" => {
+                self.encoding = PositionEncoding::negotiate(&params["capabilities"]);
+                vec![self.respond(id, json!({
+                    "capabilities": {
+                        "positionEncoding": self.encoding.name(),
+                        "textDocumentSync": TEXT_DOCUMENT_SYNC_FULL,
+                    },
+                    "serverInfo": {
+                        "name": "ta",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }))]
+            }
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+                self.analyze(document["uri"].as_str(), document["text"].as_str())
+            }
+            "textDocument/didChange" => {
+                // Full sync: the last change carries the whole document
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+                self.analyze(params["textDocument"]["uri"].as_str(), text)
+            }
+            "textDocument/didClose" => match params["textDocument"]["uri"].as_str() {
+                Some(uri) => vec![publish_diagnostics(uri, "", &[], self.encoding)],
+                None => Vec::new(),
+            },
+            "shutdown" => {
+                self.shutdown_requested = true;
+                vec![self.respond(id, Value::Null)]
+            }
+            "exit" => {
+                self.exited = true;
+                Vec::new()
+            }
+            // Unknown requests get an error; unknown notifications are ignored
+            _ => match id {
+                Some(id) => vec![json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": {
+                        "code": METHOD_NOT_FOUND,
+                        "message": format!("method not supported: {}", method),
+                    },
+                })],
+                None => Vec::new(),
+            },
+        }
+    }
+
+    fn respond(&self, id: Option<Value>, result: Value) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id.unwrap_or(Value::Null),
+            "result": result,
+        })
+    }
+
+    fn analyze(&self, uri: Option<&str>, text: Option<&str>) -> Vec<Value> {
+        let (Some(uri), Some(text)) = (uri, text) else {
+            return Vec::new();
+        };
+        let errors = match self.analyzer.analyze_source(text, &uri_to_filename(uri)) {
+            Ok(analysis) => analysis.type_errors,
+            Err(e) => {
+                log::warn!("Skipping diagnostics for {}: {}", uri, e);
+                Vec::new()
+            }
+        };
+        vec![publish_diagnostics(uri, text, &errors, self.encoding)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn did_open(uri: &str, text: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": { "uri": uri, "languageId": "typescript", "version": 1, "text": text },
+            },
+        })
+    }
+
+    #[test]
+    fn test_did_open_publishes_diagnostics() {
+        let mut session = LspSession::new();
+
+        let out = session.handle(&did_open("file:///project/src/a.ts", "const a = 1;\nconst a = 2;\n"));
+
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0]["method"], "textDocument/publishDiagnostics");
+        assert_eq!(out[0]["params"]["uri"], "file:///project/src/a.ts");
+        let diagnostics = out[0]["params"]["diagnostics"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["source"], "ta");
+        assert_eq!(diagnostics[0]["severity"], 1);
+        assert_eq!(diagnostics[0]["range"]["start"], json!({ "line": 0, "character": 6 }));
+        assert_eq!(diagnostics[0]["range"]["end"], json!({ "line": 0, "character": 7 }));
+    }
+
+    #[test]
+    fn test_did_change_republishes_clean_document() {
+        let mut session = LspSession::new();
+        session.handle(&did_open("file:///a.ts", "const a = 1;\nconst a = 2;\n"));
+
+        let out = session.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": { "uri": "file:///a.ts", "version": 2 },
+                "contentChanges": [{ "text": "const a = 1;\n" }],
+            },
+        }));
+
+        assert_eq!(out[0]["params"]["diagnostics"], json!([]));
+    }
+
+    #[test]
+    fn test_columns_count_utf16_units_unless_utf32_is_negotiated() {
+        let text = "\"😀\"; const e = 1; const e = 2;\n";
+        let start = |session: &mut LspSession| {
+            let out = session.handle(&did_open("file:///a.ts", text));
+            out[0]["params"]["diagnostics"][0]["range"]["start"]["character"].clone()
+        };
+
+        // The emoji is two UTF-16 code units but one character
+        assert_eq!(start(&mut LspSession::new()), 12);
+
+        let mut session = LspSession::new();
+        let init = session.handle(&json!({
+            "jsonrpc": "2.0", "id": 1, "method": "initialize",
+            "params": { "capabilities": { "general": { "positionEncodings": ["utf-32", "utf-16"] } } },
+        }));
+        assert_eq!(init[0]["result"]["capabilities"]["positionEncoding"], "utf-32");
+        assert_eq!(start(&mut session), 11);
+    }
+
+    #[test]
+    fn test_positions_count_a_byte_order_mark_on_the_first_line() {
+        let start = |text: &str| {
+            let out = LspSession::new().handle(&did_open("file:///a.ts", text));
+            out[0]["params"]["diagnostics"][0]["range"]["start"].clone()
+        };
+
+        // Analysis drops the BOM, but the client's first line still starts with it
+        assert_eq!(start("\u{feff}const a = 1; const a = 2;\n"), json!({ "line": 0, "character": 7 }));
+        assert_eq!(start("const a = 1; const a = 2;\n"), json!({ "line": 0, "character": 6 }));
+        // Later lines have no BOM to account for
+        assert_eq!(start("\u{feff}\nconst a = 1;\r\nconst a = 2;\r\n"), json!({ "line": 1, "character": 6 }));
+    }
+
+    #[test]
+    fn test_uri_path_is_percent_decoded() {
+        assert_eq!(uri_to_filename("file:///my%20project/src/a%2Bb.ts"), "/my project/src/a+b.ts");
+        assert_eq!(uri_to_filename("file:///100%.ts"), "/100%.ts");
+        assert_eq!(uri_to_filename("untitled:a.ts"), "untitled:a.ts");
+    }
+
+    #[test]
+    fn test_initialize_and_shutdown_handshake() {
+        let mut session = LspSession::new();
+
+        let init = session.handle(&json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }));
+        assert_eq!(init[0]["id"], 1);
+        assert_eq!(init[0]["result"]["capabilities"]["textDocumentSync"], 1);
+        assert_eq!(init[0]["result"]["capabilities"]["positionEncoding"], "utf-16");
+
+        let unknown = session.handle(&json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/hover" }));
+        assert_eq!(unknown[0]["error"]["code"], METHOD_NOT_FOUND);
+
+        session.handle(&json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" }));
+        assert!(!session.exited());
+        session.handle(&json!({ "jsonrpc": "2.0", "method": "exit" }));
+        assert!(session.exited());
+        assert_eq!(session.exit_code(), 0);
+    }
+
+    #[test]
+    fn test_message_framing_round_trip() {
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &json!({ "jsonrpc": "2.0", "method": "exit" })).unwrap();
+
+        let mut reader = Cursor::new(buffer);
+        let message = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(message["method"], "exit");
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_missing_content_length_is_protocol_error() {
+        let mut reader = Cursor::new(b"X-Other: 1\r\n\r\n{}".to_vec());
+
+        assert!(matches!(read_message(&mut reader), Err(Error::ProtocolError(_))));
+    }
+}
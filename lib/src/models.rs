@@ -1,19 +1,67 @@
 use oxc_span::Span;
 use serde::Serialize;
+use crate::highlighting::ErrorSeverity;
+use crate::source_map::SourceMap;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct TypeError {
     pub id: String,
     pub message: String,
+    pub severity: ErrorSeverity,
     pub file: String,
     pub line: usize,
     pub column: usize,
-    pub scope: String,  // "file::symbol" format
+    pub scope: String,  // "Class.method" format, see `ScopeNaming`
     pub block: String,  // Plain text code block (legacy - kept for backward compatibility)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_code: Option<SourceCode>,  // New field - context-aware code extraction
     #[serde(serialize_with = "span_serializer::serialize")]
     pub span: Span,
+    /// `span` as lines and columns, flattened into the JSON next to it
+    #[serde(flatten)]
+    pub position: SpanPosition,
+    /// Other places in the file the diagnostic points at, e.g. the outer
+    /// declaration a `SHADOW` warning is about
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedSpan>,
+}
+
+/// A secondary labelled location of a [`TypeError`], always in the same file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RelatedSpan {
+    /// What is at this location ("outer declaration")
+    pub message: String,
+    #[serde(flatten)]
+    pub position: SpanPosition,
+}
+
+/// Line/column form of a byte span (1-indexed; columns count characters, not bytes)
+///
+/// Like the byte span, the end is exclusive: `end_column` is the column just past the span.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct SpanPosition {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl SpanPosition {
+    /// Computes the position of `span` in `source`, as [`ErrorAnnotation`] does for display.
+    ///
+    /// Offsets past the end of `source` are clamped to it.
+    ///
+    /// [`ErrorAnnotation`]: crate::highlighting::ErrorAnnotation
+    pub fn from_span(source: &str, span: Span) -> Self {
+        Self::from_source_map(&SourceMap::new(source), span)
+    }
+
+    /// Like [`SpanPosition::from_span`], reusing a [`SourceMap`] already built for the file.
+    pub fn from_source_map(map: &SourceMap<'_>, span: Span) -> Self {
+        let (start_line, start_column) = map.line_col(span.start as usize);
+        let (end_line, end_column) = map.line_col(span.end as usize);
+        Self { start_line, start_column, end_line, end_column }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -22,9 +70,14 @@ pub struct SourceCode {
     pub display_code: String,
     pub scope_type: crate::highlighting::ScopeType,
     pub scope_name: String,
+    /// Predominant indentation of the file, used to indent the code block when displayed
+    pub indent_unit: crate::highlighting::IndentUnit,
+    /// Which lines of `full_code` were left out of `display_code`, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation_info: Option<crate::highlighting::TruncationInfo>,
 }
 
-mod span_serializer {
+pub(crate) mod span_serializer {
     use oxc_span::Span;
     use serde::Serializer;
 
@@ -48,10 +101,23 @@ pub struct SymbolInfo {
     pub start_line: usize,
     pub end_line: usize,
     pub exported: bool,
+    /// True for `export default` declarations; anonymous defaults are named `default`
+    pub is_default_export: bool,
     pub parameters: Option<Vec<ParameterInfo>>,
+    /// Members of interfaces, classes and object types; props of function and arrow components
     pub properties: Option<Vec<PropertyInfo>>,
     pub return_type: Option<String>,
+    /// JSDoc text without its `@tag` block; empty for a comment with only tags
     pub jsdoc: Option<String>,
+    /// Class superclass or interfaces extended (e.g. `Animal`, `Base<T>`)
+    #[serde(default)]
+    pub extends: Vec<String>,
+    /// Interfaces implemented by a class
+    #[serde(default)]
+    pub implements: Vec<String>,
+    /// Identifiers referenced within the symbol's declaration, used to bind imports to symbols
+    #[serde(skip)]
+    pub references: Vec<String>,
 }
 
 impl SymbolInfo {
@@ -59,10 +125,12 @@ impl SymbolInfo {
     /// Examples:
     /// - function createContext(name: string): Context
     /// - class UserApi
+    /// - class Dog extends Animal implements Pet
     /// - interface User { id: number, name: string }
     /// - type ApiResponse<T>
+    /// - export default class App
     pub fn display_signature(&self) -> String {
-        match self.kind {
+        let signature = match self.kind {
             SymbolKind::Function => {
                 let params = if let Some(params) = &self.parameters {
                     params.iter()
@@ -86,12 +154,12 @@ impl SymbolInfo {
                 }
             }
             SymbolKind::Class => {
-                format!("class {}", self.name)
+                format!("class {}{}", self.name, self.heritage_clause())
             }
             SymbolKind::Interface => {
                 if let Some(props) = &self.properties {
                     if props.is_empty() {
-                        format!("interface {}", self.name)
+                        format!("interface {}{}", self.name, self.heritage_clause())
                     } else {
                         let prop_str = props.iter()
                             .take(3) // Limit to first 3 properties
@@ -106,10 +174,10 @@ impl SymbolInfo {
                             .join(", ");
 
                         let suffix = if props.len() > 3 { ", ..." } else { "" };
-                        format!("interface {} {{ {}{} }}", self.name, prop_str, suffix)
+                        format!("interface {}{} {{ {}{} }}", self.name, self.heritage_clause(), prop_str, suffix)
                     }
                 } else {
-                    format!("interface {}", self.name)
+                    format!("interface {}{}", self.name, self.heritage_clause())
                 }
             }
             SymbolKind::Type => {
@@ -121,8 +189,26 @@ impl SymbolInfo {
             SymbolKind::Enum => {
                 format!("enum {}", self.name)
             }
+        };
+
+        if self.is_default_export {
+            format!("export default {}", signature)
+        } else {
+            signature
         }
     }
+
+    /// ` extends A, B implements C` (empty when the symbol has no heritage)
+    pub fn heritage_clause(&self) -> String {
+        let mut clause = String::new();
+        if !self.extends.is_empty() {
+            clause.push_str(&format!(" extends {}", self.extends.join(", ")));
+        }
+        if !self.implements.is_empty() {
+            clause.push_str(&format!(" implements {}", self.implements.join(", ")));
+        }
+        clause
+    }
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
@@ -135,6 +221,20 @@ pub enum SymbolKind {
     Enum,
 }
 
+impl SymbolKind {
+    /// Single-letter kind used in ctags files, following universal-ctags' TypeScript kinds
+    pub fn ctags_kind(&self) -> char {
+        match self {
+            SymbolKind::Function => 'f',
+            SymbolKind::Class => 'c',
+            SymbolKind::Interface => 'i',
+            SymbolKind::Type => 'a',
+            SymbolKind::Variable => 'v',
+            SymbolKind::Enum => 'g',
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ParameterInfo {
     pub name: String,
@@ -142,11 +242,19 @@ pub struct ParameterInfo {
     pub description: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct PropertyInfo {
     pub name: String,
     pub type_annotation: Option<String>,
     pub description: Option<String>,
+    pub readonly: bool,
+    /// Declared with `?` (`id?: number`)
+    pub optional: bool,
+    /// `public`, `private` or `protected` when written on a class member
+    pub accessibility: Option<String>,
+    /// A `static` class member
+    #[serde(rename = "static")]
+    pub is_static: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -170,7 +278,11 @@ pub struct SymbolDependency {
     pub file: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+/// Where a dependency lives.
+///
+/// Imports are classified by [`crate::dependencies::classify_import`]: relative
+/// paths are `Local`, tsconfig path aliases `Repo` and bare specifiers `External`.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
 pub enum DependencyScope {
     Local,
     Repo,
@@ -183,7 +295,13 @@ pub struct TypeTest {
     pub file: String,
     pub describe_block: String,
     pub test_name: String,
+    /// Line of the `it`/`test` call (1-indexed)
     pub line: usize,
+    /// Column of the `it`/`test` call (1-indexed)
+    pub column: usize,
+    /// Byte range of the whole `it`/`test` call, callback included
+    #[serde(serialize_with = "span_serializer::serialize")]
+    pub span: Span,
     pub has_type_cases: bool,
     pub status: TestStatus,
 }
@@ -195,6 +313,41 @@ pub enum TestStatus {
     NoTypeCases,
 }
 
+/// Summary of one `ta source` run, printed by `--manifest`
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisManifest {
+    /// Files selected for analysis
+    pub total_files: usize,
+    /// Files that were parsed and checked
+    pub analyzed_files: usize,
+    /// Files that could not be analyzed (unreadable or unsupported)
+    pub skipped_files: usize,
+    /// Reported diagnostics of error severity
+    pub error_count: usize,
+    /// Reported diagnostics of warning severity
+    pub warning_count: usize,
+    /// Wall-clock time from the start of analysis to the final report
+    pub duration_ms: u64,
+    pub ta_version: String,
+}
+
+impl AnalysisManifest {
+    /// The manifest of a run over `total_files` files, `skipped_files` of which
+    /// failed, that reported `errors` after `duration`
+    pub fn new(total_files: usize, skipped_files: usize, errors: &[TypeError], duration: std::time::Duration) -> Self {
+        let count = |severity: ErrorSeverity| errors.iter().filter(|e| e.severity == severity).count();
+        Self {
+            total_files,
+            analyzed_files: total_files.saturating_sub(skipped_files),
+            skipped_files,
+            error_count: count(ErrorSeverity::Error),
+            warning_count: count(ErrorSeverity::Warning),
+            duration_ms: duration.as_millis() as u64,
+            ta_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -204,6 +357,7 @@ mod tests {
         let error = TypeError {
             id: "TS2322".to_string(),
             message: "Type 'string' is not assignable to type 'number'".to_string(),
+            severity: ErrorSeverity::Error,
             file: "src/main.ts".to_string(),
             line: 10,
             column: 5,
@@ -211,10 +365,13 @@ mod tests {
             block: "const x: number = 'hello';".to_string(),
             source_code: None,
             span: Span::new(0, 10),
+            position: SpanPosition::default(),
+            related: Vec::new(),
         };
 
         let json = serde_json::to_string(&error).unwrap();
         assert!(json.contains("TS2322"));
+        assert!(json.contains("\"severity\":\"error\""));
         assert!(json.contains("src/main.ts"));
         assert!(json.contains("\"start\":0"));
         assert!(json.contains("\"end\":10"));
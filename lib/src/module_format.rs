@@ -0,0 +1,298 @@
+//! ES module vs CommonJS detection and module syntax mismatch checks.
+//!
+//! OXC parses `.ts`/`.js` files as modules and `.cts`/`.cjs` files as scripts,
+//! so syntax that is only wrong at runtime (top-level `await` in a `.ts` file
+//! of a CommonJS package, `require` in an ES module) goes unreported.
+//! [`find_module_mismatches`] checks a parsed program against the format Node
+//! resolves for the file, see [`ModuleFormat::resolve`].
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use oxc_ast::ast::*;
+use oxc_ast::visit::{walk, Visit};
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_semantic::{ScopeFlags, Semantic};
+use oxc_span::{GetSpan, SourceType, Span};
+
+/// Error id for module syntax that the file's format does not allow
+pub const MODULE_FORMAT_ERROR_ID: &str = "MODULE";
+
+/// Error id for top-level `await` in a CommonJS file (matches `tsc`)
+pub const TOP_LEVEL_AWAIT_ERROR_ID: &str = "TS1309";
+
+/// How Node loads a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleFormat {
+    Esm,
+    CommonJs,
+}
+
+impl ModuleFormat {
+    /// Format implied by the extension alone: `.mts`/`.mjs` are ES modules,
+    /// `.cts`/`.cjs` CommonJS, anything else depends on `package.json`.
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "mts" | "mjs" => Some(ModuleFormat::Esm),
+            "cts" | "cjs" => Some(ModuleFormat::CommonJs),
+            _ => None,
+        }
+    }
+
+    /// Format Node uses for `path`: the extension, else the `type` field of the
+    /// nearest `package.json` (`"module"` is ESM, anything else CommonJS).
+    ///
+    /// `None` when the extension is ambiguous and no `package.json` is found.
+    pub fn resolve(path: &Path, packages: &PackageTypes) -> Option<Self> {
+        if let Some(format) = Self::from_extension(path) {
+            return Some(format);
+        }
+
+        let package_type = packages.nearest(path)?;
+        Some(if package_type.as_deref() == Some("module") {
+            ModuleFormat::Esm
+        } else {
+            ModuleFormat::CommonJs
+        })
+    }
+}
+
+/// `type` of the nearest `package.json`, remembered per directory.
+///
+/// Every file of a project would otherwise read and parse the same
+/// `package.json` files on its way up the tree. Entries are never invalidated;
+/// call [`Self::clear`] before analyzing files that may have changed.
+#[derive(Debug, Default)]
+pub struct PackageTypes {
+    /// What [`Self::nearest`] found for each directory visited so far
+    by_dir: Mutex<HashMap<PathBuf, Option<Option<String>>>>,
+}
+
+impl PackageTypes {
+    /// `type` of the nearest `package.json` above `path`: `None` when there is
+    /// no `package.json`, `Some(None)` when it has no (string) `type` field.
+    pub fn nearest(&self, path: &Path) -> Option<Option<String>> {
+        let start = std::fs::canonicalize(path).ok()?;
+        let mut visited = Vec::new();
+        let mut found = None;
+        for dir in start.ancestors().skip(1) {
+            if let Some(cached) = self.lock().get(dir) {
+                found = cached.clone();
+                break;
+            }
+            visited.push(dir.to_path_buf());
+            if let Some(package_type) = read_package_type(dir) {
+                found = Some(package_type);
+                break;
+            }
+        }
+
+        let mut by_dir = self.lock();
+        for dir in visited {
+            by_dir.insert(dir, found.clone());
+        }
+        found
+    }
+
+    /// Forget every directory, e.g. before re-analyzing after files changed
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<PathBuf, Option<Option<String>>>> {
+        // The map is only ever extended, so a panic mid-update leaves it usable
+        self.by_dir.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// `type` of `dir/package.json`, or `None` when it is missing or not valid JSON
+fn read_package_type(dir: &Path) -> Option<Option<String>> {
+    let text = std::fs::read_to_string(dir.join("package.json")).ok()?;
+    let package = serde_json::from_str::<serde_json::Value>(&text).ok()?;
+    Some(package["type"].as_str().map(str::to_string))
+}
+
+/// Report syntax that `format` does not allow:
+///
+/// - top-level `await` (and `for await`) in CommonJS ([`TOP_LEVEL_AWAIT_ERROR_ID`]);
+///   for `.cts`/`.cjs` files the parser already reports it
+/// - `import`/`export` declarations in CommonJS JavaScript; TypeScript compiles
+///   them to `require`, so `.ts`/`.cts` files are not reported
+/// - calls to an undeclared `require` in ES modules, as warnings
+pub fn find_module_mismatches(
+    program: &Program<'_>,
+    semantic: &Semantic<'_>,
+    source_type: SourceType,
+    format: ModuleFormat,
+) -> Vec<OxcDiagnostic> {
+    let mut visitor = ModuleSyntaxVisitor { semantic, function_depth: 0, top_level_awaits: Vec::new(), requires: Vec::new() };
+    visitor.visit_program(program);
+
+    let mut diagnostics = Vec::new();
+    match format {
+        ModuleFormat::CommonJs => {
+            if source_type.is_module() {
+                diagnostics.extend(visitor.top_level_awaits.into_iter().map(|span| {
+                    OxcDiagnostic::error("The current file is a CommonJS module and cannot use 'await' at the top level")
+                        .with_error_code_scope(TOP_LEVEL_AWAIT_ERROR_ID)
+                        .with_label(span)
+                }));
+            }
+            if source_type.is_javascript() {
+                diagnostics.extend(program.body.iter().filter(|stmt| stmt.is_module_declaration()).map(|stmt| {
+                    OxcDiagnostic::error("'import' and 'export' cannot be used in a CommonJS module")
+                        .with_error_code_scope(MODULE_FORMAT_ERROR_ID)
+                        .with_label(stmt.span())
+                }));
+            }
+        }
+        ModuleFormat::Esm => {
+            diagnostics.extend(visitor.requires.into_iter().map(|span| {
+                OxcDiagnostic::warn("'require' is not defined in ES modules; use 'import' or 'createRequire'")
+                    .with_error_code_scope(MODULE_FORMAT_ERROR_ID)
+                    .with_label(span)
+            }));
+        }
+    }
+    diagnostics.sort_by_key(|d| d.labels.as_ref().and_then(|l| l.first()).map(|l| l.offset()));
+    diagnostics
+}
+
+/// Collects top-level `await`s and calls to the global `require`
+struct ModuleSyntaxVisitor<'s, 'b> {
+    semantic: &'s Semantic<'b>,
+    /// Functions enclosing the current node; `await` inside one is not top-level
+    function_depth: usize,
+    top_level_awaits: Vec<Span>,
+    requires: Vec<Span>,
+}
+
+impl<'a> Visit<'a> for ModuleSyntaxVisitor<'_, '_> {
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        self.function_depth += 1;
+        walk::walk_function(self, func, flags);
+        self.function_depth -= 1;
+    }
+
+    fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
+        self.function_depth += 1;
+        walk::walk_arrow_function_expression(self, arrow);
+        self.function_depth -= 1;
+    }
+
+    fn visit_await_expression(&mut self, expr: &AwaitExpression<'a>) {
+        if self.function_depth == 0 {
+            self.top_level_awaits.push(expr.span);
+        }
+        walk::walk_await_expression(self, expr);
+    }
+
+    fn visit_for_of_statement(&mut self, stmt: &ForOfStatement<'a>) {
+        if stmt.r#await && self.function_depth == 0 {
+            self.top_level_awaits.push(stmt.span);
+        }
+        walk::walk_for_of_statement(self, stmt);
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        if let Expression::Identifier(ident) = &call.callee {
+            if ident.name == "require" && self.semantic.is_reference_to_global_variable(ident) {
+                self.requires.push(call.span);
+            }
+        }
+        walk::walk_call_expression(self, call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisOptions, Analyzer};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn error_ids(source: &str, file: &Path) -> Vec<(String, usize)> {
+        fs::write(file, source).unwrap();
+        Analyzer::new(AnalysisOptions::default())
+            .analyze_single_file(file)
+            .unwrap()
+            .type_errors
+            .into_iter()
+            .map(|e| (e.id, e.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_format_from_extension_and_package_type() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package.json"), r#"{ "type": "module" }"#).unwrap();
+        fs::create_dir(dir.path().join("cjs")).unwrap();
+        fs::write(dir.path().join("cjs/package.json"), r#"{ "name": "legacy" }"#).unwrap();
+        for name in ["a.ts", "b.cts", "cjs/c.ts"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let packages = PackageTypes::default();
+        assert_eq!(ModuleFormat::resolve(&dir.path().join("a.ts"), &packages), Some(ModuleFormat::Esm));
+        assert_eq!(ModuleFormat::resolve(&dir.path().join("b.cts"), &packages), Some(ModuleFormat::CommonJs));
+        assert_eq!(ModuleFormat::resolve(&dir.path().join("cjs/c.ts"), &packages), Some(ModuleFormat::CommonJs));
+        assert_eq!(ModuleFormat::from_extension(Path::new("x.mts")), Some(ModuleFormat::Esm));
+        assert_eq!(ModuleFormat::from_extension(Path::new("x.ts")), None);
+    }
+
+    #[test]
+    fn test_package_types_are_cached_per_directory() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package.json"), r#"{ "type": "module" }"#).unwrap();
+        fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        for name in ["src/a.ts", "src/nested/b.ts"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let packages = PackageTypes::default();
+        assert_eq!(packages.nearest(&dir.path().join("src/nested/b.ts")), Some(Some("module".to_string())));
+
+        // Directories already visited answer from the cache until it is cleared
+        fs::write(dir.path().join("package.json"), "{}").unwrap();
+        assert_eq!(ModuleFormat::resolve(&dir.path().join("src/a.ts"), &packages), Some(ModuleFormat::Esm));
+        packages.clear();
+        assert_eq!(ModuleFormat::resolve(&dir.path().join("src/a.ts"), &packages), Some(ModuleFormat::CommonJs));
+    }
+
+    #[test]
+    fn test_top_level_await_in_cts_is_reported() {
+        let dir = TempDir::new().unwrap();
+        let errors = error_ids("const config = await load();\n", &dir.path().join("config.cts"));
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert_eq!(errors[0].1, 1);
+    }
+
+    #[test]
+    fn test_top_level_await_in_commonjs_package() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package.json"), r#"{ "name": "app" }"#).unwrap();
+        let source = "async function f() { await g(); }\nconst x = await f();\nfor await (const y of z) {}\n";
+
+        let errors = error_ids(source, &dir.path().join("main.ts"));
+        assert_eq!(errors, vec![(TOP_LEVEL_AWAIT_ERROR_ID.to_string(), 2), (TOP_LEVEL_AWAIT_ERROR_ID.to_string(), 3)]);
+
+        fs::write(dir.path().join("package.json"), r#"{ "type": "module" }"#).unwrap();
+        assert!(error_ids(source, &dir.path().join("main.ts")).is_empty());
+    }
+
+    #[test]
+    fn test_import_in_cjs_and_require_in_esm() {
+        let dir = TempDir::new().unwrap();
+        let errors = error_ids("import fs from 'fs';\nmodule.exports = fs;\n", &dir.path().join("legacy.cjs"));
+        assert_eq!(errors, vec![(MODULE_FORMAT_ERROR_ID.to_string(), 1)]);
+
+        let source = "const fs = require('fs');\nimport { createRequire } from 'module';\n";
+        let errors = error_ids(source, &dir.path().join("esm.mts"));
+        assert_eq!(errors, vec![(MODULE_FORMAT_ERROR_ID.to_string(), 1)]);
+
+        // A local `require` (e.g. from createRequire) is fine
+        let source = "import { createRequire } from 'module';\nconst require = createRequire(import.meta.url);\nrequire('fs');\n";
+        assert!(error_ids(source, &dir.path().join("esm.mts")).is_empty());
+    }
+}
@@ -1,16 +1,32 @@
-use crate::models::{SymbolInfo, TypeError};
-use crate::highlighting::{highlight_code, HighlightOptions};
+use crate::models::{PropertyInfo, RelatedSpan, SymbolInfo, TypeError};
+use crate::type_errors::DEFAULT_SCOPE_SEPARATOR;
+use crate::explain::explain_hint;
+use crate::glyphs::Glyphs;
+use crate::source_map::SourceMap;
+use crate::highlighting::{highlight_code, plain_truncation_markers, truncation_markers, HighlightOptions, HtmlOptions, IndentUnit, TruncationInfo, ErrorSeverity};
+use unicode_width::UnicodeWidthStr;
 use serde::Serialize;
 use clap::ValueEnum;
 use colored::*;
-use std::path::Path;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Wrap `text` in an OSC8 hyperlink pointing at `target`
+///
+/// Format: \x1b]8;;target\x1b\\text\x1b]8;;\x1b\\
+///
+/// This always emits the escape; use [`RenderOptions::link_file`] in formatters
+/// so that terminals without OSC8 support get plain text instead.
+pub fn hyperlink(text: &str, target: &str) -> String {
+    // OSC8 format: ESC]8;;URI ESC\\ TEXT ESC]8;; ESC\\
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", target, text)
+}
 
 /// Create a clickable terminal link using OSC8 standard
 ///
 /// The displayed text remains a relative path, but the link target is an absolute path.
 /// This allows terminals that support OSC8 to make file paths clickable.
-///
-/// Format: \x1b]8;;file://absolute_path\x1b\\display_text\x1b]8;;\x1b\\
 pub fn link_file(text: &str, filepath: &str) -> String {
     // Convert to absolute path
     let abs_path = if Path::new(filepath).is_absolute() {
@@ -23,8 +39,40 @@ pub fn link_file(text: &str, filepath: &str) -> String {
         }
     };
 
-    // OSC8 format: ESC]8;;URI ESC\\ TEXT ESC]8;; ESC\\
-    format!("\x1b]8;;file://{}\x1b\\{}\x1b]8;;\x1b\\", abs_path, text)
+    hyperlink(text, &format!("file://{}", abs_path))
+}
+
+/// Whether the current terminal is expected to render OSC8 hyperlinks
+///
+/// `FORCE_HYPERLINKS=1` (or `0`) overrides detection. Otherwise stdout must be
+/// a TTY running a terminal known to support OSC8, so that others never see
+/// the raw escape bytes.
+pub fn hyperlinks_supported() -> bool {
+    use std::io::IsTerminal;
+
+    if let Ok(force) = std::env::var("FORCE_HYPERLINKS") {
+        return force != "0";
+    }
+
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+
+    let env = |name: &str| std::env::var(name).ok();
+    if env("WT_SESSION").is_some() || env("KITTY_WINDOW_ID").is_some() || env("KONSOLE_VERSION").is_some() {
+        return true;
+    }
+    if env("VTE_VERSION").and_then(|v| v.parse::<u32>().ok()).is_some_and(|v| v >= 5000) {
+        return true;
+    }
+    if let Some(program) = env("TERM_PROGRAM") {
+        if matches!(program.as_str(), "iTerm.app" | "WezTerm" | "vscode" | "Hyper" | "ghostty" | "rio") {
+            return true;
+        }
+    }
+    env("TERM").is_some_and(|term| {
+        ["kitty", "alacritty", "wezterm", "foot", "ghostty"].iter().any(|t| term.contains(t))
+    })
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum, Default, Serialize, PartialEq, Eq)]
@@ -34,6 +82,677 @@ pub enum OutputFormat {
     Console,
     Html,
     Json,
+    /// Markdown documents (currently symbol API references); other output
+    /// falls back to the console layout.
+    Markdown,
+}
+
+/// When a console report is piped through a pager (`$PAGER`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PagerMode {
+    /// Print directly to stdout.
+    #[default]
+    Never,
+    /// Page only when the report is taller than the terminal.
+    Auto,
+    /// Always page.
+    Always,
+}
+
+/// How file paths are displayed in human-readable output.
+///
+/// JSON output always keeps the paths produced by the analysis.
+#[derive(Debug, Clone, Copy, ValueEnum, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+    /// Relative to the directory the walk started from (as discovered)
+    #[default]
+    Relative,
+    /// Absolute filesystem path
+    Absolute,
+    /// File name only, with the parent directory appended when names collide
+    Basename,
+    /// Relative to the nearest enclosing `.git` root
+    Repo,
+}
+
+impl PathStyle {
+    /// Display a single path in this style, without basename disambiguation.
+    pub fn display(self, path: &str) -> String {
+        match self {
+            PathStyle::Relative => path.to_string(),
+            PathStyle::Absolute => absolute_path(path).to_string_lossy().to_string(),
+            PathStyle::Basename => Path::new(path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string()),
+            PathStyle::Repo => {
+                let absolute = absolute_path(path);
+                absolute
+                    .ancestors()
+                    .skip(1)
+                    .find(|dir| dir.join(".git").exists())
+                    .and_then(|root| absolute.strip_prefix(root).ok())
+                    .map(|relative| relative.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.to_string())
+            }
+        }
+    }
+}
+
+fn absolute_path(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        match std::env::current_dir() {
+            Ok(cwd) => cwd.join(path),
+            Err(_) => path.to_path_buf(),
+        }
+    };
+
+    // Drop `.` components so `./src/a.ts` renders as `/cwd/src/a.ts`
+    absolute.components().filter(|c| !matches!(c, std::path::Component::CurDir)).collect()
+}
+
+/// Displays a set of paths in a [`PathStyle`], disambiguating colliding basenames.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::output::{PathDisplay, PathStyle};
+///
+/// let paths = PathDisplay::new(PathStyle::Basename, ["src/deep/dir/file.ts", "src/a/util.ts", "src/b/util.ts"]);
+/// assert_eq!(paths.display("src/deep/dir/file.ts"), "file.ts");
+/// assert_eq!(paths.display("src/a/util.ts"), "util.ts (src/a)");
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathDisplay {
+    style: PathStyle,
+    /// Basenames shared by more than one distinct path
+    colliding: Vec<String>,
+}
+
+impl PathDisplay {
+    pub fn new<'a>(style: PathStyle, paths: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut by_name: HashMap<String, Vec<&str>> = HashMap::new();
+        if style == PathStyle::Basename {
+            for path in paths {
+                let entry = by_name.entry(PathStyle::Basename.display(path)).or_default();
+                if !entry.contains(&path) {
+                    entry.push(path);
+                }
+            }
+        }
+
+        let colliding = by_name
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .map(|(name, _)| name)
+            .collect();
+
+        Self { style, colliding }
+    }
+
+    pub fn display(&self, path: &str) -> String {
+        let display = self.style.display(path);
+        if self.style != PathStyle::Basename || !self.colliding.contains(&display) {
+            return display;
+        }
+
+        match Path::new(path).parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => {
+                let parent = parent.to_string_lossy();
+                format!("{} ({})", display, parent.trim_start_matches("./"))
+            }
+            _ => display,
+        }
+    }
+}
+
+/// How much of each symbol's shape is shown in console and HTML output.
+///
+/// JSON output always includes every property with its type.
+#[derive(Debug, Clone, Copy, ValueEnum, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DetailLevel {
+    /// Compact signatures (first three interface properties, names only in HTML)
+    #[default]
+    Summary,
+    /// Every property and method with its type
+    Full,
+}
+
+/// Splits a member into display name and type, folding method signatures
+/// (`find()` + `(id: number): User`) so they render as `find(id: number): User`.
+///
+/// At [`DetailLevel::Full`] the name also carries the member's modifiers and
+/// optional marker (`private static readonly id?`).
+fn member_parts(prop: &PropertyInfo, detail: DetailLevel) -> (Cow<'_, str>, Option<&str>) {
+    let (name, ty) = match prop.type_annotation.as_deref() {
+        Some(ty) if ty.starts_with('(') => (prop.name.trim_end_matches("()"), Some(ty)),
+        ty => (prop.name.as_str(), ty),
+    };
+    if detail == DetailLevel::Summary {
+        return (Cow::Borrowed(name), ty);
+    }
+
+    let modifiers = [prop.accessibility.as_deref(), prop.is_static.then_some("static"), prop.readonly.then_some("readonly")];
+    let mut label: String = modifiers.into_iter().flatten().map(|modifier| format!("{} ", modifier)).collect();
+    label.push_str(name);
+    if prop.optional {
+        label.push('?');
+    }
+    (Cow::Owned(label), ty)
+}
+
+/// Keeps only `fields` in each object of `value` (an object or an array of objects).
+///
+/// Returns the projected value and the requested fields that were not present
+/// in any object. Non-object values pass through unchanged.
+pub fn select_fields(value: serde_json::Value, fields: &[String]) -> (serde_json::Value, Vec<String>) {
+    use serde_json::Value;
+
+    let mut seen = std::collections::HashSet::new();
+    let mut project = |value: Value| match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(key, _)| fields.contains(key))
+                .inspect(|(key, _)| { seen.insert(key.clone()); })
+                .collect(),
+        ),
+        other => other,
+    };
+
+    let is_empty_array = matches!(&value, Value::Array(items) if items.is_empty());
+    let projected = match value {
+        Value::Array(items) => Value::Array(items.into_iter().map(&mut project).collect()),
+        other => project(other),
+    };
+
+    // With no objects to inspect, no field can be called unknown
+    let unknown = if is_empty_array {
+        Vec::new()
+    } else {
+        fields.iter().filter(|f| !seen.contains(*f)).cloned().collect()
+    };
+    (projected, unknown)
+}
+
+/// First sentence of a JSDoc comment's first paragraph, stopping before any `@tag`.
+fn jsdoc_summary(jsdoc: &str) -> &str {
+    let text = jsdoc.lines().next().unwrap_or_default();
+    let text = text.split(" @").next().unwrap_or(text).trim();
+    match text.find(". ") {
+        Some(end) => &text[..=end],
+        None => text,
+    }
+}
+
+/// Renders truncated code for HTML, folding each omitted section into a `<details>`.
+///
+/// Visible lines are highlighted as usual; every section in `info` becomes a
+/// native (keyboard-operable) `<details>` whose `<summary>` reads "N lines omitted"
+/// and which expands to the hidden lines taken from `full_code`.
+fn folded_code_html(full_code: &str, info: &TruncationInfo, options: &HighlightOptions) -> String {
+    let lines: Vec<&str> = full_code.lines().collect();
+    let highlight_lines = |range: &[&str]| {
+        let code = range.join("\n");
+        match highlight_code(&code, options.clone()) {
+            Ok(highlighted) => highlighted.render_html(),
+            Err(_) => format!("<pre>{}</pre>", html_escape::encode_text(&code)),
+        }
+    };
+
+    let mut output = String::new();
+    let mut next = 0;
+    for &(start, end) in &info.truncated_sections {
+        let end = end.min(lines.len().saturating_sub(1));
+        if start < next || start > end {
+            continue;
+        }
+        if start > next {
+            output.push_str(&highlight_lines(&lines[next..start]));
+            output.push('\n');
+        }
+        output.push_str(&format!(
+            "<details class=\"{}\">\n<summary>{} lines omitted</summary>\n",
+            options.html.class("code-fold"),
+            end - start + 1
+        ));
+        output.push_str(&highlight_lines(&lines[start..=end]));
+        output.push_str("\n</details>\n");
+        next = end + 1;
+    }
+    if next < lines.len() {
+        output.push_str(&highlight_lines(&lines[next..]));
+        output.push('\n');
+    }
+
+    output
+}
+
+/// "outer declaration on line 2": a related span of an error, which is always in its file
+fn related_location(related: &RelatedSpan) -> String {
+    format!("{} on line {}", related.message, related.position.start_line)
+}
+
+/// Escapes characters that would break a markdown table cell.
+fn escape_table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Groups errors or symbols by file, ordering files by their first item (i.e.
+/// the order items were reported in).
+fn group_by_file<'a, T>(items: &'a [T], file: impl Fn(&'a T) -> &'a str) -> Vec<(&'a str, Vec<&'a T>)> {
+    let mut groups: Vec<(&str, Vec<&T>)> = Vec::new();
+    for item in items {
+        let item_file = file(item);
+        match groups.iter_mut().find(|(file, _)| *file == item_file) {
+            Some((_, group)) => group.push(item),
+            None => groups.push((item_file, vec![item])),
+        }
+    }
+    groups
+}
+
+/// `(1 error)` / `(3 symbols)`
+fn count_label(count: usize, noun: &str) -> String {
+    format!("({} {}{})", count, noun, if count == 1 { "" } else { "s" })
+}
+
+/// Prefixes every non-empty line with `spaces` spaces.
+fn indent_lines(text: &str, spaces: usize) -> String {
+    let pad = " ".repeat(spaces);
+    text.split_inclusive('\n')
+        .map(|line| if line.trim().is_empty() { line.to_string() } else { format!("{}{}", pad, line) })
+        .collect()
+}
+
+/// Keeps the innermost `depth` segments of `scope`, prefixed with `ellipsis`
+/// when outer segments were dropped.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::output::truncate_scope;
+///
+/// assert_eq!(truncate_scope("a.b.c.d.e", 2, ".", "…"), "…d.e");
+/// assert_eq!(truncate_scope("a.b", 2, ".", "…"), "a.b");
+/// ```
+pub fn truncate_scope<'s>(scope: &'s str, depth: usize, separator: &str, ellipsis: &str) -> Cow<'s, str> {
+    let depth = depth.max(1);
+    match scope.rmatch_indices(separator).nth(depth - 1) {
+        Some((i, _)) => Cow::Owned(format!("{}{}", ellipsis, &scope[i + separator.len()..])),
+        None => Cow::Borrowed(scope),
+    }
+}
+
+/// Console width assumed when none is detected (e.g. output is not a TTY).
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Wraps `message` to `width` columns with a hanging indent of `indent` columns.
+///
+/// The first line is assumed to follow an `indent`-wide prefix; continuation lines
+/// are padded so they align under the message start. `'quoted'` spans are kept
+/// together where they fit, and words longer than a line are never split.
+fn wrap_message(message: &str, width: usize, indent: usize) -> Vec<String> {
+    let available = width.saturating_sub(indent).max(20);
+
+    // Group words so that a 'quoted type' spanning spaces stays one token
+    let mut tokens: Vec<String> = Vec::new();
+    let mut in_quote = false;
+    for word in message.split_whitespace() {
+        if in_quote {
+            if let Some(last) = tokens.last_mut() {
+                last.push(' ');
+                last.push_str(word);
+            }
+        } else {
+            tokens.push(word.to_string());
+        }
+        if quote_delimiters(word) % 2 == 1 {
+            in_quote = !in_quote;
+        }
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for token in tokens {
+        // Quoted spans that can't fit on a line of their own are split at spaces
+        let pieces: Vec<String> = if token.chars().count() > available && token.contains(' ') {
+            token.split(' ').map(str::to_string).collect()
+        } else {
+            vec![token]
+        };
+
+        for piece in pieces {
+            let needed = if current.is_empty() { 0 } else { current.chars().count() + 1 };
+            if !current.is_empty() && needed + piece.chars().count() > available {
+                lines.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(&piece);
+        }
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+/// Number of `'` in `word` that open or close a quote.
+///
+/// An apostrophe between two letters or digits, as in `can't` or `'O'Brien'`,
+/// is part of the word rather than a quote delimiter.
+fn quote_delimiters(word: &str) -> usize {
+    let chars: Vec<char> = word.chars().collect();
+    (0..chars.len())
+        .filter(|&i| chars[i] == '\'')
+        .filter(|&i| {
+            let before = i.checked_sub(1).and_then(|b| chars.get(b));
+            let after = chars.get(i + 1);
+            !(before.is_some_and(|c| c.is_alphanumeric()) && after.is_some_and(|c| c.is_alphanumeric()))
+        })
+        .count()
+}
+
+/// Collapse the whitespace that the HTML builders indent with.
+///
+/// Every run of whitespace collapses to one space, including runs between tags
+/// where it separates inline elements (`<span>in</span> <span>scope</span>`);
+/// runs at either end are dropped. `<pre>` blocks, which hold highlighted code,
+/// are copied unchanged.
+pub fn minify_html(html: &str) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(c) = rest.chars().next() {
+        let is_pre = rest.strip_prefix("<pre").and_then(|tail| tail.chars().next()).is_some_and(|c| c == '>' || c.is_whitespace());
+        if is_pre {
+            let end = rest.find("</pre>").map_or(rest.len(), |i| i + "</pre>".len());
+            output.push_str(&rest[..end]);
+            rest = &rest[end..];
+        } else if c.is_whitespace() {
+            let next = rest.trim_start();
+            if !output.is_empty() && !next.is_empty() {
+                output.push(' ');
+            }
+            rest = next;
+        } else {
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    output
+}
+
+/// Rendering options shared by the console formatters.
+///
+/// Threaded explicitly rather than relying on the global `colored` override so
+/// that callers can choose a renderer per invocation.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Emit plain ASCII: no ANSI colors, hyperlinks, or box-drawing characters.
+    pub plain: bool,
+    /// How file paths are displayed.
+    pub path_style: PathStyle,
+    /// Console width used to wrap long messages; `None` falls back to 80 columns.
+    pub width: Option<usize>,
+    /// How much of each symbol's shape is shown.
+    pub detail: DetailLevel,
+    /// Wrap file paths in OSC8 hyperlinks (see [`hyperlinks_supported`]).
+    pub hyperlinks: bool,
+    /// Print each file once as a header with its type errors or symbols beneath it.
+    pub group_by_file: bool,
+    /// Top-level fields kept in each JSON output object; `None` keeps all.
+    pub fields: Option<Vec<String>>,
+    /// Show a progress bar on stderr while large analyses run.
+    pub progress: bool,
+    /// Suppress status lines ("Analyzing N files...", summaries) on stderr.
+    pub quiet: bool,
+    /// Indent unit for code blocks; `None` uses each file's detected unit.
+    pub indent: Option<IndentUnit>,
+    /// Whether large console reports go through a pager.
+    pub pager: PagerMode,
+    /// Markers and borders drawn in console output.
+    pub glyphs: Glyphs,
+    /// Collapse the indentation of HTML output to single spaces (see [`minify_html`]).
+    pub minify_html: bool,
+    /// Innermost scope segments shown in console error headers; `None` shows all.
+    pub scope_depth: Option<usize>,
+    /// Separator `scope_depth` splits scopes on; empty uses [`DEFAULT_SCOPE_SEPARATOR`].
+    pub scope_separator: String,
+    /// Show only the first sentence of each symbol's JSDoc in console output.
+    pub jsdoc_summary: bool,
+    /// Class names used in HTML output.
+    pub html: HtmlOptions,
+    /// Write ANSI styling even when `colored` would not, e.g. to convert it to HTML.
+    pub force_color: bool,
+}
+
+/// Styled text from [`RenderOptions::paint`]
+#[derive(Debug, Clone)]
+pub struct Painted {
+    styled: ColoredString,
+    /// Write the styling without asking `colored` whether colors are enabled
+    force: bool,
+}
+
+impl std::fmt::Display for Painted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.force || self.styled.is_plain() {
+            return self.styled.fmt(f);
+        }
+        // The same sequence `colored` writes: styles, then background, then foreground
+        const STYLES: [(Styles, &str); 8] = [
+            (Styles::Bold, "1"),
+            (Styles::Dimmed, "2"),
+            (Styles::Italic, "3"),
+            (Styles::Underline, "4"),
+            (Styles::Blink, "5"),
+            (Styles::Reversed, "7"),
+            (Styles::Hidden, "8"),
+            (Styles::Strikethrough, "9"),
+        ];
+        let styled = &self.styled;
+        let mut codes: Vec<Cow<'_, str>> = STYLES
+            .iter()
+            .filter(|(style, _)| styled.style.contains(*style))
+            .map(|(_, code)| Cow::Borrowed(*code))
+            .collect();
+        codes.extend(styled.bgcolor.map(|color| color.to_bg_str()));
+        codes.extend(styled.fgcolor.map(|color| color.to_fg_str()));
+        write!(f, "\x1b[{}m{}\x1b[0m", codes.join(";"), styled.input)
+    }
+}
+
+impl RenderOptions {
+    /// Creates the default rendering options (colored, Unicode output, no hyperlinks).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether output should be plain ASCII, suitable for log files.
+    ///
+    /// Enabling it also selects [`Glyphs::ASCII`].
+    pub fn with_plain(mut self, plain: bool) -> Self {
+        self.plain = plain;
+        if plain {
+            self.glyphs = Glyphs::ASCII;
+        }
+        self
+    }
+
+    /// Sets the markers and borders drawn in console output; colors are unaffected.
+    pub fn with_glyphs(mut self, glyphs: Glyphs) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Sets how file paths are displayed.
+    pub fn with_path_style(mut self, path_style: PathStyle) -> Self {
+        self.path_style = path_style;
+        self
+    }
+
+    /// Sets whether file paths are wrapped in OSC8 hyperlinks.
+    pub fn with_hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = hyperlinks;
+        self
+    }
+
+    /// Overrides the indent unit used for code blocks (`None` detects it per file).
+    pub fn with_indent(mut self, indent: Option<IndentUnit>) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Sets whether long-running analyses may show a progress bar on stderr.
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Sets whether status lines on stderr are suppressed.
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Sets when console reports are piped through a pager.
+    pub fn with_pager(mut self, pager: PagerMode) -> Self {
+        self.pager = pager;
+        self
+    }
+
+    /// Restricts JSON output objects to the given top-level fields (empty keeps all).
+    pub fn with_fields(mut self, fields: Vec<String>) -> Self {
+        self.fields = (!fields.is_empty()).then_some(fields);
+        self
+    }
+
+    /// Serializes `value` as pretty JSON, projected down to [`Self::fields`].
+    ///
+    /// Requested fields that appear in no output object are logged as warnings.
+    pub fn to_json<T: Serialize + ?Sized>(&self, value: &T) -> String {
+        let Some(fields) = &self.fields else {
+            return serde_json::to_string_pretty(value).unwrap_or_default();
+        };
+
+        let value = serde_json::to_value(value).unwrap_or_default();
+        let (projected, unknown) = select_fields(value, fields);
+        for field in unknown {
+            log::warn!("Unknown field '{}' ignored by --fields", field);
+        }
+        serde_json::to_string_pretty(&projected).unwrap_or_default()
+    }
+
+    /// Sets whether HTML output has its indentation collapsed to single spaces.
+    pub fn with_minify_html(mut self, minify_html: bool) -> Self {
+        self.minify_html = minify_html;
+        self
+    }
+
+    /// Sets the class names used in HTML output (e.g. a prefix namespacing every class).
+    pub fn with_html(mut self, html: HtmlOptions) -> Self {
+        self.html = html;
+        self
+    }
+
+    /// HTML builder output, minified when [`Self::minify_html`] is set
+    fn finish_html(&self, html: String) -> String {
+        if self.minify_html {
+            minify_html(&html)
+        } else {
+            html
+        }
+    }
+
+    /// Shows only the innermost `depth` segments of each scope in console
+    /// headers, with scopes split on `separator` (JSON keeps the full scope).
+    pub fn with_scope_depth(mut self, depth: Option<usize>, separator: &str) -> Self {
+        self.scope_depth = depth;
+        self.scope_separator = separator.to_string();
+        self
+    }
+
+    /// `scope` as shown in console headers, cut to [`Self::scope_depth`] segments
+    fn display_scope<'s>(&self, scope: &'s str) -> Cow<'s, str> {
+        let Some(depth) = self.scope_depth else {
+            return Cow::Borrowed(scope);
+        };
+        let separator = if self.scope_separator.is_empty() { DEFAULT_SCOPE_SEPARATOR } else { &self.scope_separator };
+        truncate_scope(scope, depth, separator, self.glyphs.ellipsis)
+    }
+
+    /// Sets whether type errors are grouped under one header per file.
+    pub fn with_group_by_file(mut self, group_by_file: bool) -> Self {
+        self.group_by_file = group_by_file;
+        self
+    }
+
+    /// Links `text` to `filepath` when hyperlinks are enabled, otherwise returns `text` unchanged.
+    pub fn link_file(&self, text: &str, filepath: &str) -> String {
+        if self.hyperlinks && !self.plain {
+            link_file(text, filepath)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Returns `styled` unchanged, or with its colors and styles dropped in plain mode.
+    ///
+    /// With [`Self::force_color`] the styling is written even where `colored`
+    /// would leave it out (stdout is not a terminal, `NO_COLOR`).
+    ///
+    /// ```
+    /// use colored::Colorize;
+    /// use ta_lib::output::RenderOptions;
+    ///
+    /// let render = RenderOptions::new().with_plain(true);
+    /// assert_eq!(render.paint("error".red().bold()).to_string(), "error");
+    ///
+    /// let render = RenderOptions::new().with_force_color(true);
+    /// assert_eq!(render.paint("error".red().bold()).to_string(), "\x1b[1;31merror\x1b[0m");
+    /// ```
+    pub fn paint(&self, styled: ColoredString) -> Painted {
+        if self.plain {
+            Painted { styled: styled.clear(), force: false }
+        } else {
+            Painted { styled, force: self.force_color }
+        }
+    }
+
+    /// Sets whether [`Self::paint`] writes ANSI styling regardless of
+    /// `colored`'s own terminal detection; plain mode still wins.
+    pub fn with_force_color(mut self, force_color: bool) -> Self {
+        self.force_color = force_color;
+        self
+    }
+
+    /// Sets whether console symbol listings cut JSDoc to its first sentence
+    /// (JSON keeps the full text).
+    pub fn with_jsdoc_summary(mut self, jsdoc_summary: bool) -> Self {
+        self.jsdoc_summary = jsdoc_summary;
+        self
+    }
+
+    /// Sets how much of each symbol's shape is shown.
+    pub fn with_detail(mut self, detail: DetailLevel) -> Self {
+        self.detail = detail;
+        self
+    }
+
+    /// Sets the console width used to wrap long messages.
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
 }
 
 pub struct OutputFormatter;
@@ -41,20 +760,63 @@ pub struct OutputFormatter;
 impl OutputFormatter {
     /// Format a symbol signature with colors for console output
     pub fn format_symbol_signature_colored(symbol: &SymbolInfo) -> String {
-        use colored::*;
+        Self::format_symbol_signature_colored_with_detail(symbol, DetailLevel::Summary)
+    }
+
+    /// Format a symbol signature with colors, listing members according to `detail`.
+    ///
+    /// `Summary` shows the first three interface properties; `Full` shows every
+    /// property and method (with types) of interfaces, classes and object types.
+    pub fn format_symbol_signature_colored_with_detail(symbol: &SymbolInfo, detail: DetailLevel) -> String {
+        Self::format_symbol_signature_with_options(symbol, &RenderOptions::new().with_detail(detail))
+    }
+
+    /// Format a symbol signature for console output, listing members according
+    /// to `render.detail` and leaving out colors in plain mode.
+    pub fn format_symbol_signature_with_options(symbol: &SymbolInfo, render: &RenderOptions) -> String {
+        let detail = render.detail;
+
+        let members = |limit: Option<usize>| -> Option<String> {
+            let props = symbol.properties.as_ref().filter(|props| !props.is_empty())?;
+            let shown = limit.unwrap_or(props.len());
+            let prop_str = props.iter()
+                .take(shown)
+                .map(|p| {
+                    let (name, ty) = member_parts(p, detail);
+                    match ty {
+                        Some(ty) if ty.starts_with('(') => format!("{}{}", render.paint(name.yellow()), render.paint(ty.green())),
+                        Some(ty) => format!("{}: {}", render.paint(name.yellow()), render.paint(ty.green())),
+                        None => render.paint(name.yellow()).to_string(),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let suffix = if props.len() > shown { ", ..." } else { "" };
+            Some(format!("{{ {}{} }}", prop_str, suffix))
+        };
+
+        // Name plus any ` extends A implements B` clause
+        let mut declared = render.paint(symbol.name.cyan().bold()).to_string();
+        for (keyword, names) in [("extends", &symbol.extends), ("implements", &symbol.implements)] {
+            if !names.is_empty() {
+                let names = names.iter().map(|n| render.paint(n.cyan()).to_string()).collect::<Vec<_>>().join(", ");
+                declared.push_str(&format!(" {} {}", render.paint(keyword.magenta()), names));
+            }
+        }
 
-        match symbol.kind {
+        let signature = match symbol.kind {
             crate::models::SymbolKind::Function => {
-                let keyword = "function".magenta();
-                let name = symbol.name.cyan().bold();
+                let keyword = render.paint("function".magenta());
+                let name = render.paint(symbol.name.cyan().bold());
 
                 let params = if let Some(params) = &symbol.parameters {
                     params.iter()
                         .map(|p| {
                             if let Some(ty) = &p.type_annotation {
-                                format!("{}: {}", p.name.yellow(), ty.green())
+                                format!("{}: {}", render.paint(p.name.yellow()), render.paint(ty.green()))
                             } else {
-                                p.name.yellow().to_string()
+                                render.paint(p.name.yellow()).to_string()
                             }
                         })
                         .collect::<Vec<_>>()
@@ -64,285 +826,1234 @@ impl OutputFormatter {
                 };
 
                 if let Some(ret) = &symbol.return_type {
-                    format!("{} {}({}): {}", keyword, name, params, ret.green())
+                    format!("{} {}({}): {}", keyword, name, params, render.paint(ret.green()))
                 } else {
                     format!("{} {}({})", keyword, name, params)
                 }
             }
-            crate::models::SymbolKind::Class => {
-                format!("{} {}", "class".magenta(), symbol.name.cyan().bold())
-            }
             crate::models::SymbolKind::Interface => {
-                let keyword = "interface".magenta();
-                let name = symbol.name.cyan().bold();
-
-                if let Some(props) = &symbol.properties {
-                    if props.is_empty() {
-                        format!("{} {}", keyword, name)
-                    } else {
-                        let prop_str = props.iter()
-                            .take(3)
-                            .map(|p| {
-                                if let Some(ty) = &p.type_annotation {
-                                    format!("{}: {}", p.name.yellow(), ty.green())
-                                } else {
-                                    p.name.yellow().to_string()
-                                }
-                            })
-                            .collect::<Vec<_>>()
-                            .join(", ");
-
-                        let suffix = if props.len() > 3 { ", ..." } else { "" };
-                        format!("{} {} {{ {}{} }}", keyword, name, prop_str, suffix)
-                    }
-                } else {
-                    format!("{} {}", keyword, name)
+                let limit = match detail {
+                    DetailLevel::Summary => Some(3),
+                    DetailLevel::Full => None,
+                };
+                match members(limit) {
+                    Some(members) => format!("{} {} {}", render.paint("interface".magenta()), declared, members),
+                    None => format!("{} {}", render.paint("interface".magenta()), declared),
                 }
             }
-            crate::models::SymbolKind::Type => {
-                format!("{} {}", "type".magenta(), symbol.name.cyan().bold())
+            crate::models::SymbolKind::Class | crate::models::SymbolKind::Type => {
+                let keyword = if symbol.kind == crate::models::SymbolKind::Class { "class" } else { "type" };
+                match detail {
+                    DetailLevel::Full => match members(None) {
+                        Some(members) => format!("{} {} {}", render.paint(keyword.magenta()), declared, members),
+                        None => format!("{} {}", render.paint(keyword.magenta()), declared),
+                    },
+                    DetailLevel::Summary => format!("{} {}", render.paint(keyword.magenta()), declared),
+                }
             }
             crate::models::SymbolKind::Variable => {
-                format!("{} {}", "variable".magenta(), symbol.name.cyan().bold())
+                format!("{} {}", render.paint("variable".magenta()), render.paint(symbol.name.cyan().bold()))
             }
             crate::models::SymbolKind::Enum => {
-                format!("{} {}", "enum".magenta(), symbol.name.cyan().bold())
+                format!("{} {}", render.paint("enum".magenta()), render.paint(symbol.name.cyan().bold()))
             }
+        };
+
+        // Component props (functions and arrow components typed by a props interface)
+        let signature = match (&symbol.kind, detail) {
+            (crate::models::SymbolKind::Function | crate::models::SymbolKind::Variable, DetailLevel::Full) => match members(None) {
+                Some(members) => format!("{} {} {}", signature, render.paint("props".magenta()), members),
+                None => signature,
+            },
+            _ => signature,
+        };
+
+        if symbol.is_default_export {
+            format!("{} {}", render.paint("export default".magenta()), signature)
+        } else {
+            signature
         }
     }
 
     pub fn format_type_errors(errors: &[TypeError], format: OutputFormat) -> String {
-        match format {
-            OutputFormat::Console => Self::format_type_errors_console(errors),
-            OutputFormat::Html => Self::format_type_errors_html(errors),
-            OutputFormat::Json => serde_json::to_string_pretty(errors).unwrap_or_default(),
-        }
+        Self::format_type_errors_with_options(errors, format, &RenderOptions::default())
     }
 
-    pub fn format_symbols(symbols: &[SymbolInfo], format: OutputFormat) -> String {
+    /// Format type errors, honoring the given [`RenderOptions`] for console output.
+    pub fn format_type_errors_with_options(
+        errors: &[TypeError],
+        format: OutputFormat,
+        render: &RenderOptions,
+    ) -> String {
+        let paths = PathDisplay::new(render.path_style, errors.iter().map(|e| e.file.as_str()));
+        let width = render.width.unwrap_or(DEFAULT_WIDTH);
+
         match format {
-            OutputFormat::Console => Self::format_symbols_console(symbols),
-            OutputFormat::Html => Self::format_symbols_html(symbols),
-            OutputFormat::Json => serde_json::to_string_pretty(symbols).unwrap_or_default(),
+            // Markdown has no dedicated error layout; plain text reads fine in a document
+            OutputFormat::Console if render.plain => Self::format_type_errors_plain(errors, &paths, width, render),
+            OutputFormat::Markdown => Self::format_type_errors_plain(errors, &paths, width, render),
+            OutputFormat::Console => Self::format_type_errors_console(errors, &paths, width, render),
+            OutputFormat::Html => render.finish_html(Self::format_type_errors_html(errors, &paths, render)),
+            OutputFormat::Json => render.to_json(errors),
         }
     }
 
-    fn format_type_errors_console(errors: &[TypeError]) -> String {
-        let mut output = String::new();
+    /// Format type errors one per line as `file:line:col: [CODE] message`, like
+    /// `tsc --pretty false`, for Vim's quickfix list and grep-style tooling.
+    ///
+    /// Lines and columns are 1-indexed (columns count characters) and errors are
+    /// sorted by file, line and column. Multi-line messages are joined onto one line.
+    pub fn format_type_errors_compact(errors: &[TypeError], render: &RenderOptions) -> String {
+        let paths = PathDisplay::new(render.path_style, errors.iter().map(|e| e.file.as_str()));
+        let mut sorted: Vec<&TypeError> = errors.iter().collect();
+        sorted.sort_by(|a, b| {
+            (&a.file, a.position.start_line, a.position.start_column).cmp(&(&b.file, b.position.start_line, b.position.start_column))
+        });
 
-        for error in errors {
-            // New format: [❌] Message (bold)
-            //   in scope at file:line:col
+        let mut output = String::new();
+        for error in sorted {
+            let message = error.message.split_whitespace().collect::<Vec<_>>().join(" ");
             output.push_str(&format!(
-                "{} {}\n",
-                "[❌]".red().bold(),
-                error.message.bold()
+                "{}:{}:{}: [{}] {}\n",
+                paths.display(&error.file),
+                error.position.start_line,
+                error.position.start_column,
+                error.id,
+                message
             ));
+        }
+        output
+    }
+
+    /// Format one type error without the collection scaffolding (e.g. the HTML
+    /// `type-errors` wrapper or the JSON array), for incremental or streaming display.
+    pub fn format_single_error(error: &TypeError, format: OutputFormat) -> String {
+        Self::format_single_error_with_options(error, format, &RenderOptions::default())
+    }
+
+    /// [`Self::format_single_error`], honoring the given [`RenderOptions`].
+    ///
+    /// Grouping by file does not apply to a single error.
+    pub fn format_single_error_with_options(error: &TypeError, format: OutputFormat, render: &RenderOptions) -> String {
+        let paths = PathDisplay::new(render.path_style, [error.file.as_str()]);
+        Self::error_block(error, format, &paths, render.width.unwrap_or(DEFAULT_WIDTH), render)
+    }
+
+    /// One error in `format`, shared by the single and batch formatters
+    fn error_block(error: &TypeError, format: OutputFormat, paths: &PathDisplay, width: usize, render: &RenderOptions) -> String {
+        match format {
+            OutputFormat::Console if render.plain => Self::plain_error_block(error, &render.display_scope(&error.scope), &Self::error_location(error, paths), width),
+            OutputFormat::Markdown => Self::plain_error_block(error, &render.display_scope(&error.scope), &Self::error_location(error, paths), width),
+            OutputFormat::Console => {
+                // Use OSC8 hyperlink for clickable file path (when the terminal supports it)
+                let linked_file = render.link_file(&Self::error_location(error, paths), &error.file);
+                Self::console_error_block(error, &linked_file, width, render)
+            }
+            OutputFormat::Html => render.finish_html(Self::html_error_block(error, paths, render)),
+            OutputFormat::Json => render.to_json(error),
+        }
+    }
 
-            // Location line: in scope at file:line:col
-            // Use OSC8 hyperlink for clickable file path
-            let file_with_location = format!("{}:{}:{}", error.file, error.line, error.column);
-            let linked_file = link_file(&file_with_location, &error.file).blue();
+    /// `file:line:col` of an error
+    fn error_location(error: &TypeError, paths: &PathDisplay) -> String {
+        format!("{}:{}:{}", paths.display(&error.file), error.line, error.column)
+    }
 
+    /// Format symbols as a ctags `tags` file for Vim and Emacs.
+    ///
+    /// Each line is `name<TAB>file<TAB>/^source line$/;"<TAB>kind` with the kind
+    /// letter from [`SymbolKind::ctags_kind`](crate::models::SymbolKind::ctags_kind),
+    /// sorted by name (then file and line) as `!_TAG_FILE_SORTED` declares. The
+    /// pattern is the symbol's first line in `sources`, the text of each file by
+    /// its [`SymbolInfo::file`]; for files missing from it the line number is
+    /// used as the address instead.
+    pub fn format_symbols_ctags(symbols: &[SymbolInfo], sources: &HashMap<String, String>) -> String {
+        let maps: HashMap<&str, SourceMap<'_>> = sources.iter().map(|(file, text)| (file.as_str(), SourceMap::new(text))).collect();
+        let mut sorted: Vec<&SymbolInfo> = symbols.iter().collect();
+        sorted.sort_by(|a, b| (&a.name, &a.file, a.start_line).cmp(&(&b.name, &b.file, b.start_line)));
+
+        let mut output = String::from("!_TAG_FILE_FORMAT\t2\t/extended format/\n!_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted/\n");
+        for symbol in sorted {
+            let line = maps.get(symbol.file.as_str()).and_then(|map| map.line_text(symbol.start_line));
+            let address = match line {
+                Some(line) => format!("/^{}$/", line.replace('\\', "\\\\").replace('/', "\\/")),
+                None => symbol.start_line.to_string(),
+            };
             output.push_str(&format!(
-                "  {} {} {} {}\n\n",  // Add blank line after location
-                "in".dimmed(),
-                error.scope.cyan(),
-                "at".dimmed(),
-                linked_file
+                "{}\t{}\t{};\"\t{}\n",
+                symbol.name,
+                symbol.file.trim_start_matches("./"),
+                address,
+                symbol.kind.ctags_kind()
             ));
+        }
+        output
+    }
+
+    pub fn format_symbols(symbols: &[SymbolInfo], format: OutputFormat) -> String {
+        Self::format_symbols_with_options(symbols, format, &RenderOptions::default())
+    }
 
-            // Use new highlighting if available, fallback to legacy
-            if let Some(source) = &error.source_code {
-                // Create highlighting options with error annotations
-                // Note: TypeScript is a superset of JavaScript, so we use "js" syntax
-                // which is what syntect supports (TypeScript syntax is not included)
-                let options = HighlightOptions::new("js")
-                    .with_line_numbers(true)
-                    .with_indent(2)  // Indent code blocks for visual nesting
-                    .for_format(OutputFormat::Console);
+    /// Format symbols, honoring the given [`RenderOptions`] for console and HTML output.
+    pub fn format_symbols_with_options(
+        symbols: &[SymbolInfo],
+        format: OutputFormat,
+        render: &RenderOptions,
+    ) -> String {
+        let paths = PathDisplay::new(render.path_style, symbols.iter().map(|s| s.file.as_str()));
 
-                match highlight_code(&source.display_code, options) {
-                    Ok(highlighted) => {
-                        output.push_str(&highlighted.render_console());
-                        output.push('\n');
-                    }
-                    Err(e) => {
-                        // Log error for debugging, fallback to plain text
-                        log::debug!("Highlighting failed: {}", e);
-                        output.push_str(&format!("  {}\n", source.display_code.dimmed()));
-                    }
-                }
-            } else if !error.block.is_empty() {
-                // Legacy fallback
-                output.push_str(&format!("  {}\n", error.block.dimmed()));
-            }
+        match format {
+            OutputFormat::Console => Self::format_symbols_console(symbols, &paths, render),
+            OutputFormat::Html => render.finish_html(Self::format_symbols_html(symbols, &paths, render)),
+            OutputFormat::Json => render.to_json(symbols),
+            OutputFormat::Markdown => Self::format_symbols_markdown(symbols, &paths),
+        }
+    }
+
+    fn format_type_errors_console(errors: &[TypeError], paths: &PathDisplay, width: usize, render: &RenderOptions) -> String {
+        let mut output = String::new();
+
+        if render.group_by_file {
+            for (file, group) in group_by_file(errors, |e| &e.file) {
+                let header = render.paint(render.link_file(&paths.display(file), file).blue().bold());
+                output.push_str(&format!("{} {}\n\n", header, render.paint(count_label(group.len(), "error").dimmed())));
+
+                for error in group {
+                    // The file is in the header, so only line:col is repeated
+                    let location = format!("{}:{}", error.line, error.column);
+                    let block = Self::console_error_block(error, &location, width.saturating_sub(2), render);
+                    output.push_str(&indent_lines(&block, 2));
+                }
+            }
+            return output;
+        }
+
+        for error in errors {
+            output.push_str(&Self::error_block(error, OutputFormat::Console, paths, width, render));
+        }
+
+        output
+    }
+
+    /// One console error: message, `in scope at <location>`, then highlighted code.
+    fn console_error_block(error: &TypeError, location: &str, width: usize, render: &RenderOptions) -> String {
+        let mut output = String::new();
+        let glyphs = render.glyphs;
+
+        // New format: [❌] Message (bold), wrapped with a hanging indent
+        //   in scope at file:line:col
+        // Warnings use the warning marker; the hanging indent matches the marker's
+        // display width ("[❌]" is 4 columns)
+        let (glyph, marker) = match error.severity {
+            ErrorSeverity::Warning => (glyphs.warning, render.paint(glyphs.warning.yellow().bold())),
+            _ => (glyphs.error, render.paint(glyphs.error.red().bold())),
+        };
+        let hang = UnicodeWidthStr::width(glyph) + 1;
+        for (i, line) in wrap_message(&error.message, width, hang).iter().enumerate() {
+            if i == 0 {
+                output.push_str(&format!("{} {}\n", marker, render.paint(line.bold())));
+            } else {
+                output.push_str(&format!("{}{}\n", " ".repeat(hang), render.paint(line.bold())));
+            }
+        }
+
+        // Location line: in scope at file:line:col
+        output.push_str(&format!(
+            "  {} {} {} {}\n",
+            render.paint("in".dimmed()),
+            render.paint(render.display_scope(&error.scope).cyan()),
+            render.paint("at".dimmed()),
+            render.paint(location.blue())
+        ));
+        for related in &error.related {
+            output.push_str(&format!("  {}\n", render.paint(related_location(related).dimmed())));
+        }
+        if let Some(hint) = explain_hint(&error.id) {
+            output.push_str(&format!("  {}\n", render.paint(hint.dimmed())));
+        }
+        output.push('\n');
+
+        // Use new highlighting if available, fallback to legacy
+        if let Some(source) = &error.source_code {
+            // Create highlighting options with error annotations
+            // Note: TypeScript is a superset of JavaScript, so we use "js" syntax
+            // which is what syntect supports (TypeScript syntax is not included)
+            // Nest the code block by one indent unit, matching the file's own style
+            let options = HighlightOptions::new("js")
+                .with_line_numbers(true)
+                .with_indent(1)
+                .with_indent_unit(render.indent.unwrap_or(source.indent_unit))
+                .for_format(OutputFormat::Console);
+
+            match highlight_code(&truncation_markers(&source.display_code, &glyphs), options) {
+                Ok(highlighted) => {
+                    output.push_str(&highlighted.render_console());
+                    output.push('\n');
+                }
+                Err(e) => {
+                    // Log error for debugging, fallback to plain text
+                    log::debug!("Highlighting failed: {}", e);
+                    output.push_str(&format!("  {}\n", render.paint(truncation_markers(&source.display_code, &glyphs).dimmed())));
+                }
+            }
+        } else if !error.block.is_empty() {
+            // Legacy fallback
+            output.push_str(&format!("  {}\n", render.paint(truncation_markers(&error.block, &glyphs).dimmed())));
+        }
+
+        output.push('\n');
+        output
+    }
+
+    /// Plain ASCII variant of [`Self::format_type_errors_console`] for logs and CI.
+    fn format_type_errors_plain(errors: &[TypeError], paths: &PathDisplay, width: usize, render: &RenderOptions) -> String {
+        let mut output = String::new();
+
+        if render.group_by_file {
+            for (file, group) in group_by_file(errors, |e| &e.file) {
+                output.push_str(&format!("{} {}\n\n", paths.display(file), count_label(group.len(), "error")));
+                for error in group {
+                    let location = format!("{}:{}", error.line, error.column);
+                    let block = Self::plain_error_block(error, &render.display_scope(&error.scope), &location, width.saturating_sub(2));
+                    output.push_str(&indent_lines(&block, 2));
+                }
+            }
+            return output;
+        }
+
+        for error in errors {
+            output.push_str(&Self::plain_error_block(error, &render.display_scope(&error.scope), &Self::error_location(error, paths), width));
+        }
+
+        output
+    }
+
+    /// One plain-ASCII error: `[X]` message, scope and location, and `  | ` code lines.
+    fn plain_error_block(error: &TypeError, scope: &str, location: &str, width: usize) -> String {
+        let mut output = String::new();
+
+        for (i, line) in wrap_message(&error.message, width, 4).iter().enumerate() {
+            let prefix = if i == 0 { format!("{} ", Glyphs::ASCII.error) } else { "    ".to_string() };
+            output.push_str(&format!("{}{}\n", prefix, line));
+        }
+        output.push_str(&format!("  in {} at {}\n", scope, location));
+        for related in &error.related {
+            output.push_str(&format!("  {}\n", related_location(related)));
+        }
+        if let Some(hint) = explain_hint(&error.id) {
+            output.push_str(&format!("  {}\n", hint));
+        }
+        output.push('\n');
+
+        let code = match &error.source_code {
+            Some(source) => plain_truncation_markers(&source.display_code),
+            None => plain_truncation_markers(&error.block),
+        };
+
+        if !code.is_empty() {
+            for line in code.lines() {
+                output.push_str(&format!("  | {}\n", line));
+            }
+            output.push('\n');
+        }
+
+        output.push('\n');
+        output
+    }
+
+    fn format_type_errors_html(errors: &[TypeError], paths: &PathDisplay, render: &RenderOptions) -> String {
+        let mut output = format!("<div class=\"{}\">\n", render.html.class("type-errors"));
+
+        for error in errors {
+            output.push_str(&Self::html_error_block(error, paths, render));
+        }
+
+        output.push_str("</div>");
+        output
+    }
+
+    /// One `error-block` div: header, location, message and highlighted code.
+    fn html_error_block(error: &TypeError, paths: &PathDisplay, render: &RenderOptions) -> String {
+        let class = |name: &str| render.html.class(name);
+        let mut output = String::new();
+
+        output.push_str(&format!(
+            r#"<div class="{}">
+  <div class="{}">
+    <span class="{}">[{}]</span>
+    <span class="{}">in</span>
+    <span class="{}">{}</span>
+  </div>
+  <div class="{}">
+    <span class="{}">at</span>
+    <span class="{}">{}:{}:{}</span>
+  </div>
+  <div class="{}">{}</div>
+"#,
+            class("error-block"),
+            class("error-header"),
+            class("error-id"),
+            html_escape::encode_text(&error.id),
+            class("keyword"),
+            class("scope"),
+            html_escape::encode_text(&error.scope),
+            class("error-location"),
+            class("keyword"),
+            class("file-path"),
+            html_escape::encode_text(&paths.display(&error.file)),
+            error.line,
+            error.column,
+            class("error-message"),
+            html_escape::encode_text(&error.message)
+        ));
+        for related in &error.related {
+            output.push_str(&format!(
+                "  <div class=\"{}\">{}</div>\n",
+                class("related-location"),
+                html_escape::encode_text(&related_location(related))
+            ));
+        }
+
+        // Use highlighting for HTML output
+        // TypeScript uses JavaScript syntax (syntect doesn't have native TS support)
+        if let Some(source) = &error.source_code {
+            let options = HighlightOptions::new("js")
+                .with_line_numbers(true)
+                .with_indent(1)  // Indent code blocks for visual nesting
+                .with_indent_unit(render.indent.unwrap_or(source.indent_unit))
+                .with_html(render.html.clone())
+                .for_format(OutputFormat::Html);
+
+            let folds = source.truncation_info.as_ref().filter(|info| !info.truncated_sections.is_empty());
+            if let Some(info) = folds {
+                output.push_str(&format!("  <div class=\"{}\">\n", class("code-highlight")));
+                output.push_str(&folded_code_html(&source.full_code, info, &options));
+                output.push_str("  </div>\n");
+            } else {
+                match highlight_code(&source.display_code, options) {
+                    Ok(highlighted) => {
+                        output.push_str(&format!("  <div class=\"{}\">\n", class("code-highlight")));
+                        output.push_str(&highlighted.render_html());
+                        output.push_str("  </div>\n");
+                    }
+                    Err(_) => {
+                        // Fallback
+                        output.push_str(&format!("  <pre>{}</pre>\n",
+                            html_escape::encode_text(&source.display_code)));
+                    }
+                }
+            }
+        } else if !error.block.is_empty() {
+            // Legacy fallback
+            output.push_str(&format!("  <pre>{}</pre>\n",
+                html_escape::encode_text(&error.block)));
+        }
+
+        output.push_str("</div>\n");
+        output
+    }
+
+    fn format_symbols_console(symbols: &[SymbolInfo], paths: &PathDisplay, render: &RenderOptions) -> String {
+        let mut output = String::new();
+
+        if render.group_by_file {
+            for (file, group) in group_by_file(symbols, |s| &s.file) {
+                let header = render.paint(render.link_file(&paths.display(file), file).blue().bold());
+                output.push_str(&format!("{} {}\n\n", header, render.paint(count_label(group.len(), "symbol").dimmed())));
+
+                for symbol in group {
+                    // The file is in the header, so only the line range is repeated
+                    let location = format!("{}-{}", symbol.start_line, symbol.end_line);
+                    output.push_str(&indent_lines(&Self::console_symbol_block(symbol, &location, render), 2));
+                }
+            }
+            return output;
+        }
+
+        for symbol in symbols {
+            let location = format!("{}:{}-{}", paths.display(&symbol.file), symbol.start_line, symbol.end_line);
+            output.push_str(&Self::console_symbol_block(symbol, &location, render));
+        }
+
+        output
+    }
+
+    /// One console symbol: colored signature, `location` and JSDoc.
+    fn console_symbol_block(symbol: &SymbolInfo, location: &str, render: &RenderOptions) -> String {
+        let mut output = String::new();
+
+        // Use colored signature display
+        let signature = Self::format_symbol_signature_with_options(symbol, render);
+
+        // File path in blue
+        let file_str = render.paint(render.link_file(location, &symbol.file).blue());
+
+        output.push_str(&format!("{} {}\n", signature, file_str));
+
+        // JSDoc if present
+        if let Some(jsdoc) = &symbol.jsdoc {
+            let jsdoc = if render.jsdoc_summary { jsdoc_summary(jsdoc) } else { jsdoc };
+            for line in jsdoc.lines() {
+                if line.is_empty() {
+                    output.push('\n');
+                } else {
+                    output.push_str(&format!("  {}\n", render.paint(line.dimmed().italic())));
+                }
+            }
+        }
+
+        output.push('\n');
+        output
+    }
+
+    /// Markdown API reference: one section per symbol kind, each a table of
+    /// name, signature, location and JSDoc summary.
+    fn format_symbols_markdown(symbols: &[SymbolInfo], paths: &PathDisplay) -> String {
+        use crate::models::SymbolKind;
+
+        let sections = [
+            (SymbolKind::Function, "Functions"),
+            (SymbolKind::Class, "Classes"),
+            (SymbolKind::Interface, "Interfaces"),
+            (SymbolKind::Type, "Types"),
+            (SymbolKind::Enum, "Enums"),
+            (SymbolKind::Variable, "Variables"),
+        ];
+
+        let mut output = String::from("# API Reference\n");
+
+        for (kind, heading) in sections {
+            let in_section: Vec<&SymbolInfo> = symbols.iter().filter(|s| s.kind == kind).collect();
+            if in_section.is_empty() {
+                continue;
+            }
+
+            output.push_str(&format!("\n## {}\n\n", heading));
+            output.push_str("| Name | Signature | Location | Description |\n");
+            output.push_str("| ---- | --------- | -------- | ----------- |\n");
+
+            for symbol in in_section {
+                let description = symbol.jsdoc.as_deref().map(jsdoc_summary).unwrap_or_default();
+                output.push_str(&format!(
+                    "| `{}` | `{}` | `{}:{}` | {} |\n",
+                    escape_table_cell(&symbol.name),
+                    escape_table_cell(&symbol.display_signature()),
+                    escape_table_cell(&paths.display(&symbol.file)),
+                    symbol.start_line,
+                    escape_table_cell(description)
+                ));
+            }
+        }
+
+        output
+    }
+
+    fn format_symbols_html(symbols: &[SymbolInfo], paths: &PathDisplay, render: &RenderOptions) -> String {
+        let mut output = format!("<div class=\"{}\">\n", render.html.class("symbols"));
+
+        if render.group_by_file {
+            for (file, group) in group_by_file(symbols, |s| &s.file) {
+                output.push_str(&format!(
+                    "<section class=\"{}\" data-file=\"{}\" data-count=\"{}\">\n",
+                    render.html.class("symbol-file"),
+                    html_escape::encode_double_quoted_attribute(&paths.display(file)),
+                    group.len()
+                ));
+                for symbol in group {
+                    output.push_str(&Self::html_symbol_block(symbol, paths, render));
+                }
+                output.push_str("</section>\n");
+            }
+        } else {
+            for symbol in symbols {
+                output.push_str(&Self::html_symbol_block(symbol, paths, render));
+            }
+        }
+
+        output.push_str("</div>");
+        output
+    }
+
+    /// One `symbol-block` div: header, heritage, parameters and properties.
+    fn html_symbol_block(symbol: &SymbolInfo, paths: &PathDisplay, render: &RenderOptions) -> String {
+        let class = |name: &str| render.html.class(name);
+        let mut output = String::new();
+
+        let default_export = if symbol.is_default_export {
+            format!(r#"<span class="{}">export default</span> "#, class("keyword"))
+        } else {
+            String::new()
+        };
+        output.push_str(&format!(
+            r#"<div class="{}" data-kind="{:?}" data-default-export="{}">
+  <div class="{}">
+    {}<span class="{}">{:?}</span>
+    <span class="{}">{}</span>
+    <span class="{}">{}:{}-{}</span>
+  </div>
+"#,
+            class("symbol-block"),
+            symbol.kind,
+            symbol.is_default_export,
+            class("symbol-header"),
+            default_export,
+            class("keyword"),
+            symbol.kind,
+            class("symbol-name"),
+            html_escape::encode_text(&symbol.name),
+            class("file-path"),
+            html_escape::encode_text(&paths.display(&symbol.file)),
+            symbol.start_line,
+            symbol.end_line
+        ));
+
+        // Heritage (extends / implements)
+        if !symbol.extends.is_empty() || !symbol.implements.is_empty() {
+            output.push_str(&format!(r#"  <div class="{}">"#, class("heritage")));
+            for (keyword, names) in [("extends", &symbol.extends), ("implements", &symbol.implements)] {
+                if !names.is_empty() {
+                    output.push_str(&format!(r#"<span class="{}">{}</span>"#, class("keyword"), keyword));
+                    for name in names {
+                        output.push_str(&format!(
+                            r#"<span class="{}">{}</span>"#,
+                            class("type-annotation"),
+                            html_escape::encode_text(name)
+                        ));
+                    }
+                }
+            }
+            output.push_str("</div>\n");
+        }
+
+        // Parameters
+        if let Some(params) = &symbol.parameters {
+            if !params.is_empty() {
+                output.push_str(&format!(r#"  <div class="{}">"#, class("parameters")));
+                for param in params {
+                    output.push_str(&format!(
+                        r#"<span class="{}">{}</span>"#,
+                        class("type-annotation"),
+                        html_escape::encode_text(&param.name)
+                    ));
+                }
+                output.push_str("</div>\n");
+            }
+        }
+
+        // Properties
+        if let Some(props) = &symbol.properties {
+            if !props.is_empty() {
+                output.push_str(&format!(r#"  <div class="{}">"#, class("properties")));
+                for prop in props {
+                    let text = match (render.detail, member_parts(prop, render.detail)) {
+                        (DetailLevel::Full, (name, Some(ty))) if ty.starts_with('(') => format!("{}{}", name, ty),
+                        (DetailLevel::Full, (name, Some(ty))) => format!("{}: {}", name, ty),
+                        _ => prop.name.clone(),
+                    };
+                    output.push_str(&format!(
+                        r#"<span class="{}">{}</span>"#,
+                        class("type-annotation"),
+                        html_escape::encode_text(&text)
+                    ));
+                }
+                output.push_str("</div>\n");
+            }
+        }
+
+        output.push_str("</div>\n");
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc_span::Span;
+    use crate::models::{SpanPosition, SymbolKind};
+    use crate::highlighting::ErrorSeverity;
+    use serial_test::serial;
+
+    #[test]
+    fn test_format_type_errors_json() {
+        let error = TypeError {
+            id: "TS100".to_string(),
+            message: "Error msg".to_string(),
+            severity: ErrorSeverity::Error,
+            file: "test.ts".to_string(),
+            line: 1,
+            column: 1,
+            scope: "global".to_string(),
+            block: "code".to_string(),
+            source_code: None,
+            span: Span::new(0, 4),
+            position: SpanPosition::default(),
+            related: Vec::new(),
+        };
+        let output = OutputFormatter::format_type_errors(&[error], OutputFormat::Json);
+        assert!(output.contains("TS100"));
+    }
+
+    #[test]
+    fn test_wrap_message_hanging_indent() {
+        let message = "Argument of type '{ name: string; age: number; }' is not assignable to parameter \
+                       of type 'User' because property 'email' is missing in the provided object literal";
+        let lines = wrap_message(message, 60, 5);
+
+        assert!(lines.len() > 1);
+        for line in &lines {
+            assert!(line.chars().count() <= 55, "Line too long: {}", line);
+        }
+        // Quoted type kept intact on one line
+        assert!(lines.iter().any(|l| l.contains("'{ name: string; age: number; }'")));
+        assert_eq!(lines.join(" "), message.split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+
+    #[test]
+    fn test_wrap_message_apostrophes_inside_words_are_not_quotes() {
+        let lines = wrap_message("Value can't be 'string | number' for this field", 20, 0);
+        assert_eq!(lines, vec!["Value can't be", "'string | number'", "for this field"]);
+
+        let lines = wrap_message("Property 'O'Brien' is missing in type 'Customer'", 20, 0);
+        assert_eq!(lines, vec!["Property 'O'Brien'", "is missing in type", "'Customer'"]);
+    }
+
+    #[test]
+    fn test_wrap_message_short_message_single_line() {
+        assert_eq!(wrap_message("Short message", 80, 5), vec!["Short message"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_console_long_message_wraps_with_consistent_indent() {
+        colored::control::set_override(false);
+
+        let message = "Type 'string' is not assignable to type 'number'. ".repeat(5);
+        let error = TypeError {
+            id: "TS2322".to_string(),
+            message: message.trim().to_string(),
+            severity: ErrorSeverity::Error,
+            file: "test.ts".to_string(),
+            line: 1,
+            column: 1,
+            scope: "global".to_string(),
+            block: String::new(),
+            source_code: None,
+            span: Span::new(0, 1),
+            position: SpanPosition::default(),
+            related: Vec::new(),
+        };
+        let render = RenderOptions::new().with_width(60);
+        let output = OutputFormatter::format_type_errors_with_options(
+            &[error],
+            OutputFormat::Console,
+            &render,
+        );
+
+        colored::control::unset_override();
+
+        let message_lines: Vec<&str> = output.lines().take_while(|l| !l.trim_start().starts_with("in ")).collect();
+        assert!(message_lines.len() > 1, "Got: {}", output);
+        assert!(message_lines[0].starts_with("[❌] Type"));
+        for line in &message_lines[1..] {
+            assert!(line.starts_with("     "), "Bad indent: {:?}", line);
+            assert!(!line.starts_with("      "), "Bad indent: {:?}", line);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_format_single_error_has_no_collection_wrapper() {
+        colored::control::set_override(false);
+
+        let error = TypeError {
+            id: "TS2322".to_string(),
+            message: "Type 'string' is not assignable to type 'number'.".to_string(),
+            severity: ErrorSeverity::Error,
+            file: "src/app.ts".to_string(),
+            line: 3,
+            column: 7,
+            scope: "global".to_string(),
+            block: "const x: number = 'a';".to_string(),
+            source_code: None,
+            span: Span::new(0, 1),
+            position: SpanPosition::default(),
+            related: Vec::new(),
+        };
+
+        let html = OutputFormatter::format_single_error(&error, OutputFormat::Html);
+        assert!(html.starts_with("<div class=\"error-block\">"), "Got: {}", html);
+        assert!(!html.contains("type-errors"));
+        assert!(html.contains("src/app.ts:3:7"));
+
+        // The batch formatter wraps exactly the single-error block
+        let batch = OutputFormatter::format_type_errors(std::slice::from_ref(&error), OutputFormat::Html);
+        assert!(batch.contains(&html));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&OutputFormatter::format_single_error(&error, OutputFormat::Json)).unwrap();
+        assert_eq!(json["id"], "TS2322");
+
+        let console = OutputFormatter::format_single_error(&error, OutputFormat::Console);
+        assert!(console.contains("in global at src/app.ts:3:7"), "Got: {}", console);
+
+        colored::control::unset_override();
+    }
+
+    #[test]
+    fn test_plain_error_hints_at_explain_for_ts_codes() {
+        let mut error = TypeError {
+            id: "TS2322".to_string(),
+            message: "Type 'string' is not assignable to type 'number'.".to_string(),
+            severity: ErrorSeverity::Error,
+            file: "test.ts".to_string(),
+            line: 1,
+            column: 1,
+            scope: "global".to_string(),
+            block: String::new(),
+            source_code: None,
+            span: Span::new(0, 1),
+            position: SpanPosition::default(),
+            related: Vec::new(),
+        };
+        let render = RenderOptions::new().with_plain(true);
+        let output = OutputFormatter::format_type_errors_with_options(&[error.clone()], OutputFormat::Console, &render);
+        assert!(output.contains("TS2322 (run `ta explain TS2322`)"), "Got: {}", output);
+
+        error.id = "PARSE".to_string();
+        let output = OutputFormatter::format_type_errors_with_options(&[error], OutputFormat::Console, &render);
+        assert!(!output.contains("ta explain"), "Got: {}", output);
+    }
+
+    #[test]
+    fn test_path_style_basename_shrinks_deep_path() {
+        assert_eq!(PathStyle::Basename.display("src/deep/dir/file.ts"), "file.ts");
+
+        let paths = PathDisplay::new(PathStyle::Basename, ["src/deep/dir/file.ts"]);
+        assert_eq!(paths.display("src/deep/dir/file.ts"), "file.ts");
+    }
+
+    #[test]
+    fn test_path_style_basename_disambiguates_collisions() {
+        let paths = PathDisplay::new(
+            PathStyle::Basename,
+            ["./src/a/index.ts", "./src/b/index.ts", "./src/a/index.ts", "./src/main.ts"],
+        );
+        assert_eq!(paths.display("./src/a/index.ts"), "index.ts (src/a)");
+        assert_eq!(paths.display("./src/b/index.ts"), "index.ts (src/b)");
+        assert_eq!(paths.display("./src/main.ts"), "main.ts");
+    }
+
+    #[test]
+    fn test_path_style_relative_and_absolute() {
+        assert_eq!(PathStyle::Relative.display("src/a.ts"), "src/a.ts");
+
+        let absolute = PathStyle::Absolute.display("./src/a.ts");
+        assert!(Path::new(&absolute).is_absolute());
+        assert!(absolute.ends_with("src/a.ts"));
+        assert!(!absolute.contains("/./"));
+    }
+
+    #[test]
+    fn test_path_style_repo_relative_to_git_root() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        let file = dir.path().join("packages/app/src/main.ts");
+
+        assert_eq!(
+            PathStyle::Repo.display(&file.to_string_lossy()),
+            "packages/app/src/main.ts"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_format_symbols_basename_path_style() {
+        colored::control::set_override(false);
+
+        let symbol = SymbolInfo {
+            name: "deep".to_string(),
+            kind: SymbolKind::Variable,
+            file: "src/deep/dir/file.ts".to_string(),
+            start_line: 1,
+            end_line: 1,
+            exported: false,
+            is_default_export: false,
+            parameters: None,
+            properties: None,
+            return_type: None,
+            jsdoc: None,
+            extends: Vec::new(),
+            implements: Vec::new(),
+            references: Vec::new(),
+        };
+        let render = RenderOptions::new().with_path_style(PathStyle::Basename);
+        let output = OutputFormatter::format_symbols_with_options(
+            std::slice::from_ref(&symbol),
+            OutputFormat::Console,
+            &render,
+        );
+
+        colored::control::unset_override();
+
+        assert!(output.contains("file.ts:1-1"));
+        assert!(!output.contains("src/deep/dir"));
+    }
+
+    #[test]
+    fn test_console_error_context_keeps_tab_indentation() {
+        use crate::analyzer::{AnalysisOptions, Analyzer};
+
+        control::set_override(false);
+        let source = "function tabs() {\n\tif (true) {\n\t\tlet x = 1;\n\t\tlet x = 2;\n\t}\n}\n";
+        let analysis = Analyzer::new(AnalysisOptions::default())
+            .analyze_source(source, "tabs.ts")
+            .unwrap();
+        let error = &analysis.type_errors[0];
+        assert_eq!(error.source_code.as_ref().unwrap().indent_unit, IndentUnit::Tab);
+
+        let output = OutputFormatter::format_type_errors(&analysis.type_errors, OutputFormat::Console);
+        control::unset_override();
+
+        // Drop the highlighter's color codes to compare the whitespace itself
+        let mut text = String::new();
+        let mut chars = output.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                text.push(c);
+            }
+        }
+        let output = text;
+
+        assert!(output.contains("\n\tfunction tabs() {\n\t\tif (true) {\n\t\t\tlet x = 1;"), "{:?}", output);
+        assert!(!output.contains("  function tabs()"));
+
+        let spaced = RenderOptions::new().with_indent(Some(IndentUnit::Spaces(4)));
+        let output = OutputFormatter::format_type_errors_with_options(&analysis.type_errors, OutputFormat::Html, &spaced);
+        assert!(output.contains("    <span"), "{}", output);
+    }
+
+    #[test]
+    fn test_html_folds_truncated_function_sections() {
+        use crate::analyzer::{AnalysisOptions, Analyzer};
+
+        let body = |prefix: &str| -> String {
+            (0..20).map(|i| format!("    const {}{} = {};\n", prefix, i, i)).collect()
+        };
+        let source = format!(
+            "function long() {{\n{}    let x = 1;\n    let x = 2;\n{}}}\n",
+            body("before"),
+            body("after")
+        );
+        let analysis = Analyzer::new(AnalysisOptions::default())
+            .analyze_source(&source, "long.ts")
+            .unwrap();
+        let error = &analysis.type_errors[0];
+        let info = error.source_code.as_ref().unwrap().truncation_info.as_ref().unwrap();
+        assert_eq!(info.truncated_sections.len(), 2);
+
+        let html = OutputFormatter::format_type_errors(&analysis.type_errors, OutputFormat::Html);
+
+        assert_eq!(html.matches("<details class=\"code-fold\">").count(), 2);
+        assert!(html.contains("<summary>19 lines omitted</summary>"), "{}", html);
+        assert!(html.contains("<summary>18 lines omitted</summary>"), "{}", html);
+        // Folded lines are present in the markup, only collapsed
+        assert!(html.contains("before0"));
+        assert!(!html.contains(crate::highlighting::code_context::TRUNCATION_MARKER));
+    }
+
+    #[test]
+    fn test_format_symbols_markdown_tables() {
+        use crate::models::ParameterInfo;
+
+        let symbol = |name: &str, kind: SymbolKind, jsdoc: Option<&str>| SymbolInfo {
+            name: name.to_string(),
+            kind,
+            file: "src/api.ts".to_string(),
+            start_line: 3,
+            end_line: 5,
+            exported: true,
+            is_default_export: false,
+            parameters: None,
+            properties: None,
+            return_type: None,
+            jsdoc: jsdoc.map(str::to_string),
+            extends: Vec::new(),
+            implements: Vec::new(),
+            references: Vec::new(),
+        };
+        let mut fetch = symbol("fetchUser", SymbolKind::Function, Some("Loads a user. Retries once. @param id the id"));
+        fetch.parameters = Some(vec![ParameterInfo {
+            name: "id".to_string(),
+            type_annotation: Some("string | number".to_string()),
+            description: None,
+        }]);
+        fetch.return_type = Some("User".to_string());
+        let symbols = [fetch, symbol("User", SymbolKind::Interface, None)];
+
+        let md = OutputFormatter::format_symbols(&symbols, OutputFormat::Markdown);
+
+        assert!(md.contains("## Functions"));
+        assert!(md.contains("## Interfaces"));
+        assert!(md.contains("| Name | Signature | Location | Description |"));
+        assert!(
+            md.contains("| `fetchUser` | `function fetchUser(id: string \\| number): User` | `src/api.ts:3` | Loads a user. |"),
+            "{}",
+            md
+        );
+        assert!(md.find("## Functions").unwrap() < md.find("## Interfaces").unwrap());
+    }
+
+    #[test]
+    fn test_jsdoc_summary_truncates_console_only() {
+        let symbol = SymbolInfo {
+            name: "parse".to_string(),
+            kind: SymbolKind::Function,
+            file: "src/parse.ts".to_string(),
+            start_line: 1,
+            end_line: 3,
+            exported: true,
+            is_default_export: false,
+            parameters: None,
+            properties: None,
+            return_type: None,
+            jsdoc: Some("Parses a file. Comments are ignored.\n\nSecond paragraph.".to_string()),
+            extends: Vec::new(),
+            implements: Vec::new(),
+            references: Vec::new(),
+        };
+        let symbols = [symbol];
 
-            output.push('\n');
-        }
+        let full = OutputFormatter::format_symbols_with_options(&symbols, OutputFormat::Console, &RenderOptions::new());
+        assert!(full.contains("Comments are ignored."), "{}", full);
+        assert!(full.contains("Second paragraph."), "{}", full);
 
-        output
+        let render = RenderOptions::new().with_jsdoc_summary(true);
+        let summary = OutputFormatter::format_symbols_with_options(&symbols, OutputFormat::Console, &render);
+        assert!(summary.contains("Parses a file."), "{}", summary);
+        assert!(!summary.contains("Comments are ignored."), "{}", summary);
+        assert!(!summary.contains("Second paragraph."), "{}", summary);
+
+        let json = OutputFormatter::format_symbols_with_options(&symbols, OutputFormat::Json, &render);
+        assert!(json.contains(r#""jsdoc": "Parses a file. Comments are ignored.\n\nSecond paragraph.""#), "{}", json);
     }
 
-    fn format_type_errors_html(errors: &[TypeError]) -> String {
-        let mut output = String::from("<div class=\"type-errors\">\n");
+    #[test]
+    #[serial]
+    fn test_symbols_grouped_by_file_show_each_path_once() {
+        colored::control::set_override(false);
 
-        for error in errors {
-            output.push_str(&format!(
-                r#"<div class="error-block">
-  <div class="error-header">
-    <span class="error-id">[{}]</span>
-    <span class="keyword">in</span>
-    <span class="scope">{}</span>
-  </div>
-  <div class="error-location">
-    <span class="keyword">at</span>
-    <span class="file-path">{}:{}:{}</span>
-  </div>
-  <div class="error-message">{}</div>
-"#,
-                html_escape::encode_text(&error.id),
-                html_escape::encode_text(&error.scope),
-                html_escape::encode_text(&error.file),
-                error.line,
-                error.column,
-                html_escape::encode_text(&error.message)
-            ));
+        let symbol = |name: &str, file: &str, line: usize| SymbolInfo {
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            file: file.to_string(),
+            start_line: line,
+            end_line: line + 2,
+            exported: true,
+            is_default_export: false,
+            parameters: None,
+            properties: None,
+            return_type: None,
+            jsdoc: None,
+            extends: Vec::new(),
+            implements: Vec::new(),
+            references: Vec::new(),
+        };
+        let symbols = [
+            symbol("first", "src/math.ts", 1),
+            symbol("second", "src/math.ts", 5),
+            symbol("other", "src/io.ts", 1),
+            symbol("third", "src/math.ts", 9),
+        ];
+        let render = RenderOptions::new().with_plain(true).with_group_by_file(true);
+
+        let console = OutputFormatter::format_symbols_with_options(&symbols, OutputFormat::Console, &render);
+        assert_eq!(console.matches("src/math.ts").count(), 1, "{}", console);
+        assert!(console.contains("src/math.ts (3 symbols)"), "{}", console);
+        assert!(console.contains("src/io.ts (1 symbol)"), "{}", console);
+        assert!(console.find("third").unwrap() < console.find("src/io.ts").unwrap(), "{}", console);
+        assert!(console.contains("  function third() 9-11"), "{}", console);
+
+        let html = OutputFormatter::format_symbols_with_options(&symbols, OutputFormat::Html, &render);
+        assert_eq!(html.matches("<section").count(), 2, "{}", html);
+        assert!(html.contains(r#"<section class="symbol-file" data-file="src/math.ts" data-count="3">"#), "{}", html);
+
+        // Flat remains the default
+        let flat = OutputFormatter::format_symbols_with_options(&symbols, OutputFormat::Console, &RenderOptions::new().with_plain(true));
+        assert_eq!(flat.matches("src/math.ts").count(), 3, "{}", flat);
 
-            // Use highlighting for HTML output
-            // TypeScript uses JavaScript syntax (syntect doesn't have native TS support)
-            if let Some(source) = &error.source_code {
-                let options = HighlightOptions::new("js")
-                    .with_line_numbers(true)
-                    .with_indent(2)  // Indent code blocks for visual nesting
-                    .for_format(OutputFormat::Html);
+        colored::control::unset_override();
+    }
 
-                match highlight_code(&source.display_code, options) {
-                    Ok(highlighted) => {
-                        output.push_str("  <div class=\"code-highlight\">\n");
-                        output.push_str(&highlighted.render_html());
-                        output.push_str("  </div>\n");
-                    }
-                    Err(_) => {
-                        // Fallback
-                        output.push_str(&format!("  <pre>{}</pre>\n",
-                            html_escape::encode_text(&source.display_code)));
-                    }
-                }
-            } else if !error.block.is_empty() {
-                // Legacy fallback
-                output.push_str(&format!("  <pre>{}</pre>\n",
-                    html_escape::encode_text(&error.block)));
-            }
+    #[test]
+    fn test_select_fields_projects_objects_and_reports_unknown() {
+        let value = serde_json::json!([
+            { "file": "a.ts", "message": "m", "line": 1 },
+            { "file": "b.ts", "message": "n", "line": 2 },
+        ]);
+        let fields = vec!["file".to_string(), "message".to_string(), "nope".to_string()];
+
+        let (projected, unknown) = select_fields(value, &fields);
+
+        assert_eq!(projected, serde_json::json!([
+            { "file": "a.ts", "message": "m" },
+            { "file": "b.ts", "message": "n" },
+        ]));
+        assert_eq!(unknown, vec!["nope".to_string()]);
+
+        let (_, unknown) = select_fields(serde_json::json!([]), &fields);
+        assert!(unknown.is_empty());
+    }
 
-            output.push_str("</div>\n");
+    #[test]
+    #[serial]
+    fn test_grouped_console_output_prints_each_file_once() {
+        colored::control::set_override(false);
+
+        let error = |file: &str, line: usize| TypeError {
+            id: "TS2451".to_string(),
+            message: "Cannot redeclare block-scoped variable".to_string(),
+            severity: ErrorSeverity::Error,
+            file: file.to_string(),
+            line,
+            column: 3,
+            scope: "global".to_string(),
+            block: String::new(),
+            source_code: None,
+            span: Span::new(0, 1),
+            position: SpanPosition::default(),
+            related: Vec::new(),
+        };
+        let errors = [error("src/a.ts", 1), error("src/b.ts", 4), error("src/a.ts", 9)];
+
+        for render in [RenderOptions::new(), RenderOptions::new().with_plain(true)] {
+            let render = render.with_group_by_file(true);
+            let output = OutputFormatter::format_type_errors_with_options(&errors, OutputFormat::Console, &render);
+
+            assert_eq!(output.matches("src/a.ts").count(), 1, "{}", output);
+            assert!(output.contains("src/a.ts (2 errors)"));
+            assert!(output.contains("src/b.ts (1 error)"));
+            assert!(output.contains("at 9:3"));
+            // Files appear in the order of their first error
+            assert!(output.find("src/a.ts").unwrap() < output.find("src/b.ts").unwrap());
         }
 
-        output.push_str("</div>");
-        output
+        colored::control::unset_override();
     }
 
-    fn format_symbols_console(symbols: &[SymbolInfo]) -> String {
-        let mut output = String::new();
-
-        for symbol in symbols {
-            // Use colored signature display
-            let signature = Self::format_symbol_signature_colored(symbol);
+    #[test]
+    #[serial]
+    fn test_scope_depth_truncates_console_scope_only() {
+        colored::control::set_override(false);
+
+        let errors = [TypeError {
+            id: "TS2451".to_string(),
+            message: "Cannot redeclare block-scoped variable".to_string(),
+            severity: ErrorSeverity::Error,
+            file: "src/a.ts".to_string(),
+            line: 1,
+            column: 3,
+            scope: "a.b.c.d.e".to_string(),
+            block: String::new(),
+            source_code: None,
+            span: Span::new(0, 1),
+            position: SpanPosition::default(),
+            related: Vec::new(),
+        }];
 
-            // File path in blue
-            let file_str = format!("{}:{}-{}", symbol.file, symbol.start_line, symbol.end_line).blue();
+        let render = RenderOptions::new().with_scope_depth(Some(2), ".");
+        let output = OutputFormatter::format_type_errors_with_options(&errors, OutputFormat::Console, &render);
+        assert!(output.contains("in …d.e at"), "{}", output);
+        assert!(!output.contains("a.b.c"));
 
-            output.push_str(&format!("{} {}\n", signature, file_str));
+        let plain = OutputFormatter::format_type_errors_with_options(&errors, OutputFormat::Console, &render.clone().with_plain(true));
+        assert!(plain.contains("in ...d.e at"), "{}", plain);
 
-            // JSDoc if present
-            if let Some(jsdoc) = &symbol.jsdoc {
-                output.push_str(&format!("  {}\n", jsdoc.dimmed().italic()));
-            }
+        let json = OutputFormatter::format_type_errors_with_options(&errors, OutputFormat::Json, &render);
+        assert!(json.contains("\"a.b.c.d.e\""));
 
-            output.push('\n');
-        }
+        let unlimited = OutputFormatter::format_type_errors_with_options(&errors, OutputFormat::Console, &RenderOptions::new());
+        assert!(unlimited.contains("in a.b.c.d.e at"));
 
-        output
+        colored::control::unset_override();
     }
 
-    fn format_symbols_html(symbols: &[SymbolInfo]) -> String {
-        let mut output = String::from("<div class=\"symbols\">\n");
-
-        for symbol in symbols {
-            output.push_str(&format!(
-                r#"<div class="symbol-block" data-kind="{:?}">
-  <div class="symbol-header">
-    <span class="keyword">{:?}</span>
-    <span class="symbol-name">{}</span>
-    <span class="file-path">{}:{}-{}</span>
-  </div>
-"#,
-                symbol.kind,
-                symbol.kind,
-                html_escape::encode_text(&symbol.name),
-                html_escape::encode_text(&symbol.file),
-                symbol.start_line,
-                symbol.end_line
-            ));
+    #[test]
+    fn test_truncate_scope_uses_separator() {
+        assert_eq!(truncate_scope("mod::Outer::inner::cb", 2, "::", "…"), "…inner::cb");
+        assert_eq!(truncate_scope("Class#method", 1, ".", "…"), "Class#method");
+        assert_eq!(truncate_scope("a.b.c", 0, ".", "..."), "...c");
+    }
 
-            // Parameters
-            if let Some(params) = &symbol.parameters {
-                if !params.is_empty() {
-                    output.push_str(r#"  <div class="parameters">"#);
-                    for param in params {
-                        output.push_str(&format!(
-                            r#"<span class="type-annotation">{}</span>"#,
-                            html_escape::encode_text(&param.name)
-                        ));
-                    }
-                    output.push_str("</div>\n");
-                }
-            }
+    #[test]
+    #[serial]
+    fn test_plain_console_output_is_ascii() {
+        colored::control::set_override(true);
 
-            // Properties
-            if let Some(props) = &symbol.properties {
-                if !props.is_empty() {
-                    output.push_str(r#"  <div class="properties">"#);
-                    for prop in props {
-                        output.push_str(&format!(
-                            r#"<span class="type-annotation">{}</span>"#,
-                            html_escape::encode_text(&prop.name)
-                        ));
-                    }
-                    output.push_str("</div>\n");
-                }
-            }
+        let error = TypeError {
+            id: "TS2322".to_string(),
+            message: "Type 'string' is not assignable to type 'number'".to_string(),
+            severity: ErrorSeverity::Error,
+            file: "test.ts".to_string(),
+            line: 5,
+            column: 10,
+            scope: "myFunction".to_string(),
+            block: "const x: number = \"hello\";\n┄┄┄\nreturn x;".to_string(),
+            source_code: None,
+            span: Span::new(0, 10),
+            position: SpanPosition::default(),
+            related: Vec::new(),
+        };
+        let render = RenderOptions::new().with_plain(true);
+        let output = OutputFormatter::format_type_errors_with_options(
+            &[error],
+            OutputFormat::Console,
+            &render,
+        );
 
-            output.push_str("</div>\n");
-        }
+        colored::control::unset_override();
 
-        output.push_str("</div>");
-        output
+        assert!(output.is_ascii(), "Plain output contained non-ASCII: {}", output);
+        assert!(!output.contains('\x1b'));
+        assert!(output.contains("[X] Type 'string'"));
+        assert!(output.contains("  in myFunction at test.ts:5:10"));
+        assert!(output.contains("  | const x: number"));
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use oxc_span::Span;
-    use crate::models::SymbolKind;
-    use serial_test::serial;
 
     #[test]
-    fn test_format_type_errors_json() {
+    #[serial]
+    fn test_force_color_styles_console_output_while_colors_are_off() {
+        colored::control::set_override(false);
+
         let error = TypeError {
-            id: "TS100".to_string(),
-            message: "Error msg".to_string(),
+            id: "TS2322".to_string(),
+            message: "Type mismatch".to_string(),
+            severity: ErrorSeverity::Error,
             file: "test.ts".to_string(),
             line: 1,
             column: 1,
             scope: "global".to_string(),
-            block: "code".to_string(),
+            block: String::new(),
             source_code: None,
-            span: Span::new(0, 4),
+            span: Span::new(0, 1),
+            position: SpanPosition::default(),
+            related: Vec::new(),
         };
-        let output = OutputFormatter::format_type_errors(&[error], OutputFormat::Json);
-        assert!(output.contains("TS100"));
+        let format = |render: &RenderOptions| {
+            OutputFormatter::format_type_errors_with_options(std::slice::from_ref(&error), OutputFormat::Console, render)
+        };
+        let unforced = format(&RenderOptions::new());
+        let forced = format(&RenderOptions::new().with_force_color(true));
+        let plain = format(&RenderOptions::new().with_plain(true).with_force_color(true));
+
+        colored::control::unset_override();
+
+        assert!(!unforced.contains('\x1b'), "{}", unforced);
+        assert!(forced.contains("\x1b[1mType mismatch\x1b[0m"), "{}", forced);
+        assert!(forced.contains("\x1b[36mglobal\x1b[0m"), "{}", forced);
+        assert!(!plain.contains('\x1b'), "{}", plain);
     }
 
     #[test]
@@ -355,6 +2066,7 @@ mod tests {
         let errors = vec![TypeError {
             id: "TS2322".to_string(),
             message: "Type mismatch".to_string(),
+            severity: ErrorSeverity::Error,
             file: "test.ts".to_string(),
             line: 42,
             column: 10,
@@ -362,6 +2074,8 @@ mod tests {
             block: String::new(),
             source_code: None,
             span: Span::new(0, 10),
+            position: SpanPosition::default(),
+            related: Vec::new(),
         }];
 
         let output = OutputFormatter::format_type_errors(&errors, OutputFormat::Console);
@@ -386,6 +2100,7 @@ mod tests {
         let errors = vec![TypeError {
             id: "TS2322".to_string(),
             message: "Type mismatch".to_string(),
+            severity: ErrorSeverity::Error,
             file: "test.ts".to_string(),
             line: 42,
             column: 10,
@@ -393,6 +2108,8 @@ mod tests {
             block: String::new(),
             source_code: None,
             span: Span::new(0, 10),
+            position: SpanPosition::default(),
+            related: Vec::new(),
         }];
 
         let output = OutputFormatter::format_type_errors(&errors, OutputFormat::Console);
@@ -412,6 +2129,7 @@ mod tests {
         let errors = vec![TypeError {
             id: "TS2322".to_string(),
             message: "Type mismatch".to_string(),
+            severity: ErrorSeverity::Error,
             file: "test.ts".to_string(),
             line: 42,
             column: 10,
@@ -419,6 +2137,8 @@ mod tests {
             block: String::new(),
             source_code: None,
             span: Span::new(0, 10),
+            position: SpanPosition::default(),
+            related: Vec::new(),
         }];
 
         let output = OutputFormatter::format_type_errors(&errors, OutputFormat::Html);
@@ -429,6 +2149,104 @@ mod tests {
         assert!(output.contains("class=\"error-message\""), "HTML should contain error-message class");
     }
 
+    /// Every class name in every `class="..."` attribute of `html`
+    fn html_classes(html: &str) -> Vec<String> {
+        html.split("class=\"")
+            .skip(1)
+            .flat_map(|attr| attr.split('"').next().unwrap().split_whitespace().map(str::to_string).collect::<Vec<_>>())
+            .collect()
+    }
+
+    #[test]
+    fn test_html_class_prefix_applies_to_every_class() {
+        use crate::models::ParameterInfo;
+
+        let source = "class Base {}\nexport class Shape extends Base {\n  area(size: number) {\n    let a = 1;\n    let a = 2;\n    return a;\n  }\n}\n";
+        let result = crate::analyzer::Analyzer::new(crate::analyzer::AnalysisOptions::default())
+            .analyze_source(source, "test.ts")
+            .unwrap();
+        assert!(!result.type_errors.is_empty());
+        let mut symbols = result.symbols;
+        symbols[0].is_default_export = true;
+        symbols[0].parameters = Some(vec![ParameterInfo { name: "size".to_string(), type_annotation: None, description: None }]);
+
+        let render = RenderOptions::new().with_html(HtmlOptions::new().with_class_prefix("ta-"));
+        let errors = OutputFormatter::format_type_errors_with_options(&result.type_errors, OutputFormat::Html, &render);
+        let symbols = OutputFormatter::format_symbols_with_options(&symbols, OutputFormat::Html, &render);
+
+        for html in [errors, symbols] {
+            let classes = html_classes(&html);
+            assert!(classes.len() > 5, "{}", html);
+            let unprefixed: Vec<_> = classes.iter().filter(|c| !c.starts_with("ta-")).collect();
+            assert!(unprefixed.is_empty(), "{:?} in {}", unprefixed, html);
+        }
+
+        // No prefix keeps the current class names
+        let plain = OutputFormatter::format_type_errors(&result.type_errors, OutputFormat::Html);
+        assert!(html_classes(&plain).contains(&"error-block".to_string()));
+    }
+
+    #[test]
+    fn test_minified_html_keeps_only_pre_newlines() {
+        let source = "function f() {\n  let a = 1;\n  let a = 2;\n  return a;\n}\n";
+        let errors = crate::analyzer::Analyzer::new(crate::analyzer::AnalysisOptions::default())
+            .analyze_source(source, "test.ts")
+            .unwrap()
+            .type_errors;
+        assert!(!errors.is_empty());
+
+        let readable = OutputFormatter::format_type_errors(&errors, OutputFormat::Html);
+        let render = RenderOptions::new().with_minify_html(true);
+        let minified = OutputFormatter::format_type_errors_with_options(&errors, OutputFormat::Html, &render);
+
+        let pre_blocks = |html: &str| -> Vec<String> {
+            html.split("<pre").skip(1).map(|block| block.split("</pre>").next().unwrap().to_string()).collect()
+        };
+        assert!(!pre_blocks(&minified).is_empty());
+        assert_eq!(pre_blocks(&minified), pre_blocks(&readable));
+
+        let outside_pre: String = minified
+            .split("<pre")
+            .enumerate()
+            .map(|(i, part)| if i == 0 { part } else { part.split_once("</pre>").map_or("", |(_, after)| after) })
+            .collect();
+        assert!(!outside_pre.contains('\n'), "{}", minified);
+        assert!(minified.len() < readable.len());
+    }
+
+    #[test]
+    fn test_minify_html_collapses_whitespace() {
+        assert_eq!(minify_html("<ul>\n  <li>a  b</li>\n  <li> c</li>\n</ul>\n"), "<ul> <li>a b</li> <li> c</li> </ul>");
+        assert_eq!(minify_html("<div>\n  <pre class=\"x\">  a\n    b</pre>\n</div>"), "<div> <pre class=\"x\">  a\n    b</pre> </div>");
+        assert_eq!(minify_html("<pretty>\n</pretty>"), "<pretty> </pretty>");
+    }
+
+    #[test]
+    fn test_minified_error_header_keeps_words_apart() {
+        let errors = crate::analyzer::Analyzer::new(crate::analyzer::AnalysisOptions::default())
+            .analyze_source("let a = 1;\nlet a = 2;\n", "./src/test.ts")
+            .unwrap()
+            .type_errors;
+        let render = RenderOptions::new().with_minify_html(true);
+        let minified = OutputFormatter::format_type_errors_with_options(&errors, OutputFormat::Html, &render);
+
+        // The text a browser shows, with tags removed
+        let header = minified.split("<pre").next().unwrap();
+        let mut text = String::new();
+        let mut in_tag = false;
+        for c in header.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                _ if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        assert!(text.contains(&format!("[{}] in global", errors[0].id)), "{}", text);
+        assert!(text.contains("at ./src/test.ts:"), "{}", text);
+    }
+
     #[test]
     #[serial]
     fn test_format_symbols_console_colorization() {
@@ -443,10 +2261,14 @@ mod tests {
             start_line: 1,
             end_line: 10,
             exported: true,
+            is_default_export: false,
             parameters: None,
             properties: None,
             return_type: None,
             jsdoc: None,
+            extends: Vec::new(),
+            implements: Vec::new(),
+            references: Vec::new(),
         };
 
         let output = OutputFormatter::format_symbols(&[symbol], OutputFormat::Console);
@@ -465,6 +2287,271 @@ mod tests {
         colored::control::unset_override();
     }
 
+    #[test]
+    #[serial]
+    fn test_plain_symbols_skip_colors_even_when_forced() {
+        use crate::models::ParameterInfo;
+
+        colored::control::set_override(true);
+
+        let symbol = SymbolInfo {
+            name: "greet".to_string(),
+            kind: SymbolKind::Function,
+            file: "greet.ts".to_string(),
+            start_line: 1,
+            end_line: 3,
+            exported: true,
+            is_default_export: true,
+            parameters: Some(vec![ParameterInfo {
+                name: "name".to_string(),
+                type_annotation: Some("string".to_string()),
+                description: None,
+            }]),
+            properties: None,
+            return_type: Some("string".to_string()),
+            jsdoc: Some("Says hello.".to_string()),
+            extends: Vec::new(),
+            implements: Vec::new(),
+            references: Vec::new(),
+        };
+
+        let render = RenderOptions::new().with_plain(true).with_group_by_file(true);
+        let output = OutputFormatter::format_symbols_with_options(&[symbol], OutputFormat::Console, &render);
+
+        assert!(!output.contains('\x1b'), "Plain output should have no ANSI codes. Got: {}", output);
+        assert!(output.contains("export default function greet(name: string): string 1-3"), "Got: {}", output);
+
+        colored::control::unset_override();
+    }
+
+    #[test]
+    #[serial]
+    fn test_format_symbols_console_default_export() {
+        colored::control::set_override(false);
+
+        let symbol = SymbolInfo {
+            name: "App".to_string(),
+            kind: SymbolKind::Class,
+            file: "app.ts".to_string(),
+            start_line: 1,
+            end_line: 3,
+            exported: true,
+            is_default_export: true,
+            parameters: None,
+            properties: None,
+            return_type: None,
+            jsdoc: None,
+            extends: Vec::new(),
+            implements: Vec::new(),
+            references: Vec::new(),
+        };
+
+        let output = OutputFormatter::format_symbols(std::slice::from_ref(&symbol), OutputFormat::Console);
+        assert!(output.contains("export default class App"), "Got: {}", output);
+        assert_eq!(symbol.display_signature(), "export default class App");
+
+        let html = OutputFormatter::format_symbols(&[symbol], OutputFormat::Html);
+        assert!(html.contains("data-default-export=\"true\""));
+
+        colored::control::unset_override();
+    }
+
+    #[test]
+    #[serial]
+    fn test_format_symbols_full_detail_lists_all_members() {
+        colored::control::set_override(false);
+
+        let prop = |name: &str, ty: &str| PropertyInfo {
+            name: name.to_string(),
+            type_annotation: Some(ty.to_string()),
+            ..Default::default()
+        };
+        let symbol = SymbolInfo {
+            name: "Repo".to_string(),
+            kind: SymbolKind::Interface,
+            file: "repo.ts".to_string(),
+            start_line: 1,
+            end_line: 6,
+            exported: true,
+            is_default_export: false,
+            parameters: None,
+            properties: Some(vec![
+                prop("id", "string"),
+                prop("name", "string"),
+                prop("tags", "string[]"),
+                prop("find()", "(id: number): User"),
+            ]),
+            return_type: None,
+            jsdoc: None,
+            extends: Vec::new(),
+            implements: Vec::new(),
+            references: Vec::new(),
+        };
+
+        let summary = OutputFormatter::format_symbol_signature_colored(&symbol);
+        assert!(summary.contains("tags: string[], ..."), "Got: {}", summary);
+        assert!(!summary.contains("find"));
+
+        let full = OutputFormatter::format_symbol_signature_colored_with_detail(&symbol, DetailLevel::Full);
+        assert!(full.contains("find(id: number): User"), "Got: {}", full);
+        assert!(!full.contains("..."));
+
+        let render = RenderOptions::new().with_detail(DetailLevel::Full);
+        let html = OutputFormatter::format_symbols_with_options(&[symbol], OutputFormat::Html, &render);
+        assert!(html.contains("tags: string[]"));
+
+        colored::control::unset_override();
+    }
+
+    #[test]
+    #[serial]
+    fn test_full_detail_shows_member_modifiers() {
+        colored::control::set_override(false);
+
+        let source = "export interface Entity { readonly x?: string }\n\
+                      export class Registry { private static count: number = 0; static create?(): Registry {} }\n";
+        let analysis = crate::analyzer::Analyzer::new(crate::analyzer::AnalysisOptions::default())
+            .analyze_source(source, "src/registry.ts")
+            .unwrap();
+        let symbol = |name: &str| analysis.symbols.iter().find(|s| s.name == name).unwrap();
+
+        let full = OutputFormatter::format_symbol_signature_colored_with_detail(symbol("Entity"), DetailLevel::Full);
+        assert!(full.contains("{ readonly x?: string }"), "Got: {}", full);
+        let summary = OutputFormatter::format_symbol_signature_colored(symbol("Entity"));
+        assert!(summary.contains("{ x: string }"), "Got: {}", summary);
+
+        let full = OutputFormatter::format_symbol_signature_colored_with_detail(symbol("Registry"), DetailLevel::Full);
+        assert!(full.contains("{ private static count: number, static create?(): Registry }"), "Got: {}", full);
+
+        let render = RenderOptions::new().with_detail(DetailLevel::Full);
+        let html = OutputFormatter::format_symbols_with_options(&[symbol("Entity").clone()], OutputFormat::Html, &render);
+        assert!(html.contains("readonly x?: string"));
+
+        colored::control::unset_override();
+    }
+
+    #[test]
+    #[serial]
+    fn test_full_detail_lists_component_props() {
+        colored::control::set_override(false);
+
+        let source = "interface ButtonProps { label: string; size?: number }\n\
+                      export function Button(props: ButtonProps) { return <button>{props.label}</button>; }\n";
+        let analysis = crate::analyzer::Analyzer::new(crate::analyzer::AnalysisOptions::default())
+            .analyze_source(source, "src/Button.tsx")
+            .unwrap();
+        let button = analysis.symbols.iter().find(|s| s.name == "Button").unwrap();
+
+        let full = OutputFormatter::format_symbol_signature_colored_with_detail(button, DetailLevel::Full);
+        assert!(full.contains("props { label: string, size?: number }"), "Got: {}", full);
+
+        let summary = OutputFormatter::format_symbol_signature_colored(button);
+        assert!(!summary.contains("label"), "Got: {}", summary);
+
+        colored::control::unset_override();
+    }
+
+    #[test]
+    fn test_compact_errors_sorted_one_per_line() {
+        let error = |file: &str, line: usize, column: usize, message: &str| TypeError {
+            id: "TS2322".to_string(),
+            message: message.to_string(),
+            severity: ErrorSeverity::Error,
+            file: file.to_string(),
+            line,
+            column: column - 1,
+            scope: "global".to_string(),
+            block: String::new(),
+            source_code: None,
+            span: Span::new(0, 1),
+            position: SpanPosition { start_line: line, start_column: column, end_line: line, end_column: column + 1 },
+            related: Vec::new(),
+        };
+        let output = OutputFormatter::format_type_errors_compact(
+            &[error("b.ts", 1, 1, "last"), error("a.ts", 9, 4, "second"), error("a.ts", 2, 7, "first\n  continued")],
+            &RenderOptions::default(),
+        );
+        assert_eq!(output, "a.ts:2:7: [TS2322] first continued\na.ts:9:4: [TS2322] second\nb.ts:1:1: [TS2322] last\n");
+    }
+
+    #[test]
+    fn test_ctags_falls_back_to_line_number() {
+        let symbol = |name: &str, kind: SymbolKind, line: usize| SymbolInfo {
+            name: name.to_string(),
+            kind,
+            file: "./missing/api.ts".to_string(),
+            start_line: line,
+            end_line: line,
+            exported: true,
+            is_default_export: false,
+            parameters: None,
+            properties: None,
+            return_type: None,
+            jsdoc: None,
+            extends: Vec::new(),
+            implements: Vec::new(),
+            references: Vec::new(),
+        };
+        let tags = OutputFormatter::format_symbols_ctags(&[
+            symbol("load", SymbolKind::Function, 7),
+            symbol("Config", SymbolKind::Interface, 2),
+        ], &HashMap::new());
+
+        let lines: Vec<&str> = tags.lines().skip(2).collect();
+        assert_eq!(lines, vec!["Config\tmissing/api.ts\t2;\"\ti", "load\tmissing/api.ts\t7;\"\tf"]);
+
+        // Patterns come from the given source text, escaped for a search command
+        let sources = HashMap::from([("./missing/api.ts".to_string(), "interface Config {}\r\nconst load = (a: string) => a.split('/');\n".to_string())]);
+        let tags = OutputFormatter::format_symbols_ctags(&[symbol("load", SymbolKind::Function, 2)], &sources);
+        assert_eq!(tags.lines().nth(2), Some("load\tmissing/api.ts\t/^const load = (a: string) => a.split('\\/');$/;\"\tf"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_format_symbols_console_heritage() {
+        colored::control::set_override(false);
+
+        let symbol = SymbolInfo {
+            name: "Dog".to_string(),
+            kind: SymbolKind::Class,
+            file: "dog.ts".to_string(),
+            start_line: 1,
+            end_line: 1,
+            exported: true,
+            is_default_export: false,
+            parameters: None,
+            properties: None,
+            return_type: None,
+            jsdoc: None,
+            extends: vec!["Animal".to_string()],
+            implements: vec!["Pet".to_string()],
+            references: Vec::new(),
+        };
+
+        let output = OutputFormatter::format_symbols(std::slice::from_ref(&symbol), OutputFormat::Console);
+        assert!(output.contains("class Dog extends Animal implements Pet"), "Got: {}", output);
+
+        let html = OutputFormatter::format_symbols(&[symbol], OutputFormat::Html);
+        assert!(html.contains(r#"<div class="heritage"><span class="keyword">extends</span>"#));
+
+        colored::control::unset_override();
+    }
+
+    #[test]
+    fn test_link_file_respects_hyperlink_toggle() {
+        let disabled = RenderOptions::new();
+        assert_eq!(disabled.link_file("src/a.ts:1:2", "src/a.ts"), "src/a.ts:1:2");
+
+        let enabled = RenderOptions::new().with_hyperlinks(true);
+        let linked = enabled.link_file("src/a.ts:1:2", "src/a.ts");
+        assert!(linked.starts_with("\x1b]8;;file://"));
+        assert!(linked.contains("src/a.ts:1:2"));
+
+        // Plain output never carries escapes
+        let plain = RenderOptions::new().with_hyperlinks(true).with_plain(true);
+        assert!(!plain.link_file("src/a.ts", "src/a.ts").contains("\x1b]8;;"));
+    }
+
     #[test]
     fn test_format_symbols_html_classes() {
         use crate::models::{PropertyInfo, ParameterInfo};
@@ -476,6 +2563,7 @@ mod tests {
             start_line: 1,
             end_line: 10,
             exported: true,
+            is_default_export: false,
             parameters: Some(vec![ParameterInfo {
                 name: "param1".to_string(),
                 type_annotation: Some("string".to_string()),
@@ -484,10 +2572,13 @@ mod tests {
             properties: Some(vec![PropertyInfo {
                 name: "prop1".to_string(),
                 type_annotation: Some("number".to_string()),
-                description: None,
+                ..Default::default()
             }]),
             return_type: None,
             jsdoc: None,
+            extends: Vec::new(),
+            implements: Vec::new(),
+            references: Vec::new(),
         };
         let output = OutputFormatter::format_symbols(&[symbol], OutputFormat::Html);
 
@@ -509,6 +2600,7 @@ mod tests {
             start_line: 5,
             end_line: 10,
             exported: true,
+            is_default_export: false,
             parameters: Some(vec![
                 ParameterInfo {
                     name: "a".to_string(),
@@ -524,6 +2616,9 @@ mod tests {
             properties: None,
             return_type: None,
             jsdoc: None,
+            extends: Vec::new(),
+            implements: Vec::new(),
+            references: Vec::new(),
         };
         // Clear any previous color settings and force enable colors for testing
         colored::control::unset_override();
@@ -0,0 +1,155 @@
+//! Custom diagnostic rules configured by the project (`[[rules]]` in `ta.toml`).
+//!
+//! Each rule pairs a matcher for one kind of AST node with the diagnostic
+//! reported wherever it matches. Rules are deserialized from configuration, e.g.
+//!
+//! ```toml
+//! [[rules]]
+//! kind = "call"
+//! callee = "console.log"
+//! code = "NO_CONSOLE"
+//! message = "Use the logger instead of console.log"
+//! severity = "error"
+//! ```
+
+use oxc_ast::ast::{CallExpression, Expression, Program};
+use oxc_ast::visit::{walk, Visit};
+use oxc_diagnostics::{OxcDiagnostic, Severity};
+use serde::Deserialize;
+
+use crate::highlighting::ErrorSeverity;
+
+/// One configured rule
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CustomRule {
+    /// Error id of the reported diagnostics (e.g. `NO_CONSOLE`)
+    pub code: String,
+    pub message: String,
+    /// Defaults to a warning
+    #[serde(default = "default_severity")]
+    pub severity: ErrorSeverity,
+    #[serde(flatten)]
+    pub matcher: RuleMatcher,
+}
+
+fn default_severity() -> ErrorSeverity {
+    ErrorSeverity::Warning
+}
+
+/// The nodes a rule applies to, selected by the rule's `kind`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum RuleMatcher {
+    /// Calls whose callee is `callee`: a name (`eval`) or a member chain (`console.log`)
+    Call { callee: String },
+}
+
+impl CustomRule {
+    fn diagnostic(&self, span: oxc_span::Span) -> OxcDiagnostic {
+        let diagnostic = match self.severity {
+            ErrorSeverity::Error => OxcDiagnostic::error(self.message.clone()),
+            ErrorSeverity::Warning => OxcDiagnostic::warn(self.message.clone()),
+            _ => OxcDiagnostic::warn(self.message.clone()).with_severity(Severity::Advice),
+        };
+        diagnostic.with_error_code_scope(self.code.clone()).with_label(span)
+    }
+}
+
+/// Run `rules` over `program`, one diagnostic per matching node in source order
+pub fn find_rule_violations(program: &Program<'_>, rules: &[CustomRule]) -> Vec<OxcDiagnostic> {
+    if rules.is_empty() {
+        return Vec::new();
+    }
+    let mut runner = RuleRunner { rules, diagnostics: Vec::new() };
+    runner.visit_program(program);
+    runner.diagnostics
+}
+
+/// Walks the AST once, testing every node against each rule's matcher
+struct RuleRunner<'r> {
+    rules: &'r [CustomRule],
+    diagnostics: Vec<OxcDiagnostic>,
+}
+
+impl<'a> Visit<'a> for RuleRunner<'_> {
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        if let Some(callee) = callee_name(&call.callee) {
+            for rule in self.rules {
+                let RuleMatcher::Call { callee: banned } = &rule.matcher;
+                if *banned == callee {
+                    self.diagnostics.push(rule.diagnostic(call.span));
+                }
+            }
+        }
+        walk::walk_call_expression(self, call);
+    }
+}
+
+/// `console.log` for `console.log(...)`; `None` for computed or called callees
+fn callee_name(expression: &Expression<'_>) -> Option<String> {
+    match expression.without_parentheses() {
+        Expression::Identifier(ident) => Some(ident.name.to_string()),
+        Expression::ThisExpression(_) => Some("this".to_string()),
+        Expression::StaticMemberExpression(member) => {
+            Some(format!("{}.{}", callee_name(&member.object)?, member.property.name))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisOptions, Analyzer};
+
+    fn ban(callee: &str, severity: ErrorSeverity) -> CustomRule {
+        CustomRule {
+            code: "BANNED".to_string(),
+            message: format!("Do not call {}", callee),
+            severity,
+            matcher: RuleMatcher::Call { callee: callee.to_string() },
+        }
+    }
+
+    #[test]
+    fn test_banned_console_log_is_reported_at_each_call() {
+        let source = "console.log('start');\nexport function run() {\n  console.warn('ok');\n  (console).log('done');\n  console?.log('maybe');\n}\n";
+        let errors = Analyzer::new(AnalysisOptions { rules: vec![ban("console.log", ErrorSeverity::Error)], ..Default::default() })
+            .analyze_source(source, "rules.ts")
+            .unwrap()
+            .type_errors;
+
+        let mut lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        lines.sort();
+        assert_eq!(lines, vec![1, 4, 5]);
+        assert!(errors.iter().all(|e| e.id == "BANNED" && e.message == "Do not call console.log"));
+        assert!(errors.iter().all(|e| e.severity == ErrorSeverity::Error));
+        assert!(errors.iter().any(|e| e.scope == "run"));
+    }
+
+    #[test]
+    fn test_rule_deserializes_with_default_severity() {
+        let rule: CustomRule = serde_json::from_str(r#"{ "kind": "call", "callee": "eval", "code": "NO_EVAL", "message": "no eval" }"#).unwrap();
+        assert_eq!(rule, CustomRule { code: "NO_EVAL".to_string(), ..ban("eval", ErrorSeverity::Warning) }.with_message("no eval"));
+
+        let unknown = serde_json::from_str::<CustomRule>(r#"{ "kind": "import", "code": "X", "message": "x" }"#);
+        assert!(unknown.is_err());
+    }
+
+    impl CustomRule {
+        fn with_message(mut self, message: &str) -> Self {
+            self.message = message.to_string();
+            self
+        }
+    }
+
+    #[test]
+    fn test_computed_and_other_callees_do_not_match() {
+        let source = "const key = 'log';\nconsole[key]('x');\nlogger.console.log('y');\nthis.console;\n";
+        let errors = Analyzer::new(AnalysisOptions { rules: vec![ban("console.log", ErrorSeverity::Info)], ..Default::default() })
+            .analyze_source(source, "rules.ts")
+            .unwrap()
+            .type_errors;
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+}
@@ -0,0 +1,96 @@
+//! Scope names of diagnostics.
+//!
+//! One derivation shared by [`TypeError::scope`](crate::models::TypeError::scope)
+//! and [`CodeContext::scope_name`](crate::highlighting::CodeContext::scope_name),
+//! so both always name the same scope for an error. A scope is named after each
+//! enclosing named function, named class and method, outermost first; anonymous
+//! functions and classes add nothing, and code outside all of them is `global`.
+
+use oxc_ast::ast::PropertyKey;
+use oxc_ast::AstKind;
+use oxc_semantic::Semantic;
+use oxc_span::{GetSpan, Span};
+
+/// Scope of code outside every function, class and method
+pub const GLOBAL_SCOPE: &str = "global";
+
+/// Name `kind` contributes to the scopes inside it, if any.
+///
+/// Private methods keep their `#` (`#scale`), and methods with computed keys
+/// are named `dynamic_method`.
+pub fn segment_name(kind: &AstKind<'_>) -> Option<String> {
+    match kind {
+        AstKind::Function(func) => func.id.as_ref().map(|id| id.name.to_string()),
+        AstKind::Class(class) => class.id.as_ref().map(|id| id.name.to_string()),
+        AstKind::MethodDefinition(method) => Some(match &method.key {
+            PropertyKey::StaticIdentifier(id) => id.name.to_string(),
+            PropertyKey::PrivateIdentifier(id) => format!("#{}", id.name),
+            _ => "dynamic_method".to_string(),
+        }),
+        _ => None,
+    }
+}
+
+/// Names of the scopes enclosing `span`, outermost first
+pub fn enclosing_scope_names(semantic: &Semantic<'_>, span: Span) -> Vec<String> {
+    let nodes = semantic.nodes();
+    // Nodes are numbered in visit order, which puts descendants after their
+    // ancestors: the last node containing `span` is the innermost one
+    let Some(innermost) = nodes.iter().filter(|node| node.kind().span().contains_inclusive(span)).last() else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = nodes.ancestors(innermost.id()).filter_map(|id| segment_name(&nodes.kind(id))).collect();
+    names.reverse();
+    names
+}
+
+/// Joins scope `names` with `separator`, after `module` when given.
+///
+/// # Examples
+///
+/// ```
+/// use ta_lib::scope_names::join_scope_names;
+///
+/// let names = vec!["Shape".to_string(), "area".to_string()];
+/// assert_eq!(join_scope_names(&names, ".", None), "Shape.area");
+/// assert_eq!(join_scope_names(&names, "::", Some("src/shapes")), "src/shapes::Shape::area");
+/// assert_eq!(join_scope_names(&[], ".", None), "global");
+/// ```
+pub fn join_scope_names(names: &[String], separator: &str, module: Option<&str>) -> String {
+    let segments: Vec<&str> = module.into_iter().chain(names.iter().map(String::as_str)).collect();
+    if segments.is_empty() {
+        return GLOBAL_SCOPE.to_string();
+    }
+    segments.join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_semantic::SemanticBuilder;
+    use oxc_span::SourceType;
+
+    fn names_at(source: &str, needle: &str) -> Vec<String> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let semantic = SemanticBuilder::new(source).build(&ret.program).semantic;
+        let start = source.find(needle).unwrap() as u32;
+        enclosing_scope_names(&semantic, Span::new(start, start + needle.len() as u32))
+    }
+
+    #[test]
+    fn test_named_functions_classes_and_methods_nest() {
+        let source = "class Shape {\n  #scale() {\n    const f = () => { function inner() { return missing; } };\n  }\n}\n";
+        assert_eq!(names_at(source, "missing"), vec!["Shape", "#scale", "inner"]);
+    }
+
+    #[test]
+    fn test_anonymous_and_top_level_code_is_global() {
+        let source = "const handler = function () { return missing; };\nexport default class { run() {} }\n";
+        assert!(names_at(source, "missing").is_empty());
+        assert_eq!(names_at(source, "run"), vec!["run"]);
+    }
+}
@@ -0,0 +1,172 @@
+//! `<script>` blocks of Vue and Svelte single-file components.
+//!
+//! Rather than cutting the script out and mapping positions back, everything
+//! outside the script blocks is blanked: each byte becomes a space except line
+//! breaks. The result has the same length and line structure as the component,
+//! so byte offsets, lines and columns reported for it are those of the original
+//! file, and a component's several scripts (`<script>` and `<script setup>`,
+//! Svelte's `context="module"` script) are analyzed as one module.
+
+use std::path::Path;
+
+use oxc_span::SourceType;
+
+/// Extensions of the single-file components whose scripts can be analyzed
+pub const SFC_EXTENSIONS: [&str; 2] = ["vue", "svelte"];
+
+/// Whether `path` is a `.vue` or `.svelte` file
+pub fn is_sfc_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SFC_EXTENSIONS.contains(&ext))
+}
+
+/// The scripts of a component, in place, with the rest of the file blanked
+#[derive(Debug, Clone)]
+pub struct SfcScript {
+    /// The component with everything but script content replaced by spaces
+    pub source: String,
+    /// TypeScript when any script has `lang="ts"` (or `tsx`), JavaScript otherwise
+    pub source_type: SourceType,
+}
+
+/// Blank everything in `source` outside its `<script>` blocks.
+///
+/// A component without scripts yields an all-blank source.
+pub fn extract_scripts(source: &str) -> SfcScript {
+    let mut bytes: Vec<u8> = source.bytes().map(|b| if b == b'\n' || b == b'\r' { b } else { b' ' }).collect();
+    let mut lang: Option<String> = None;
+
+    for block in script_blocks(source) {
+        bytes[block.start..block.end].copy_from_slice(&source.as_bytes()[block.start..block.end]);
+        if block.lang.is_some() && lang.as_deref() != Some("tsx") {
+            lang = block.lang;
+        }
+    }
+
+    let source_type = match lang.as_deref() {
+        Some("ts" | "typescript") => SourceType::ts(),
+        Some("tsx") => SourceType::tsx(),
+        Some("jsx") => SourceType::jsx(),
+        _ => SourceType::mjs(),
+    };
+
+    SfcScript {
+        // Only whole UTF-8 sequences were copied, everything else is ASCII
+        source: String::from_utf8(bytes).unwrap_or_default(),
+        source_type,
+    }
+}
+
+/// Byte range of a script block's content and its `lang` attribute
+struct ScriptBlock {
+    start: usize,
+    end: usize,
+    lang: Option<String>,
+}
+
+fn script_blocks(source: &str) -> Vec<ScriptBlock> {
+    let lower = source.to_ascii_lowercase();
+    let mut blocks = Vec::new();
+    let mut from = 0;
+
+    while let Some(found) = lower[from..].find("<script") {
+        let tag_start = from + found;
+        let after_name = tag_start + "<script".len();
+        // `<scripts>` or `<script-foo>` is some other element
+        if !lower[after_name..].starts_with(|c: char| c == '>' || c.is_ascii_whitespace()) {
+            from = after_name;
+            continue;
+        }
+        let Some(tag_end) = open_tag_end(source, after_name) else {
+            break;
+        };
+        let content_start = tag_end + 1;
+        let content_end = lower[content_start..].find("</script").map_or(source.len(), |i| content_start + i);
+
+        blocks.push(ScriptBlock {
+            start: content_start,
+            end: content_end,
+            lang: attribute(&source[after_name..tag_end], "lang").map(|lang| lang.to_ascii_lowercase()),
+        });
+        from = content_end;
+    }
+
+    blocks
+}
+
+/// Offset of the `>` closing an opening tag, skipping quoted attribute values
+fn open_tag_end(source: &str, from: usize) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in source[from..].char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(from + i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Value of `name="value"` (or single-quoted, or unquoted) among `attributes`
+fn attribute<'s>(attributes: &'s str, name: &str) -> Option<&'s str> {
+    let mut rest = attributes;
+    while let Some(i) = rest.find(name) {
+        let before = rest[..i].chars().next_back();
+        let after = rest[i + name.len()..].trim_start();
+        rest = &rest[i + name.len()..];
+        if before.is_some_and(|c| !c.is_ascii_whitespace()) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        return match value.chars().next() {
+            Some(q @ ('"' | '\'')) => value[1..].split(q).next(),
+            _ => value.split(|c: char| c.is_ascii_whitespace() || c == '/').next(),
+        };
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripts_keep_their_offsets() {
+        let source = "<template>\n  <p>{{ count }}</p>\n</template>\n\n<script setup lang=\"ts\">\nconst count: number = 1;\n</script>\n";
+        let script = extract_scripts(source);
+
+        assert_eq!(script.source.len(), source.len());
+        assert_eq!(script.source.lines().count(), source.lines().count());
+        let offset = source.find("const count").unwrap();
+        assert_eq!(&script.source[offset..offset + 11], "const count");
+        assert!(script.source[..offset].trim().is_empty());
+        assert!(script.source_type.is_typescript());
+    }
+
+    #[test]
+    fn test_all_script_blocks_and_lang() {
+        let source = "<script context=\"module\">\nexport const a = 1;\n</script>\n<script lang='ts'>\nlet b: string = 'é';\n</script>\n<div>é</div>\n";
+        let script = extract_scripts(source);
+
+        assert!(script.source.contains("export const a = 1;"));
+        assert!(script.source.contains("let b: string = 'é';"));
+        assert!(!script.source.contains("div"));
+        assert_eq!(script.source.len(), source.len());
+        assert!(script.source_type.is_typescript());
+
+        assert!(!extract_scripts("<script>\nlet c = 1;\n</script>\n").source_type.is_typescript());
+        assert!(extract_scripts("<template><p/></template>\n").source.trim().is_empty());
+    }
+
+    #[test]
+    fn test_is_sfc_path() {
+        assert!(is_sfc_path(Path::new("src/App.vue")));
+        assert!(is_sfc_path(Path::new("src/Button.svelte")));
+        assert!(!is_sfc_path(Path::new("src/main.ts")));
+    }
+}
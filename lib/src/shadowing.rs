@@ -0,0 +1,176 @@
+//! Variable shadowing checks (`ta source --lint-shadowing`).
+//!
+//! Uses the semantic scope tree: a value binding shadows another when an
+//! enclosing scope already binds the same name, e.g. a `const x` in a block
+//! inside a function that also declares `x`.
+
+use oxc_ast::ast::{Argument, CallExpression};
+use oxc_ast::AstKind;
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_semantic::{NodeId, Semantic, SymbolFlags, SymbolId};
+use oxc_span::GetSpan;
+
+/// Error id for a binding that shadows an outer binding of the same name
+pub const SHADOWING_ERROR_ID: &str = "SHADOW";
+
+/// Warn about each binding that shadows one in an enclosing scope.
+///
+/// The warning is labelled at the inner declaration, with a second label at the
+/// outer one. Shadowing an outer function parameter by passing it straight into
+/// an immediately invoked function under the same name (`((x) => ...)(x)`) is
+/// intentional and not reported, nor are the names of function and class
+/// expressions, which are only visible inside themselves.
+pub fn find_shadowed_bindings(semantic: &Semantic<'_>) -> Vec<OxcDiagnostic> {
+    let symbols = semantic.symbols();
+    let scopes = semantic.scopes();
+
+    let mut diagnostics = Vec::new();
+    for inner in symbols.symbol_ids() {
+        let flags = symbols.get_flags(inner);
+        if !flags.intersects(SymbolFlags::Variable | SymbolFlags::Function | SymbolFlags::Class | SymbolFlags::CatchVariable)
+            || is_expression_name(semantic, inner)
+        {
+            continue;
+        }
+
+        let name = symbols.get_name(inner);
+        let Some(parent) = scopes.get_parent_id(symbols.get_scope_id(inner)) else {
+            continue;
+        };
+        let Some(outer) = scopes.ancestors(parent).find_map(|scope| {
+            scopes
+                .get_binding(scope, name)
+                .filter(|&outer| symbols.get_flags(outer).intersects(SymbolFlags::Value | SymbolFlags::Function | SymbolFlags::Import))
+        }) else {
+            continue;
+        };
+
+        if is_parameter(semantic, outer) && is_passed_to_iife(semantic, inner) {
+            continue;
+        }
+
+        // The message stays the same wherever the outer declaration moves; its
+        // location is the second label
+        diagnostics.push(
+            OxcDiagnostic::warn(format!("'{}' shadows an outer declaration", name))
+                .with_error_code_scope(SHADOWING_ERROR_ID)
+                .with_labels([symbols.get_span(inner).label("shadowing declaration"), symbols.get_span(outer).label("outer declaration")]),
+        );
+    }
+
+    diagnostics.sort_by_key(|d| d.labels.as_ref().and_then(|l| l.first()).map(|l| l.offset()));
+    diagnostics
+}
+
+/// `f` in `const g = function f() {}`, which oxc binds in the function's own scope
+fn is_expression_name(semantic: &Semantic<'_>, symbol: SymbolId) -> bool {
+    match semantic.nodes().kind(semantic.symbols().get_declaration(symbol)) {
+        AstKind::Function(func) => func.is_expression(),
+        AstKind::Class(class) => class.is_expression(),
+        _ => false,
+    }
+}
+
+fn is_parameter(semantic: &Semantic<'_>, symbol: SymbolId) -> bool {
+    matches!(semantic.nodes().kind(semantic.symbols().get_declaration(symbol)), AstKind::FormalParameter(_))
+}
+
+/// Whether `symbol` is a parameter of an immediately invoked function whose
+/// argument in that position is an identifier of the same name
+fn is_passed_to_iife(semantic: &Semantic<'_>, symbol: SymbolId) -> bool {
+    let nodes = semantic.nodes();
+    let declaration = semantic.symbols().get_declaration(symbol);
+    let AstKind::FormalParameter(param) = nodes.kind(declaration) else {
+        return false;
+    };
+    let Some(AstKind::FormalParameters(params)) = nodes.parent_kind(declaration) else {
+        return false;
+    };
+    let Some(index) = params.items.iter().position(|p| p.span == param.span) else {
+        return false;
+    };
+    let Some(function) = nodes.parent_node(declaration).and_then(|n| nodes.parent_node(n.id())) else {
+        return false;
+    };
+    if !matches!(function.kind(), AstKind::Function(_) | AstKind::ArrowFunctionExpression(_)) {
+        return false;
+    }
+
+    let Some(call) = enclosing_call(semantic, function.id()) else {
+        return false;
+    };
+    let callee_span = call.callee.without_parentheses().span();
+    let name = semantic.symbols().get_name(symbol);
+    callee_span == function.kind().span()
+        && matches!(call.arguments.get(index), Some(Argument::Identifier(ident)) if ident.name == name)
+}
+
+/// The call expression `node` is (possibly parenthesized) part of
+fn enclosing_call<'a>(semantic: &'a Semantic<'_>, node: NodeId) -> Option<&'a CallExpression<'a>> {
+    let nodes = semantic.nodes();
+    nodes.ancestors(node).skip(1).find_map(|id| match nodes.kind(id) {
+        AstKind::ParenthesizedExpression(_) => None,
+        AstKind::CallExpression(call) => Some(Some(call)),
+        _ => Some(None),
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisOptions, Analyzer};
+
+    fn shadow_warnings(source: &str) -> Vec<(usize, String)> {
+        Analyzer::new(AnalysisOptions { lint_shadowing: true, ..Default::default() })
+            .analyze_source(source, "shadow.ts")
+            .unwrap()
+            .type_errors
+            .into_iter()
+            .filter(|e| e.id == SHADOWING_ERROR_ID)
+            .map(|e| (e.line, e.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_block_redeclaring_x_is_reported() {
+        let source = "function f() {\n  const x = 1;\n  if (x) {\n    const x = 2;\n    return x;\n  }\n}\n";
+        assert_eq!(shadow_warnings(source), vec![(4, "'x' shadows an outer declaration".to_string())]);
+
+        let errors = Analyzer::new(AnalysisOptions { lint_shadowing: true, ..Default::default() })
+            .analyze_source(source, "shadow.ts")
+            .unwrap()
+            .type_errors;
+        assert_eq!(errors[0].severity, crate::highlighting::ErrorSeverity::Warning);
+        // The outer declaration is a related span rather than part of the message
+        assert_eq!(errors[0].related.len(), 1);
+        assert_eq!(errors[0].related[0].message, "outer declaration");
+        assert_eq!((errors[0].related[0].position.start_line, errors[0].related[0].position.start_column), (2, 9));
+    }
+
+    #[test]
+    fn test_parameter_and_catch_shadowing() {
+        let source = "const id = 1;\nfunction load(id: number) {\n  try {} catch (id) {}\n}\n";
+        let mut lines: Vec<usize> = shadow_warnings(source).into_iter().map(|(line, _)| line).collect();
+        lines.sort();
+        assert_eq!(lines, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_unrelated_names_and_expression_names_are_not_reported() {
+        let source = "const f = function f() {};\nconst C = class C {};\ntype T = string;\nfunction g(T: number) { return T; }\nfunction h() { const y = 1; }\nfunction k() { const y = 2; }\n";
+        assert!(shadow_warnings(source).is_empty(), "{:?}", shadow_warnings(source));
+    }
+
+    #[test]
+    fn test_parameter_passed_to_iife_is_not_reported() {
+        let source = "function f(x: number) {\n  return ((x) => x + 1)(x);\n}\nfunction g(x: number) {\n  return ((x) => x + 1)(2);\n}\n";
+        assert_eq!(shadow_warnings(source).into_iter().map(|(line, _)| line).collect::<Vec<_>>(), vec![5]);
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        let source = "const x = 1;\n{\n  const x = 2;\n}\n";
+        let errors = Analyzer::new(AnalysisOptions::default()).analyze_source(source, "shadow.ts").unwrap().type_errors;
+        assert!(errors.is_empty());
+    }
+}
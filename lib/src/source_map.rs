@@ -0,0 +1,179 @@
+//! Line and column lookups for byte offsets into one source file.
+//!
+//! Spans from the parser are byte offsets; everything shown to users is a
+//! 1-based line and a 1-based column counted in characters. [`SourceMap`]
+//! records where each line starts once per file, so every lookup after that
+//! is a binary search instead of a scan from the start of the file.
+
+/// Line starts of a source text, for mapping byte offsets to lines and columns.
+///
+/// Lines are separated by `\n`; a `\r` before it belongs to no line's text
+/// but still counts toward the column of an offset pointing at it.
+#[derive(Debug, Clone)]
+pub struct SourceMap<'s> {
+    source: &'s str,
+    /// Byte offset of the first character of each line; always starts with `0`
+    line_starts: Vec<usize>,
+}
+
+impl<'s> SourceMap<'s> {
+    /// Records the start of every line in `source`.
+    pub fn new(source: &'s str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { source, line_starts }
+    }
+
+    /// The mapped source text
+    pub fn source(&self) -> &'s str {
+        self.source
+    }
+
+    /// Number of lines, counting the (possibly empty) line after a trailing newline
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// 1-based line containing the byte `offset`.
+    ///
+    /// Offsets past the end of the source are clamped to it, so they land on
+    /// the last line.
+    pub fn line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset.min(self.source.len()))
+    }
+
+    /// 1-based line and character column of the byte `offset`.
+    ///
+    /// Offsets past the end of the source are clamped to it, and offsets inside
+    /// a multi-byte character resolve to that character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_lib::source_map::SourceMap;
+    ///
+    /// let map = SourceMap::new("const s = \"é\";\nlet x;");
+    /// assert_eq!(map.line_col(0), (1, 1));
+    /// assert_eq!(map.line_col(13), (1, 13)); // the closing quote, after a two-byte `é`
+    /// assert_eq!(map.line_col(20), (2, 5));
+    /// ```
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let mut offset = offset.min(self.source.len());
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.line(offset);
+        let column = self.source[self.line_starts[line - 1]..offset].chars().count() + 1;
+        (line, column)
+    }
+
+    /// Text of the 1-based `line`, without its line ending, or `None` past the last line.
+    pub fn line_text(&self, line: usize) -> Option<&'s str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        match self.line_starts.get(line) {
+            Some(next) => {
+                let text = &self.source[start..next - 1];
+                Some(text.strip_suffix('\r').unwrap_or(text))
+            }
+            None => Some(&self.source[start..]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_col_at_line_boundaries() {
+        let map = SourceMap::new("line 1\nline 2\nline 3");
+        assert_eq!(map.line_col(0), (1, 1));
+        assert_eq!(map.line_col(5), (1, 6));
+        assert_eq!(map.line_col(6), (1, 7));
+        assert_eq!(map.line_col(7), (2, 1));
+        assert_eq!(map.line_col(10), (2, 4));
+        assert_eq!(map.line_col(14), (3, 1));
+
+        let map = SourceMap::new("a\nb\nc");
+        assert_eq!([0, 1, 2, 4].map(|offset| map.line(offset)), [1, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_offsets_are_clamped_to_the_source() {
+        let map = SourceMap::new("ab\ncd\n");
+        assert_eq!(map.line_col(6), (3, 1));
+        assert_eq!(map.line_col(100), (3, 1));
+        assert_eq!(SourceMap::new("").line_col(3), (1, 1));
+    }
+
+    #[test]
+    fn test_columns_count_characters() {
+        let source = "// é ✓\nx";
+        let map = SourceMap::new(source);
+        assert_eq!(map.line_col(source.find('✓').unwrap()), (1, 6));
+        // Inside the three bytes of `✓`
+        assert_eq!(map.line_col(source.find('✓').unwrap() + 1), (1, 6));
+        assert_eq!(map.line_col(source.find('x').unwrap()), (2, 1));
+    }
+
+    #[test]
+    fn test_line_text_drops_line_endings() {
+        let map = SourceMap::new("first\r\nsecond\n\nlast");
+        assert_eq!(map.line_count(), 4);
+        assert_eq!(map.line_text(1), Some("first"));
+        assert_eq!(map.line_text(2), Some("second"));
+        assert_eq!(map.line_text(3), Some(""));
+        assert_eq!(map.line_text(4), Some("last"));
+        assert_eq!(map.line_text(0), None);
+        assert_eq!(map.line_text(5), None);
+        assert_eq!(SourceMap::new("x\n").line_text(2), Some(""));
+    }
+}
+
+// Property-based tests
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use crate::highlighting::code_context::{calculate_column_number, calculate_line_number};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_line_col_matches_scanning_helpers(
+            source in "(\\PC|\n|\r\n){0,200}",
+            offset in 0usize..1000,
+        ) {
+            let map = SourceMap::new(&source);
+            // The scanning helpers return line 1 past the end instead of clamping
+            let clamped = offset.min(source.len());
+            let expected = (calculate_line_number(&source, clamped), calculate_column_number(&source, clamped));
+            prop_assert_eq!(map.line_col(offset), expected);
+            prop_assert_eq!(map.line(offset), expected.0);
+        }
+
+        #[test]
+        fn test_line_text_matches_str_lines(source in "(\\PC|\n|\r\n){0,200}") {
+            let map = SourceMap::new(&source);
+            let lines: Vec<&str> = source.lines().collect();
+            for (i, line) in lines.iter().enumerate() {
+                prop_assert_eq!(map.line_text(i + 1), Some(*line));
+            }
+            prop_assert_eq!(map.line_count(), source.matches('\n').count() + 1);
+            prop_assert_eq!(map.line_text(map.line_count() + 1), None);
+        }
+
+        #[test]
+        fn test_lookups_never_panic(
+            source in "\\PC{0,1000}",
+            offset in 0usize..10000,
+            line in 0usize..100,
+        ) {
+            let map = SourceMap::new(&source);
+            let (line_number, column) = map.line_col(offset);
+            prop_assert!(line_number >= 1 && line_number <= map.line_count());
+            prop_assert!(column >= 1);
+            let _ = map.line_text(line);
+        }
+    }
+}
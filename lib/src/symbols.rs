@@ -1,8 +1,13 @@
 use oxc_ast::ast::Program;
 use oxc_ast::visit::Visit;
-use crate::models::SymbolInfo;
+use oxc_span::Span;
+use crate::highlighting::ErrorSeverity;
+use crate::models::{SpanPosition, SymbolInfo, SymbolKind, TypeError};
 use crate::visitors::symbol_visitor::SymbolVisitor;
 
+/// Error id for exported symbols without a JSDoc comment
+pub const MISSING_DOCS_ERROR_ID: &str = "DOCS";
+
 pub fn extract_symbols<'a>(
     source: &'a str,
     program: &Program<'a>,
@@ -13,3 +18,67 @@ pub fn extract_symbols<'a>(
     visitor.visit_program(program);
     visitor.symbols
 }
+
+/// A warning for each exported symbol without JSDoc, skipping names for which `allowed` is true.
+///
+/// Symbols carry lines but no spans, so the warnings point at the start of the
+/// declaration's first line.
+pub fn find_missing_docs(symbols: &[SymbolInfo], allowed: impl Fn(&str) -> bool) -> Vec<TypeError> {
+    symbols
+        .iter()
+        .filter(|s| s.exported && s.jsdoc.is_none() && !allowed(&s.name))
+        .map(|s| TypeError {
+            id: MISSING_DOCS_ERROR_ID.to_string(),
+            message: format!("Exported {} '{}' has no JSDoc comment", kind_label(&s.kind), s.name),
+            severity: ErrorSeverity::Warning,
+            file: s.file.clone(),
+            line: s.start_line,
+            column: 1,
+            scope: s.name.clone(),
+            block: String::new(),
+            source_code: None,
+            span: Span::default(),
+            position: SpanPosition {
+                start_line: s.start_line,
+                start_column: 1,
+                end_line: s.end_line,
+                end_column: 1,
+            },
+            related: Vec::new(),
+        })
+        .collect()
+}
+
+fn kind_label(kind: &SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::Function => "function",
+        SymbolKind::Class => "class",
+        SymbolKind::Interface => "interface",
+        SymbolKind::Type => "type",
+        SymbolKind::Variable => "variable",
+        SymbolKind::Enum => "enum",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisOptions, Analyzer};
+
+    #[test]
+    fn test_only_undocumented_exports_are_reported() {
+        let source = "/** Adds two numbers */\nexport function add(a: number, b: number) { return a + b; }\n\nexport function sub(a: number, b: number) { return a - b; }\n\nfunction internal() {}\n";
+        let symbols = Analyzer::new(AnalysisOptions::default())
+            .analyze_source(source, "math.ts")
+            .unwrap()
+            .symbols;
+
+        let missing = find_missing_docs(&symbols, |_| false);
+        assert_eq!(missing.len(), 1, "{:?}", missing);
+        assert_eq!(missing[0].scope, "sub");
+        assert_eq!(missing[0].line, 4);
+        assert_eq!(missing[0].severity, ErrorSeverity::Warning);
+
+        assert!(find_missing_docs(&symbols, |name| name == "sub").is_empty());
+    }
+}
@@ -1,13 +1,125 @@
+use std::collections::HashSet;
+
 use oxc_ast::ast::Program;
 use oxc_ast::visit::Visit;
-use crate::models::TypeTest;
+use oxc_ast::Trivias;
+use oxc_diagnostics::{OxcDiagnostic, Severity};
+use crate::models::{TestStatus, TypeTest};
+use crate::source_map::SourceMap;
 use crate::visitors::test_visitor::TestVisitor;
 
+/// Comments that expect a type error on the line they cover: `tsc`'s
+/// `@ts-expect-error` and dtslint's `$ExpectError`
+pub const EXPECT_ERROR_DIRECTIVES: &[&str] = &["@ts-expect-error", "$ExpectError"];
+
 pub fn extract_tests(
+    source: &str,
     program: &Program<'_>,
     file_path: String,
 ) -> Vec<TypeTest> {
-    let mut visitor = TestVisitor::new(file_path);
+    let mut visitor = TestVisitor::new(source, file_path);
     visitor.visit_program(program);
     visitor.tests
 }
+
+/// Decide the status of each test with type assertions from the file's diagnostics.
+///
+/// An [`EXPECT_ERROR_DIRECTIVES`] comment covers the line it trails, or the next
+/// line when it stands alone, and is met when an error diagnostic has a label on
+/// that line. A test fails when one of its expectations goes unmet or when an
+/// error it did not expect is reported inside it, and passes otherwise. Tests
+/// containing such a comment count as having type cases.
+///
+/// The diagnostics only come from the parser and semantic checks, not a type
+/// checker, so an expectation above a type mismatch that `tsc` would report is
+/// unmet here and its test fails.
+pub fn check_type_tests(tests: &mut [TypeTest], source: &str, trivias: &Trivias, diagnostics: &[OxcDiagnostic]) {
+    let source_map = SourceMap::new(source);
+
+    // (offset of the comment, line it expects an error on)
+    let expectations: Vec<(u32, usize)> = trivias
+        .comments()
+        .filter(|comment| {
+            let text = comment.span.source_text(source);
+            EXPECT_ERROR_DIRECTIVES.iter().any(|directive| text.contains(directive))
+        })
+        .map(|comment| {
+            let (line, column) = source_map.line_col(comment.span.start as usize);
+            let before: String = source_map.line_text(line).unwrap_or_default().chars().take(column - 1).collect();
+            let before = before.trim_end();
+            let code = before.strip_suffix("//").or_else(|| before.strip_suffix("/*")).unwrap_or(before);
+            let covered = if code.trim().is_empty() {
+                source_map.line(comment.span.end as usize) + 1
+            } else {
+                line
+            };
+            (comment.span.start, covered)
+        })
+        .collect();
+
+    // (offset of the first label, lines of all labels) of each error
+    let errors: Vec<(u32, HashSet<usize>)> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .filter_map(|diagnostic| {
+            let labels = diagnostic.labels.as_ref()?;
+            let first = labels.first()?.offset() as u32;
+            Some((first, labels.iter().map(|label| source_map.line(label.offset())).collect()))
+        })
+        .collect();
+
+    for test in tests.iter_mut() {
+        let covered: HashSet<usize> = expectations
+            .iter()
+            .filter(|(offset, _)| test.span.start <= *offset && *offset < test.span.end)
+            .map(|(_, line)| *line)
+            .collect();
+        if covered.is_empty() && !test.has_type_cases {
+            continue;
+        }
+        test.has_type_cases = true;
+
+        let unmet = covered.iter().any(|line| !errors.iter().any(|(_, lines)| lines.contains(line)));
+        let unexpected = errors.iter().any(|(first, lines)| {
+            test.span.start <= *first && *first < test.span.end && lines.is_disjoint(&covered)
+        });
+        test.status = if unmet || unexpected { TestStatus::Failing } else { TestStatus::Passing };
+    }
+}
+
+#[cfg(test)]
+mod check_tests {
+    use crate::analyzer::{AnalysisOptions, Analyzer};
+    use crate::models::{TestStatus, TypeTest};
+
+    fn checked_tests(source: &str) -> Vec<TypeTest> {
+        Analyzer::new(AnalysisOptions { check_type_tests: true, ..Default::default() })
+            .analyze_source(source, "types.test.ts")
+            .unwrap()
+            .tests
+    }
+
+    #[test]
+    fn test_expect_error_without_an_error_fails() {
+        let source = "it(\"rejects strings\", () => {\n  // @ts-expect-error\n  const n: number = 1;\n});\n";
+        let tests = checked_tests(source);
+        assert_eq!(tests.len(), 1);
+        assert!(tests[0].has_type_cases);
+        assert_eq!(tests[0].status, TestStatus::Failing);
+    }
+
+    #[test]
+    fn test_expect_error_with_an_error_passes() {
+        let source = "it(\"redeclares\", () => {\n  let a = 1;\n  // @ts-expect-error\n  let a = 2;\n  let b = 1; let b = 2; // $ExpectError\n});\n";
+        let tests = checked_tests(source);
+        assert_eq!(tests[0].status, TestStatus::Passing);
+    }
+
+    #[test]
+    fn test_unexpected_error_fails_a_test_with_type_cases() {
+        let source = "it(\"infers\", () => {\n  expectTypeOf(1).toEqualTypeOf<number>();\n  let a = 1;\n  let a = 2;\n});\nit(\"plain\", () => {\n  expect(1).toBe(1);\n});\n";
+        let tests = checked_tests(source);
+        let statuses: Vec<_> = tests.iter().map(|t| (t.test_name.as_str(), t.status.clone())).collect();
+        assert_eq!(statuses, vec![("infers", TestStatus::Failing), ("plain", TestStatus::NoTypeCases)]);
+    }
+}
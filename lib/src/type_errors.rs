@@ -1,23 +1,457 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use oxc_ast::AstKind;
 use oxc_semantic::Semantic;
-use oxc_diagnostics::OxcDiagnostic;
+use oxc_diagnostics::{OxcDiagnostic, Severity};
 use oxc_ast::ast::Program;
 use oxc_ast::visit::Visit;
+use oxc_ast::Trivias;
+use oxc_span::Span;
+use serde::Serialize;
+use crate::highlighting::ansi::strip_ansi;
 use crate::models::TypeError;
+use crate::source_map::SourceMap;
 use crate::visitors::type_error_visitor::TypeErrorVisitor;
 
+/// Error id given to parser (syntax) diagnostics that carry no code of their own
+pub const PARSE_ERROR_ID: &str = "PARSE";
+
+/// Combine parser and semantic diagnostics into the list fed to the visitor.
+///
+/// Parser diagnostics come first and, when uncoded, are tagged with
+/// [`PARSE_ERROR_ID`] so syntax errors are distinguishable from semantic ones.
+pub fn merge_diagnostics(parse_errors: Vec<OxcDiagnostic>, semantic_errors: Vec<OxcDiagnostic>) -> Vec<OxcDiagnostic> {
+    parse_errors
+        .into_iter()
+        .map(|error| {
+            if error.code.is_some() {
+                error
+            } else {
+                error.with_error_code_scope(PARSE_ERROR_ID)
+            }
+        })
+        .chain(semantic_errors)
+        .collect()
+}
+
+/// Separator between nested scope names in [`TypeError::scope`] unless configured (`Class.method`)
+pub const DEFAULT_SCOPE_SEPARATOR: &str = ".";
+
+/// How the [`TypeError::scope`] of each error is named
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeNaming {
+    /// Joins nested function, class and method names
+    pub separator: String,
+    /// Start each scope with the file's module path (`src/shapes.Circle.area`),
+    /// making scopes unique across files; file-level errors get the module alone
+    pub include_module: bool,
+}
+
+impl Default for ScopeNaming {
+    fn default() -> Self {
+        Self { separator: DEFAULT_SCOPE_SEPARATOR.to_string(), include_module: false }
+    }
+}
+
+impl ScopeNaming {
+    /// Sets the separator between nested scope names.
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Sets whether scopes start with the file's module path.
+    pub fn with_module(mut self, include_module: bool) -> Self {
+        self.include_module = include_module;
+        self
+    }
+}
+
+/// Module path used as the outermost scope: the file path without its
+/// extension or a leading `./` (`./src/shapes.ts` is `src/shapes`)
+pub fn module_scope_name(file_path: &str) -> String {
+    let path = file_path.strip_prefix("./").unwrap_or(file_path);
+    std::path::Path::new(path).with_extension("").to_string_lossy().into_owned()
+}
+
+/// A `// ta-ignore [CODE...]` or `// ta-ignore-next-line [CODE...]` comment
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IgnoreDirective {
+    pub file: String,
+    /// Line of the comment itself (1-indexed)
+    pub line: usize,
+    /// Line whose errors are suppressed: the comment's own line, or the next for `-next-line`
+    pub target_line: usize,
+    /// Error codes suppressed; empty suppresses every error on `target_line`
+    pub codes: Vec<String>,
+}
+
+impl IgnoreDirective {
+    /// Parses a comment's text (without `//` or `/* */`), returning `None` for other comments.
+    fn parse(comment: &str, line: usize) -> Option<Self> {
+        let comment = comment.trim();
+        let (rest, target_line) = if let Some(rest) = comment.strip_prefix("ta-ignore-next-line") {
+            (rest, line + 1)
+        } else {
+            (comment.strip_prefix("ta-ignore")?, line)
+        };
+        // `ta-ignored` and friends are not directives
+        if rest.starts_with(|c: char| !c.is_whitespace()) {
+            return None;
+        }
+
+        let codes = rest
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|code| !code.is_empty())
+            .map(str::to_string)
+            .collect();
+        Some(Self { file: String::new(), line, target_line, codes })
+    }
+
+    /// Whether this directive suppresses `error`: `target_line` is a line the
+    /// error is labelled on, where it starts or one of its [`TypeError::related`]
+    /// spans does (a redeclaration is labelled at both declarations).
+    ///
+    /// Only the first line of each label counts, so a directive inside a
+    /// multi-line span does not match.
+    pub fn suppresses(&self, error: &TypeError) -> bool {
+        let mut labelled_lines = std::iter::once(error.position.start_line)
+            .chain(error.related.iter().map(|related| related.position.start_line));
+        labelled_lines.any(|line| line == self.target_line)
+            && (self.codes.is_empty() || self.codes.iter().any(|code| code.eq_ignore_ascii_case(&error.id)))
+    }
+}
+
+/// Finds every `ta-ignore` directive among the comments of `source`.
+pub fn find_ignore_directives(source: &str, trivias: &Trivias) -> Vec<IgnoreDirective> {
+    let source_map = SourceMap::new(source);
+    trivias
+        .comments()
+        .filter_map(|comment| {
+            let text = comment.span.source_text(source);
+            let line = source_map.line(comment.span.start as usize);
+            IgnoreDirective::parse(text, line)
+        })
+        .collect()
+}
+
+/// Creates an info-level diagnostic for each comment line containing one of `markers`.
+///
+/// Markers such as `TODO` or `FIXME` match case-insensitively as whole words, so
+/// `todo:` matches `TODO` but `todos` does not. Each diagnostic's code is the
+/// marker in upper case, its message is the comment text from the marker on,
+/// and its span starts at the marker.
+pub fn find_comment_markers(source: &str, trivias: &Trivias, markers: &[String]) -> Vec<OxcDiagnostic> {
+    let mut diagnostics = Vec::new();
+    for comment in trivias.comments() {
+        let text = comment.span.source_text(source);
+        let mut line_start = comment.span.start as usize;
+        for line in text.split_inclusive('\n') {
+            if let Some((offset, marker)) = find_marker(line, markers) {
+                let note = line[offset..].trim_end();
+                let start = (line_start + offset) as u32;
+                diagnostics.push(
+                    OxcDiagnostic::warn(note.to_string())
+                        .with_severity(Severity::Advice)
+                        .with_error_code_scope(marker.to_uppercase())
+                        .with_label(Span::new(start, start + note.len() as u32)),
+                );
+            }
+            line_start += line.len();
+        }
+    }
+    diagnostics
+}
+
+/// Earliest whole-word, case-insensitive occurrence of any marker in `line`.
+fn find_marker<'m>(line: &str, markers: &'m [String]) -> Option<(usize, &'m str)> {
+    let lower = line.to_ascii_lowercase();
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    markers
+        .iter()
+        .filter(|marker| !marker.is_empty())
+        .filter_map(|marker| {
+            let needle = marker.to_ascii_lowercase();
+            lower
+                .match_indices(&needle)
+                .map(|(index, _)| index)
+                .find(|&index| {
+                    let before = lower[..index].chars().next_back();
+                    let after = lower[index + needle.len()..].chars().next();
+                    !before.is_some_and(is_word) && !after.is_some_and(is_word)
+                })
+                .map(|index| (index, marker.as_str()))
+        })
+        .min_by_key(|(index, _)| *index)
+}
+
+/// Indices of redeclaration diagnostics raised only between members of a legal overload set.
+///
+/// TypeScript allows a function to be declared by any number of body-less
+/// signatures plus at most one implementation. A redeclaration whose labels all
+/// point at such function names, with no more than one body among them, is
+/// spurious; duplicate implementations are still reported.
+fn overload_redeclarations(semantic: &Semantic<'_>, diagnostics: &[OxcDiagnostic]) -> Vec<usize> {
+    let functions: HashMap<u32, bool> = semantic
+        .nodes()
+        .iter()
+        .filter_map(|node| match node.kind() {
+            AstKind::Function(function) => function.id.as_ref().map(|id| (id.span.start, function.body.is_some())),
+            _ => None,
+        })
+        .collect();
+
+    diagnostics
+        .iter()
+        .enumerate()
+        .filter(|(_, diagnostic)| diagnostic.message.ends_with("has already been declared"))
+        .filter_map(|(index, diagnostic)| {
+            let labels = diagnostic.labels.as_ref().filter(|labels| labels.len() > 1)?;
+            let mut bodies = 0;
+            for label in labels {
+                bodies += usize::from(*functions.get(&(label.offset() as u32))?);
+            }
+            (bodies <= 1).then_some(index)
+        })
+        .collect()
+}
+
 pub fn extract_type_errors<'a>(
     source: &'a str,
     semantic: &'a Semantic<'a>,
     diagnostics: &'a Vec<OxcDiagnostic>,
     program: &Program<'a>,
     file_path: String,
+    context_lines: Option<usize>,
 ) -> Vec<TypeError> {
-    let mut visitor = TypeErrorVisitor::new(source, semantic, diagnostics);
+    extract_type_errors_with_ignores(source, semantic, diagnostics, program, file_path, context_lines, &ScopeNaming::default()).0
+}
+
+/// Like [`extract_type_errors`], also returning the `ta-ignore` directives that suppressed nothing.
+///
+/// Scopes are named as `scope_naming` describes.
+///
+/// Directives are read from the comments recorded on `semantic`, so the
+/// semantic model must be built with the parser's trivias
+/// (`SemanticBuilder::with_trivias`) for suppression to apply.
+pub fn extract_type_errors_with_ignores<'a>(
+    source: &'a str,
+    semantic: &'a Semantic<'a>,
+    diagnostics: &'a Vec<OxcDiagnostic>,
+    program: &Program<'a>,
+    file_path: String,
+    context_lines: Option<usize>,
+    scope_naming: &ScopeNaming,
+) -> (Vec<TypeError>, Vec<IgnoreDirective>) {
+    let mut directives = find_ignore_directives(source, semantic.trivias());
+    for directive in &mut directives {
+        directive.file = file_path.clone();
+    }
+
+    // Fast path: clean files (the common case) have nothing to attribute to a scope
+    if diagnostics.is_empty() {
+        return (Vec::new(), directives);
+    }
+
+    let module = scope_naming.include_module.then(|| module_scope_name(&file_path));
+    let mut visitor = TypeErrorVisitor::new(source, semantic, diagnostics)
+        .with_context_lines(context_lines)
+        .with_scope_naming(&scope_naming.separator, module)
+        .with_skipped_diagnostics(overload_redeclarations(semantic, diagnostics));
     visitor.visit_program(program);
     
     let mut errors = visitor.errors;
     for error in &mut errors {
         error.file = file_path.clone();
+        // Diagnostics may carry styling under forced colors; stored text must stay plain for JSON
+        for text in [&mut error.message, &mut error.scope, &mut error.block] {
+            if let Cow::Owned(plain) = strip_ansi(text) {
+                *text = plain;
+            }
+        }
+    }
+
+    let mut used = vec![false; directives.len()];
+    errors.retain(|error| {
+        let mut suppressed = false;
+        for (directive, used) in directives.iter().zip(used.iter_mut()) {
+            if directive.suppresses(error) {
+                *used = true;
+                suppressed = true;
+            }
+        }
+        !suppressed
+    });
+    let unused = directives
+        .into_iter()
+        .zip(used)
+        .filter_map(|(directive, used)| (!used).then_some(directive))
+        .collect();
+
+    (errors, unused)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighting::ErrorSeverity;
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_semantic::SemanticBuilder;
+    use oxc_span::SourceType;
+
+    #[test]
+    fn test_syntax_error_is_reported() {
+        let source = "function add(a: number, b: number {\n  return a + b;\n}\n";
+        let allocator = Allocator::default();
+        let parse_ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let semantic_ret = SemanticBuilder::new(source).build(&parse_ret.program);
+        let diagnostics = merge_diagnostics(parse_ret.errors, semantic_ret.errors);
+
+        let errors = extract_type_errors(
+            source,
+            &semantic_ret.semantic,
+            &diagnostics,
+            &parse_ret.program,
+            "broken.ts".to_string(),
+            None,
+        );
+
+        assert!(!errors.is_empty());
+        assert_eq!(errors[0].id, PARSE_ERROR_ID);
+        assert_eq!(errors[0].severity, ErrorSeverity::Error);
+        assert_eq!(errors[0].file, "broken.ts");
+        assert_eq!(errors[0].line, 1);
+    }
+
+    #[test]
+    fn test_module_scope_name_drops_extension_and_dot_slash() {
+        assert_eq!(module_scope_name("./src/shapes.ts"), "src/shapes");
+        assert_eq!(module_scope_name("lib/index.d.ts"), "lib/index.d");
+        assert_eq!(module_scope_name("main.tsx"), "main");
+    }
+
+    #[test]
+    fn test_clean_file_returns_no_errors() {
+        let source = "export class Counter {\n  private count = 0;\n  increment(): number {\n    return ++this.count;\n  }\n}\n";
+        let allocator = Allocator::default();
+        let parse_ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let semantic_ret = SemanticBuilder::new(source).build(&parse_ret.program);
+        let diagnostics = merge_diagnostics(parse_ret.errors, semantic_ret.errors);
+        assert!(diagnostics.is_empty());
+
+        let errors = extract_type_errors(
+            source,
+            &semantic_ret.semantic,
+            &diagnostics,
+            &parse_ret.program,
+            "counter.ts".to_string(),
+            None,
+        );
+
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_json_carries_byte_span_and_line_columns() {
+        // "é" is two bytes but one column, so columns must count characters
+        let source = "const s = \"é\"; const a = 1;\nconst a = 2;\n";
+        let allocator = Allocator::default();
+        let parse_ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let semantic_ret = SemanticBuilder::new(source).build(&parse_ret.program);
+        let diagnostics = merge_diagnostics(parse_ret.errors, semantic_ret.errors);
+
+        let errors = extract_type_errors(
+            source,
+            &semantic_ret.semantic,
+            &diagnostics,
+            &parse_ret.program,
+            "dup.ts".to_string(),
+            None,
+        );
+        let json = serde_json::to_value(&errors[0]).unwrap();
+
+        // The first label points at the original `a` declaration
+        assert_eq!(json["span"]["start"], 22);
+        assert_eq!(json["span"]["end"], 23);
+        assert_eq!(json["start_line"], 1);
+        assert_eq!(json["start_column"], 22);
+        assert_eq!(json["end_line"], 1);
+        assert_eq!(json["end_column"], 23);
+    }
+
+    #[test]
+    fn test_ansi_styling_is_stripped_from_stored_errors() {
+        let source = "function run() {\n  const a = 1;\n}\n";
+        let allocator = Allocator::default();
+        let parse_ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let semantic_ret = SemanticBuilder::new(source).build(&parse_ret.program);
+        let diagnostics = vec![OxcDiagnostic::error("\x1b[31mIdentifier `a` is \x1b[1mstyled\x1b[0m")
+            .with_label(Span::new(25, 26))];
+
+        let errors = extract_type_errors(
+            source,
+            &semantic_ret.semantic,
+            &diagnostics,
+            &parse_ret.program,
+            "styled.ts".to_string(),
+            None,
+        );
+
+        assert_eq!(errors[0].message, "Identifier `a` is styled");
+        assert_eq!(errors[0].scope, "run");
+        let json = serde_json::to_string(&errors).unwrap();
+        assert!(!json.contains('\x1b') && !json.contains("\\u001b"), "{}", json);
+    }
+
+    #[test]
+    fn test_comment_markers_match_whole_words_case_insensitively() {
+        let source = "// todo: wire this up\nconst todos = 1; // TODOS are fine\n/* multi\n * FIXME later */\n";
+        let allocator = Allocator::default();
+        let parse_ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let markers = vec!["TODO".to_string(), "fixme".to_string()];
+
+        let diagnostics = find_comment_markers(source, &parse_ret.trivias, &markers);
+
+        let notes: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(notes, vec!["todo: wire this up", "FIXME later"]);
+        assert_eq!(diagnostics[0].severity, Severity::Advice);
+        assert_eq!(diagnostics[1].code.scope.as_deref(), Some("FIXME"));
+    }
+
+    fn overload_errors(source: &str, diagnostics: Vec<OxcDiagnostic>) -> Vec<TypeError> {
+        let allocator = Allocator::default();
+        let parse_ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let semantic_ret = SemanticBuilder::new(source).build(&parse_ret.program);
+        let diagnostics = merge_diagnostics(parse_ret.errors, semantic_ret.errors.into_iter().chain(diagnostics).collect());
+        extract_type_errors(source, &semantic_ret.semantic, &diagnostics, &parse_ret.program, "overload.ts".to_string(), None)
+    }
+
+    fn redeclaration(name: &str, first: u32, second: u32) -> OxcDiagnostic {
+        let len = name.len() as u32;
+        OxcDiagnostic::error(format!("Identifier `{name}` has already been declared"))
+            .with_labels([Span::new(first, first + len), Span::new(second, second + len)])
+    }
+
+    #[test]
+    fn test_overload_signatures_are_not_redeclarations() {
+        let source = "export function parse(a: string): number;\nexport function parse(a: number): number;\nexport function parse(a: any): number {\n  return Number(a);\n}\n";
+        assert!(overload_errors(source, Vec::new()).is_empty());
+
+        // A redeclaration reported between a signature and the implementation is dropped
+        let signature = source.find("parse").unwrap() as u32;
+        let implementation = source.rfind("parse").unwrap() as u32;
+        assert!(overload_errors(source, vec![redeclaration("parse", signature, implementation)]).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_implementations_are_redeclarations() {
+        let source = "function parse(a: string): number;\nfunction parse(a: string) {\n  return 1;\n}\nfunction parse(a: string) {\n  return 2;\n}\n";
+        let errors = overload_errors(source, Vec::new());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Identifier `parse` has already been declared");
+        assert_eq!(errors[0].line, 2);
     }
-    errors
 }
@@ -1,17 +1,78 @@
 use oxc_ast::visit::{walk, Visit};
 use oxc_ast::ast::*;
+use oxc_span::Span;
 use std::path::PathBuf;
 use serde::Serialize;
+use crate::dependencies::classify_import;
+use crate::models::DependencyScope;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ImportInfo {
     pub source: String,
     pub symbols: Vec<String>,
+    /// `import type { ... }`, or every specifier is `type`: erased at compile time
+    pub type_only: bool,
+    /// The `symbols` bound by a type-only specifier (`helper, type Options`
+    /// lists `Options`); all of them when `type_only`
+    pub type_only_symbols: Vec<String>,
+    /// Relative, path alias or external package; aliases are only recognized
+    /// with [`crate::analyzer::AnalysisOptions::path_aliases`]
+    pub scope: DependencyScope,
+}
+
+impl ImportInfo {
+    /// Whether `symbol`, one of `symbols`, is only imported as a type
+    pub fn is_type_only(&self, symbol: &str) -> bool {
+        self.type_only_symbols.iter().any(|s| s == symbol)
+    }
+}
+
+/// A single name re-exported from another module (`export { a as b } from './x'`).
+///
+/// `exported` is the name this module exposes, `imported` the name in `source`.
+/// `export * from './x'` is recorded as `exported: "*"`, `imported: "*"`, and
+/// `export * as ns from './x'` as `exported: "ns"`, `imported: "*"`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ReexportInfo {
+    pub source: String,
+    pub exported: String,
+    pub imported: String,
+    /// `export type { ... } from './x'` or `export { type X } from './x'`
+    pub type_only: bool,
+}
+
+/// A single `import` declaration with the local names it binds.
+///
+/// Unlike [`ImportInfo`], side-effect imports (`import './x'`) are included and
+/// spans are kept so import hygiene checks can point at the offending line.
+#[derive(Debug, Clone)]
+pub struct ImportStatement {
+    pub source: String,
+    /// `import type { ... } from './x'`
+    pub type_only: bool,
+    pub bindings: Vec<ImportBinding>,
+    pub span: Span,
+}
+
+/// A local name introduced by an import specifier
+#[derive(Debug, Clone)]
+pub struct ImportBinding {
+    pub local: String,
+    /// Name exported by the source module: differs from `local` for `import { a as b }`,
+    /// and is `default` / `*` for default and namespace imports
+    pub imported: String,
+    /// Bound by a type-only import, either `import type` or `import { type X }`
+    pub type_only: bool,
+    /// `import * as local from './x'`
+    pub namespace: bool,
+    pub span: Span,
 }
 
 pub struct DependencyVisitor {
     pub dependencies: Vec<String>,
     pub imports: Vec<ImportInfo>,
+    pub reexports: Vec<ReexportInfo>,
+    pub statements: Vec<ImportStatement>,
     pub current_file: PathBuf,
 }
 
@@ -20,6 +81,8 @@ impl DependencyVisitor {
         Self {
             dependencies: Vec::new(),
             imports: Vec::new(),
+            reexports: Vec::new(),
+            statements: Vec::new(),
             current_file,
         }
     }
@@ -32,28 +95,55 @@ impl<'a> Visit<'a> for DependencyVisitor {
 
         // Extract imported symbols
         let mut symbols = Vec::new();
+        let mut type_only_symbols = Vec::new();
+        let type_only = decl.import_kind.is_type();
+        let mut bindings = Vec::new();
 
         if let Some(specifiers) = &decl.specifiers {
             for specifier in specifiers {
-                match specifier {
+                let (local, imported, specifier_type_only, namespace) = match specifier {
                     ImportDeclarationSpecifier::ImportSpecifier(spec) => {
                         // Named import: import { foo } from './bar'
                         symbols.push(spec.local.name.to_string());
+                        (&spec.local, spec.imported.name().to_string(), spec.import_kind.is_type(), false)
                     }
                     ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
                         // Default import: import foo from './bar'
                         symbols.push(spec.local.name.to_string());
+                        (&spec.local, "default".to_string(), false, false)
                     }
                     ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
                         // Namespace import: import * as foo from './bar'
                         symbols.push(format!("* as {}", spec.local.name));
+                        (&spec.local, "*".to_string(), false, true)
                     }
+                };
+                if type_only || specifier_type_only {
+                    type_only_symbols.push(symbols.last().cloned().unwrap_or_default());
                 }
+                bindings.push(ImportBinding {
+                    local: local.name.to_string(),
+                    imported,
+                    type_only: type_only || specifier_type_only,
+                    namespace,
+                    span: local.span,
+                });
             }
         }
 
+        // `import { type A, type B }` is erased just like `import type { A, B }`
+        let erased = bindings.iter().all(|binding| binding.type_only);
+
+        self.statements.push(ImportStatement {
+            source: source.clone(),
+            type_only,
+            bindings,
+            span: decl.span,
+        });
+
         if !symbols.is_empty() {
-            self.imports.push(ImportInfo { source, symbols });
+            let scope = classify_import(&source, &[]);
+            self.imports.push(ImportInfo { source, symbols, type_only: erased, type_only_symbols, scope });
         }
 
         walk::walk_import_declaration(self, decl);
@@ -64,16 +154,14 @@ impl<'a> Visit<'a> for DependencyVisitor {
             let source_str = source.value.to_string();
             self.dependencies.push(source_str.clone());
 
-            // Extract re-exported symbols
-            let mut symbols = Vec::new();
+            // Re-exports are tracked separately from imports so barrels can be followed
+            let type_only = decl.export_kind.is_type();
             for spec in &decl.specifiers {
-                symbols.push(spec.local.name().to_string());
-            }
-
-            if !symbols.is_empty() {
-                self.imports.push(ImportInfo {
-                    source: source_str,
-                    symbols,
+                self.reexports.push(ReexportInfo {
+                    source: source_str.clone(),
+                    exported: spec.exported.name().to_string(),
+                    imported: spec.local.name().to_string(),
+                    type_only: type_only || spec.export_kind.is_type(),
                 });
             }
         }
@@ -84,16 +172,50 @@ impl<'a> Visit<'a> for DependencyVisitor {
         let source = decl.source.value.to_string();
         self.dependencies.push(source.clone());
 
-        // Export * means all symbols
-        self.imports.push(ImportInfo {
+        // `export *` re-exports every name; `export * as ns` exposes a namespace
+        let exported = decl
+            .exported
+            .as_ref()
+            .map(|name| name.name().to_string())
+            .unwrap_or_else(|| "*".to_string());
+        self.reexports.push(ReexportInfo {
             source,
-            symbols: vec!["*".to_string()],
+            exported,
+            imported: "*".to_string(),
+            type_only: decl.export_kind.is_type(),
         });
 
         walk::walk_export_all_declaration(self, decl);
     }
 }
 
+/// Collects every identifier reference (value or type position) with its span.
+///
+/// Used to scope import usage to the symbol whose AST node contains the reference.
+#[derive(Debug, Default)]
+pub struct IdentifierReferenceVisitor {
+    pub references: Vec<(String, Span)>,
+}
+
+impl IdentifierReferenceVisitor {
+    /// Names referenced within `span`, deduplicated and in source order.
+    pub fn names_within(&self, span: Span) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for (name, ref_span) in &self.references {
+            if ref_span.start >= span.start && ref_span.end <= span.end && !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+}
+
+impl<'a> Visit<'a> for IdentifierReferenceVisitor {
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        self.references.push((ident.name.to_string(), ident.span));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,6 +258,93 @@ mod tests {
         assert_eq!(deps[0], "./all");
     }
 
+    #[test]
+    fn test_reexports_tracked_separately() {
+        let source = r#"
+            import { used } from './used';
+            export { a, b as c } from './lib';
+            export * from './all';
+            export * as ns from './ns';
+        "#;
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_typescript(true);
+        let ret = Parser::new(&allocator, source, source_type).parse();
+
+        let mut visitor = DependencyVisitor::new(PathBuf::from("index.ts"));
+        visitor.visit_program(&ret.program);
+
+        assert_eq!(visitor.imports.len(), 1);
+        assert_eq!(visitor.imports[0].source, "./used");
+
+        let reexport = |source: &str, exported: &str, imported: &str| ReexportInfo {
+            source: source.to_string(),
+            exported: exported.to_string(),
+            imported: imported.to_string(),
+            type_only: false,
+        };
+        assert_eq!(
+            visitor.reexports,
+            vec![
+                reexport("./lib", "a", "a"),
+                reexport("./lib", "c", "b"),
+                reexport("./all", "*", "*"),
+                reexport("./ns", "ns", "*"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_only_imports_and_reexports_marked() {
+        let source = r#"
+            import type { Config } from './config';
+            import { type A, type B } from './types';
+            import { helper, type Options } from './helper';
+            export type { Shape } from './shape';
+            export { value } from './value';
+        "#;
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_typescript(true);
+        let ret = Parser::new(&allocator, source, source_type).parse();
+
+        let mut visitor = DependencyVisitor::new(PathBuf::from("index.ts"));
+        visitor.visit_program(&ret.program);
+
+        let type_only: Vec<_> = visitor
+            .imports
+            .iter()
+            .map(|import| (import.source.as_str(), import.type_only))
+            .collect();
+        assert_eq!(
+            type_only,
+            vec![("./config", true), ("./types", true), ("./helper", false)]
+        );
+        let type_only_symbols: Vec<_> = visitor.imports.iter().map(|import| import.type_only_symbols.clone()).collect();
+        assert_eq!(type_only_symbols, vec![vec!["Config"], vec!["A", "B"], vec!["Options"]]);
+        assert!(visitor.imports[2].is_type_only("Options"));
+        assert!(!visitor.imports[2].is_type_only("helper"));
+
+        assert!(visitor.reexports[0].type_only);
+        assert!(!visitor.reexports[1].type_only);
+    }
+
+    #[test]
+    fn test_identifier_references_scoped_to_span() {
+        let source = "function a() { return x; }\nfunction b(): T { return y; }";
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_typescript(true);
+        let ret = Parser::new(&allocator, source, source_type).parse();
+
+        let mut visitor = IdentifierReferenceVisitor::default();
+        visitor.visit_program(&ret.program);
+
+        let second = source.find("function b").unwrap() as u32;
+        assert_eq!(visitor.names_within(Span::new(0, second)), vec!["x"]);
+        assert_eq!(
+            visitor.names_within(Span::new(second, source.len() as u32)),
+            vec!["T", "y"]
+        );
+    }
+
     #[test]
     fn test_mixed_deps() {
         let source = r#"
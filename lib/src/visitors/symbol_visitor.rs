@@ -1,15 +1,44 @@
 use crate::models::{SymbolInfo, SymbolKind, ParameterInfo, PropertyInfo};
+use crate::source_map::SourceMap;
+use crate::visitors::dependency_visitor::IdentifierReferenceVisitor;
 use oxc_ast::visit::{walk, Visit};
 use oxc_ast::ast::*;
-use oxc_span::Span;
+use oxc_span::{GetSpan, Span};
 use oxc_semantic::ScopeFlags;
+use std::collections::HashMap;
+
+/// Keywords that can sit between a JSDoc comment and a declaration's span
+const JSDOC_SKIPPED_KEYWORDS: &[&str] = &["export", "default", "declare", "abstract", "async", "const", "let", "var"];
+
+/// Where a component's props come from: its single parameter's type
+enum ComponentProps {
+    /// `props: ButtonProps`, resolved against the file's interfaces and type aliases after the walk
+    Named(String),
+    /// `props: { label: string }`
+    Inline(Vec<PropertyInfo>),
+}
 
 pub struct SymbolVisitor<'a> {
     pub symbols: Vec<SymbolInfo>,
     pub exported_only: bool,
     pub source: &'a str,
+    source_map: SourceMap<'a>,
     file_path: String,
     is_exporting: bool,
+    /// Set while visiting the declaration directly under `export default`;
+    /// consumed by the first symbol added so nested declarations are unaffected.
+    is_default_exporting: bool,
+    /// Identifier referenced by `export default foo;`, resolved after the walk
+    default_export_target: Option<(String, Span)>,
+    /// Every identifier reference in the program, collected before the walk
+    identifier_references: IdentifierReferenceVisitor,
+    /// `extends`/`implements` clauses of the declaration being added;
+    /// consumed by the next symbol added
+    pending_heritage: (Vec<String>, Vec<String>),
+    /// Members of every interface and object type alias, exported or not, by name
+    type_members: HashMap<String, Vec<PropertyInfo>>,
+    /// Components (symbol index) whose props type is resolved after the walk
+    pending_props: Vec<(usize, String)>,
 }
 
 impl<'a> SymbolVisitor<'a> {
@@ -18,8 +47,57 @@ impl<'a> SymbolVisitor<'a> {
             symbols: Vec::new(),
             exported_only,
             source,
+            source_map: SourceMap::new(source),
             file_path,
             is_exporting: false,
+            is_default_exporting: false,
+            default_export_target: None,
+            identifier_references: IdentifierReferenceVisitor::default(),
+            pending_heritage: (Vec::new(), Vec::new()),
+            type_members: HashMap::new(),
+            pending_props: Vec::new(),
+        }
+    }
+
+    /// Props of a component: a capitalized function whose single parameter is
+    /// typed by a named type or an object type literal
+    fn component_props(&self, name: &str, params: &FormalParameters<'a>) -> Option<ComponentProps> {
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) || params.items.len() != 1 {
+            return None;
+        }
+        let mut pattern = &params.items[0].pattern;
+        // `{ size = 'md' }: ButtonProps` keeps the annotation on the left side
+        if let BindingPatternKind::AssignmentPattern(assign) = &pattern.kind {
+            pattern = &assign.left;
+        }
+        match &pattern.type_annotation.as_ref()?.type_annotation {
+            TSType::TSTypeLiteral(literal) => Some(ComponentProps::Inline(self.signature_properties(&literal.members))),
+            TSType::TSTypeReference(reference) => match &reference.type_name {
+                TSTypeName::IdentifierReference(ident) => Some(ComponentProps::Named(ident.name.to_string())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Attaches `props` to the symbol just added, if it was added at all
+    fn attach_component_props(&mut self, symbols_before: usize, props: Option<ComponentProps>) {
+        if self.symbols.len() == symbols_before {
+            return;
+        }
+        match props {
+            Some(ComponentProps::Inline(props)) => self.symbols[symbols_before].properties = Some(props),
+            Some(ComponentProps::Named(type_name)) => self.pending_props.push((symbols_before, type_name)),
+            None => {}
+        }
+    }
+
+    /// Fills in props typed by interfaces or type aliases declared anywhere in the file
+    fn resolve_component_props(&mut self) {
+        for (index, type_name) in std::mem::take(&mut self.pending_props) {
+            if let Some(props) = self.type_members.get(&type_name) {
+                self.symbols[index].properties = Some(props.clone());
+            }
         }
     }
 
@@ -34,12 +112,15 @@ impl<'a> SymbolVisitor<'a> {
         return_type: Option<String>,
         jsdoc: Option<String>,
     ) {
+        let is_default_export = std::mem::take(&mut self.is_default_exporting);
+        let (extends, implements) = std::mem::take(&mut self.pending_heritage);
+
         if self.exported_only && !self.is_exporting {
             return;
         }
 
-        let (start_line, _) = self.get_line_col(span.start);
-        let (end_line, _) = self.get_line_col(span.end);
+        let start_line = self.source_map.line(span.start as usize);
+        let end_line = self.source_map.line(span.end as usize);
 
         self.symbols.push(SymbolInfo {
             name,
@@ -48,13 +129,135 @@ impl<'a> SymbolVisitor<'a> {
             start_line,
             end_line,
             exported: self.is_exporting,
+            is_default_export,
             parameters: params,
             properties: props,
             return_type,
             jsdoc,
+            extends,
+            implements,
+            references: self.identifier_references.names_within(span),
         });
     }
 
+    /// Name for a declaration, falling back to `default` for an anonymous
+    /// `export default function () {}` or `export default class {}`
+    fn declaration_name(&self, id: Option<&BindingIdentifier>) -> Option<String> {
+        match id {
+            Some(id) => Some(id.name.to_string()),
+            None if self.is_default_exporting => Some("default".to_string()),
+            None => None,
+        }
+    }
+
+    /// Marks the symbol referenced by `export default foo;` as the default export,
+    /// synthesizing a variable symbol when the declaration was not collected.
+    fn resolve_default_export_target(&mut self) {
+        let Some((name, span)) = self.default_export_target.take() else {
+            return;
+        };
+
+        if let Some(symbol) = self.symbols.iter_mut().find(|s| s.name == name) {
+            symbol.exported = true;
+            symbol.is_default_export = true;
+        } else {
+            let was_exporting = self.is_exporting;
+            self.is_exporting = true;
+            self.is_default_exporting = true;
+            self.add_symbol(name, SymbolKind::Variable, span, None, None, None, None);
+            self.is_exporting = was_exporting;
+        }
+    }
+
+    /// Source text between two offsets, trimmed of a leading `:` and trailing `;`/`,`
+    fn member_type_text(&self, start: u32, end: u32) -> Option<String> {
+        let text = self.source.get(start as usize..end as usize)?
+            .trim()
+            .trim_start_matches(':')
+            .trim_end_matches([';', ','])
+            .trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    /// Properties and methods (with their types) of an interface body or type literal
+    fn signature_properties(&self, signatures: &[TSSignature<'a>]) -> Vec<PropertyInfo> {
+        let mut props = Vec::new();
+        for element in signatures {
+            match element {
+                TSSignature::TSPropertySignature(prop) => {
+                    if let Some(name) = self.member_key_name(&prop.key) {
+                        let type_ann = prop.type_annotation.as_ref().map(|t| {
+                            let span = t.span;
+                            self.source.get(span.start as usize..span.end as usize)
+                                .unwrap_or("type")
+                                .trim_start_matches(':')
+                                .trim()
+                                .to_string()
+                        });
+                        props.push(PropertyInfo {
+                            name,
+                            type_annotation: type_ann,
+                            description: None,
+                            readonly: prop.readonly,
+                            optional: prop.optional,
+                            accessibility: None,
+                            is_static: false,
+                        });
+                    }
+                }
+                TSSignature::TSMethodSignature(method) => {
+                    if let Some(name) = self.member_key_name(&method.key) {
+                        // Everything after the name: `(id: number): User`
+                        props.push(PropertyInfo {
+                            name: format!("{}()", name),
+                            type_annotation: self.member_type_text(self.member_key_end(&method.key), method.span.end),
+                            description: None,
+                            readonly: false,
+                            optional: method.optional,
+                            accessibility: None,
+                            is_static: false,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        props
+    }
+
+    /// Display name of a class or interface member key.
+    ///
+    /// Identifiers are kept as written; string, numeric and computed keys are
+    /// shown in brackets as in the source (`['a-b']`, `[0]`, `[Symbol.iterator]`).
+    /// Private `#names` are skipped.
+    fn member_key_name(&self, key: &PropertyKey<'a>) -> Option<String> {
+        match key {
+            PropertyKey::StaticIdentifier(key) => Some(key.name.to_string()),
+            PropertyKey::PrivateIdentifier(_) => None,
+            _ => Some(format!("[{}]", self.span_text(key.span()))),
+        }
+    }
+
+    /// Offset just past a member key, including the `]` closing a computed key
+    fn member_key_end(&self, key: &PropertyKey<'a>) -> u32 {
+        let end = key.span().end;
+        let rest = self.source.get(end as usize..).unwrap_or_default();
+        let after_space = rest.len() - rest.trim_start().len();
+        if rest[after_space..].starts_with(']') {
+            end + after_space as u32 + 1
+        } else {
+            end
+        }
+    }
+
+    /// Source text of a span (used to reconstruct heritage clauses like `Base<T>`)
+    fn span_text(&self, span: Span) -> String {
+        self.source.get(span.start as usize..span.end as usize)
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    }
+
     /// Extract JSDoc comment from leading comments
     fn extract_jsdoc(&self, span: Span) -> Option<String> {
         // Look backwards from span.start to find JSDoc comment
@@ -65,43 +268,59 @@ impl<'a> SymbolVisitor<'a> {
 
         let before = &self.source[..start];
 
-        // Find JSDoc block /** ... */ immediately before this declaration
-        let trimmed = before.trim_end();
+        // Find JSDoc block /** ... */ immediately before this declaration,
+        // skipping modifiers outside the span (`export const` before a declarator)
+        let mut trimmed = before.trim_end();
+        while let Some(rest) = JSDOC_SKIPPED_KEYWORDS.iter().find_map(|kw| {
+            trimmed
+                .strip_suffix(kw)
+                .filter(|rest| rest.is_empty() || rest.ends_with(char::is_whitespace))
+        }) {
+            trimmed = rest.trim_end();
+        }
         if trimmed.ends_with("*/") {
             if let Some(doc_start) = trimmed.rfind("/**") {
                 let doc = &trimmed[doc_start..];
-                // Clean up the JSDoc: remove /** */, strip * from each line
-                let cleaned = doc.lines()
+                // Clean up the JSDoc: remove /** */, strip * from each line and
+                // drop the `@tag` block; wrapped lines are joined, paragraphs kept
+                let lines = doc.lines()
                     .map(|line| {
                         line.trim()
                             .trim_start_matches("/**")
-                            .trim_start_matches("*/")
+                            .trim_end_matches("*/")
                             .trim_start_matches('*')
                             .trim()
                     })
-                    .filter(|line| !line.is_empty())
+                    .take_while(|line| !line.starts_with('@'));
+                let mut paragraphs: Vec<String> = vec![String::new()];
+                for line in lines {
+                    let paragraph = paragraphs.last_mut().expect("at least one paragraph");
+                    if line.is_empty() {
+                        if !paragraph.is_empty() {
+                            paragraphs.push(String::new());
+                        }
+                    } else {
+                        if !paragraph.is_empty() {
+                            paragraph.push(' ');
+                        }
+                        paragraph.push_str(line);
+                    }
+                }
+                let cleaned = paragraphs
+                    .iter()
+                    .filter(|p| !p.is_empty())
+                    .map(String::as_str)
                     .collect::<Vec<_>>()
-                    .join(" ");
+                    .join("\n\n");
 
-                if !cleaned.is_empty() {
-                    return Some(cleaned);
-                }
+                // A tag-only comment (`/** @internal */`) still documents the symbol
+                return Some(cleaned);
             }
         }
 
         None
     }
 
-    fn get_line_col(&self, offset: u32) -> (usize, usize) {
-        let offset = offset as usize;
-        if offset >= self.source.len() {
-            return (1, 1);
-        }
-        let before = &self.source[..offset];
-        let line = before.lines().count().max(1);
-        (line, 0)
-    }
-
     /// Extract parameter name from binding pattern (handles defaults and destructuring)
     fn extract_param_name(pattern: &BindingPattern) -> String {
         match &pattern.kind {
@@ -145,15 +364,49 @@ impl<'a> Visit<'a> for SymbolVisitor<'a> {
         self.is_exporting = was_exporting;
     }
 
+    fn visit_program(&mut self, program: &Program<'a>) {
+        self.identifier_references.visit_program(program);
+        walk::walk_program(self, program);
+        self.resolve_default_export_target();
+        self.resolve_component_props();
+    }
+
     fn visit_export_default_declaration(&mut self, decl: &ExportDefaultDeclaration<'a>) {
         let was_exporting = self.is_exporting;
         self.is_exporting = true;
+        self.is_default_exporting = true;
+
+        match &decl.declaration {
+            ExportDefaultDeclarationKind::FunctionDeclaration(_)
+            | ExportDefaultDeclarationKind::ClassDeclaration(_)
+            | ExportDefaultDeclarationKind::TSInterfaceDeclaration(_) => {}
+            ExportDefaultDeclarationKind::Identifier(ident) => {
+                // `export default foo;` refers to a declaration elsewhere in the file
+                self.is_default_exporting = false;
+                self.default_export_target = Some((ident.name.to_string(), decl.span));
+            }
+            ExportDefaultDeclarationKind::ArrowFunctionExpression(_)
+            | ExportDefaultDeclarationKind::FunctionExpression(_) => {
+                let jsdoc = self.extract_jsdoc(decl.span);
+                self.add_symbol("default".to_string(), SymbolKind::Function, decl.span, None, None, None, jsdoc);
+            }
+            ExportDefaultDeclarationKind::ClassExpression(_) => {
+                let jsdoc = self.extract_jsdoc(decl.span);
+                self.add_symbol("default".to_string(), SymbolKind::Class, decl.span, None, None, None, jsdoc);
+            }
+            _ => {
+                let jsdoc = self.extract_jsdoc(decl.span);
+                self.add_symbol("default".to_string(), SymbolKind::Variable, decl.span, None, None, None, jsdoc);
+            }
+        }
+
         walk::walk_export_default_declaration(self, decl);
+        self.is_default_exporting = false;
         self.is_exporting = was_exporting;
     }
 
     fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
-        let name = func.id.as_ref().map(|id| id.name.to_string());
+        let name = self.declaration_name(func.id.as_ref());
 
         if let Some(name) = name {
             let mut params = Vec::new();
@@ -184,23 +437,26 @@ impl<'a> Visit<'a> for SymbolVisitor<'a> {
             // Extract JSDoc
             let jsdoc = self.extract_jsdoc(func.span);
 
+            let props = self.component_props(&name, &func.params);
+            let symbols_before = self.symbols.len();
             self.add_symbol(name, SymbolKind::Function, func.span, Some(params), None, return_type, jsdoc);
+            self.attach_component_props(symbols_before, props);
         }
 
         walk::walk_function(self, func, flags);
     }
 
     fn visit_class(&mut self, class: &Class<'a>) {
-        let name = class.id.as_ref().map(|id| id.name.to_string());
+        let name = self.declaration_name(class.id.as_ref());
 
         if let Some(name) = name {
             let mut props = Vec::new();
             for element in &class.body.body {
                 match element {
                     ClassElement::PropertyDefinition(prop) => {
-                        if let PropertyKey::StaticIdentifier(key) = &prop.key {
+                        if let Some(name) = self.member_key_name(&prop.key) {
                              props.push(PropertyInfo {
-                                 name: key.name.to_string(),
+                                 name,
                                  type_annotation: prop.type_annotation.as_ref().map(|t| {
                                      let span = t.span;
                                      self.source.get(span.start as usize..span.end as usize)
@@ -210,15 +466,26 @@ impl<'a> Visit<'a> for SymbolVisitor<'a> {
                                          .to_string()
                                  }),
                                  description: None,
+                                 readonly: prop.readonly,
+                                 optional: prop.optional,
+                                 accessibility: prop.accessibility.map(|a| a.as_str().to_string()),
+                                 is_static: prop.r#static,
                              });
                         }
                     }
                     ClassElement::MethodDefinition(method) => {
-                        if let PropertyKey::StaticIdentifier(key) = &method.key {
+                        if let Some(name) = self.member_key_name(&method.key) {
+                             let signature_end = method.value.return_type.as_ref()
+                                 .map(|rt| rt.span.end)
+                                 .unwrap_or(method.value.params.span.end);
                              props.push(PropertyInfo {
-                                 name: format!("{}()", key.name),
-                                 type_annotation: None,
+                                 name: format!("{}()", name),
+                                 type_annotation: self.member_type_text(method.value.params.span.start, signature_end),
                                  description: None,
+                                 readonly: false,
+                                 optional: method.optional,
+                                 accessibility: method.accessibility.map(|a| a.as_str().to_string()),
+                                 is_static: method.r#static,
                              });
                         }
                     }
@@ -226,6 +493,19 @@ impl<'a> Visit<'a> for SymbolVisitor<'a> {
                 }
             }
 
+            let extends = class.super_class.as_ref()
+                .map(|super_class| {
+                    let end = class.super_type_parameters.as_ref()
+                        .map(|params| params.span.end)
+                        .unwrap_or(super_class.span().end);
+                    vec![self.span_text(Span::new(super_class.span().start, end))]
+                })
+                .unwrap_or_default();
+            let implements = class.implements.as_ref()
+                .map(|list| list.iter().map(|i| self.span_text(i.span)).collect())
+                .unwrap_or_default();
+            self.pending_heritage = (extends, implements);
+
             let jsdoc = self.extract_jsdoc(class.span);
             self.add_symbol(name, SymbolKind::Class, class.span, None, Some(props), None, jsdoc);
         }
@@ -236,7 +516,15 @@ impl<'a> Visit<'a> for SymbolVisitor<'a> {
     fn visit_variable_declarator(&mut self, decl: &VariableDeclarator<'a>) {
         if let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind {
              let jsdoc = self.extract_jsdoc(decl.span);
+             // Arrow and function-expression components: `const Button = (props: ButtonProps) => ...`
+             let props = match &decl.init {
+                 Some(Expression::ArrowFunctionExpression(arrow)) => self.component_props(&id.name, &arrow.params),
+                 Some(Expression::FunctionExpression(func)) => self.component_props(&id.name, &func.params),
+                 _ => None,
+             };
+             let symbols_before = self.symbols.len();
              self.add_symbol(id.name.to_string(), SymbolKind::Variable, decl.span, None, None, None, jsdoc);
+             self.attach_component_props(symbols_before, props);
         }
         walk::walk_variable_declarator(self, decl);
     }
@@ -244,27 +532,14 @@ impl<'a> Visit<'a> for SymbolVisitor<'a> {
     fn visit_ts_interface_declaration(&mut self, decl: &TSInterfaceDeclaration<'a>) {
         let name = decl.id.name.to_string();
 
-        // Extract interface properties
-        let mut props = Vec::new();
-        for element in &decl.body.body {
-            if let TSSignature::TSPropertySignature(prop) = element {
-                if let PropertyKey::StaticIdentifier(key) = &prop.key {
-                    let type_ann = prop.type_annotation.as_ref().map(|t| {
-                        let span = t.span;
-                        self.source.get(span.start as usize..span.end as usize)
-                            .unwrap_or("type")
-                            .trim_start_matches(':')
-                            .trim()
-                            .to_string()
-                    });
-                    props.push(PropertyInfo {
-                        name: key.name.to_string(),
-                        type_annotation: type_ann,
-                        description: None,
-                    });
-                }
-            }
-        }
+        // Extract interface properties and methods
+        let props = self.signature_properties(&decl.body.body);
+        self.type_members.insert(name.clone(), props.clone());
+
+        let extends = decl.extends.as_ref()
+            .map(|list| list.iter().map(|h| self.span_text(h.span)).collect())
+            .unwrap_or_default();
+        self.pending_heritage = (extends, Vec::new());
 
         let jsdoc = self.extract_jsdoc(decl.span);
         self.add_symbol(name, SymbolKind::Interface, decl.span, None, Some(props), None, jsdoc);
@@ -273,8 +548,18 @@ impl<'a> Visit<'a> for SymbolVisitor<'a> {
 
     fn visit_ts_type_alias_declaration(&mut self, decl: &TSTypeAliasDeclaration<'a>) {
         let name = decl.id.name.to_string();
+
+        // Object-literal types carry their members like interfaces do
+        let props = match &decl.type_annotation {
+            TSType::TSTypeLiteral(literal) => Some(self.signature_properties(&literal.members)),
+            _ => None,
+        };
+        if let Some(props) = &props {
+            self.type_members.insert(name.clone(), props.clone());
+        }
+
         let jsdoc = self.extract_jsdoc(decl.span);
-        self.add_symbol(name, SymbolKind::Type, decl.span, None, None, None, jsdoc);
+        self.add_symbol(name, SymbolKind::Type, decl.span, None, props, None, jsdoc);
         walk::walk_ts_type_alias_declaration(self, decl);
     }
 
@@ -315,6 +600,55 @@ mod tests {
         assert_eq!(symbols[0].parameters.as_ref().unwrap()[0].name, "a");
     }
 
+    #[test]
+    fn test_symbol_lines_at_line_start_and_end_of_file() {
+        // `helper` starts right after a newline and `last` ends at the very end of the source
+        let source = "export const a = 1;\nfunction helper() {\n  return a;\n}\nclass last {}";
+        let symbols = parse_and_visit(source, false);
+        let lines: Vec<_> = symbols.iter().map(|s| (s.name.as_str(), s.start_line, s.end_line)).collect();
+        assert_eq!(lines, vec![("a", 1, 1), ("helper", 2, 4), ("last", 5, 5)]);
+    }
+
+    #[test]
+    fn test_jsdoc_before_export_modifiers() {
+        let source = "/** Adds */\nexport function add() {}\n/** Limit */\nexport const LIMIT = 3;\n/** Shape */\nexport default class Shape {}\n// not a doc\nexport function sub() {}\n";
+        let symbols = parse_and_visit(source, false);
+        let docs: Vec<_> = symbols.iter().map(|s| (s.name.as_str(), s.jsdoc.as_deref())).collect();
+        assert_eq!(docs, vec![("add", Some("Adds")), ("LIMIT", Some("Limit")), ("Shape", Some("Shape")), ("sub", None)]);
+    }
+
+    #[test]
+    fn test_jsdoc_keeps_paragraphs_and_drops_tags() {
+        let source = "/**\n * Parses a config file.\n * Comments are ignored.\n *\n *\n * Second paragraph\n * wraps here.\n * @param path The file\n *   to read\n * @returns The config\n */\nfunction parse(path: string) {}\n/** @deprecated */\nfunction old() {}\n";
+        let symbols = parse_and_visit(source, false);
+        assert_eq!(
+            symbols[0].jsdoc.as_deref(),
+            Some("Parses a config file. Comments are ignored.\n\nSecond paragraph wraps here.")
+        );
+        assert_eq!(symbols[1].jsdoc.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_literal_and_computed_member_keys() {
+        let source = "interface Headers { 'content-type': string; 0: number; [KEY]?: boolean; ['get'](id: string): void; }\nclass List { #secret = 1; [Symbol.iterator](): Iterator<number> { return [].values(); } 'a-b' = 2; }";
+        let symbols = parse_and_visit(source, false);
+
+        let members = |index: usize| -> Vec<(String, Option<String>)> {
+            symbols[index].properties.as_ref().unwrap().iter().map(|p| (p.name.clone(), p.type_annotation.clone())).collect()
+        };
+        assert_eq!(members(0), vec![
+            ("['content-type']".to_string(), Some("string".to_string())),
+            ("[0]".to_string(), Some("number".to_string())),
+            ("[KEY]".to_string(), Some("boolean".to_string())),
+            ("['get']()".to_string(), Some("(id: string): void".to_string())),
+        ]);
+        assert!(symbols[0].properties.as_ref().unwrap()[2].optional);
+        assert_eq!(members(1), vec![
+            ("[Symbol.iterator]()".to_string(), Some("(): Iterator<number>".to_string())),
+            ("['a-b']".to_string(), None),
+        ]);
+    }
+
     #[test]
     fn test_extract_class() {
         let source = "class MyClass { prop: string; method() {} }";
@@ -335,6 +669,111 @@ mod tests {
         assert_eq!(symbols[0].kind, SymbolKind::Variable);
     }
 
+    #[test]
+    fn test_class_heritage_clauses() {
+        let source = "class Dog extends Animal<Paw> implements Pet, Comparable<Dog> { bark() {} }";
+        let symbols = parse_and_visit(source, false);
+
+        let dog = symbols.iter().find(|s| s.name == "Dog").unwrap();
+        assert_eq!(dog.extends, vec!["Animal<Paw>"]);
+        assert_eq!(dog.implements, vec!["Pet", "Comparable<Dog>"]);
+        assert_eq!(dog.display_signature(), "class Dog extends Animal<Paw> implements Pet, Comparable<Dog>");
+    }
+
+    #[test]
+    fn test_interface_extends_multiple() {
+        let source = "interface Named { name: string }\ninterface Aged { age: number }\ninterface Person extends Named, Aged { id: string }";
+        let symbols = parse_and_visit(source, false);
+
+        let person = symbols.iter().find(|s| s.name == "Person").unwrap();
+        assert_eq!(person.extends, vec!["Named", "Aged"]);
+        assert!(person.implements.is_empty());
+        assert_eq!(person.display_signature(), "interface Person extends Named, Aged { id: string }");
+
+        let named = symbols.iter().find(|s| s.name == "Named").unwrap();
+        assert!(named.extends.is_empty());
+    }
+
+    #[test]
+    fn test_symbol_references_scoped_to_declaration() {
+        let source = r#"
+            import { format } from './utils';
+            export function usesImport(d: Date) { return format(d); }
+            export function helper() { return 1; }
+        "#;
+        let symbols = parse_and_visit(source, false);
+
+        let uses = symbols.iter().find(|s| s.name == "usesImport").unwrap();
+        assert!(uses.references.contains(&"format".to_string()));
+        assert!(uses.references.contains(&"Date".to_string()));
+
+        let helper = symbols.iter().find(|s| s.name == "helper").unwrap();
+        assert!(helper.references.is_empty());
+    }
+
+    #[test]
+    fn test_member_modifiers_are_captured() {
+        let source = r#"
+            interface Entity {
+                readonly x?: string;
+                load?(): void;
+            }
+            class Registry {
+                private static instances: number = 0;
+                protected readonly id: string;
+                static create(): Registry {}
+            }
+        "#;
+        let symbols = parse_and_visit(source, false);
+
+        let entity = symbols.iter().find(|s| s.name == "Entity").unwrap();
+        let x = &entity.properties.as_ref().unwrap()[0];
+        assert!(x.readonly && x.optional);
+        assert_eq!(x.accessibility, None);
+        assert!(!x.is_static);
+        assert!(entity.properties.as_ref().unwrap()[1].optional);
+
+        let registry = symbols.iter().find(|s| s.name == "Registry").unwrap();
+        let props = registry.properties.as_ref().unwrap();
+        assert_eq!(props[0].name, "instances");
+        assert_eq!(props[0].accessibility.as_deref(), Some("private"));
+        assert!(props[0].is_static && !props[0].readonly && !props[0].optional);
+        assert_eq!(props[1].accessibility.as_deref(), Some("protected"));
+        assert!(props[1].readonly && !props[1].is_static);
+        assert!(props[2].is_static);
+        assert_eq!(props[2].accessibility, None);
+    }
+
+    #[test]
+    fn test_interface_and_type_literal_properties_with_types() {
+        let source = r#"
+            interface Repo {
+                name: string;
+                find(id: number): User;
+            }
+            type Point = { x: number; y: number };
+            class Service {
+                load(id: string): Promise<void> {}
+            }
+        "#;
+        let symbols = parse_and_visit(source, false);
+
+        let repo = symbols.iter().find(|s| s.name == "Repo").unwrap();
+        let props = repo.properties.as_ref().unwrap();
+        assert_eq!(props.len(), 2);
+        assert_eq!(props[0].type_annotation.as_deref(), Some("string"));
+        assert_eq!(props[1].name, "find()");
+        assert_eq!(props[1].type_annotation.as_deref(), Some("(id: number): User"));
+
+        let point = symbols.iter().find(|s| s.name == "Point").unwrap();
+        let props = point.properties.as_ref().unwrap();
+        assert_eq!(props.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), vec!["x", "y"]);
+
+        let service = symbols.iter().find(|s| s.name == "Service").unwrap();
+        let load = &service.properties.as_ref().unwrap()[0];
+        assert_eq!(load.type_annotation.as_deref(), Some("(id: string): Promise<void>"));
+    }
+
     #[test]
     fn test_extract_exported_only() {
         let source = "const x = 1; export const y = 2;";
@@ -343,6 +782,50 @@ mod tests {
         assert_eq!(symbols[0].name, "y");
     }
 
+    #[test]
+    fn test_extract_default_export_class() {
+        let source = "export default class Foo { bar() {} }";
+        let symbols = parse_and_visit(source, false);
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "Foo");
+        assert_eq!(symbols[0].kind, SymbolKind::Class);
+        assert!(symbols[0].exported);
+        assert!(symbols[0].is_default_export);
+    }
+
+    #[test]
+    fn test_extract_anonymous_default_export_function() {
+        let source = "export default function(a: number) { function inner() {} }";
+        let symbols = parse_and_visit(source, false);
+        let default = symbols.iter().find(|s| s.name == "default").unwrap();
+        assert_eq!(default.kind, SymbolKind::Function);
+        assert!(default.is_default_export);
+        assert_eq!(default.parameters.as_ref().unwrap().len(), 1);
+
+        // Nested declarations are not the default export
+        let inner = symbols.iter().find(|s| s.name == "inner").unwrap();
+        assert!(!inner.is_default_export);
+    }
+
+    #[test]
+    fn test_extract_default_export_identifier() {
+        let source = "const config = {}; export const other = 1; export default config;";
+        let symbols = parse_and_visit(source, false);
+        let config = symbols.iter().find(|s| s.name == "config").unwrap();
+        assert!(config.exported);
+        assert!(config.is_default_export);
+        let other = symbols.iter().find(|s| s.name == "other").unwrap();
+        assert!(!other.is_default_export);
+    }
+
+    #[test]
+    fn test_named_export_is_not_default() {
+        let source = "export function foo() {}";
+        let symbols = parse_and_visit(source, false);
+        assert!(symbols[0].exported);
+        assert!(!symbols[0].is_default_export);
+    }
+
     #[test]
     fn test_extract_interface_and_type() {
         let source = "interface I {} type T = {};";
@@ -351,4 +834,24 @@ mod tests {
         assert!(symbols.iter().any(|s| s.name == "I" && s.kind == SymbolKind::Interface));
         assert!(symbols.iter().any(|s| s.name == "T" && s.kind == SymbolKind::Type));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_component_props_from_separate_interface() {
+        let source = "export function Button(props: ButtonProps) { return null; }\n\
+                      interface ButtonProps { label: string; disabled?: boolean; onClick(): void }\n\
+                      export const Card = ({ title }: { title: string }) => null;\n\
+                      export function format(value: ButtonProps) {}\n";
+        let symbols = parse_and_visit(source, true);
+
+        let button = symbols.iter().find(|s| s.name == "Button").unwrap();
+        let props: Vec<_> = button.properties.as_ref().unwrap().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(props, vec!["label", "disabled", "onClick()"]);
+
+        let card = symbols.iter().find(|s| s.name == "Card").unwrap();
+        assert_eq!(card.properties.as_ref().unwrap()[0].name, "title");
+
+        // Lowercase functions are not components
+        let format = symbols.iter().find(|s| s.name == "format").unwrap();
+        assert!(format.properties.is_none());
+    }
+}
@@ -1,6 +1,9 @@
 use std::collections::HashSet;
-use crate::models::{TypeError, SourceCode};
-use crate::highlighting::extract_code_context;
+use crate::models::{TypeError, RelatedSpan, SourceCode, SpanPosition};
+use crate::scope_names::{enclosing_scope_names, join_scope_names};
+use crate::source_map::SourceMap;
+use crate::type_errors::DEFAULT_SCOPE_SEPARATOR;
+use crate::highlighting::{detect_indent_unit, extract_code_context_with_lines, IndentUnit};
 use oxc_ast::visit::{walk, Visit};
 use oxc_ast::ast::*;
 use oxc_semantic::{Semantic, ScopeFlags};
@@ -13,8 +16,16 @@ pub struct TypeErrorVisitor<'a> {
     pub source: &'a str,
     pub semantic: &'a Semantic<'a>,
     pub diagnostics: &'a Vec<OxcDiagnostic>, // Input
-    current_scope: Vec<String>,
+    source_map: SourceMap<'a>,
     processed_errors: HashSet<usize>,
+    /// Overrides the code context window around each error (see `AnalysisOptions::context_lines`)
+    context_lines: Option<usize>,
+    /// Detected once per file and attached to every error's `SourceCode`
+    indent_unit: IndentUnit,
+    /// Joins scope names (see `ScopeNaming::separator`)
+    scope_separator: String,
+    /// Outermost scope segment, when scopes include the module
+    module_scope: Option<String>,
 }
 
 impl<'a> TypeErrorVisitor<'a> {
@@ -24,16 +35,37 @@ impl<'a> TypeErrorVisitor<'a> {
             source,
             semantic,
             diagnostics,
-            current_scope: Vec::new(),
+            source_map: SourceMap::new(source),
             processed_errors: HashSet::new(),
+            context_lines: None,
+            indent_unit: detect_indent_unit(source),
+            scope_separator: DEFAULT_SCOPE_SEPARATOR.to_string(),
+            module_scope: None,
         }
     }
 
-    fn get_scope_string(&self) -> String {
-        if self.current_scope.is_empty() {
-            return "global".to_string();
-        }
-        self.current_scope.join("::")
+    /// Limits the code context of each error to `context_lines` lines around it.
+    pub fn with_context_lines(mut self, context_lines: Option<usize>) -> Self {
+        self.context_lines = context_lines;
+        self
+    }
+
+    /// Joins scope names with `separator`, prefixed by `module` when given.
+    pub fn with_scope_naming(mut self, separator: &str, module: Option<String>) -> Self {
+        self.scope_separator = separator.to_string();
+        self.module_scope = module;
+        self
+    }
+
+    /// Drops the diagnostics at `indices` instead of reporting them.
+    pub fn with_skipped_diagnostics(mut self, indices: impl IntoIterator<Item = usize>) -> Self {
+        self.processed_errors.extend(indices);
+        self
+    }
+
+    /// Scope of an error at `span`, as also given to its code context
+    fn scope_name(&self, span: Span) -> String {
+        join_scope_names(&enclosing_scope_names(self.semantic, span), &self.scope_separator, self.module_scope.as_deref())
     }
 
     fn to_oxc_span(span: &SourceSpan) -> Span {
@@ -63,28 +95,44 @@ impl<'a> TypeErrorVisitor<'a> {
         // OxcCode has scope (e.g., "TS") and number (e.g., "2322")
         let error_id = Self::extract_error_code(error);
 
+        let scope = self.scope_name(error_span);
+
         // Extract code context if possible using the highlighting module
-        let source_code = extract_code_context(
+        let source_code = extract_code_context_with_lines(
             self.source,
             error_span,
-            self.semantic
+            self.semantic,
+            self.context_lines,
         ).ok().map(|ctx| SourceCode {
             full_code: ctx.full_code,
             display_code: ctx.display_code,
             scope_type: ctx.scope_type,
-            scope_name: ctx.scope_name,
+            scope_name: scope.clone(),
+            indent_unit: self.indent_unit,
+            truncation_info: ctx.truncation_info,
         });
 
+        // Labels after the first mark further places the diagnostic is about
+        let related = error.labels.iter().flatten().skip(1).filter_map(|label| {
+            Some(RelatedSpan {
+                message: label.label()?.to_string(),
+                position: SpanPosition::from_source_map(&self.source_map, Self::to_oxc_span(label.inner())),
+            })
+        }).collect();
+
         self.errors.push(TypeError {
             id: error_id,
             message,
+            severity: error.severity.into(),
             file: "unknown".to_string(), // Will be set by extract_type_errors in type_errors.rs
             line,
             column,
-            scope: self.get_scope_string(),
+            scope,
             block,
             source_code,
             span: error_span,
+            position: SpanPosition::from_source_map(&self.source_map, error_span),
+            related,
         });
 
         self.processed_errors.insert(index);
@@ -112,19 +160,25 @@ impl<'a> TypeErrorVisitor<'a> {
         }
     }
 
+    /// 1-based line and 0-based column of `offset`, or `(0, 0)` past the end of the source
     fn get_line_col(&self, offset: u32) -> (usize, usize) {
-        let offset = offset as usize;
-        if offset >= self.source.len() {
+        if offset as usize >= self.source.len() {
             return (0, 0);
         }
-        let before = &self.source[..offset];
-        let line = before.lines().count();
-        let last_line = before.lines().last().unwrap_or("");
-        let column = last_line.chars().count(); 
-        (line, column)
+        let (line, column) = self.source_map.line_col(offset as usize);
+        (line, column - 1)
+    }
+
+    /// True once every diagnostic has been attributed to a scope
+    fn all_errors_processed(&self) -> bool {
+        self.processed_errors.len() == self.diagnostics.len()
     }
 
     fn check_errors_in_span(&mut self, span: Span) {
+        if self.all_errors_processed() {
+            return;
+        }
+
         for (i, error) in self.diagnostics.iter().enumerate() {
              if !self.processed_errors.contains(&i) {
                 let error_span = error.labels.as_ref()
@@ -156,52 +210,16 @@ impl<'a> Visit<'a> for TypeErrorVisitor<'a> {
         }
     }
 
+    // Errors are collected innermost scope first; their scope names come from
+    // `scope_names`, not from the walk
     fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
-        let mut pushed = false;
-        if let Some(id) = &func.id {
-            self.current_scope.push(id.name.to_string());
-            pushed = true;
-        }
-        
         walk::walk_function(self, func, flags);
-        
         self.check_errors_in_span(func.span);
-
-        if pushed {
-            self.current_scope.pop();
-        }
     }
 
     fn visit_class(&mut self, class: &Class<'a>) {
-        let mut pushed = false;
-        if let Some(id) = &class.id {
-             self.current_scope.push(id.name.to_string());
-             pushed = true;
-        } else {
-             // For anonymous classes (e.g. export default class {}), maybe push "anonymous"? 
-             // Or keep parent scope.
-             // Let's keep parent scope for consistency with function logic change.
-        }
-        
         walk::walk_class(self, class);
-        
         self.check_errors_in_span(class.span);
-        
-        if pushed {
-            self.current_scope.pop();
-        }
-    }
-    
-    fn visit_method_definition(&mut self, def: &MethodDefinition<'a>) {
-        let name = match &def.key {
-            PropertyKey::StaticIdentifier(id) => id.name.to_string(),
-            PropertyKey::PrivateIdentifier(id) => id.name.to_string(),
-            _ => "dynamic_method".to_string(),
-        };
-        
-        self.current_scope.push(name);
-        walk::walk_method_definition(self, def);
-        self.current_scope.pop();
     }
     
     fn visit_statement(&mut self, stmt: &Statement<'a>) {
@@ -261,7 +279,51 @@ mod tests {
         let source = "class MyClass { method() { let z = 1; let z = 2; } }";
         let errors = parse_and_visit(source);
         assert!(!errors.is_empty());
-        assert_eq!(errors[0].scope, "MyClass::method");
+        assert_eq!(errors[0].scope, "MyClass.method");
+    }
+
+    #[test]
+    fn test_configured_separator_and_module_scope() {
+        let source = "class Shape {\n  area() { let r = 1; let r = 2; }\n  #scale() { let k = 1; let k = 2; }\n}\n";
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::default().with_typescript(true)).parse();
+        let semantic_ret = SemanticBuilder::new(source).build(&ret.program);
+        let diagnostics = semantic_ret.errors;
+
+        let mut visitor = TypeErrorVisitor::new(source, &semantic_ret.semantic, &diagnostics)
+            .with_scope_naming("::", None);
+        visitor.visit_program(&ret.program);
+        let scopes: Vec<&str> = visitor.errors.iter().map(|e| e.scope.as_str()).collect();
+        assert_eq!(scopes, vec!["Shape::area", "Shape::#scale"]);
+
+        let mut visitor = TypeErrorVisitor::new(source, &semantic_ret.semantic, &diagnostics)
+            .with_scope_naming(".", Some("src/shapes".to_string()));
+        visitor.visit_program(&ret.program);
+        let scopes: Vec<&str> = visitor.errors.iter().map(|e| e.scope.as_str()).collect();
+        assert_eq!(scopes, vec!["src/shapes.Shape.area", "src/shapes.Shape.#scale"]);
+    }
+
+    #[test]
+    fn test_code_context_scope_name_matches_error_scope() {
+        let source = "class Shape {\n  area() {\n    const run = () => { let r = 1; let r = 2; };\n  }\n}\nconst value = 1;\nlet value = 2;\n";
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::default().with_typescript(true)).parse();
+        let semantic_ret = SemanticBuilder::new(source).build(&ret.program);
+        let diagnostics = semantic_ret.errors;
+
+        for (separator, module) in [(".", None), ("::", Some("src/shapes".to_string()))] {
+            let mut visitor = TypeErrorVisitor::new(source, &semantic_ret.semantic, &diagnostics)
+                .with_scope_naming(separator, module);
+            visitor.visit_program(&ret.program);
+
+            assert_eq!(visitor.errors.len(), 2);
+            for error in &visitor.errors {
+                assert_eq!(error.source_code.as_ref().unwrap().scope_name, error.scope);
+            }
+        }
+        let errors = parse_and_visit(source);
+        assert_eq!(errors[0].scope, "Shape.area");
+        assert_eq!(errors[1].scope, "global");
     }
 
     #[test]
@@ -269,7 +331,15 @@ mod tests {
         let source = "function outer() { function inner() { let a = 1; let a = 2; } }";
         let errors = parse_and_visit(source);
         assert!(!errors.is_empty());
-        assert_eq!(errors[0].scope, "outer::inner");
+        assert_eq!(errors[0].scope, "outer.inner");
+    }
+
+    #[test]
+    fn test_severity_defaults_to_error() {
+        let source = "let x = 1; let x = 2;";
+        let errors = parse_and_visit(source);
+        assert!(!errors.is_empty());
+        assert_eq!(errors[0].severity, crate::highlighting::ErrorSeverity::Error);
     }
 
     #[test]
@@ -279,6 +349,25 @@ mod tests {
         assert!(errors.is_empty());
     }
 
+    #[test]
+    fn test_errors_starting_a_line_report_that_line() {
+        // Diagnostics at the very start of the file or of a line sit on an
+        // empty prefix, which `str::lines` would not count as a line
+        let source = "let a = 1;\nlet b = 2;\n";
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::default().with_typescript(true)).parse();
+        let semantic = SemanticBuilder::new(source).build(&ret.program).semantic;
+        let diagnostics = vec![
+            OxcDiagnostic::error("first").with_label(Span::new(0, 3)),
+            OxcDiagnostic::error("second").with_label(Span::new(11, 14)),
+        ];
+
+        let mut visitor = TypeErrorVisitor::new(source, &semantic, &diagnostics);
+        visitor.visit_program(&ret.program);
+        let positions: Vec<(usize, usize)> = visitor.errors.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(positions, vec![(1, 0), (2, 0)]);
+    }
+
     #[test]
     fn test_error_id_extraction() {
         // Test that error IDs are properly extracted (not hardcoded to "error")
@@ -0,0 +1,22 @@
+//! The directory walk shared by `ta source` and the file watcher.
+
+use std::path::Path;
+
+use ignore::WalkBuilder;
+
+/// Per-directory ignore file honored by every walker, using gitignore syntax
+pub const TAIGNORE_FILENAME: &str = ".taignore";
+
+/// Walk of `root` that skips hidden files and what `.gitignore`, `.ignore`
+/// and [`TAIGNORE_FILENAME`] files exclude, and never enters `.git`.
+///
+/// Callers adjust the returned builder further, e.g. with a depth limit.
+pub fn project_walker(root: &Path) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .standard_filters(true)
+        .add_custom_ignore_filename(TAIGNORE_FILENAME)
+        // Without the hidden filter (see `ta source --glob`) .git would be walked
+        .filter_entry(|e| e.file_name() != ".git");
+    builder
+}
@@ -0,0 +1,219 @@
+//! Running file analyses against a deadline, see
+//! [`AnalysisOptions::per_file_timeout`](crate::analyzer::AnalysisOptions::per_file_timeout).
+//!
+//! A thread cannot be cancelled, so an analysis that misses its deadline keeps
+//! running in the background while the caller moves on. [`Watchdog`] runs
+//! analyses on helper threads it keeps for reuse, remembers which files are
+//! still running after their deadline so they are not started a second time,
+//! and stops taking work once [`MAX_ABANDONED`] of them pile up.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use crate::{Error, Result};
+
+/// Timed-out analyses that may still be running before [`Watchdog::run`]
+/// refuses new files
+pub const MAX_ABANDONED: usize = 8;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Helper threads for analyses with a deadline, reused across files and runs.
+pub struct Watchdog {
+    jobs: Sender<Job>,
+    receiver: Arc<Mutex<mpsc::Receiver<Job>>>,
+    /// Helper threads waiting for a job that no caller has claimed yet
+    idle: Arc<AtomicUsize>,
+    /// Files whose analysis missed its deadline and has not finished since
+    abandoned: Arc<Mutex<HashSet<PathBuf>>>,
+}
+
+impl Default for Watchdog {
+    fn default() -> Self {
+        let (jobs, receiver) = mpsc::channel();
+        Self {
+            jobs,
+            receiver: Arc::new(Mutex::new(receiver)),
+            idle: Arc::new(AtomicUsize::new(0)),
+            abandoned: Arc::default(),
+        }
+    }
+}
+
+impl Watchdog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Files whose analysis missed its deadline and is still running
+    pub fn abandoned(&self) -> Vec<PathBuf> {
+        lock(&self.abandoned).iter().cloned().collect()
+    }
+
+    /// Run `analyze` for `path` on a helper thread and wait at most `timeout` for it.
+    ///
+    /// Fails with [`Error::Timeout`] when the deadline passes, or right away
+    /// while an earlier analysis of `path` is still running. Once
+    /// [`MAX_ABANDONED`] analyses are still running, new files fail without
+    /// being started.
+    pub fn run<T, F>(&self, path: &Path, timeout: Duration, analyze: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        {
+            let abandoned = lock(&self.abandoned);
+            if abandoned.contains(path) {
+                return Err(Error::Timeout(timeout));
+            }
+            if abandoned.len() >= MAX_ABANDONED {
+                return Err(Error::AnalysisError(format!(
+                    "{} timed-out analyses are still running",
+                    abandoned.len()
+                )));
+            }
+        }
+
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let abandoned = Arc::clone(&self.abandoned);
+        let idle = Arc::clone(&self.idle);
+        let owned_path = path.to_path_buf();
+        self.execute(Box::new(move || {
+            let analysis = analyze();
+            // Free before the caller hears back, so its next file reuses this thread
+            idle.fetch_add(1, Ordering::AcqRel);
+            // Decided under the lock the caller holds while giving up, so the
+            // result is either received or the file is no longer abandoned
+            let mut abandoned = lock(&abandoned);
+            if !abandoned.remove(&owned_path) {
+                let _ = sender.send(analysis);
+            }
+        }))?;
+
+        match receiver.recv_timeout(timeout) {
+            Ok(analysis) => analysis,
+            Err(RecvTimeoutError::Timeout) => {
+                let mut abandoned = lock(&self.abandoned);
+                // It may have finished between the deadline and taking the lock
+                match receiver.try_recv() {
+                    Ok(analysis) => analysis,
+                    Err(_) => {
+                        abandoned.insert(path.to_path_buf());
+                        Err(Error::Timeout(timeout))
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => Err(Error::AnalysisError("analysis thread panicked".to_string())),
+        }
+    }
+
+    /// Hand `job` to an idle helper thread, starting a new one when all are busy
+    fn execute(&self, job: Job) -> Result<()> {
+        // Claiming an idle thread up front means the job never queues behind a
+        // busy one, which would count against its deadline
+        let claimed = self.idle.fetch_update(Ordering::AcqRel, Ordering::Acquire, |idle| idle.checked_sub(1)).is_ok();
+        if !claimed {
+            let receiver = Arc::clone(&self.receiver);
+            std::thread::Builder::new()
+                .name("ta-analyze".to_string())
+                .spawn(move || loop {
+                    // The lock is released before the job runs; ends once the watchdog is dropped
+                    let Ok(job) = lock(&receiver).recv() else { break };
+                    job();
+                })?;
+        }
+        self.jobs
+            .send(job)
+            .map_err(|_| Error::AnalysisError("analysis threads stopped".to_string()))
+    }
+}
+
+/// Lock `mutex`, ignoring poisoning: the guarded sets stay consistent even if
+/// an analysis panicked while a helper held the lock
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::Receiver;
+
+    /// An analysis that blocks until the returned sender releases it
+    fn held_analysis() -> (Sender<()>, impl FnOnce() -> Result<u32> + Send + 'static) {
+        let (release, held): (Sender<()>, Receiver<()>) = mpsc::channel();
+        (release, move || {
+            let _ = held.recv();
+            Ok(1)
+        })
+    }
+
+    #[test]
+    fn test_finished_analysis_is_returned() {
+        let watchdog = Watchdog::new();
+        let analysis = watchdog.run(Path::new("a.ts"), Duration::from_secs(60), || Ok(42));
+        assert_eq!(analysis.unwrap(), 42);
+        assert!(watchdog.abandoned().is_empty());
+    }
+
+    #[test]
+    fn test_slow_analysis_times_out_and_is_not_restarted() {
+        let watchdog = Watchdog::new();
+        let path = Path::new("slow.ts");
+        let (release, slow) = held_analysis();
+        let analysis = watchdog.run(path, Duration::from_millis(10), slow);
+        assert!(matches!(analysis, Err(Error::Timeout(_))));
+        assert_eq!(watchdog.abandoned(), vec![path.to_path_buf()]);
+
+        // Still running, so it is reported as timed out without starting again
+        let started = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&started);
+        let analysis = watchdog.run(path, Duration::from_secs(60), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(2)
+        });
+        assert!(matches!(analysis, Err(Error::Timeout(_))));
+        assert_eq!(started.load(Ordering::SeqCst), 0);
+
+        // Once it finishes the file can be analyzed again
+        release.send(()).unwrap();
+        while !watchdog.abandoned().is_empty() {
+            std::thread::yield_now();
+        }
+        assert_eq!(watchdog.run(path, Duration::from_secs(60), || Ok(3)).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_abandoned_analyses_are_capped() {
+        let watchdog = Watchdog::new();
+        let releases: Vec<Sender<()>> = (0..MAX_ABANDONED)
+            .map(|i| {
+                let (release, slow) = held_analysis();
+                let analysis = watchdog.run(Path::new(&format!("slow{i}.ts")), Duration::from_millis(1), slow);
+                assert!(matches!(analysis, Err(Error::Timeout(_))));
+                release
+            })
+            .collect();
+
+        let analysis = watchdog.run(Path::new("fast.ts"), Duration::from_secs(60), || Ok(1));
+        assert!(matches!(analysis, Err(Error::AnalysisError(message)) if message.contains("still running")));
+        drop(releases);
+    }
+
+    #[test]
+    fn test_helper_threads_are_reused() {
+        let watchdog = Watchdog::new();
+        let threads: HashSet<_> = (0..20)
+            .map(|i| {
+                let path = PathBuf::from(format!("file{i}.ts"));
+                watchdog
+                    .run(&path, Duration::from_secs(60), || Ok(std::thread::current().id()))
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(threads.len(), 1);
+    }
+}
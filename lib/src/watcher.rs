@@ -1,11 +1,13 @@
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use notify_debouncer_full::{new_debouncer, DebouncedEvent};
 use notify_debouncer_full::notify::{RecursiveMode, EventKind};
-use crate::models::{SymbolKind, TestStatus};
+use oxc_span::Span;
+use crate::models::{SymbolKind, TestStatus, TypeTest};
 use crate::analyzer::{Analyzer, AnalysisResult, AnalysisOptions};
-use crate::Result;
+use crate::{Error, Result};
 
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(tag = "type", content = "data")]
@@ -18,20 +20,135 @@ pub enum WatchEvent {
     SymbolRemoved { name: String, file: String },
     ModuleDepChanged { file: String },
     ExternalDepChanged { package: String },
-    TestStatusChanged { file: String, test: String, status: TestStatus },
-    NewFailingTest { file: String, test: String },
-    TestFixed { file: String, test: String },
-    NewTestAdded { file: String, test: String },
+    // Test events carry the location of the `it`/`test` call (see [`TypeTest`])
+    TestStatusChanged {
+        file: String,
+        test: String,
+        status: TestStatus,
+        line: usize,
+        column: usize,
+        #[serde(serialize_with = "crate::models::span_serializer::serialize")]
+        span: Span,
+    },
+    NewFailingTest {
+        file: String,
+        test: String,
+        line: usize,
+        column: usize,
+        #[serde(serialize_with = "crate::models::span_serializer::serialize")]
+        span: Span,
+    },
+    TestFixed {
+        file: String,
+        test: String,
+        line: usize,
+        column: usize,
+        #[serde(serialize_with = "crate::models::span_serializer::serialize")]
+        span: Span,
+    },
+    NewTestAdded {
+        file: String,
+        test: String,
+        line: usize,
+        column: usize,
+        #[serde(serialize_with = "crate::models::span_serializer::serialize")]
+        span: Span,
+    },
+    TestRemoved {
+        file: String,
+        test: String,
+        line: usize,
+        column: usize,
+        #[serde(serialize_with = "crate::models::span_serializer::serialize")]
+        span: Span,
+    },
+}
+
+/// Names of the [`WatchEvent`] variants, as accepted by `ta watch --events`
+pub const WATCH_EVENT_NAMES: [&str; 13] = [
+    "source-file-changed",
+    "source-file-created",
+    "source-file-removed",
+    "symbol-renamed",
+    "symbol-added",
+    "symbol-removed",
+    "module-dep-changed",
+    "external-dep-changed",
+    "test-status-changed",
+    "new-failing-test",
+    "test-fixed",
+    "new-test-added",
+    "test-removed",
+];
+
+impl WatchEvent {
+    /// The event's name, one of [`WATCH_EVENT_NAMES`] (e.g. `symbol-added`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            WatchEvent::SourceFileChanged { .. } => "source-file-changed",
+            WatchEvent::SourceFileCreated { .. } => "source-file-created",
+            WatchEvent::SourceFileRemoved { .. } => "source-file-removed",
+            WatchEvent::SymbolRenamed { .. } => "symbol-renamed",
+            WatchEvent::SymbolAdded { .. } => "symbol-added",
+            WatchEvent::SymbolRemoved { .. } => "symbol-removed",
+            WatchEvent::ModuleDepChanged { .. } => "module-dep-changed",
+            WatchEvent::ExternalDepChanged { .. } => "external-dep-changed",
+            WatchEvent::TestStatusChanged { .. } => "test-status-changed",
+            WatchEvent::NewFailingTest { .. } => "new-failing-test",
+            WatchEvent::TestFixed { .. } => "test-fixed",
+            WatchEvent::NewTestAdded { .. } => "new-test-added",
+            WatchEvent::TestRemoved { .. } => "test-removed",
+        }
+    }
 }
 
 pub trait WatchHandler: Send + Sync {
     fn handle_event(&self, event: &WatchEvent) -> Result<()>;
 }
 
+/// Passes only the subscribed events on to another handler
+pub struct FilteredHandler {
+    events: HashSet<&'static str>,
+    inner: Box<dyn WatchHandler>,
+}
+
+impl FilteredHandler {
+    /// Subscribes `inner` to the events named in `names` (see [`WATCH_EVENT_NAMES`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownWatchEvent`] for a name that is not an event.
+    pub fn new<S: AsRef<str>>(names: &[S], inner: Box<dyn WatchHandler>) -> Result<Self> {
+        let events = names
+            .iter()
+            .map(|name| {
+                let name = name.as_ref().trim();
+                WATCH_EVENT_NAMES.iter().copied().find(|known| known.eq_ignore_ascii_case(name)).ok_or_else(|| {
+                    Error::UnknownWatchEvent { name: name.to_string(), valid: WATCH_EVENT_NAMES.join(", ") }
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { events, inner })
+    }
+}
+
+impl WatchHandler for FilteredHandler {
+    fn handle_event(&self, event: &WatchEvent) -> Result<()> {
+        if self.events.contains(event.name()) {
+            self.inner.handle_event(event)
+        } else {
+            Ok(())
+        }
+    }
+}
+
 pub struct FileWatcher {
     analyzer: Analyzer,
     handlers: Vec<Box<dyn WatchHandler>>,
-    previous_state: Arc<Mutex<Option<AnalysisResult>>>,
+    /// Symbols and tests of every watched file as of its last analysis
+    state: Arc<Mutex<AnalysisResult>>,
+    /// Suppress the startup status line on stderr
+    quiet: bool,
 }
 
 impl FileWatcher {
@@ -39,22 +156,39 @@ impl FileWatcher {
         Self {
             analyzer: Analyzer::new(options),
             handlers,
-            previous_state: Arc::new(Mutex::new(None)),
+            state: Arc::new(Mutex::new(AnalysisResult::default())),
+            quiet: false,
         }
     }
 
+    /// Don't print "Watching for changes" on stderr (watch errors are still reported).
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
     pub fn watch(&self, paths: &[PathBuf]) -> Result<()> {
         let (tx, rx) = std::sync::mpsc::channel();
 
         let mut debouncer = new_debouncer(Duration::from_millis(500), None, tx)
             .map_err(|e| crate::error::Error::AnalysisError(format!("Failed to create debouncer: {}", e)))?;
 
-        for path in paths {
-            debouncer.watch(path, RecursiveMode::Recursive)
-                .map_err(|e| crate::error::Error::IoError(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+        // Watch canonical paths so baseline file names match the absolute paths notify reports
+        let roots = paths
+            .iter()
+            .map(|path| path.canonicalize())
+            .collect::<std::io::Result<Vec<_>>>()?;
+        for root in &roots {
+            debouncer.watch(root, RecursiveMode::Recursive)
+                .map_err(|e| crate::error::Error::IoError(std::io::Error::other(e.to_string())))?;
         }
 
-        println!("Watching for changes in {:?}...", paths);
+        self.record_baseline(&roots)?;
+
+        // Status goes to stderr so stdout stays a clean event stream
+        if !self.quiet {
+            eprintln!("Watching for changes in {:?}...", paths);
+        }
 
         for result in rx {
             match result {
@@ -72,6 +206,31 @@ impl FileWatcher {
         Ok(())
     }
 
+    /// Analyze every TypeScript file under `roots` without emitting events.
+    ///
+    /// Later changes are diffed against this state, so existing symbols and tests
+    /// are not reported as added the first time their file changes.
+    pub fn record_baseline(&self, roots: &[PathBuf]) -> Result<()> {
+        let mut files = Vec::new();
+        for root in roots {
+            self.collect_ts_files(root, &mut files);
+        }
+
+        let baseline = self.analyzer.analyze_files(&files)?;
+        *self.state.lock().unwrap() = baseline;
+        Ok(())
+    }
+
+    /// Collects the TypeScript files under `path` with the same ignore rules as
+    /// `ta source`, so ignored files are never part of the baseline.
+    fn collect_ts_files(&self, path: &Path, files: &mut Vec<PathBuf>) {
+        for entry in crate::walk::project_walker(path).build().flatten() {
+            if entry.file_type().is_some_and(|t| t.is_file()) && self.is_ts_file(entry.path()) {
+                files.push(entry.into_path());
+            }
+        }
+    }
+
     fn process_debounced_events(&self, events: Vec<DebouncedEvent>) -> Result<()> {
         let mut affected_files = Vec::new();
         for event in events {
@@ -88,30 +247,54 @@ impl FileWatcher {
             }
         }
 
+        self.process_changes(affected_files)
+    }
+
+    /// Re-analyze the changed files and dispatch the differences to every handler.
+    ///
+    /// Only the changed files are compared, so symbols in untouched files are
+    /// never reported as removed. Deleted files report all their symbols removed.
+    pub fn process_changes(&self, mut affected_files: Vec<PathBuf>) -> Result<()> {
+        affected_files.sort();
+        affected_files.dedup();
         if affected_files.is_empty() {
             return Ok(());
         }
 
-        let current_result = self.analyzer.analyze_files(&affected_files)?;
-        let mut previous_state = self.previous_state.lock().unwrap();
-        
-        if let Some(prev) = previous_state.as_ref() {
-            let diff_events = self.compute_diff(prev, &current_result);
-            for event in diff_events {
-                for handler in &self.handlers {
-                    handler.handle_event(&event)?;
-                }
+        let current = self.analyzer.analyze_files(&affected_files)?;
+        let touched: HashSet<String> = affected_files
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+
+        let events = {
+            let mut state = self.state.lock().unwrap();
+            let previous = AnalysisResult {
+                symbols: state.symbols.iter().filter(|s| touched.contains(&s.file)).cloned().collect(),
+                tests: state.tests.iter().filter(|t| touched.contains(&t.file)).cloned().collect(),
+                ..Default::default()
+            };
+            let events = self.compute_diff(&previous, &current);
+
+            state.symbols.retain(|s| !touched.contains(&s.file));
+            state.symbols.extend(current.symbols);
+            state.tests.retain(|t| !touched.contains(&t.file));
+            state.tests.extend(current.tests);
+            events
+        };
+
+        for event in events {
+            for handler in &self.handlers {
+                handler.handle_event(&event)?;
             }
         }
-
-        *previous_state = Some(current_result);
         Ok(())
     }
 
     fn is_ts_file(&self, path: &Path) -> bool {
         path.extension()
             .and_then(|s| s.to_str())
-            .map(|s| s == "ts" || s == "tsx")
+            .map(|s| matches!(s, "ts" | "tsx" | "mts" | "cts"))
             .unwrap_or(false)
     }
 
@@ -138,25 +321,39 @@ impl FileWatcher {
             }
         }
 
-        // 2. Detect Test changes
+        // 2. Detect Test changes (tests are identified by file, describe block and name)
+        let same_test = |a: &TypeTest, b: &TypeTest| {
+            a.file == b.file && a.describe_block == b.describe_block && a.test_name == b.test_name
+        };
+
         for new_test in &new.tests {
-            if let Some(old_test) = old.tests.iter().find(|t| t.file == new_test.file && t.test_name == new_test.test_name) {
+            if let Some(old_test) = old.tests.iter().find(|t| same_test(t, new_test)) {
+                // Any transition is reported, including to/from NoTypeCases
                 if old_test.status != new_test.status {
                     events.push(WatchEvent::TestStatusChanged {
                         file: new_test.file.clone(),
                         test: new_test.test_name.clone(),
                         status: new_test.status.clone(),
+                        line: new_test.line,
+                        column: new_test.column,
+                        span: new_test.span,
                     });
                     
                     if new_test.status == TestStatus::Failing && old_test.status != TestStatus::Failing {
                          events.push(WatchEvent::NewFailingTest {
                             file: new_test.file.clone(),
                             test: new_test.test_name.clone(),
+                            line: new_test.line,
+                            column: new_test.column,
+                            span: new_test.span,
                         });
                     } else if new_test.status == TestStatus::Passing && old_test.status == TestStatus::Failing {
                          events.push(WatchEvent::TestFixed {
                             file: new_test.file.clone(),
                             test: new_test.test_name.clone(),
+                            line: new_test.line,
+                            column: new_test.column,
+                            span: new_test.span,
                         });
                     }
                 }
@@ -164,6 +361,22 @@ impl FileWatcher {
                 events.push(WatchEvent::NewTestAdded {
                     file: new_test.file.clone(),
                     test: new_test.test_name.clone(),
+                    line: new_test.line,
+                    column: new_test.column,
+                    span: new_test.span,
+                });
+            }
+        }
+
+        // Removed tests report where they were in the previous version of the file
+        for old_test in &old.tests {
+            if !new.tests.iter().any(|t| same_test(t, old_test)) {
+                events.push(WatchEvent::TestRemoved {
+                    file: old_test.file.clone(),
+                    test: old_test.test_name.clone(),
+                    line: old_test.line,
+                    column: old_test.column,
+                    span: old_test.span,
                 });
             }
         }
@@ -171,3 +384,170 @@ impl FileWatcher {
         events
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_case(name: &str, status: TestStatus) -> TypeTest {
+        TypeTest {
+            file: "test/math.test.ts".to_string(),
+            describe_block: "math".to_string(),
+            test_name: name.to_string(),
+            line: 2,
+            column: 5,
+            span: Span::new(20, 60),
+            has_type_cases: status != TestStatus::NoTypeCases,
+            status,
+        }
+    }
+
+    fn diff(old_tests: Vec<TypeTest>, new_tests: Vec<TypeTest>) -> Vec<WatchEvent> {
+        let watcher = FileWatcher::new(AnalysisOptions::default(), Vec::new());
+        let old = AnalysisResult { tests: old_tests, ..Default::default() };
+        let new = AnalysisResult { tests: new_tests, ..Default::default() };
+        watcher.compute_diff(&old, &new)
+    }
+
+    #[test]
+    fn test_diff_reports_test_gaining_type_cases() {
+        let events = diff(
+            vec![test_case("adds", TestStatus::NoTypeCases)],
+            vec![test_case("adds", TestStatus::Passing)],
+        );
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            WatchEvent::TestStatusChanged { test, status: TestStatus::Passing, .. } if test == "adds"
+        ));
+    }
+
+    #[test]
+    fn test_diff_reports_test_losing_type_cases() {
+        let events = diff(
+            vec![test_case("adds", TestStatus::Failing)],
+            vec![test_case("adds", TestStatus::NoTypeCases)],
+        );
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            WatchEvent::TestStatusChanged { status: TestStatus::NoTypeCases, .. }
+        ));
+    }
+
+    #[test]
+    fn test_test_events_serialize_location() {
+        let events = diff(Vec::new(), vec![test_case("adds", TestStatus::Passing)]);
+        let json = serde_json::to_value(&events[0]).unwrap();
+
+        assert_eq!(json["type"], "NewTestAdded");
+        assert_eq!(json["data"]["line"], 2);
+        assert_eq!(json["data"]["column"], 5);
+        assert_eq!(json["data"]["span"]["start"], 20);
+        assert_eq!(json["data"]["span"]["end"], 60);
+    }
+
+    #[test]
+    fn test_diff_reports_removed_test() {
+        let events = diff(
+            vec![
+                test_case("adds", TestStatus::Passing),
+                test_case("subtracts", TestStatus::Passing),
+            ],
+            vec![test_case("adds", TestStatus::Passing)],
+        );
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            WatchEvent::TestRemoved { test, .. } if test == "subtracts"
+        ));
+    }
+
+    /// Records every dispatched event
+    #[derive(Default)]
+    struct Recorder(Arc<Mutex<Vec<WatchEvent>>>);
+
+    impl WatchHandler for Recorder {
+        fn handle_event(&self, event: &WatchEvent) -> Result<()> {
+            self.0.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_baseline_suppresses_existing_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let changed = root.join("a.ts");
+        let untouched = root.join("b.ts");
+        std::fs::write(&changed, "export const existing = 1;").unwrap();
+        std::fs::write(&untouched, "export const other = 1;").unwrap();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let watcher = FileWatcher::new(
+            AnalysisOptions::default(),
+            vec![Box::new(Recorder(Arc::clone(&events)))],
+        );
+        watcher.record_baseline(&[root]).unwrap();
+        assert!(events.lock().unwrap().is_empty());
+
+        std::fs::write(&changed, "export const existing = 1;\nexport function added() {}").unwrap();
+        watcher.process_changes(vec![changed.clone(), changed]).unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1, "{:?}", events);
+        assert!(matches!(&events[0], WatchEvent::SymbolAdded { name, .. } if name == "added"));
+    }
+
+    #[test]
+    fn test_filtered_handler_passes_only_subscribed_events() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let handler = FilteredHandler::new(&["symbol-added", " Test-Fixed"], Box::new(Recorder(Arc::clone(&events)))).unwrap();
+
+        let removed = WatchEvent::SymbolRemoved { name: "gone".to_string(), file: "a.ts".to_string() };
+        let added = WatchEvent::SymbolAdded { name: "added".to_string(), kind: SymbolKind::Function, file: "a.ts".to_string() };
+        let fixed = WatchEvent::TestFixed { file: "a.test.ts".to_string(), test: "adds".to_string(), line: 1, column: 1, span: Span::default() };
+        for event in [&removed, &added, &fixed] {
+            handler.handle_event(event).unwrap();
+        }
+
+        let names: Vec<&str> = events.lock().unwrap().iter().map(WatchEvent::name).collect();
+        assert_eq!(names, vec!["symbol-added", "test-fixed"]);
+    }
+
+    #[test]
+    fn test_filtered_handler_rejects_unknown_event() {
+        let err = FilteredHandler::new(&["symbol-added", "symbol-moved"], Box::new(Recorder::default())).err().unwrap();
+        assert!(matches!(&err, Error::UnknownWatchEvent { name, .. } if name == "symbol-moved"));
+        assert!(err.to_string().contains("test-fixed"));
+    }
+
+    #[test]
+    fn test_diff_unchanged_tests_emit_nothing() {
+        let tests = vec![test_case("adds", TestStatus::NoTypeCases)];
+        assert!(diff(tests.clone(), tests).is_empty());
+    }
+
+    #[test]
+    fn test_collected_files_skip_taignored_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/generated")).unwrap();
+        std::fs::write(dir.path().join(".taignore"), "generated/\n").unwrap();
+        std::fs::write(dir.path().join("src/index.ts"), "export const a = 1;").unwrap();
+        std::fs::write(dir.path().join("src/generated/api.ts"), "export const b = 2;").unwrap();
+        std::fs::write(dir.path().join("src/readme.md"), "# notes").unwrap();
+
+        let watcher = FileWatcher::new(AnalysisOptions::default(), Vec::new());
+        let mut files = Vec::new();
+        watcher.collect_ts_files(dir.path(), &mut files);
+        assert_eq!(files, vec![dir.path().join("src/index.ts")]);
+
+        // A file root is collected as is
+        let mut files = Vec::new();
+        watcher.collect_ts_files(&dir.path().join("src/index.ts"), &mut files);
+        assert_eq!(files, vec![dir.path().join("src/index.ts")]);
+    }
+}
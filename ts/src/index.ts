@@ -21,10 +21,15 @@ export type TestStatus =
   | 'Failing'
   | 'NoTypeCases';
 
+/**
+ * Severity of a reported diagnostic
+ */
+export type ErrorSeverity = 'error' | 'warning' | 'info';
+
 /**
  * Scope type where a type error occurred
  */
-export type ScopeType = 'Function' | 'Method' | 'TypeUtility' | 'ModuleLevel';
+export type ScopeType = 'Function' | 'Method' | 'TypeUtility' | 'Variable' | 'ModuleLevel';
 
 /**
  * Scope of a dependency
@@ -43,13 +48,15 @@ export interface TypeError {
   id: string;
   /** Human-readable error message */
   message: string;
+  /** Diagnostic severity */
+  severity: ErrorSeverity;
   /** Relative file path where error occurred */
   file: string;
   /** Line number (1-indexed) */
   line: number;
   /** Column number (1-indexed) */
   column: number;
-  /** Scope identifier (e.g., "myFunction", "MyClass::method", "global") */
+  /** Scope identifier (e.g., "myFunction", "MyClass.method", "global"); the separator is configurable and a module prefix optional */
   scope: string;
   /** Legacy plain text code block (deprecated, use source_code instead) */
   block: string;
@@ -61,8 +68,17 @@ export interface TypeError {
     display_code: string;
     /** Type of scope containing the error */
     scope_type: ScopeType;
-    /** Name of the scope (function/method/class name) */
+    /** Name of the scope, always equal to the error's `scope` */
     scope_name: string;
+    /** Predominant indentation of the file */
+    indent_unit: "tab" | { spaces: number };
+    /** Lines of `full_code` omitted from `display_code` (absent when nothing was cut) */
+    truncation_info?: {
+      original_line_count: number;
+      displayed_line_count: number;
+      /** Omitted `[start, end]` line ranges (0-based, inclusive) within `full_code` */
+      truncated_sections: [number, number][];
+    };
   };
   /** Source span information */
   span: {
@@ -71,6 +87,14 @@ export interface TypeError {
     /** Byte offset of error end */
     end: number;
   };
+  /** Line of `span.start` (1-indexed) */
+  start_line: number;
+  /** Column of `span.start` in characters (1-indexed) */
+  start_column: number;
+  /** Line of `span.end` (1-indexed) */
+  end_line: number;
+  /** Column of `span.end` in characters (1-indexed, exclusive) */
+  end_column: number;
 }
 
 /**
@@ -78,6 +102,22 @@ export interface TypeError {
  */
 export type SourceOutput = TypeError[];
 
+/**
+ * JSON output from `ta source --manifest`
+ */
+export interface SourceManifest {
+  /** Files selected for analysis */
+  total_files: number;
+  /** Files that were parsed and checked */
+  analyzed_files: number;
+  /** Files that could not be read or parsed */
+  skipped_files: number;
+  error_count: number;
+  warning_count: number;
+  duration_ms: number;
+  ta_version: string;
+}
+
 // ============================================================================
 // Symbols Command - Symbol Extraction
 // ============================================================================
@@ -104,6 +144,14 @@ export interface PropertyInfo {
   type_annotation: string | null;
   /** JSDoc description (if present) */
   description: string | null;
+  /** Declared `readonly` */
+  readonly: boolean;
+  /** Declared with `?` */
+  optional: boolean;
+  /** `public`, `private` or `protected` (class members, when written) */
+  accessibility: "public" | "private" | "protected" | null;
+  /** A `static` class member */
+  static: boolean;
 }
 
 /**
@@ -122,10 +170,16 @@ export interface SymbolInfo {
   end_line: number;
   /** Whether symbol is exported */
   exported: boolean;
+  /** Whether symbol is the module's `export default` (anonymous defaults are named "default") */
+  is_default_export: boolean;
   /** Parameters (for functions/methods) */
   parameters: ParameterInfo[] | null;
   /** Properties (for classes/interfaces) */
   properties: PropertyInfo[] | null;
+  /** Superclass (for classes) or extended interfaces (for interfaces) */
+  extends: string[];
+  /** Interfaces implemented (for classes) */
+  implements: string[];
   /** Return type annotation (for functions) */
   return_type: string | null;
   /** JSDoc comment */
@@ -137,6 +191,61 @@ export interface SymbolInfo {
  */
 export type SymbolsOutput = SymbolInfo[];
 
+/**
+ * How a reference site refers to the symbol
+ */
+export type ReferenceKind = "Import" | "Usage";
+
+/**
+ * One place a symbol is imported or referenced
+ */
+export interface SymbolReference {
+  kind: ReferenceKind;
+  file: string;
+  line: number;
+  column: number;
+  /** Name at the reference site (the alias for `import { a as b }`) */
+  local: string;
+  /** Module specifier (only for imports) */
+  source?: string;
+}
+
+/**
+ * JSON output from `ta symbols --references <name> --json`
+ */
+export type ReferencesOutput = SymbolReference[];
+
+/**
+ * How an exported symbol changed between a git ref and the working tree
+ */
+export type ApiChangeKind = "Added" | "Removed" | "SignatureChanged";
+
+/**
+ * One change to the exported API
+ */
+export interface ApiChange {
+  change: ApiChangeKind;
+  name: string;
+  /** Symbol kind after the change (before it, for removals) */
+  kind: SymbolKind;
+  file: string;
+  /** Signature at the git ref (null for additions) */
+  before: string | null;
+  /** Signature in the working tree (null for removals) */
+  after: string | null;
+  /** Removals and signature changes are breaking */
+  breaking: boolean;
+}
+
+/**
+ * JSON output from `ta symbols --diff <ref> --json`
+ */
+export interface ApiDiffOutput {
+  since: string;
+  impact: "None" | "Minor" | "Major";
+  changes: ApiChange[];
+}
+
 // ============================================================================
 // File Command - File-Level Dependencies
 // ============================================================================
@@ -149,6 +258,42 @@ export interface ImportInfo {
   source: string;
   /** Imported symbol names */
   symbols: string[];
+  /** `import type`, or every specifier is `type`: erased at compile time */
+  type_only: boolean;
+  /** `Local` for relative imports, `Repo` for tsconfig path aliases, `External` for packages */
+  scope: DependencyScope;
+}
+
+/**
+ * A name re-exported from another module (e.g. `export { a as b } from './x'`)
+ */
+export interface ReexportInfo {
+  /** Source module path */
+  source: string;
+  /** Name exposed by this module (`*` for `export *`) */
+  exported: string;
+  /** Name in the source module (`*` for whole-module re-exports) */
+  imported: string;
+  /** `export type { ... } from` or `export { type X } from` */
+  type_only: boolean;
+}
+
+/**
+ * Kind of import hygiene problem reported by `ta file --lint` or `ta deps --check`
+ */
+export type ImportIssueKind = "DuplicateImport" | "UnusedImport" | "UnresolvedImport";
+
+/**
+ * An import hygiene problem in a single file
+ */
+export interface ImportIssue {
+  kind: ImportIssueKind;
+  /** Module specifier of the offending import */
+  source: string;
+  /** Local name that is never used (null for duplicate and unresolved imports) */
+  name: string | null;
+  line: number;
+  message: string;
 }
 
 /**
@@ -159,6 +304,10 @@ export interface FileDependencies {
   file: string;
   /** Import statements in the file */
   imports: ImportInfo[];
+  /** Re-exports from other modules (barrel files) */
+  reexports: ReexportInfo[];
+  /** Import hygiene problems (only present with `--lint`) */
+  issues?: ImportIssue[];
 }
 
 /**
@@ -213,8 +362,12 @@ export interface TypeTest {
   describe_block: string;
   /** Name of the test case */
   test_name: string;
-  /** Line number (1-indexed) */
+  /** Line of the it()/test() call (1-indexed) */
   line: number;
+  /** Column of the it()/test() call (1-indexed) */
+  column: number;
+  /** Byte range of the whole it()/test() call */
+  span: { start: number; end: number };
   /** Whether test has type cases array */
   has_type_cases: boolean;
   /** Test status */
@@ -238,6 +391,11 @@ export interface BaseEvent<T extends string, D> {
   data: D;
 }
 
+/**
+ * Where a test's it()/test() call is, carried by all test events
+ */
+export type TestLocation = Pick<TypeTest, "line" | "column" | "span">;
+
 export type WatchEvent =
   | BaseEvent<'SourceFileChanged', { file: string; content: string }>
   | BaseEvent<'SourceFileCreated', { file: string }>
@@ -247,10 +405,11 @@ export type WatchEvent =
   | BaseEvent<'SymbolRemoved', { name: string; file: string }>
   | BaseEvent<'ModuleDepChanged', { file: string }>
   | BaseEvent<'ExternalDepChanged', { package: string }>
-  | BaseEvent<'TestStatusChanged', { file: string; test: string; status: TestStatus }>
-  | BaseEvent<'NewFailingTest', { file: string; test: string }>
-  | BaseEvent<'TestFixed', { file: string; test: string }>
-  | BaseEvent<'NewTestAdded', { file: string; test: string }>;
+  | BaseEvent<'TestStatusChanged', { file: string; test: string; status: TestStatus } & TestLocation>
+  | BaseEvent<'NewFailingTest', { file: string; test: string } & TestLocation>
+  | BaseEvent<'TestFixed', { file: string; test: string } & TestLocation>
+  | BaseEvent<'NewTestAdded', { file: string; test: string } & TestLocation>
+  | BaseEvent<'TestRemoved', { file: string; test: string } & TestLocation>;
 
 /**
  * A handler function that receives a watch event
@@ -261,4 +420,4 @@ export type WatchHandler = (event: WatchEvent) => void | Promise<void>;
  * Specific handler types for individual events
  */
 export type SymbolAddedHandler = (event: BaseEvent<'SymbolAdded', { name: string; kind: SymbolKind; file: string }>) => void | Promise<void>;
-export type TestStatusChangedHandler = (event: BaseEvent<'TestStatusChanged', { file: string; test: string; status: TestStatus }>) => void | Promise<void>;
+export type TestStatusChangedHandler = (event: BaseEvent<'TestStatusChanged', { file: string; test: string; status: TestStatus } & TestLocation>) => void | Promise<void>;